@@ -79,6 +79,7 @@ impl From<TopicQos> for dust_dds::infrastructure::qos::TopicQos {
         Self {
             topic_data: val.topic_data.into(),
             durability: val.durability.into(),
+            durability_service: val.durability_service.into(),
             deadline: val.deadline.into(),
             latency_budget: val.latency_budget.into(),
             liveliness: val.liveliness.into(),
@@ -98,7 +99,7 @@ impl From<dust_dds::infrastructure::qos::TopicQos> for TopicQos {
         Self {
             topic_data: val.topic_data.into(),
             durability: val.durability.into(),
-            durability_service: Default::default(),
+            durability_service: val.durability_service.into(),
             deadline: val.deadline.into(),
             latency_budget: val.latency_budget.into(),
             liveliness: val.liveliness.into(),
@@ -137,6 +138,7 @@ impl From<DataWriterQos> for dust_dds::infrastructure::qos::DataWriterQos {
     fn from(val: DataWriterQos) -> Self {
         Self {
             durability: val.durability.into(),
+            durability_service: val.durability_service.into(),
             deadline: val.deadline.into(),
             latency_budget: val.latency_budget.into(),
             liveliness: val.liveliness.into(),
@@ -151,6 +153,7 @@ impl From<DataWriterQos> for dust_dds::infrastructure::qos::DataWriterQos {
             ownership_strength: val.ownership_strength.into(),
             writer_data_lifecycle: val.writer_data_lifecycle.into(),
             representation: Default::default(),
+            publish_mode: Default::default(),
         }
     }
 }
@@ -158,7 +161,7 @@ impl From<dust_dds::infrastructure::qos::DataWriterQos> for DataWriterQos {
     fn from(val: dust_dds::infrastructure::qos::DataWriterQos) -> Self {
         Self {
             durability: val.durability.into(),
-            durability_service: Default::default(),
+            durability_service: val.durability_service.into(),
             deadline: val.deadline.into(),
             latency_budget: val.latency_budget.into(),
             liveliness: val.liveliness.into(),
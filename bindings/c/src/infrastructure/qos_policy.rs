@@ -448,7 +448,7 @@ impl From<PresentationQosPolicyAccessScopeKind>
         match kind {
             PresentationQosPolicyAccessScopeKind::INSTANCE_PRESENTATION_QOS => Self::Instance,
             PresentationQosPolicyAccessScopeKind::TOPIC_PRESENTATION_QOS => Self::Topic,
-            PresentationQosPolicyAccessScopeKind::GROUP_PRESENTATION_QOS => Self::Topic,
+            PresentationQosPolicyAccessScopeKind::GROUP_PRESENTATION_QOS => Self::Group,
         }
     }
 }
@@ -462,6 +462,7 @@ impl From<dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScope
         match kind {
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Instance => Self::INSTANCE_PRESENTATION_QOS,
             dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Topic => Self::TOPIC_PRESENTATION_QOS,
+            dust_dds::infrastructure::qos_policy::PresentationQosPolicyAccessScopeKind::Group => Self::GROUP_PRESENTATION_QOS,
         }
     }
 }
@@ -1122,3 +1123,60 @@ impl Default for DurabilityServiceQosPolicy {
         }
     }
 }
+
+impl From<DurabilityServiceQosPolicy>
+    for dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy
+{
+    fn from(val: DurabilityServiceQosPolicy) -> Self {
+        let map_len = |v: i32| {
+            if v < 0 {
+                dust_dds::infrastructure::qos_policy::Length::Unlimited
+            } else {
+                dust_dds::infrastructure::qos_policy::Length::Limited(v)
+            }
+        };
+        let history_kind = match val.history_kind {
+            HistoryQosPolicyKind::KEEP_LAST_HISTORY_QOS => {
+                dust_dds::infrastructure::qos_policy::HistoryQosPolicyKind::KeepLast(
+                    val.history_depth as u32,
+                )
+            }
+            HistoryQosPolicyKind::KEEP_ALL_HISTORY_QOS => {
+                dust_dds::infrastructure::qos_policy::HistoryQosPolicyKind::KeepAll
+            }
+        };
+        Self {
+            service_cleanup_delay: val.service_cleanup_delay.into(),
+            history_kind,
+            max_samples: map_len(val.max_samples),
+            max_instances: map_len(val.max_instances),
+            max_samples_per_instance: map_len(val.max_samples_per_instance),
+        }
+    }
+}
+impl From<dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy>
+    for DurabilityServiceQosPolicy
+{
+    fn from(val: dust_dds::infrastructure::qos_policy::DurabilityServiceQosPolicy) -> Self {
+        let map_len = |l: dust_dds::infrastructure::qos_policy::Length| match l {
+            dust_dds::infrastructure::qos_policy::Length::Unlimited => -1,
+            dust_dds::infrastructure::qos_policy::Length::Limited(v) => v,
+        };
+        let (history_kind, history_depth) = match val.history_kind {
+            dust_dds::infrastructure::qos_policy::HistoryQosPolicyKind::KeepLast(depth) => {
+                (HistoryQosPolicyKind::KEEP_LAST_HISTORY_QOS, depth as i32)
+            }
+            dust_dds::infrastructure::qos_policy::HistoryQosPolicyKind::KeepAll => {
+                (HistoryQosPolicyKind::KEEP_ALL_HISTORY_QOS, 1)
+            }
+        };
+        Self {
+            service_cleanup_delay: val.service_cleanup_delay.into(),
+            history_kind,
+            history_depth,
+            max_samples: map_len(val.max_samples),
+            max_instances: map_len(val.max_instances),
+            max_samples_per_instance: map_len(val.max_samples_per_instance),
+        }
+    }
+}
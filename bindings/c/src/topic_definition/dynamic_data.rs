@@ -2,7 +2,10 @@ use crate::{
     infrastructure::error::{RETCODE_BAD_PARAMETER, RETCODE_ERROR, RETCODE_OK, ReturnCode},
     topic_definition::dynamic_type::DynamicType,
 };
-use dust_dds::xtypes::dynamic_type::{DynamicData as RustDynamicData, DynamicDataFactory};
+use dust_dds::xtypes::{
+    data_storage::DataStorage,
+    dynamic_type::{DynamicData as RustDynamicData, DynamicDataFactory},
+};
 use std::ptr::NonNull;
 
 /// cbindgen:opaque
@@ -370,6 +373,35 @@ pub unsafe extern "C" fn DDS_DynamicData_get_char8_value(
         Err(_) => RETCODE_ERROR,
     }
 }
+/// Gets a `char16` value as a single UTF-16 code unit.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+/// - `value` must be a valid pointer to a `u16` instance for writing (or null).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_get_char16_value(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+    value: *mut u16,
+) -> ReturnCode {
+    let Some(data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    if value.is_null() {
+        return RETCODE_BAD_PARAMETER;
+    }
+    match unsafe { data.as_ref() }.inner().get_char16_value(id) {
+        Ok(val) => {
+            let mut unit = [0; 1];
+            val.encode_utf16(&mut unit);
+            unsafe { *value = unit[0] };
+            RETCODE_OK
+        }
+        Err(_) => RETCODE_ERROR,
+    }
+}
 /// # Safety
 ///
 /// The caller must observe the following safety invariants:
@@ -680,6 +712,32 @@ pub unsafe extern "C" fn DDS_DynamicData_set_char8_value(
         Err(_) => RETCODE_ERROR,
     }
 }
+/// Sets a `char16` value from a single UTF-16 code unit. Surrogate code units are rejected.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_set_char16_value(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+    value: u16,
+) -> ReturnCode {
+    let Some(mut data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    let Some(r_char) = char::from_u32(value as u32) else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    match unsafe { data.as_mut() }
+        .inner_mut()
+        .set_char16_value(id, r_char)
+    {
+        Ok(()) => RETCODE_OK,
+        Err(_) => RETCODE_ERROR,
+    }
+}
 /// # Safety
 ///
 /// The caller must observe the following safety invariants:
@@ -736,3 +794,146 @@ pub unsafe extern "C" fn DDS_DynamicData_set_complex_value(
         Err(_) => RETCODE_ERROR,
     }
 }
+
+// Map entries
+// A map entry is exposed as a DynamicData holding the key in member 0 and the
+// value in member 1, so that the explicit getters and setters can be used on it.
+/// Gets the number of entries of a map member.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+/// - `length` must be a valid pointer to a `u32` instance for writing (or null).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_get_map_length(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+    length: *mut u32,
+) -> ReturnCode {
+    let Some(data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    if length.is_null() {
+        return RETCODE_BAD_PARAMETER;
+    }
+    match unsafe { data.as_ref() }.inner().get_value(id) {
+        Ok(DataStorage::Map(entries)) => {
+            unsafe { *length = entries.len() as u32 };
+            RETCODE_OK
+        }
+        _ => RETCODE_ERROR,
+    }
+}
+/// Gets the entry at `index` of a map member. The returned entry must be freed
+/// with `DDS_DynamicData_free`.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+/// - `entry` must be a valid pointer to a `DynamicData` instance for writing (or null).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_get_map_entry(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+    index: u32,
+    entry: *mut *mut DynamicData,
+) -> ReturnCode {
+    let Some(data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    if entry.is_null() {
+        return RETCODE_BAD_PARAMETER;
+    }
+    let data = unsafe { data.as_ref() }.inner();
+    let Ok(DataStorage::Map(entries)) = data.get_value(id) else {
+        return RETCODE_ERROR;
+    };
+    let Some((key, value)) = entries.get(index as usize) else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    let Ok(member_descriptor) = data.get_descriptor(id) else {
+        return RETCODE_ERROR;
+    };
+    let mut map_entry = DynamicDataFactory::create_data(member_descriptor.r#type);
+    map_entry.set_value(0, key.clone());
+    map_entry.set_value(1, value.clone());
+    unsafe { *entry = Box::into_raw(Box::new(DynamicData::new(map_entry))) };
+    RETCODE_OK
+}
+/// Sets a map member to a map without entries.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_clear_map(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+) -> ReturnCode {
+    let Some(mut data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    unsafe { data.as_mut() }
+        .inner_mut()
+        .set_value(id, DataStorage::Map(Vec::new()));
+    RETCODE_OK
+}
+/// Creates an empty entry for a map member. The key and value are set on the
+/// returned entry as members 0 and 1 before adding it with
+/// `DDS_DynamicData_add_map_entry`.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_create_map_entry(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+) -> Option<NonNull<DynamicData>> {
+    let member_descriptor = unsafe { data?.as_ref() }.inner().get_descriptor(id).ok()?;
+    let map_entry = DynamicDataFactory::create_data(member_descriptor.r#type);
+    NonNull::new(Box::into_raw(Box::new(DynamicData::new(map_entry))))
+}
+/// Adds an entry to a map member, replacing the value of an existing entry with
+/// the same key.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `data` must point to a valid, initialized `DynamicData` instance.
+/// - `entry` must point to a valid, initialized `DynamicData` instance.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicData_add_map_entry(
+    data: Option<NonNull<DynamicData>>,
+    id: u32,
+    entry: Option<NonNull<DynamicData>>,
+) -> ReturnCode {
+    let Some(mut data) = data else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    let Some(entry) = entry else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    let entry = unsafe { entry.as_ref() }.inner();
+    let (Ok(key), Ok(value)) = (entry.get_value(0), entry.get_value(1)) else {
+        return RETCODE_BAD_PARAMETER;
+    };
+    let data = unsafe { data.as_mut() }.inner_mut();
+    if matches!(data.get_value(id), Ok(storage) if !matches!(storage, DataStorage::Map(_))) {
+        return RETCODE_ERROR;
+    }
+    let mut entries = match data.remove_value(id) {
+        Ok(DataStorage::Map(entries)) => entries,
+        _ => Vec::new(),
+    };
+    match entries.iter_mut().find(|(k, _)| k == key) {
+        Some((_, v)) => *v = value.clone(),
+        None => entries.push((key.clone(), value.clone())),
+    }
+    data.set_value(id, DataStorage::Map(entries));
+    RETCODE_OK
+}
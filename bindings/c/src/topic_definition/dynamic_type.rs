@@ -100,6 +100,9 @@ pub struct TypeDescriptor {
     pub base_type: *const DynamicType,
     pub discriminator_type: *const DynamicType,
     pub bound: *const u32,
+    /// Number of values pointed to by `bound`. A non-null `bound` with a zero length holds a
+    /// single value.
+    pub bound_length: u32,
     pub element_type: *const DynamicType,
     pub key_element_type: *const DynamicType,
     pub extensibility_kind: u8,
@@ -156,6 +159,7 @@ fn extensibility_kind_from_u8(value: u8) -> Option<ExtensibilityKind> {
 ///
 /// The caller must observe the following safety invariants:
 /// - `descriptor` must be a valid pointer to a `TypeDescriptor` instance (or null).
+/// - A non-null `bound` must point to at least `bound_length` values, or one value if `bound_length` is zero.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn DDS_DynamicTypeBuilderFactory_create_type(
     descriptor: *const TypeDescriptor,
@@ -189,7 +193,12 @@ pub unsafe extern "C" fn DDS_DynamicTypeBuilderFactory_create_type(
     let bound: &'static [u32] = if descriptor.bound.is_null() {
         &[]
     } else {
-        Box::leak(vec![unsafe { *descriptor.bound }].into_boxed_slice())
+        let bound_length = descriptor.bound_length.max(1) as usize;
+        Box::leak(
+            unsafe { std::slice::from_raw_parts(descriptor.bound, bound_length) }
+                .to_vec()
+                .into_boxed_slice(),
+        )
     };
 
     let element_type = if descriptor.element_type.is_null() {
@@ -224,7 +233,7 @@ pub unsafe extern "C" fn DDS_DynamicTypeBuilderFactory_create_type(
 }
 
 // Compile-time static instances of DynamicType for standard primitive types
-use dust_dds::xtypes::type_support::Type;
+use dust_dds::xtypes::type_support::{Type, WChar};
 static BOOLEAN_TYPE: DynamicType = DynamicType(bool::TYPE);
 static INT8_TYPE: DynamicType = DynamicType(i8::TYPE);
 static UINT8_TYPE: DynamicType = DynamicType(u8::TYPE);
@@ -237,6 +246,7 @@ static UINT64_TYPE: DynamicType = DynamicType(u64::TYPE);
 static FLOAT32_TYPE: DynamicType = DynamicType(f32::TYPE);
 static FLOAT64_TYPE: DynamicType = DynamicType(f64::TYPE);
 static CHAR8_TYPE: DynamicType = DynamicType(char::TYPE);
+static CHAR16_TYPE: DynamicType = DynamicType(WChar::TYPE);
 
 /// Returns a DynamicType representing the specified primitive type kind.
 /// Returns a raw pointer to DynamicType on success, or NULL on failure.
@@ -261,6 +271,7 @@ pub unsafe extern "C" fn DDS_DynamicType_get_primitive_type(
         TYPE_KIND_FLOAT32 => &FLOAT32_TYPE,
         TYPE_KIND_FLOAT64 => &FLOAT64_TYPE,
         TYPE_KIND_CHAR8 => &CHAR8_TYPE,
+        TYPE_KIND_CHAR16 => &CHAR16_TYPE,
         _ => return None,
     };
 
@@ -282,6 +293,47 @@ pub unsafe extern "C" fn DDS_DynamicType_create_string_type(
     NonNull::new(Box::into_raw(Box::new(DynamicType::new(dynamic_type))))
 }
 
+/// Creates a DynamicType for a wide string with the specified bound.
+/// Returns a raw pointer to DynamicType on success, or NULL on failure.
+///
+/// # Safety
+///
+/// There are no special safety invariants to be observed when calling this function.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicType_create_wstring_type(
+    bound: u32,
+) -> Option<NonNull<DynamicType>> {
+    let builder = DynamicTypeBuilderFactory::create_wstring_type(bound);
+    let dynamic_type = builder.build();
+    NonNull::new(Box::into_raw(Box::new(DynamicType::new(dynamic_type))))
+}
+
+/// Creates a DynamicType for a map with the specified key type, element type and bound.
+/// Returns a raw pointer to DynamicType on success, or NULL on failure.
+///
+/// # Safety
+///
+/// The caller must observe the following safety invariants:
+/// - `key_element_type` must be a valid pointer to a `DynamicType` instance (or null).
+/// - `element_type` must be a valid pointer to a `DynamicType` instance (or null).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn DDS_DynamicType_create_map_type(
+    key_element_type: *const DynamicType,
+    element_type: *const DynamicType,
+    bound: u32,
+) -> Option<NonNull<DynamicType>> {
+    if key_element_type.is_null() || element_type.is_null() {
+        return None;
+    }
+    let builder = DynamicTypeBuilderFactory::create_map_type(
+        *unsafe { &*key_element_type }.inner(),
+        *unsafe { &*element_type }.inner(),
+        bound,
+    );
+    let dynamic_type = builder.build();
+    NonNull::new(Box::into_raw(Box::new(DynamicType::new(dynamic_type))))
+}
+
 /// Frees a DynamicType object.
 ///
 /// # Safety
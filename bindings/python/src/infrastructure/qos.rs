@@ -247,6 +247,7 @@ impl TopicQos {
         Self(dust_dds::infrastructure::qos::TopicQos {
             topic_data: topic_data.into(),
             durability: durability.into(),
+            durability_service: Default::default(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -366,6 +367,7 @@ impl DataWriterQos {
     ) -> Self {
         Self(dust_dds::infrastructure::qos::DataWriterQos {
             durability: durability.into(),
+            durability_service: Default::default(),
             deadline: deadline.into(),
             latency_budget: latency_budget.into(),
             liveliness: liveliness.into(),
@@ -380,6 +382,7 @@ impl DataWriterQos {
             ownership_strength: ownership_strength.into(),
             writer_data_lifecycle: writer_data_lifecycle.into(),
             representation: representation.into(),
+            publish_mode: Default::default(),
         })
     }
 
@@ -1,17 +1,29 @@
 use pyo3::prelude::*;
 
-use super::{condition::StatusCondition, error::into_pyerr, time::Duration};
+use super::{
+    condition::{QueryCondition, ReadCondition, StatusCondition},
+    error::into_pyerr,
+    time::Duration,
+};
 
 #[pyclass(from_py_object)]
 #[derive(Clone)]
 pub enum Condition {
-    StatusCondition { condition: StatusCondition },
+    Status { condition: StatusCondition },
+    Read { condition: ReadCondition },
+    Query { condition: QueryCondition },
 }
 
 impl From<dust_dds::wait_set::Condition> for Condition {
     fn from(value: dust_dds::wait_set::Condition) -> Self {
         match value {
-            dust_dds::wait_set::Condition::StatusCondition(c) => Condition::StatusCondition {
+            dust_dds::wait_set::Condition::StatusCondition(c) => Condition::Status {
+                condition: c.into(),
+            },
+            dust_dds::wait_set::Condition::ReadCondition(c) => Condition::Read {
+                condition: c.into(),
+            },
+            dust_dds::wait_set::Condition::QueryCondition(c) => Condition::Query {
                 condition: c.into(),
             },
         }
@@ -21,9 +33,15 @@ impl From<dust_dds::wait_set::Condition> for Condition {
 impl From<Condition> for dust_dds::wait_set::Condition {
     fn from(value: Condition) -> Self {
         match value {
-            Condition::StatusCondition { condition } => {
+            Condition::Status { condition } => {
                 dust_dds::wait_set::Condition::StatusCondition(condition.into())
             }
+            Condition::Read { condition } => {
+                dust_dds::wait_set::Condition::ReadCondition(condition.into())
+            }
+            Condition::Query { condition } => {
+                dust_dds::wait_set::Condition::QueryCondition(condition.into())
+            }
         }
     }
 }
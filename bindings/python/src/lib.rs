@@ -46,6 +46,8 @@ fn dust_dds(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<infrastructure::wait_set::Condition>()?;
     m.add_class::<infrastructure::wait_set::WaitSet>()?;
     m.add_class::<infrastructure::condition::StatusCondition>()?;
+    m.add_class::<infrastructure::condition::ReadCondition>()?;
+    m.add_class::<infrastructure::condition::QueryCondition>()?;
 
     // Add Builtin Topics
     m.add_class::<builtin_topics::BuiltInTopicKey>()?;
@@ -26,7 +26,7 @@ def test_write_read_my_data_type():
     ws = dust_dds.WaitSet()
     cond = data_writer.get_statuscondition()
     cond.set_enabled_statuses([dust_dds.StatusKind.PublicationMatched])
-    ws.attach_condition(dust_dds.Condition.StatusCondition(cond))
+    ws.attach_condition(dust_dds.Condition.Status(cond))
 
     ws.wait(dust_dds.Duration(sec=2, nanosec=0))
 
@@ -37,7 +37,7 @@ def test_write_read_my_data_type():
     ws_data_available = dust_dds.WaitSet()
     cond = data_reader.get_statuscondition()
     cond.set_enabled_statuses([dust_dds.StatusKind.DataAvailable])
-    ws_data_available.attach_condition(dust_dds.Condition.StatusCondition(cond))
+    ws_data_available.attach_condition(dust_dds.Condition.Status(cond))
 
     ws_data_available.wait(dust_dds.Duration(sec=2, nanosec=0))
 
@@ -33,7 +33,7 @@ def test_write_read():
     ws = dust_dds.WaitSet()
     cond = data_writer.get_statuscondition()
     cond.set_enabled_statuses([dust_dds.StatusKind.PublicationMatched])
-    ws.attach_condition(dust_dds.Condition.StatusCondition(cond))
+    ws.attach_condition(dust_dds.Condition.Status(cond))
 
     data = MyDataType(bytes([0,1,2,3,4]))
     data_writer.write(data)
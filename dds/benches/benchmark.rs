@@ -13,8 +13,16 @@ use dust_dds::{
     },
     subscription::data_reader_listener::DataReaderListener,
     wait_set::{Condition, WaitSet},
-    xtypes::bytes::ByteBuf,
+    xtypes::{
+        bytes::ByteBuf,
+        type_support::TypeSupport,
+        xcdr::{
+            Endianness, XcdrVersion, deserialize_dynamic_data, deserialize_top_level,
+            serialize_dynamic_data, serialize_top_level,
+        },
+    },
 };
+use std::hint::black_box;
 
 #[derive(Clone, Debug, PartialEq, DdsType)]
 struct KeyedData {
@@ -302,8 +310,70 @@ fn best_effort_write_and_receive_frag(c: &mut Criterion) {
     });
 }
 
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct ComplexData {
+    #[dust_dds(key)]
+    id: u32,
+    name: String,
+    position: [f64; 3],
+    readings: Vec<i32>,
+    labels: Vec<String>,
+    payload: ByteBuf,
+}
+
+fn complex_data() -> ComplexData {
+    ComplexData {
+        id: 1,
+        name: String::from("sensor"),
+        position: [1.0, 2.0, 3.0],
+        readings: (0..64).collect(),
+        labels: vec![String::from("a"), String::from("bc"), String::from("def")],
+        payload: ByteBuf(vec![7; 256]),
+    }
+}
+
+fn serialize_direct_vs_dynamic(c: &mut Criterion) {
+    let data = complex_data();
+    let mut group = c.benchmark_group("serialize");
+    for version in [XcdrVersion::Xcdr1, XcdrVersion::Xcdr2] {
+        group.bench_function(format!("direct_{version:?}"), |b| {
+            b.iter(|| {
+                serialize_top_level(black_box(&data), version, Endianness::LittleEndian).unwrap()
+            })
+        });
+        group.bench_function(format!("dynamic_{version:?}"), |b| {
+            b.iter(|| {
+                let dynamic_data = black_box(data.clone()).create_dynamic_sample();
+                serialize_dynamic_data(&dynamic_data, version, Endianness::LittleEndian).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn deserialize_direct_vs_dynamic(c: &mut Criterion) {
+    let data = complex_data();
+    let mut group = c.benchmark_group("deserialize");
+    for version in [XcdrVersion::Xcdr1, XcdrVersion::Xcdr2] {
+        let buffer = serialize_top_level(&data, version, Endianness::LittleEndian).unwrap();
+        group.bench_function(format!("direct_{version:?}"), |b| {
+            b.iter(|| deserialize_top_level::<ComplexData>(black_box(&buffer)).unwrap())
+        });
+        group.bench_function(format!("dynamic_{version:?}"), |b| {
+            b.iter(|| {
+                let mut dynamic_data =
+                    deserialize_dynamic_data(ComplexData::get_type(), black_box(&buffer)).unwrap();
+                ComplexData::create_sample(&mut dynamic_data)
+            })
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    serialize_direct_vs_dynamic,
+    deserialize_direct_vs_dynamic,
     best_effort_write_only,
     best_effort_read_only,
     best_effort_write_and_receive,
@@ -0,0 +1,995 @@
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    xtypes::{
+        data_storage::DataStorage,
+        dynamic_type::{DynamicData, DynamicType, DynamicTypeMember, MemberId, TypeKind},
+    },
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cmp::Ordering;
+
+/// Filter expression compiled against the type of the related topic following
+/// the SQL subset defined in Annex B of the DDS specification:
+///
+/// ```text
+/// Condition ::= Predicate | Condition 'AND' Condition | Condition 'OR' Condition
+///             | 'NOT' Condition | '(' Condition ')'
+/// Predicate ::= Operand RelOp Operand | FIELDNAME ['NOT'] 'BETWEEN' Operand 'AND' Operand
+/// RelOp     ::= '=' | '<>' | '!=' | '<' | '<=' | '>' | '>=' | ['NOT'] 'LIKE'
+/// Operand   ::= FIELDNAME | INTEGER | FLOAT | STRING | TRUE | FALSE | ENUMERATOR | '%n'
+/// ```
+pub struct ContentFilter {
+    condition: Condition,
+    key_only: bool,
+}
+
+impl ContentFilter {
+    pub fn new(
+        filter_expression: &str,
+        type_support: &DynamicType<'static>,
+        expression_parameters: &[String],
+    ) -> DdsResult<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(filter_expression)?,
+            position: 0,
+            type_support,
+            key_only: true,
+        };
+        let condition = parser.parse_condition()?;
+        if parser.position != parser.tokens.len() {
+            return Err(DdsError::BadParameter);
+        }
+        let mut content_filter = Self {
+            condition,
+            key_only: parser.key_only,
+        };
+        content_filter.set_expression_parameters(expression_parameters)?;
+        Ok(content_filter)
+    }
+
+    /// Converts every `%n` parameter used in the expression to the type of the field it is
+    /// compared with. The filter is left unchanged if any of the parameters is missing or invalid.
+    pub fn set_expression_parameters(&mut self, expression_parameters: &[String]) -> DdsResult<()> {
+        self.condition.check_parameters(expression_parameters)?;
+        self.condition.set_parameters(expression_parameters);
+        Ok(())
+    }
+
+    pub fn evaluate(&self, data: &DynamicData) -> bool {
+        self.condition.evaluate(data)
+    }
+
+    /// Whether the expression only refers to key members, in which case it can also be
+    /// evaluated on the key holder of the type.
+    pub fn is_key_only(&self) -> bool {
+        self.key_only
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Parameter(usize),
+    Operator(RelationalOperator),
+    LeftParenthesis,
+    RightParenthesis,
+    And,
+    Or,
+    Not,
+    Between,
+    Like,
+    True,
+    False,
+}
+
+fn tokenize(filter_expression: &str) -> DdsResult<Vec<Token>> {
+    let chars: Vec<char> = filter_expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while let Some(&c) = chars.get(i) {
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LeftParenthesis);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RightParenthesis);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Operator(RelationalOperator::Equal));
+                i += 1;
+            }
+            '<' => {
+                let (operator, length) = match next {
+                    Some('=') => (RelationalOperator::LessOrEqual, 2),
+                    Some('>') => (RelationalOperator::NotEqual, 2),
+                    _ => (RelationalOperator::LessThan, 1),
+                };
+                tokens.push(Token::Operator(operator));
+                i += length;
+            }
+            '>' => {
+                let (operator, length) = match next {
+                    Some('=') => (RelationalOperator::GreaterOrEqual, 2),
+                    _ => (RelationalOperator::GreaterThan, 1),
+                };
+                tokens.push(Token::Operator(operator));
+                i += length;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Operator(RelationalOperator::NotEqual));
+                i += 2;
+            }
+            '\'' => {
+                let length = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '\'')
+                    .ok_or(DdsError::BadParameter)?;
+                tokens.push(Token::String(chars[i + 1..i + 1 + length].iter().collect()));
+                i += length + 2;
+            }
+            '%' => {
+                let length = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                let index: String = chars[i + 1..i + 1 + length].iter().collect();
+                tokens.push(Token::Parameter(
+                    index.parse().map_err(|_| DdsError::BadParameter)?,
+                ));
+                i += length + 1;
+            }
+            c if c.is_ascii_digit()
+                || (matches!(c, '-' | '+') && next.is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let length = chars[i + 1..]
+                    .iter()
+                    .zip(chars[i..].iter())
+                    .take_while(|&(&c, &previous)| {
+                        c.is_ascii_alphanumeric()
+                            || c == '.'
+                            || (matches!(c, '-' | '+') && matches!(previous, 'e' | 'E'))
+                    })
+                    .count()
+                    + 1;
+                let number: String = chars[i..i + length].iter().collect();
+                tokens.push(parse_number(&number).ok_or(DdsError::BadParameter)?);
+                i += length;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let length = chars[i..]
+                    .iter()
+                    .take_while(|&&c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':'))
+                    .count();
+                let word: String = chars[i..i + length].iter().collect();
+                let token = match word.to_ascii_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "BETWEEN" => Token::Between,
+                    "LIKE" => Token::Like,
+                    "TRUE" => Token::True,
+                    "FALSE" => Token::False,
+                    _ => Token::Identifier(word),
+                };
+                tokens.push(token);
+                i += length;
+            }
+            _ => return Err(DdsError::BadParameter),
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_number(number: &str) -> Option<Token> {
+    let (sign, digits) = match number.strip_prefix('-') {
+        Some(digits) => (-1, digits),
+        None => (1, number.strip_prefix('+').unwrap_or(number)),
+    };
+    if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        i128::from_str_radix(hex, 16)
+            .ok()
+            .map(|v| Token::Integer(sign * v))
+    } else if let Ok(v) = digits.parse::<i128>() {
+        Some(Token::Integer(sign * v))
+    } else {
+        number.parse::<f64>().ok().map(Token::Float)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RelationalOperator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    Like,
+}
+
+impl RelationalOperator {
+    fn evaluate(&self, lhs: &Value, rhs: &Value) -> bool {
+        if let (Self::Like, Value::Text(value), Value::Text(pattern)) = (self, lhs, rhs) {
+            let value: Vec<char> = value.chars().collect();
+            let pattern: Vec<char> = pattern.chars().collect();
+            return is_like(&value, &pattern);
+        }
+        match compare(lhs, rhs) {
+            Some(ordering) => match self {
+                Self::Equal => ordering == Ordering::Equal,
+                Self::NotEqual => ordering != Ordering::Equal,
+                Self::LessThan => ordering == Ordering::Less,
+                Self::LessOrEqual => ordering != Ordering::Greater,
+                Self::GreaterThan => ordering == Ordering::Greater,
+                Self::GreaterOrEqual => ordering != Ordering::Less,
+                Self::Like => false,
+            },
+            None => false,
+        }
+    }
+}
+
+/// SQL LIKE matching where `%` matches any sequence of characters and `_` matches exactly one.
+/// On a mismatch only the most recent `%` is retried with one more character, which keeps
+/// the matching linear in the length of the value for each `%` in the pattern.
+fn is_like(value: &[char], pattern: &[char]) -> bool {
+    let (mut v, mut p) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '_' || *c == value[v] => {
+                v += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((percent_position, value_position)) => {
+                    backtrack = Some((percent_position, value_position + 1));
+                    p = percent_position + 1;
+                    v = value_position + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    Boolean(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueKind {
+    Numeric,
+    Text,
+    Boolean,
+}
+
+impl Value {
+    fn kind(&self) -> ValueKind {
+        match self {
+            Value::Integer(_) | Value::Float(_) => ValueKind::Numeric,
+            Value::Text(_) => ValueKind::Text,
+            Value::Boolean(_) => ValueKind::Boolean,
+        }
+    }
+
+    fn from_storage(storage: &DataStorage) -> Option<Self> {
+        Some(match storage {
+            DataStorage::UInt8(v) => Value::Integer((*v).into()),
+            DataStorage::Int8(v) => Value::Integer((*v).into()),
+            DataStorage::UInt16(v) => Value::Integer((*v).into()),
+            DataStorage::Int16(v) => Value::Integer((*v).into()),
+            DataStorage::Int32(v) => Value::Integer((*v).into()),
+            DataStorage::UInt32(v) => Value::Integer((*v).into()),
+            DataStorage::Int64(v) => Value::Integer((*v).into()),
+            DataStorage::UInt64(v) => Value::Integer((*v).into()),
+            DataStorage::Float128(v) => Value::Float(float128_to_f64(*v)),
+            DataStorage::Float32(v) => Value::Float((*v).into()),
+            DataStorage::Float64(v) => Value::Float(*v),
+            DataStorage::Char8(v) => Value::Text(v.to_string()),
+            DataStorage::Boolean(v) => Value::Boolean(*v),
+            DataStorage::String(v) => Value::Text(v.clone()),
+            // Enumerations are stored as a nested sample holding the discriminator in member 0
+            DataStorage::ComplexValue(v) if v.r#type().get_kind() == TypeKind::ENUM => {
+                Value::from_storage(v.get_value(0).ok()?)?
+            }
+            _ => return None,
+        })
+    }
+
+    /// Converts an expression parameter into a value comparable with a field of type `field_type`.
+    fn from_parameter(parameter: &str, field_type: &DynamicType) -> Option<Self> {
+        let parameter = parameter.trim();
+        let parameter = parameter
+            .strip_prefix('\'')
+            .and_then(|p| p.strip_suffix('\''))
+            .unwrap_or(parameter);
+        match value_kind(field_type)? {
+            ValueKind::Numeric => match parse_number(parameter) {
+                Some(Token::Integer(v)) => Some(Value::Integer(v)),
+                Some(Token::Float(v)) => Some(Value::Float(v)),
+                _ if field_type.get_kind() == TypeKind::ENUM => {
+                    enumerator_value(field_type, parameter).map(Value::Integer)
+                }
+                _ => None,
+            },
+            ValueKind::Text => Some(Value::Text(parameter.to_string())),
+            ValueKind::Boolean => match parameter.to_ascii_uppercase().as_str() {
+                "TRUE" => Some(Value::Boolean(true)),
+                "FALSE" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Converts the bits of an IEEE 754 binary128 value to the nearest lower precision `f64`.
+/// Magnitudes outside of the `f64` range become infinite or zero.
+fn float128_to_f64(bits: i128) -> f64 {
+    let bits = bits as u128;
+    let sign = ((bits >> 127) as u64) << 63;
+    let exponent = ((bits >> 112) & 0x7fff) as i32;
+    let mantissa = ((bits >> 60) as u64) & ((1 << 52) - 1);
+    let bits64 = match exponent {
+        0 => sign,
+        0x7fff => sign | (0x7ff << 52) | mantissa,
+        _ => match exponent - 16383 + 1023 {
+            e if e >= 0x7ff => sign | (0x7ff << 52),
+            e if e <= 0 => sign,
+            e => sign | ((e as u64) << 52) | mantissa,
+        },
+    };
+    f64::from_bits(bits64)
+}
+
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Integer(l), Value::Integer(r)) => Some(l.cmp(r)),
+        (Value::Integer(l), Value::Float(r)) => (*l as f64).partial_cmp(r),
+        (Value::Float(l), Value::Integer(r)) => l.partial_cmp(&(*r as f64)),
+        (Value::Float(l), Value::Float(r)) => l.partial_cmp(r),
+        (Value::Text(l), Value::Text(r)) => Some(l.cmp(r)),
+        (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+fn value_kind(r#type: &DynamicType) -> Option<ValueKind> {
+    match r#type.get_kind() {
+        TypeKind::BYTE
+        | TypeKind::INT8
+        | TypeKind::UINT8
+        | TypeKind::INT16
+        | TypeKind::UINT16
+        | TypeKind::INT32
+        | TypeKind::UINT32
+        | TypeKind::INT64
+        | TypeKind::UINT64
+        | TypeKind::FLOAT32
+        | TypeKind::FLOAT64
+        | TypeKind::FLOAT128
+        | TypeKind::ENUM
+        | TypeKind::BITMASK => Some(ValueKind::Numeric),
+        TypeKind::CHAR8 | TypeKind::CHAR16 | TypeKind::STRING8 | TypeKind::STRING16 => {
+            Some(ValueKind::Text)
+        }
+        TypeKind::BOOLEAN => Some(ValueKind::Boolean),
+        _ => None,
+    }
+}
+
+fn enumerator_value(enum_type: &DynamicType, name: &str) -> Option<i128> {
+    let name = name.rsplit_once("::").map_or(name, |(_, n)| n);
+    enum_type
+        .member_list
+        .iter()
+        .find(|m| m.get_name() == name)
+        .map(|m| {
+            m.descriptor
+                .label
+                .first()
+                .copied()
+                .map_or(m.descriptor.id.into(), i128::from)
+        })
+}
+
+fn find_member<'a>(r#type: &DynamicType<'a>, name: &str) -> Option<&'a DynamicTypeMember> {
+    if r#type.get_kind() != TypeKind::STRUCTURE {
+        return None;
+    }
+    r#type
+        .member_list
+        .iter()
+        .find(|m| m.get_name() == name)
+        .or_else(|| {
+            r#type
+                .descriptor
+                .base_type
+                .as_ref()
+                .and_then(|base_type| find_member(base_type, name))
+        })
+}
+
+fn member_storage<'d>(
+    data: &'d DynamicData,
+    member_id_list: &[MemberId],
+) -> Option<&'d DataStorage> {
+    match member_id_list {
+        [] => None,
+        [member_id] => data.get_value(*member_id).ok(),
+        [member_id, rest @ ..] => member_storage(data.get_complex_value(*member_id).ok()?, rest),
+    }
+}
+
+struct FieldPath {
+    member_id_list: Vec<MemberId>,
+    r#type: DynamicType<'static>,
+}
+
+impl FieldPath {
+    fn value(&self, data: &DynamicData) -> Option<Value> {
+        Value::from_storage(member_storage(data, &self.member_id_list)?)
+    }
+}
+
+enum Operand {
+    Field(FieldPath),
+    Literal(Value),
+    Parameter {
+        index: usize,
+        field_type: DynamicType<'static>,
+        value: Option<Value>,
+    },
+}
+
+impl Operand {
+    fn value(&self, data: &DynamicData) -> Option<Value> {
+        match self {
+            Operand::Field(field) => field.value(data),
+            Operand::Literal(value) => Some(value.clone()),
+            Operand::Parameter { value, .. } => value.clone(),
+        }
+    }
+
+    fn check_parameters(&self, expression_parameters: &[String]) -> DdsResult<()> {
+        if let Operand::Parameter {
+            index, field_type, ..
+        } = self
+        {
+            let parameter = expression_parameters
+                .get(*index)
+                .ok_or(DdsError::BadParameter)?;
+            Value::from_parameter(parameter, field_type).ok_or(DdsError::BadParameter)?;
+        }
+        Ok(())
+    }
+
+    fn set_parameters(&mut self, expression_parameters: &[String]) {
+        if let Operand::Parameter {
+            index,
+            field_type,
+            value,
+        } = self
+        {
+            *value = expression_parameters
+                .get(*index)
+                .and_then(|parameter| Value::from_parameter(parameter, field_type));
+        }
+    }
+}
+
+enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Comparison {
+        lhs: Operand,
+        operator: RelationalOperator,
+        rhs: Operand,
+    },
+    Between {
+        field: FieldPath,
+        low: Operand,
+        high: Operand,
+    },
+}
+
+impl Condition {
+    fn evaluate(&self, data: &DynamicData) -> bool {
+        match self {
+            Condition::And(lhs, rhs) => lhs.evaluate(data) && rhs.evaluate(data),
+            Condition::Or(lhs, rhs) => lhs.evaluate(data) || rhs.evaluate(data),
+            Condition::Not(condition) => !condition.evaluate(data),
+            Condition::Comparison { lhs, operator, rhs } => {
+                match (lhs.value(data), rhs.value(data)) {
+                    (Some(lhs), Some(rhs)) => operator.evaluate(&lhs, &rhs),
+                    _ => false,
+                }
+            }
+            Condition::Between { field, low, high } => {
+                match (field.value(data), low.value(data), high.value(data)) {
+                    (Some(value), Some(low), Some(high)) => {
+                        matches!(
+                            compare(&value, &low),
+                            Some(Ordering::Greater | Ordering::Equal)
+                        ) && matches!(
+                            compare(&value, &high),
+                            Some(Ordering::Less | Ordering::Equal)
+                        )
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    fn check_parameters(&self, expression_parameters: &[String]) -> DdsResult<()> {
+        match self {
+            Condition::And(lhs, rhs) | Condition::Or(lhs, rhs) => {
+                lhs.check_parameters(expression_parameters)?;
+                rhs.check_parameters(expression_parameters)
+            }
+            Condition::Not(condition) => condition.check_parameters(expression_parameters),
+            Condition::Comparison { lhs, rhs, .. } => {
+                lhs.check_parameters(expression_parameters)?;
+                rhs.check_parameters(expression_parameters)
+            }
+            Condition::Between { low, high, .. } => {
+                low.check_parameters(expression_parameters)?;
+                high.check_parameters(expression_parameters)
+            }
+        }
+    }
+
+    fn set_parameters(&mut self, expression_parameters: &[String]) {
+        match self {
+            Condition::And(lhs, rhs) | Condition::Or(lhs, rhs) => {
+                lhs.set_parameters(expression_parameters);
+                rhs.set_parameters(expression_parameters);
+            }
+            Condition::Not(condition) => condition.set_parameters(expression_parameters),
+            Condition::Comparison { lhs, rhs, .. } => {
+                lhs.set_parameters(expression_parameters);
+                rhs.set_parameters(expression_parameters);
+            }
+            Condition::Between { low, high, .. } => {
+                low.set_parameters(expression_parameters);
+                high.set_parameters(expression_parameters);
+            }
+        }
+    }
+}
+
+enum ParsedOperand {
+    Identifier(String),
+    Literal(Value),
+    Parameter(usize),
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    type_support: &'a DynamicType<'static>,
+    key_only: bool,
+}
+
+impl Parser<'_> {
+    fn next_token(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_condition(&mut self) -> DdsResult<Condition> {
+        let mut condition = self.parse_and_condition()?;
+        while self.next_if(&Token::Or) {
+            let rhs = self.parse_and_condition()?;
+            condition = Condition::Or(Box::new(condition), Box::new(rhs));
+        }
+        Ok(condition)
+    }
+
+    fn parse_and_condition(&mut self) -> DdsResult<Condition> {
+        let mut condition = self.parse_not_condition()?;
+        while self.next_if(&Token::And) {
+            let rhs = self.parse_not_condition()?;
+            condition = Condition::And(Box::new(condition), Box::new(rhs));
+        }
+        Ok(condition)
+    }
+
+    fn parse_not_condition(&mut self) -> DdsResult<Condition> {
+        if self.next_if(&Token::Not) {
+            Ok(Condition::Not(Box::new(self.parse_not_condition()?)))
+        } else if self.next_if(&Token::LeftParenthesis) {
+            let condition = self.parse_condition()?;
+            if !self.next_if(&Token::RightParenthesis) {
+                return Err(DdsError::BadParameter);
+            }
+            Ok(condition)
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_predicate(&mut self) -> DdsResult<Condition> {
+        let lhs = self.parse_operand()?;
+        let is_negated = self.next_if(&Token::Not);
+        let predicate = match self.next_token() {
+            Some(Token::Between) => {
+                let field = self.field_path(&lhs).ok_or(DdsError::BadParameter)?;
+                let low = self.parse_operand()?;
+                if !self.next_if(&Token::And) {
+                    return Err(DdsError::BadParameter);
+                }
+                let high = self.parse_operand()?;
+                Condition::Between {
+                    low: self.compile_operand(low, None, Some(&field))?,
+                    high: self.compile_operand(high, None, Some(&field))?,
+                    field,
+                }
+            }
+            Some(Token::Like) => self.compile_comparison(lhs, RelationalOperator::Like)?,
+            Some(Token::Operator(operator)) if !is_negated => {
+                self.compile_comparison(lhs, operator)?
+            }
+            _ => return Err(DdsError::BadParameter),
+        };
+        if is_negated {
+            Ok(Condition::Not(Box::new(predicate)))
+        } else {
+            Ok(predicate)
+        }
+    }
+
+    fn parse_operand(&mut self) -> DdsResult<ParsedOperand> {
+        match self.next_token() {
+            Some(Token::Identifier(name)) => Ok(ParsedOperand::Identifier(name)),
+            Some(Token::Integer(v)) => Ok(ParsedOperand::Literal(Value::Integer(v))),
+            Some(Token::Float(v)) => Ok(ParsedOperand::Literal(Value::Float(v))),
+            Some(Token::String(v)) => Ok(ParsedOperand::Literal(Value::Text(v))),
+            Some(Token::True) => Ok(ParsedOperand::Literal(Value::Boolean(true))),
+            Some(Token::False) => Ok(ParsedOperand::Literal(Value::Boolean(false))),
+            Some(Token::Parameter(index)) => Ok(ParsedOperand::Parameter(index)),
+            _ => Err(DdsError::BadParameter),
+        }
+    }
+
+    fn compile_comparison(
+        &mut self,
+        lhs: ParsedOperand,
+        operator: RelationalOperator,
+    ) -> DdsResult<Condition> {
+        let rhs = self.parse_operand()?;
+        let lhs_field = self.field_path(&lhs);
+        let rhs_field = self.field_path(&rhs);
+        match (&lhs_field, &rhs_field) {
+            (None, None) => return Err(DdsError::BadParameter),
+            (Some(l), Some(r)) if value_kind(&l.r#type) != value_kind(&r.r#type) => {
+                return Err(DdsError::BadParameter);
+            }
+            _ => (),
+        }
+        if operator == RelationalOperator::Like
+            && [&lhs_field, &rhs_field]
+                .into_iter()
+                .flatten()
+                .any(|f| value_kind(&f.r#type) != Some(ValueKind::Text))
+        {
+            return Err(DdsError::BadParameter);
+        }
+        let lhs = self.compile_operand(lhs, lhs_field, rhs_field.as_ref())?;
+        let rhs = match lhs {
+            Operand::Field(ref field) => self.compile_operand(rhs, rhs_field, Some(field))?,
+            _ => self.compile_operand(rhs, rhs_field, None)?,
+        };
+        Ok(Condition::Comparison { lhs, operator, rhs })
+    }
+
+    /// Converts a parsed operand into an operand that can be evaluated. Identifiers which
+    /// are not members of the type are interpreted as enumerators of the compared field.
+    fn compile_operand(
+        &self,
+        operand: ParsedOperand,
+        field: Option<FieldPath>,
+        compared_field: Option<&FieldPath>,
+    ) -> DdsResult<Operand> {
+        if let Some(field) = field {
+            value_kind(&field.r#type).ok_or(DdsError::BadParameter)?;
+            return Ok(Operand::Field(field));
+        }
+        let field_type = compared_field.ok_or(DdsError::BadParameter)?.r#type;
+        let field_kind = value_kind(&field_type).ok_or(DdsError::BadParameter)?;
+        match operand {
+            ParsedOperand::Identifier(name) if field_type.get_kind() == TypeKind::ENUM => {
+                enumerator_value(&field_type, &name)
+                    .map(|v| Operand::Literal(Value::Integer(v)))
+                    .ok_or(DdsError::BadParameter)
+            }
+            ParsedOperand::Identifier(_) => Err(DdsError::BadParameter),
+            ParsedOperand::Literal(Value::Text(name))
+                if field_type.get_kind() == TypeKind::ENUM =>
+            {
+                enumerator_value(&field_type, &name)
+                    .map(|v| Operand::Literal(Value::Integer(v)))
+                    .ok_or(DdsError::BadParameter)
+            }
+            ParsedOperand::Literal(value) if value.kind() == field_kind => {
+                Ok(Operand::Literal(value))
+            }
+            ParsedOperand::Literal(_) => Err(DdsError::BadParameter),
+            ParsedOperand::Parameter(index) => Ok(Operand::Parameter {
+                index,
+                field_type,
+                value: None,
+            }),
+        }
+    }
+
+    fn field_path(&mut self, operand: &ParsedOperand) -> Option<FieldPath> {
+        let ParsedOperand::Identifier(name) = operand else {
+            return None;
+        };
+        let mut r#type = *self.type_support;
+        let mut member_id_list = Vec::new();
+        for member_name in name.split('.') {
+            let member = find_member(&r#type, member_name)?;
+            // The key holder keeps the top level key members as they are
+            if member_id_list.is_empty() && !member.descriptor.is_key {
+                self.key_only = false;
+            }
+            member_id_list.push(member.get_id());
+            r#type = member.descriptor.r#type;
+        }
+        Some(FieldPath {
+            member_id_list,
+            r#type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xtypes::type_support::{Type, TypeSupport};
+
+    #[derive(Debug, Clone, Copy, PartialEq, TypeSupport)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[derive(Debug, Clone, PartialEq, TypeSupport)]
+    struct Position {
+        x: i32,
+        y: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, TypeSupport)]
+    struct Shape {
+        name: String,
+        color: Color,
+        pos: Position,
+        size: u16,
+        visible: bool,
+        tag: char,
+    }
+
+    fn shape() -> Shape {
+        Shape {
+            name: String::from("Circle"),
+            color: Color::Green,
+            pos: Position { x: 10, y: -2.5 },
+            size: 30,
+            visible: true,
+            tag: 'c',
+        }
+    }
+
+    fn matches(filter_expression: &str, expression_parameters: &[&str]) -> bool {
+        let expression_parameters: Vec<String> = expression_parameters
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let filter =
+            ContentFilter::new(filter_expression, &Shape::TYPE, &expression_parameters).unwrap();
+        filter.evaluate(&shape().create_dynamic_sample())
+    }
+
+    #[test]
+    fn comparison_operators() {
+        assert!(matches("size = 30", &[]));
+        assert!(matches("size <> 31", &[]));
+        assert!(matches("size != 31", &[]));
+        assert!(matches("size > 29", &[]));
+        assert!(matches("size >= 30", &[]));
+        assert!(matches("size < 31", &[]));
+        assert!(matches("size <= 30", &[]));
+        assert!(!matches("size < 30", &[]));
+        assert!(matches("30 = size", &[]));
+        assert!(matches("size = 30.0", &[]));
+        assert!(matches("size = 0x1e", &[]));
+    }
+
+    #[test]
+    fn logical_operators_and_precedence() {
+        assert!(matches("size = 30 AND name = 'Circle'", &[]));
+        assert!(!matches("size = 31 AND name = 'Circle'", &[]));
+        assert!(matches("size = 31 OR name = 'Circle'", &[]));
+        assert!(matches("NOT size = 31", &[]));
+        assert!(matches(
+            "size = 31 AND name = 'Square' OR visible = TRUE",
+            &[]
+        ));
+        assert!(!matches(
+            "size = 31 AND (name = 'Square' OR visible = TRUE)",
+            &[]
+        ));
+        assert!(matches("size = 30 and not (visible = false)", &[]));
+    }
+
+    #[test]
+    fn between_and_like() {
+        assert!(matches("size BETWEEN 10 AND 30", &[]));
+        assert!(!matches("size NOT BETWEEN 10 AND 30", &[]));
+        assert!(matches(
+            "size BETWEEN %0 AND %1 AND visible = TRUE",
+            &["10", "40"]
+        ));
+        assert!(matches("name LIKE 'Ci%'", &[]));
+        assert!(matches("name LIKE '_ircl_'", &[]));
+        assert!(!matches("name LIKE 'Sq%'", &[]));
+        assert!(matches("name NOT LIKE 'Sq%'", &[]));
+    }
+
+    #[test]
+    fn parameters_nested_members_and_enums() {
+        assert!(matches("name = %0", &["Circle"]));
+        assert!(matches("name = %0", &["'Circle'"]));
+        assert!(matches("pos.x > %0 AND pos.y < %1", &["5", "0"]));
+        assert!(matches("color = Green", &[]));
+        assert!(matches("color = Color::Green", &[]));
+        assert!(matches("color <> 'Blue'", &[]));
+        assert!(matches("color = %0", &["Green"]));
+        assert!(matches("color = %0", &["1"]));
+        assert!(matches("tag = 'c'", &[]));
+        assert!(matches("visible = %0", &["TRUE"]));
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        for filter_expression in [
+            "",
+            "size",
+            "size = ",
+            "size == 30",
+            "unknown = 1",
+            "pos.z = 1",
+            "pos = 1",
+            "size = 'text'",
+            "name > 3",
+            "color = Purple",
+            "(size = 30",
+            "size = 30)",
+            "size = 30 AND",
+            "1 = 1",
+            "size LIKE 'a%'",
+            "name = 'unterminated",
+            "name = `Circle`",
+            "name = `Circle'",
+        ] {
+            assert_eq!(
+                ContentFilter::new(filter_expression, &Shape::TYPE, &[]).err(),
+                Some(DdsError::BadParameter),
+                "{filter_expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let expression_parameters = [String::from("1"), String::from("Green")];
+        let mut filter = ContentFilter::new(
+            "size > %0 AND color = %1",
+            &Shape::TYPE,
+            &expression_parameters,
+        )
+        .unwrap();
+        assert_eq!(
+            ContentFilter::new("size > %0 AND color = %1", &Shape::TYPE, &[]).err(),
+            Some(DdsError::BadParameter)
+        );
+        assert_eq!(
+            filter.set_expression_parameters(&[String::from("1")]),
+            Err(DdsError::BadParameter)
+        );
+        assert_eq!(
+            filter.set_expression_parameters(&[String::from("big"), String::from("Red")]),
+            Err(DdsError::BadParameter)
+        );
+        // A rejected set of parameters leaves the previous ones in place
+        assert!(filter.evaluate(&shape().create_dynamic_sample()));
+        assert_eq!(
+            filter.set_expression_parameters(&[String::from("1"), String::from("Red")]),
+            Ok(())
+        );
+        assert!(!filter.evaluate(&shape().create_dynamic_sample()));
+    }
+
+    #[test]
+    fn like_with_several_wildcards() {
+        assert!(is_like(&['a', 'b', 'c'], &['%', 'b', '%']));
+        assert!(is_like(&['a', 'b', 'c'], &['%', '%', '%']));
+        assert!(is_like(&[], &['%']));
+        assert!(!is_like(&['a', 'b', 'c'], &['%', 'd', '%']));
+        assert!(!is_like(&['a', 'b'], &['a', 'b', '_']));
+        let value = ['a'; 64];
+        let mut pattern = ['%', 'a'].repeat(20);
+        pattern.push('b');
+        assert!(!is_like(&value, &pattern));
+    }
+
+    #[test]
+    fn float128_values_are_compared_as_floats() {
+        // -2.5 and 1.0 in IEEE 754 binary128
+        let minus_two_and_half =
+            i128::from_be_bytes([0xc0, 0x00, 0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let one = i128::from_be_bytes([0x3f, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(float128_to_f64(minus_two_and_half), -2.5);
+        assert_eq!(float128_to_f64(one), 1.0);
+        assert_eq!(
+            compare(
+                &Value::from_storage(&DataStorage::Float128(minus_two_and_half)).unwrap(),
+                &Value::Integer(0)
+            ),
+            Some(Ordering::Less)
+        );
+    }
+    #[test]
+    fn key_only_expressions() {
+        #[derive(TypeSupport)]
+        struct KeyedShape {
+            #[dust_dds(key)]
+            id: u32,
+            size: u16,
+        }
+
+        let filter = ContentFilter::new("id = 1", &KeyedShape::TYPE, &[]).unwrap();
+        assert!(filter.is_key_only());
+        let filter = ContentFilter::new("id = 1 AND size > 2", &KeyedShape::TYPE, &[]).unwrap();
+        assert!(!filter.is_key_only());
+    }
+}
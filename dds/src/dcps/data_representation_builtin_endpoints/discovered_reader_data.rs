@@ -1,9 +1,10 @@
 use super::parameter_id_values::{
-    DEFAULT_EXPECTS_INLINE_QOS, PID_DATA_REPRESENTATION, PID_DEADLINE, PID_DESTINATION_ORDER,
-    PID_DURABILITY, PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS, PID_GROUP_DATA, PID_GROUP_ENTITYID,
-    PID_LATENCY_BUDGET, PID_LIVELINESS, PID_MULTICAST_LOCATOR, PID_OWNERSHIP, PID_PARTICIPANT_GUID,
-    PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY, PID_TIME_BASED_FILTER, PID_TOPIC_DATA,
-    PID_TOPIC_NAME, PID_TYPE_NAME, PID_UNICAST_LOCATOR, PID_USER_DATA,
+    DEFAULT_EXPECTS_INLINE_QOS, PID_CONTENT_FILTER_PROPERTY, PID_DATA_REPRESENTATION, PID_DEADLINE,
+    PID_DESTINATION_ORDER, PID_DURABILITY, PID_ENDPOINT_GUID, PID_EXPECTS_INLINE_QOS,
+    PID_GROUP_DATA, PID_GROUP_ENTITYID, PID_LATENCY_BUDGET, PID_LIVELINESS, PID_MULTICAST_LOCATOR,
+    PID_OWNERSHIP, PID_PARTICIPANT_GUID, PID_PARTITION, PID_PRESENTATION, PID_RELIABILITY,
+    PID_TIME_BASED_FILTER, PID_TOPIC_DATA, PID_TOPIC_NAME, PID_TYPE_NAME, PID_UNICAST_LOCATOR,
+    PID_USER_DATA,
 };
 use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
@@ -20,8 +21,37 @@ use crate::{
         TypeConsistencyEnforcementQosPolicy, UserDataQosPolicy,
     },
     transport::types::{ENTITYID_UNKNOWN, EntityId, Guid, Locator},
+    xtypes::type_support::TypeSupport,
 };
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
+
+pub const DDSSQL_FILTER_CLASS_NAME: &str = "DDSSQL";
+
+#[derive(Debug, PartialEq, Eq, Clone, TypeSupport)]
+#[dust_dds(extensibility = "final", nested)]
+pub struct ContentFilterProperty {
+    pub content_filtered_topic_name: String,
+    pub related_topic_name: String,
+    pub filter_class_name: String,
+    pub filter_expression: String,
+    pub expression_parameters: Vec<String>,
+}
+
+impl ContentFilterProperty {
+    /// Signature identifying this filter in the content filter info of DATA submessages.
+    /// Computed as the MD5 hash of the filter class name, related topic name, filter expression
+    /// and expression parameters.
+    pub fn signature(&self) -> [u8; 16] {
+        let mut context = md5::Context::new();
+        context.consume(self.filter_class_name.as_bytes());
+        context.consume(self.related_topic_name.as_bytes());
+        context.consume(self.filter_expression.as_bytes());
+        for parameter in &self.expression_parameters {
+            context.consume(parameter.as_bytes());
+        }
+        context.compute().into()
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ReaderProxy {
@@ -36,6 +66,7 @@ pub struct ReaderProxy {
 pub struct DiscoveredReaderData {
     pub(crate) dds_subscription_data: SubscriptionBuiltinTopicData,
     pub(crate) reader_proxy: ReaderProxy,
+    pub(crate) content_filter: Option<ContentFilterProperty>,
 }
 
 impl DiscoveredReaderData {
@@ -132,6 +163,9 @@ impl DiscoveredReaderData {
         if self.reader_proxy.expects_inline_qos != DEFAULT_EXPECTS_INLINE_QOS {
             pl.write_cdr_parameter(PID_EXPECTS_INLINE_QOS, self.reader_proxy.expects_inline_qos);
         }
+        if let Some(content_filter) = self.content_filter {
+            pl.write_xcdr1_parameter(PID_CONTENT_FILTER_PROPERTY, content_filter);
+        }
 
         pl.write_sentinel();
         buffer
@@ -185,9 +219,11 @@ impl DiscoveredReaderData {
             expects_inline_qos: pl
                 .get_optional_parameter(PID_EXPECTS_INLINE_QOS, DEFAULT_EXPECTS_INLINE_QOS)?,
         };
+        let content_filter = pl.get_optional_parameter_xdcr1(PID_CONTENT_FILTER_PROPERTY)?;
         Ok(DiscoveredReaderData {
             dds_subscription_data,
             reader_proxy,
+            content_filter,
         })
     }
 }
@@ -247,6 +283,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
         }
         .into_bytes();
 
@@ -320,6 +357,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
         }
         .into_bytes();
 
@@ -367,6 +405,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
             dds_subscription_data: SubscriptionBuiltinTopicData {
                 key: BuiltInTopicKey {
                     value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
@@ -438,6 +477,7 @@ mod tests {
                 multicast_locator_list: vec![],
                 expects_inline_qos: false,
             },
+            content_filter: None,
             dds_subscription_data: SubscriptionBuiltinTopicData {
                 key: BuiltInTopicKey {
                     value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
@@ -495,4 +535,63 @@ mod tests {
 
         assert_eq!(DiscoveredReaderData::from_bytes(&data).unwrap(), expected);
     }
+
+    #[test]
+    fn content_filter_property_roundtrip() {
+        let content_filter = ContentFilterProperty {
+            content_filtered_topic_name: "filtered".to_string(),
+            related_topic_name: "ab".to_string(),
+            filter_class_name: DDSSQL_FILTER_CLASS_NAME.to_string(),
+            filter_expression: "x > %0".to_string(),
+            expression_parameters: vec!["10".to_string()],
+        };
+        let data = DiscoveredReaderData {
+            dds_subscription_data: SubscriptionBuiltinTopicData {
+                key: BuiltInTopicKey {
+                    value: [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0],
+                },
+                participant_key: BuiltInTopicKey {
+                    value: [6, 0, 0, 0, 7, 0, 0, 0, 8, 0, 0, 0, 9, 0, 0, 0],
+                },
+                topic_name: _String {
+                    value: "filtered".to_string(),
+                },
+                type_name: _String {
+                    value: "cd".to_string(),
+                },
+                type_information: None,
+                durability: Default::default(),
+                deadline: Default::default(),
+                latency_budget: Default::default(),
+                liveliness: Default::default(),
+                reliability: DEFAULT_RELIABILITY_QOS_POLICY_DATA_READER_AND_TOPICS,
+                ownership: Default::default(),
+                destination_order: Default::default(),
+                user_data: Default::default(),
+                time_based_filter: Default::default(),
+                presentation: Default::default(),
+                partition: Default::default(),
+                topic_data: Default::default(),
+                group_data: Default::default(),
+                representation: Default::default(),
+                type_consistency: Default::default(),
+            },
+            reader_proxy: ReaderProxy {
+                remote_reader_guid: Guid::new(
+                    [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0],
+                    EntityId::new([4, 0, 0], USER_DEFINED_UNKNOWN),
+                ),
+                remote_group_entity_id: EntityId::new([21, 22, 23], BUILT_IN_WRITER_WITH_KEY),
+                unicast_locator_list: vec![],
+                multicast_locator_list: vec![],
+                expects_inline_qos: false,
+            },
+            content_filter: Some(content_filter),
+        };
+
+        assert_eq!(
+            DiscoveredReaderData::from_bytes(&data.clone().into_bytes()).unwrap(),
+            data
+        );
+    }
 }
@@ -147,6 +147,22 @@ impl<'a> ParameterList<'a> {
         }
     }
 
+    pub(crate) fn get_optional_parameter_xdcr1<T: TypeSupport>(
+        &self,
+        pid: ParameterId,
+    ) -> CdrResult<Option<T>> {
+        if let Some(pid_data) = self.seek_to_pid(pid)? {
+            let mut dynamic_data = deserialize_top_level_type_from_representation_identifier(
+                T::TYPE,
+                [self.data[0], self.data[1]],
+                pid_data,
+            )?;
+            Ok(T::create_sample(&mut dynamic_data))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub(crate) fn get_optional_parameter_xdcr<T: TypeSupport>(
         &self,
         pid: ParameterId,
@@ -1,9 +1,9 @@
 use super::{
     parameter_id_values::{
         DEFAULT_EXPECTS_INLINE_QOS, PID_BUILTIN_ENDPOINT_QOS, PID_BUILTIN_ENDPOINT_SET,
-        PID_DEFAULT_MULTICAST_LOCATOR, PID_DEFAULT_UNICAST_LOCATOR, PID_DOMAIN_ID, PID_DOMAIN_TAG,
-        PID_EXPECTS_INLINE_QOS, PID_METATRAFFIC_MULTICAST_LOCATOR, PID_METATRAFFIC_UNICAST_LOCATOR,
-        PID_PARTICIPANT_GUID, PID_PARTICIPANT_LEASE_DURATION,
+        PID_DEFAULT_MULTICAST_LOCATOR, PID_DEFAULT_UNICAST_LOCATOR, PID_DISCOVERY_SERVER,
+        PID_DOMAIN_ID, PID_DOMAIN_TAG, PID_EXPECTS_INLINE_QOS, PID_METATRAFFIC_MULTICAST_LOCATOR,
+        PID_METATRAFFIC_UNICAST_LOCATOR, PID_PARTICIPANT_GUID, PID_PARTICIPANT_LEASE_DURATION,
         PID_PARTICIPANT_MANUAL_LIVELINESS_COUNT, PID_PROTOCOL_VERSION, PID_USER_DATA, PID_VENDORID,
     },
     rtps_data_representation::CdrResult,
@@ -18,6 +18,7 @@ use crate::{
     infrastructure::{
         domain::DomainId, instance::InstanceHandle, qos_policy::UserDataQosPolicy, time::Duration,
     },
+    rtps::types::VENDOR_ID_S2E,
     transport::types::{Guid, GuidPrefix, Locator, Long, ProtocolVersion, VendorId},
     xtypes::type_support::TypeSupport,
 };
@@ -140,6 +141,7 @@ pub struct SpdpDiscoveredParticipantData {
     pub(crate) participant_proxy: ParticipantProxy,
     pub(crate) lease_duration: Duration,
     pub(crate) discovered_participant_list: Vec<InstanceHandle>,
+    pub(crate) is_discovery_server: bool,
 }
 
 impl SpdpDiscoveredParticipantData {
@@ -203,6 +205,9 @@ impl SpdpDiscoveredParticipantData {
             );
         }
         pl.write_cdr_parameter(PID_PARTICIPANT_LEASE_DURATION, self.lease_duration);
+        if self.is_discovery_server {
+            pl.write_cdr_parameter(PID_DISCOVERY_SERVER, self.is_discovery_server);
+        }
 
         pl.write_sentinel();
 
@@ -241,6 +246,9 @@ impl SpdpDiscoveredParticipantData {
             builtin_endpoint_qos: pl
                 .get_optional_parameter(PID_BUILTIN_ENDPOINT_QOS, BuiltinEndpointQos::default())?,
         };
+        // Vendor specific parameters are only interpreted for participants of the same vendor
+        let is_discovery_server = participant_proxy.vendor_id == VENDOR_ID_S2E
+            && pl.get_optional_parameter(PID_DISCOVERY_SERVER, false)?;
         Ok(SpdpDiscoveredParticipantData {
             dds_participant_data,
             participant_proxy,
@@ -249,6 +257,7 @@ impl SpdpDiscoveredParticipantData {
                 DEFAULT_PARTICIPANT_LEASE_DURATION,
             )?,
             discovered_participant_list: vec![],
+            is_discovery_server,
         })
     }
 }
@@ -297,6 +306,7 @@ mod tests {
             },
             lease_duration: Duration::new(10, 11),
             discovered_participant_list: vec![],
+            is_discovery_server: false,
         }
         .into_bytes();
 
@@ -398,6 +408,7 @@ mod tests {
             },
             lease_duration: DEFAULT_PARTICIPANT_LEASE_DURATION,
             discovered_participant_list: Vec::new(),
+            is_discovery_server: false,
         }
         .into_bytes();
 
@@ -469,6 +480,7 @@ mod tests {
             },
             lease_duration,
             discovered_participant_list: vec![],
+            is_discovery_server: false,
         };
 
         let data = [
@@ -540,4 +552,45 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn discovery_server_flag_is_read_only_from_the_same_vendor() {
+        let data = SpdpDiscoveredParticipantData {
+            dds_participant_data: ParticipantBuiltinTopicData {
+                key: BuiltInTopicKey {
+                    value: [8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 8, 0, 0, 1, 0xc1],
+                },
+                user_data: UserDataQosPolicy::default(),
+            },
+            participant_proxy: ParticipantProxy {
+                domain_id: None,
+                domain_tag: String::from(DEFAULT_DOMAIN_TAG),
+                protocol_version: PROTOCOLVERSION_2_4,
+                guid_prefix: [8; 12],
+                vendor_id: VENDOR_ID_S2E,
+                expects_inline_qos: false,
+                metatraffic_unicast_locator_list: vec![],
+                metatraffic_multicast_locator_list: vec![],
+                default_unicast_locator_list: vec![],
+                default_multicast_locator_list: vec![],
+                available_builtin_endpoints: BuiltinEndpointSet::default(),
+                manual_liveliness_count: 0,
+                builtin_endpoint_qos: BuiltinEndpointQos::default(),
+            },
+            lease_duration: DEFAULT_PARTICIPANT_LEASE_DURATION,
+            discovered_participant_list: vec![],
+            is_discovery_server: true,
+        };
+        assert_eq!(
+            SpdpDiscoveredParticipantData::from_bytes(&data.clone().into_bytes()).unwrap(),
+            data
+        );
+
+        let mut other_vendor_data = data;
+        other_vendor_data.participant_proxy.vendor_id = [1, 2];
+        let result =
+            SpdpDiscoveredParticipantData::from_bytes(&other_vendor_data.clone().into_bytes())
+                .unwrap();
+        assert!(!result.is_discovery_server);
+    }
 }
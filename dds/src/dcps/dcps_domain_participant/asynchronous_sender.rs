@@ -0,0 +1,70 @@
+use crate::{
+    dcps::channels::notification::{NotificationSender, notification},
+    rtps::message_creator::RtpsMessageBatch,
+    runtime::Spawner,
+    transport::{interface::WriteMessage, types::Locator},
+};
+use alloc::sync::Arc;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Sender of the messages of the data writers with an asynchronous publish mode.
+///
+/// The messages are only queued by the DCPS actor and written to the transport by a sender
+/// task, which combines the messages queued while it was writing the previous ones. The task
+/// runs on the sender spawner of the runtime so that a blocking transport does not hold the
+/// DCPS actor.
+pub struct AsynchronousSender {
+    message_writer: Arc<dyn WriteMessage + Send + Sync>,
+    max_message_size: usize,
+    queued_messages: Arc<Mutex<RefCell<RtpsMessageBatch>>>,
+    notification_sender: Option<NotificationSender>,
+}
+
+impl AsynchronousSender {
+    pub fn new(
+        message_writer: Arc<dyn WriteMessage + Send + Sync>,
+        max_message_size: usize,
+    ) -> Self {
+        Self {
+            message_writer,
+            max_message_size,
+            queued_messages: Arc::new(Mutex::new(RefCell::new(RtpsMessageBatch::new()))),
+            notification_sender: None,
+        }
+    }
+
+    /// Spawns the sender task if it is not running yet. The task ends when the sender is dropped.
+    pub fn start(&mut self, spawner: &impl Spawner) {
+        if self.notification_sender.is_some() {
+            return;
+        }
+        let (notification_sender, mut notification_receiver) = notification();
+        let message_writer = self.message_writer.clone();
+        let max_message_size = self.max_message_size;
+        let queued_messages = self.queued_messages.clone();
+        spawner.spawn(async move {
+            while (&mut notification_receiver).await.is_ok() {
+                let messages = critical_section::with(|cs| {
+                    core::mem::take(&mut *queued_messages.borrow(cs).borrow_mut())
+                });
+                messages.flush(message_writer.as_ref(), max_message_size);
+            }
+        });
+        self.notification_sender = Some(notification_sender);
+    }
+}
+
+impl WriteMessage for AsynchronousSender {
+    fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+        critical_section::with(|cs| {
+            self.queued_messages
+                .borrow(cs)
+                .borrow()
+                .write_message(buf, locators)
+        });
+        if let Some(notification_sender) = &self.notification_sender {
+            notification_sender.notify();
+        }
+    }
+}
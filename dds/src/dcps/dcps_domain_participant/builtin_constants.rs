@@ -3,9 +3,10 @@ use crate::{
         qos::{DataReaderQos, DataWriterQos},
         qos_policy::{
             DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
-            LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy,
-            OwnershipStrengthQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, DurabilityServiceQosPolicy,
+            HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy,
+            LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
+            PublishModeQosPolicy, ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy,
             ReliabilityQosPolicyKind, ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy,
             TransportPriorityQosPolicy, TypeConsistencyEnforcementQosPolicy, UserDataQosPolicy,
             WriterDataLifecycleQosPolicy,
@@ -139,6 +140,7 @@ pub const TYPE_LOOKUP_WRITER_QOS: DataWriterQos = DataWriterQos {
     durability: DurabilityQosPolicy {
         kind: DurabilityQosPolicyKind::Volatile,
     },
+    durability_service: DurabilityServiceQosPolicy::const_default(),
     history: HistoryQosPolicy {
         kind: HistoryQosPolicyKind::KeepAll,
     },
@@ -158,4 +160,5 @@ pub const TYPE_LOOKUP_WRITER_QOS: DataWriterQos = DataWriterQos {
     transport_priority: TransportPriorityQosPolicy::const_default(),
     writer_data_lifecycle: WriterDataLifecycleQosPolicy::const_default(),
     representation: DataRepresentationQosPolicy::const_default(),
+    publish_mode: PublishModeQosPolicy::const_default(),
 };
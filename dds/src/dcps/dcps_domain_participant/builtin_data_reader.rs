@@ -130,6 +130,7 @@ impl<R: RtpsReader, T: Type> BuiltinDataReader<R, T> {
                     change_instance_handle.into(),
                     cache_change.source_timestamp.map(Into::into),
                     reception_timestamp,
+                    None,
                 )
                 .ok();
         }
@@ -1,6 +1,7 @@
 use crate::{
     builtin_topics::{DCPS_PARTICIPANT, DCPS_PUBLICATION, DCPS_SUBSCRIPTION, DCPS_TOPIC},
     dcps::dcps_domain_participant::data_writer_entity::DataWriterEntity,
+    dds_async::configuration::DiscoveryMode,
     infrastructure::{
         instance::InstanceHandle,
         qos::DataWriterQos,
@@ -10,7 +11,10 @@ use crate::{
         },
         time::{Duration, DurationKind},
     },
-    rtps::{stateful_writer::RtpsStatefulWriter, stateless_writer::RtpsStatelessWriter},
+    rtps::{
+        stateful_writer::{RtpsStatefulWriter, WriterTiming},
+        stateless_writer::RtpsStatelessWriter,
+    },
     transport::{
         interface::RtpsTransportParticipant,
         types::{Guid, GuidPrefix},
@@ -68,12 +72,27 @@ pub struct BuiltinPublisher {
 }
 
 impl BuiltinPublisher {
-    pub fn new(guid_prefix: GuidPrefix, transport: &RtpsTransportParticipant) -> Self {
+    pub fn new(
+        guid_prefix: GuidPrefix,
+        transport: &RtpsTransportParticipant,
+        discovery_mode: DiscoveryMode,
+        writer_timing: WriterTiming,
+    ) -> Self {
         let mut dcps_participant_transport_writer = RtpsStatelessWriter::new(Guid::new(
             guid_prefix,
             ENTITYID_SPDP_BUILTIN_PARTICIPANT_WRITER,
         ));
-        for &discovery_locator in &transport.metatraffic_multicast_locator_list {
+        // Discovery clients are only announced to the servers
+        let multicast_locator_list = match discovery_mode {
+            DiscoveryMode::Simple | DiscoveryMode::Server => {
+                transport.metatraffic_multicast_locator_list.as_slice()
+            }
+            DiscoveryMode::Client => &[],
+        };
+        for &discovery_locator in multicast_locator_list
+            .iter()
+            .chain(&transport.initial_peer_locator_list)
+        {
             dcps_participant_transport_writer.reader_locator_add(discovery_locator);
         }
         let dcps_participant_writer = DataWriterEntity::new(
@@ -86,6 +105,7 @@ impl BuiltinPublisher {
         let dcps_topics_transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid_prefix, ENTITYID_SEDP_BUILTIN_TOPICS_ANNOUNCER),
             transport.fragment_size,
+            writer_timing,
         );
         let dcps_topics_writer = DataWriterEntity::new(
             InstanceHandle::new(dcps_topics_transport_writer.guid().into()),
@@ -97,6 +117,7 @@ impl BuiltinPublisher {
         let dcps_publications_transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid_prefix, ENTITYID_SEDP_BUILTIN_PUBLICATIONS_ANNOUNCER),
             transport.fragment_size,
+            writer_timing,
         );
         let dcps_publications_writer = DataWriterEntity::new(
             InstanceHandle::new(dcps_publications_transport_writer.guid().into()),
@@ -108,6 +129,7 @@ impl BuiltinPublisher {
         let dcps_subscriptions_transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid_prefix, ENTITYID_SEDP_BUILTIN_SUBSCRIPTIONS_ANNOUNCER),
             transport.fragment_size,
+            writer_timing,
         );
         let dcps_subscriptions_writer = DataWriterEntity::new(
             InstanceHandle::new(dcps_subscriptions_transport_writer.guid().into()),
@@ -119,6 +141,7 @@ impl BuiltinPublisher {
         let type_lookup_request_transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid_prefix, ENTITYID_TL_SVC_REQ_WRITER),
             transport.fragment_size,
+            writer_timing,
         );
         let type_lookup_request_writer = DataWriterEntity::new(
             InstanceHandle::new(type_lookup_request_transport_writer.guid().into()),
@@ -130,6 +153,7 @@ impl BuiltinPublisher {
         let type_lookup_reply_transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid_prefix, ENTITYID_TL_SVC_REPLY_WRITER),
             transport.fragment_size,
+            writer_timing,
         );
         let type_lookup_reply_writer = DataWriterEntity::new(
             InstanceHandle::new(type_lookup_reply_transport_writer.guid().into()),
@@ -0,0 +1,266 @@
+use crate::{
+    infrastructure::instance::InstanceHandle,
+    rtps::cache_change::writer_group_digest,
+    transport::types::{CacheChange, CoherentSet, Guid, SequenceNumber},
+};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+struct BufferedCoherentSet {
+    coherent_set: CoherentSet,
+    is_closed: bool,
+    changes: Vec<CacheChange>,
+}
+
+enum BufferedChange {
+    Change(CacheChange),
+    CoherentSet(BufferedCoherentSet),
+}
+
+/// Changes received by a data reader from a single writer. They are kept in
+/// the order they were received so that changes following a coherent set are
+/// not made available before the set itself.
+struct ChangeStream {
+    reader_handle: InstanceHandle,
+    writer_guid: Guid,
+    /// GUID of the publisher of the writer, which scopes the group sequence numbers
+    writer_group: Guid,
+    /// Highest group sequence number received from the writer
+    last_group_sequence_number: Option<SequenceNumber>,
+    changes: VecDeque<BufferedChange>,
+}
+
+/// A writer matched with one of the data readers of the subscriber
+#[derive(Clone, Copy)]
+pub struct MatchedWriter {
+    pub writer_guid: Guid,
+    pub writer_group: Guid,
+}
+
+/// Holds the changes received by the data readers of a subscriber with coherent
+/// access until the coherent sets they belong to are complete.
+pub struct CoherentSetBuffer {
+    streams: Vec<ChangeStream>,
+}
+
+impl CoherentSetBuffer {
+    pub const fn new() -> Self {
+        Self {
+            streams: Vec::new(),
+        }
+    }
+
+    pub fn add_change(
+        &mut self,
+        reader_handle: InstanceHandle,
+        writer_group: Guid,
+        cache_change: CacheChange,
+    ) {
+        let stream = match self.streams.iter().position(|s| {
+            s.reader_handle == reader_handle && s.writer_guid == cache_change.writer_guid
+        }) {
+            Some(index) => &mut self.streams[index],
+            None => {
+                self.streams.push(ChangeStream {
+                    reader_handle,
+                    writer_guid: cache_change.writer_guid,
+                    writer_group,
+                    last_group_sequence_number: None,
+                    changes: VecDeque::new(),
+                });
+                self.streams.last_mut().expect("Stream was just added")
+            }
+        };
+        if let Some(group_sequence_number) = cache_change
+            .coherent_set
+            .and_then(|s| s.group_sequence_number)
+        {
+            stream.last_group_sequence_number = stream
+                .last_group_sequence_number
+                .max(Some(group_sequence_number));
+        }
+
+        let last_set = match stream.changes.back_mut() {
+            Some(BufferedChange::CoherentSet(s)) => Some(s),
+            _ => None,
+        };
+        match (last_set, cache_change.coherent_set) {
+            // The end of the set is only a marker and carries no data for the reader
+            (Some(last_set), Some(coherent_set))
+                if coherent_set.end_of_set
+                    && last_set.coherent_set.sequence_number == coherent_set.sequence_number =>
+            {
+                last_set.is_closed = true;
+                last_set.coherent_set.writer_set = coherent_set.writer_set;
+            }
+            // All the changes of the set were filtered out by the writer. The empty
+            // set is kept since the writer still took part in its group set.
+            (last_set, Some(coherent_set)) if coherent_set.end_of_set => {
+                if let Some(last_set) = last_set {
+                    last_set.is_closed = true;
+                }
+                stream
+                    .changes
+                    .push_back(BufferedChange::CoherentSet(BufferedCoherentSet {
+                        coherent_set,
+                        is_closed: true,
+                        changes: Vec::new(),
+                    }));
+            }
+            (Some(last_set), Some(coherent_set))
+                if !last_set.is_closed
+                    && last_set.coherent_set.sequence_number == coherent_set.sequence_number =>
+            {
+                last_set.changes.push(cache_change);
+            }
+            (last_set, coherent_set) => {
+                // A change which is not part of the set being received
+                // implicitly ends that set
+                if let Some(last_set) = last_set {
+                    last_set.is_closed = true;
+                }
+                stream.changes.push_back(match coherent_set {
+                    Some(coherent_set) => BufferedChange::CoherentSet(BufferedCoherentSet {
+                        coherent_set,
+                        is_closed: false,
+                        changes: vec![cache_change],
+                    }),
+                    None => BufferedChange::Change(cache_change),
+                });
+            }
+        }
+    }
+
+    pub fn remove_reader(&mut self, reader_handle: &InstanceHandle) {
+        self.streams.retain(|s| &s.reader_handle != reader_handle);
+    }
+
+    /// Discards the sets which can no longer be completed since their writer is
+    /// no longer matched with the reader.
+    pub fn remove_unmatched_writers(
+        &mut self,
+        reader_handle: &InstanceHandle,
+        mut is_matched: impl FnMut(Guid) -> bool,
+    ) {
+        for stream in self
+            .streams
+            .iter_mut()
+            .filter(|s| &s.reader_handle == reader_handle)
+        {
+            if !is_matched(stream.writer_guid) {
+                stream.changes.retain(|c| match c {
+                    BufferedChange::Change(_) => true,
+                    BufferedChange::CoherentSet(s) => s.is_closed,
+                });
+            }
+        }
+        self.streams.retain(|s| {
+            &s.reader_handle != reader_handle || is_matched(s.writer_guid) || !s.changes.is_empty()
+        });
+    }
+
+    /// A group coherent set is complete once the sets of all the writers which
+    /// took part in it are closed. The writers are identified by the digest
+    /// sent with the end of the set. When the subscriber does not read all the
+    /// topics written in the set the digest can't match, so the set is then
+    /// complete once every matched writer of the publisher has either closed its
+    /// part or has already written past the set.
+    fn is_group_complete(
+        &self,
+        writer_group: Guid,
+        group_sequence_number: SequenceNumber,
+        matched_writers: &[MatchedWriter],
+    ) -> bool {
+        let group_streams = self
+            .streams
+            .iter()
+            .filter(move |stream| stream.writer_group == writer_group);
+        let group_sets = group_streams.clone().flat_map(|stream| {
+            stream.changes.iter().filter_map(move |c| match c {
+                BufferedChange::CoherentSet(s)
+                    if s.coherent_set.group_sequence_number == Some(group_sequence_number) =>
+                {
+                    Some((stream.writer_guid, s))
+                }
+                _ => None,
+            })
+        });
+        if group_sets.clone().any(|(_, s)| !s.is_closed) {
+            return false;
+        }
+        if let Some(writer_set) = group_sets
+            .clone()
+            .find_map(|(_, s)| s.coherent_set.writer_set)
+        {
+            if writer_group_digest(group_sets.clone().map(|(guid, _)| guid)) == writer_set {
+                return true;
+            }
+        }
+        matched_writers
+            .iter()
+            .filter(|w| w.writer_group == writer_group)
+            .all(|w| {
+                group_sets.clone().any(|(g, _)| g == w.writer_guid)
+                    || group_streams.clone().any(|stream| {
+                        stream.writer_guid == w.writer_guid
+                            && stream.last_group_sequence_number > Some(group_sequence_number)
+                    })
+            })
+    }
+
+    /// Removes from the buffer and returns the changes which can be made
+    /// available to the data readers together with the handle of their reader.
+    pub fn take_available_changes(
+        &mut self,
+        is_group_access_scope: bool,
+        matched_writers: &[MatchedWriter],
+    ) -> Vec<(InstanceHandle, CacheChange)> {
+        let mut complete_groups = Vec::new();
+        if is_group_access_scope {
+            for stream in &self.streams {
+                for change in &stream.changes {
+                    if let BufferedChange::CoherentSet(s) = change {
+                        if let Some(group_sequence_number) = s.coherent_set.group_sequence_number {
+                            let group = (stream.writer_group, group_sequence_number);
+                            if !complete_groups.contains(&group)
+                                && self.is_group_complete(group.0, group.1, matched_writers)
+                            {
+                                complete_groups.push(group);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut available_changes = Vec::new();
+        for stream in &mut self.streams {
+            while let Some(change) = stream.changes.front() {
+                let is_available = match change {
+                    BufferedChange::Change(_) => true,
+                    BufferedChange::CoherentSet(s) => {
+                        s.is_closed
+                            && match s.coherent_set.group_sequence_number {
+                                Some(group_sequence_number) if is_group_access_scope => {
+                                    complete_groups
+                                        .contains(&(stream.writer_group, group_sequence_number))
+                                }
+                                _ => true,
+                            }
+                    }
+                };
+                if !is_available {
+                    break;
+                }
+                match stream.changes.pop_front() {
+                    Some(BufferedChange::Change(c)) => {
+                        available_changes.push((stream.reader_handle, c))
+                    }
+                    Some(BufferedChange::CoherentSet(s)) => available_changes
+                        .extend(s.changes.into_iter().map(|c| (stream.reader_handle, c))),
+                    None => (),
+                }
+            }
+        }
+        available_changes
+    }
+}
@@ -1,22 +1,30 @@
-use alloc::{string::String, vec::Vec};
+use alloc::vec::Vec;
 use tracing::info;
 
 use crate::{
     dcps::{
         dcps_domain_participant::{
-            data_reader_entity::AddChangeResult, participant_entity::DcpsDomainParticipant,
-            reader_methods::deserialize_topic_type, topic_entity::get_topic_type_support,
+            coherent_set_buffer::MatchedWriter,
+            data_reader_entity::{AddChangeResult, GroupOrder, ReaderSample},
+            participant_entity::DcpsDomainParticipant,
+            reader_methods::deserialize_topic_type,
+            topic_entity::get_topic_type_support,
+            user_defined_publisher::publication_message_writer,
         },
         listeners::domain_participant_listener::ListenerMail,
         xtypes_glue::key_and_instance_handle::{
             KeyHolderType, get_instance_handle_from_dynamic_data,
         },
     },
+    dds_async::configuration::FlowControllerSchedulingPolicy,
     dds_async::{
         data_reader::DataReaderAsync, domain_participant::DomainParticipantAsync,
         subscriber::SubscriberAsync,
     },
-    infrastructure::{instance::InstanceHandle, status::StatusKind},
+    infrastructure::{
+        instance::InstanceHandle, qos_policy::PresentationQosPolicyAccessScopeKind,
+        status::StatusKind, time::Time,
+    },
     rtps::message_receiver::MessageReceiver,
     rtps_messages::{
         overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
@@ -26,7 +34,10 @@ use crate::{
         },
     },
     runtime::{Clock, DdsRuntime},
-    transport::types::{ChangeKind, Guid},
+    transport::{
+        interface::WriteMessage,
+        types::{CacheChange, ChangeKind, Guid},
+    },
     xtypes::deserializer::deserialize_top_level_type,
 };
 
@@ -44,6 +55,80 @@ impl DcpsDomainParticipant {
         let locally_created_topic_list = &self.domain_participant.locally_created_topic_list;
 
         for subscriber in &mut self.domain_participant.user_defined_subscriber_list {
+            let presentation = &subscriber.qos.presentation;
+            let is_group_access_scope =
+                presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group;
+            // With GROUP ordered access the order of the samples across the readers is
+            // also kept while the application is accessing them
+            if (presentation.coherent_access
+                && presentation.access_scope != PresentationQosPolicyAccessScopeKind::Instance)
+                || (presentation.ordered_access && is_group_access_scope)
+            {
+                let is_coherent_access = presentation.coherent_access;
+                for data_reader in &mut subscriber.data_reader_list {
+                    for mut cache_change in
+                        core::mem::take(data_reader.transport_reader.changes_mut())
+                    {
+                        // Without coherent access the changes of a set are not held back
+                        if !is_coherent_access {
+                            if cache_change.is_end_of_coherent_set() {
+                                continue;
+                            }
+                            cache_change.coherent_set = None;
+                        }
+                        let Some(writer_group) = data_reader
+                            .transport_reader
+                            .matched_writer_lookup(cache_change.writer_guid)
+                            .map(|writer_proxy| writer_proxy.remote_group_guid())
+                        else {
+                            continue;
+                        };
+                        subscriber.coherent_set_buffer.add_change(
+                            data_reader.instance_handle,
+                            writer_group,
+                            cache_change,
+                        );
+                    }
+                    let data_reader_handle = data_reader.instance_handle;
+                    subscriber.coherent_set_buffer.remove_unmatched_writers(
+                        &data_reader_handle,
+                        |writer_guid| {
+                            data_reader
+                                .transport_reader
+                                .matched_writer_lookup(writer_guid)
+                                .is_some()
+                        },
+                    );
+                }
+                // The data in the readers is not modified while the application is accessing it
+                if subscriber.access_count == 0 {
+                    let matched_writers: Vec<MatchedWriter> = subscriber
+                        .data_reader_list
+                        .iter()
+                        .flat_map(|dr| dr.transport_reader.matched_writers())
+                        .map(|writer_proxy| MatchedWriter {
+                            writer_guid: writer_proxy.remote_writer_guid(),
+                            writer_group: writer_proxy.remote_group_guid(),
+                        })
+                        .collect();
+                    for (data_reader_handle, cache_change) in subscriber
+                        .coherent_set_buffer
+                        .take_available_changes(is_group_access_scope, &matched_writers)
+                    {
+                        if let Some(data_reader) = subscriber
+                            .data_reader_list
+                            .iter_mut()
+                            .find(|dr| dr.instance_handle == data_reader_handle)
+                        {
+                            data_reader
+                                .transport_reader
+                                .changes_mut()
+                                .push(cache_change);
+                        }
+                    }
+                }
+            }
+
             let subscriber_handle = subscriber.instance_handle;
             let subscriber_listener_mask = subscriber.listener_mask;
             let subscriber_listener_sender = subscriber.listener_sender.clone();
@@ -53,246 +138,218 @@ impl DcpsDomainParticipant {
             );
             'data_readers: for data_reader in data_reader_list {
                 let changes = core::mem::take(data_reader.transport_reader.changes_mut());
+                let pending_samples = data_reader.take_due_pending_samples(reception_timestamp);
                 let data_reader_handle = &data_reader.instance_handle.clone();
                 tracing::trace!(subscriber_handle=?subscriber_handle, data_reader_handle=?data_reader_handle, "Processing {} reader cache changes", changes.len());
+                if changes.is_empty() && pending_samples.is_empty() {
+                    continue;
+                }
 
-                for cache_change in changes {
-                    if let Some(matched_participant) = discovered_participant_list
-                        .iter_mut()
-                        .find(|x| x.guid_prefix == cache_change.writer_guid.prefix())
-                    {
-                        matched_participant.last_communication_timestamp = runtime.clock().now();
-                    }
-                    let Some(type_support) = get_topic_type_support(
-                        &data_reader.topic_name,
-                        content_filtered_topic_list,
-                        locally_created_topic_list,
-                    ) else {
-                        tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find type support for reader");
+                let Some(type_support) = get_topic_type_support(
+                    &data_reader.topic_name,
+                    content_filtered_topic_list,
+                    locally_created_topic_list,
+                ) else {
+                    tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find type support for reader");
+                    continue 'data_readers;
+                };
+
+                let content_filtered_topic = content_filtered_topic_list
+                    .iter()
+                    .find(|t| t.topic_name == data_reader.topic_name);
+                let (topic_name, type_name) = if let Some(content_filtered_topic) =
+                    content_filtered_topic
+                {
+                    let Some(reader_topic) = locally_created_topic_list
+                        .iter()
+                        .find(|t| t.topic_name == content_filtered_topic.related_topic_name)
+                    else {
+                        tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find related_topic_name for reader");
+                        continue 'data_readers;
+                    };
+                    (
+                        reader_topic.type_name.clone(),
+                        reader_topic.topic_name.clone(),
+                    )
+                } else {
+                    let Some(reader_topic) = locally_created_topic_list
+                        .iter()
+                        .find(|t| t.topic_name == data_reader.topic_name)
+                    else {
+                        tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find topic for reader");
                         continue 'data_readers;
                     };
+                    (
+                        data_reader.topic_name.clone(),
+                        reader_topic.type_name.clone(),
+                    )
+                };
+
+                let the_participant =
+                    DomainParticipantAsync::new(dcps_sender, domain_id, dp_instance_handle);
+                let the_subscriber = SubscriberAsync::new(subscriber_handle, the_participant);
+                let the_reader = DataReaderAsync::new(
+                    *data_reader_handle,
+                    the_subscriber.clone(),
+                    topic_name,
+                    type_name,
+                );
 
-                    let (topic_name, type_name) = if let Some(content_filtered_topic) =
-                        content_filtered_topic_list
-                            .iter()
-                            .find(|t| t.topic_name == data_reader.topic_name)
-                    {
-                        let Some(reader_topic) = locally_created_topic_list
-                            .iter()
-                            .find(|t| t.topic_name == content_filtered_topic.related_topic_name)
-                        else {
-                            tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find related_topic_name for reader");
-                            continue 'data_readers;
-                        };
-                        if cache_change.kind == ChangeKind::Alive {
-                            let Some(data) = deserialize_topic_type(
-                                &data_reader.topic_name,
-                                *type_support,
-                                cache_change.data_value.as_ref(),
-                            ) else {
-                                continue 'data_readers;
-                            };
-                            enum Operator {
-                                LessThan,
-                                Equal,
+                // The samples held back by the time based filter are delivered before the
+                // newly received changes since their minimum separation already elapsed
+                let received_samples = pending_samples
+                    .into_iter()
+                    .map(ReceivedSample::TimeBasedFilterPending)
+                    .chain(changes.into_iter().map(ReceivedSample::Change));
+                for received_sample in received_samples {
+                    let (
+                        change_instance_handle,
+                        previous_instance_state,
+                        change_kind,
+                        data_value,
+                        add_change_result,
+                    ) = match received_sample {
+                        ReceivedSample::TimeBasedFilterPending(sample) => {
+                            let change_instance_handle = sample.instance_handle;
+                            let previous_instance_state =
+                                data_reader.get_instance_state(&change_instance_handle);
+                            let change_kind = sample.kind;
+                            let data_value = sample.data_value.clone();
+                            (
+                                change_instance_handle,
+                                previous_instance_state,
+                                change_kind,
+                                data_value,
+                                data_reader.add_sample(sample),
+                            )
+                        }
+                        ReceivedSample::Change(cache_change) => {
+                            if let Some(matched_participant) = discovered_participant_list
+                                .iter_mut()
+                                .find(|x| x.guid_prefix == cache_change.writer_guid.prefix())
+                            {
+                                matched_participant.last_communication_timestamp =
+                                    runtime.clock().now();
+                            }
+                            if cache_change.is_end_of_coherent_set() {
+                                continue;
                             }
 
-                            impl Operator {
-                                fn to_str(&self) -> &'static str {
-                                    match self {
-                                        Self::Equal => "=",
-                                        Self::LessThan => "<=",
-                                    }
-                                }
-
-                                fn compare_string(&self, lhs: &String, rhs: &String) -> bool {
-                                    match self {
-                                        Self::Equal => lhs == rhs,
-                                        Self::LessThan => lhs <= rhs,
-                                    }
-                                }
-                                fn compare_int32(&self, lhs: &i32, rhs: &i32) -> bool {
-                                    match self {
-                                        Self::Equal => lhs == rhs,
-                                        Self::LessThan => lhs <= rhs,
+                            if let Some(content_filtered_topic) = content_filtered_topic {
+                                // Samples already filtered by the writer with the same filter are
+                                // not evaluated again
+                                let is_filtered_by_writer = cache_change
+                                    .filter_signatures
+                                    .contains(&content_filtered_topic.filter_signature);
+                                if cache_change.kind == ChangeKind::Alive && !is_filtered_by_writer
+                                {
+                                    let Some(data) = deserialize_topic_type(
+                                        &data_reader.topic_name,
+                                        *type_support,
+                                        cache_change.data_value.as_ref(),
+                                    ) else {
+                                        continue 'data_readers;
+                                    };
+                                    if !content_filtered_topic.content_filter.evaluate(&data) {
+                                        continue;
                                     }
                                 }
                             }
-
-                            let mut operators = [Operator::LessThan, Operator::Equal].iter();
-                            let filter = loop {
-                                if let Some(operator) = operators.next() {
-                                    if let Some((variable_name, _)) = content_filtered_topic
-                                        .filter_expression
-                                        .split_once(operator.to_str())
-                                    {
-                                        break Some((variable_name, operator));
-                                    }
-                                } else {
-                                    break None;
-                                };
-                            };
-
-                            if let Some((variable_name, comparison_function)) = filter {
-                                let Some(member_id) =
-                                    data.get_member_id_by_name(variable_name.trim())
-                                else {
-                                    continue 'data_readers;
-                                };
-                                let Ok(member_descriptor) = data.get_descriptor(member_id) else {
-                                    continue 'data_readers;
-                                };
-                                match member_descriptor.r#type.get_kind() {
-                                    crate::xtypes::dynamic_type::TypeKind::NONE => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::BOOLEAN => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::BYTE => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::INT16 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::INT32 => {
-                                        let member_value = data.get_int32_value(member_id).unwrap();
-                                        if !comparison_function.compare_int32(
-                                            member_value,
-                                            &content_filtered_topic.expression_parameters[0]
-                                                .parse()
-                                                .expect("valid number"),
-                                        ) {
+                            let change_instance_handle = if let Some(i) =
+                                cache_change.instance_handle
+                            {
+                                InstanceHandle::new(i)
+                            } else {
+                                match cache_change.kind {
+                                    ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                                        let Some(data_value) = deserialize_topic_type(
+                                            &data_reader.topic_name,
+                                            *type_support,
+                                            cache_change.data_value.as_ref(),
+                                        ) else {
+                                            tracing::warn!(
+                                                "Failed to deserialize user defined data"
+                                            );
                                             continue 'data_readers;
-                                        }
-                                    }
-                                    crate::xtypes::dynamic_type::TypeKind::INT64 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::UINT16 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::UINT32 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::UINT64 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::FLOAT32 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::FLOAT64 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::FLOAT128 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::INT8 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::UINT8 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::CHAR8 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::CHAR16 => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::STRING8
-                                    | crate::xtypes::dynamic_type::TypeKind::STRING16 => {
-                                        let member_value =
-                                            data.get_string_value(member_id).unwrap();
-                                        if !comparison_function.compare_string(
-                                            member_value,
-                                            &content_filtered_topic.expression_parameters[0],
-                                        ) {
+                                        };
+                                        let Ok(instance_handle) =
+                                            get_instance_handle_from_dynamic_data(&data_value)
+                                        else {
+                                            tracing::warn!(
+                                                "Failed to get instance handle from dynamic_data"
+                                            );
                                             continue 'data_readers;
-                                        }
+                                        };
+                                        instance_handle
                                     }
-                                    crate::xtypes::dynamic_type::TypeKind::ALIAS => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::ENUM => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::BITMASK => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::ANNOTATION => {
-                                        todo!()
+                                    ChangeKind::NotAliveDisposed
+                                    | ChangeKind::NotAliveUnregistered
+                                    | ChangeKind::NotAliveDisposedUnregistered => {
+                                        let mut dynamic_members = Vec::new();
+                                        let Ok(key_holder) = KeyHolderType::from_dynamic_type(
+                                            type_support,
+                                            &mut dynamic_members,
+                                        ) else {
+                                            tracing::warn!("Failed to create key holder");
+                                            continue 'data_readers;
+                                        };
+
+                                        let Ok(data_value) = deserialize_top_level_type(
+                                            *key_holder.as_dynamic_type(),
+                                            cache_change.data_value.as_ref(),
+                                        ) else {
+                                            tracing::warn!(
+                                                "Failed to deserialize disposed user defined data"
+                                            );
+                                            continue 'data_readers;
+                                        };
+
+                                        let Ok(instance_handle) =
+                                            get_instance_handle_from_dynamic_data(&data_value)
+                                        else {
+                                            tracing::warn!(
+                                                "Failed to deserialize disposed key user defined data"
+                                            );
+                                            continue 'data_readers;
+                                        };
+                                        instance_handle
                                     }
-                                    crate::xtypes::dynamic_type::TypeKind::STRUCTURE => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::UNION => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::BITSET => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::SEQUENCE => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::ARRAY => todo!(),
-                                    crate::xtypes::dynamic_type::TypeKind::MAP => todo!(),
                                 }
-                            } else {
-                                continue 'data_readers;
                             };
-                        }
-                        (
-                            reader_topic.type_name.clone(),
-                            reader_topic.topic_name.clone(),
-                        )
-                    } else {
-                        let Some(reader_topic) = locally_created_topic_list
-                            .iter()
-                            .find(|t| t.topic_name == data_reader.topic_name)
-                        else {
-                            tracing::warn!(topic_name = ?data_reader.topic_name, "Failed to find topic for reader");
-                            continue 'data_readers;
-                        };
-                        (
-                            data_reader.topic_name.clone(),
-                            reader_topic.type_name.clone(),
-                        )
-                    };
-
-                    let change_instance_handle = if let Some(i) = cache_change.instance_handle {
-                        InstanceHandle::new(i)
-                    } else {
-                        match cache_change.kind {
-                            ChangeKind::Alive | ChangeKind::AliveFiltered => {
-                                let Some(data_value) = deserialize_topic_type(
-                                    &data_reader.topic_name,
-                                    *type_support,
-                                    cache_change.data_value.as_ref(),
-                                ) else {
-                                    tracing::warn!("Failed to deserialize user defined data");
-                                    continue 'data_readers;
-                                };
-                                let Ok(instance_handle) =
-                                    get_instance_handle_from_dynamic_data(&data_value)
-                                else {
-                                    tracing::warn!(
-                                        "Failed to get instance handle from dynamic_data"
-                                    );
-                                    continue 'data_readers;
-                                };
-                                instance_handle
-                            }
-                            ChangeKind::NotAliveDisposed
-                            | ChangeKind::NotAliveUnregistered
-                            | ChangeKind::NotAliveDisposedUnregistered => {
-                                let mut dynamic_members = Vec::new();
-                                let Ok(key_holder) = KeyHolderType::from_dynamic_type(
-                                    type_support,
-                                    &mut dynamic_members,
-                                ) else {
-                                    tracing::warn!("Failed to create key holder");
-                                    continue 'data_readers;
-                                };
 
-                                let Ok(data_value) = deserialize_top_level_type(
-                                    *key_holder.as_dynamic_type(),
-                                    cache_change.data_value.as_ref(),
-                                ) else {
-                                    tracing::warn!(
-                                        "Failed to deserialize disposed user defined data"
-                                    );
-                                    continue 'data_readers;
-                                };
-
-                                let Ok(instance_handle) =
-                                    get_instance_handle_from_dynamic_data(&data_value)
-                                else {
-                                    tracing::warn!(
-                                        "Failed to deserialize disposed key user defined data"
-                                    );
-                                    continue 'data_readers;
-                                };
-                                instance_handle
-                            }
+                            let group_order = cache_change
+                                .group_sequence_number
+                                .zip(
+                                    data_reader
+                                        .transport_reader
+                                        .matched_writer_lookup(cache_change.writer_guid),
+                                )
+                                .map(|(group_sequence_number, writer_proxy)| GroupOrder {
+                                    writer_group: writer_proxy.remote_group_guid(),
+                                    group_sequence_number,
+                                });
+                            let previous_instance_state =
+                                data_reader.get_instance_state(&change_instance_handle);
+                            let change_kind = cache_change.kind;
+                            let data_value = cache_change.data_value.clone();
+                            (
+                                change_instance_handle,
+                                previous_instance_state,
+                                change_kind,
+                                data_value,
+                                data_reader.add_reader_change(
+                                    cache_change.writer_guid,
+                                    cache_change.data_value,
+                                    cache_change.kind,
+                                    change_instance_handle.into(),
+                                    cache_change.source_timestamp.map(Into::into),
+                                    reception_timestamp,
+                                    group_order,
+                                ),
+                            )
                         }
                     };
-
-                    let the_participant =
-                        DomainParticipantAsync::new(dcps_sender, domain_id, dp_instance_handle);
-                    let the_subscriber =
-                        SubscriberAsync::new(subscriber_handle, the_participant.clone());
-
-                    let the_reader = DataReaderAsync::new(
-                        *data_reader_handle,
-                        the_subscriber.clone(),
-                        topic_name,
-                        type_name,
-                    );
-
-                    match data_reader.add_reader_change(
-                        cache_change.writer_guid,
-                        cache_change.data_value,
-                        cache_change.kind,
-                        change_instance_handle.into(),
-                        cache_change.source_timestamp.map(Into::into),
-                        reception_timestamp,
-                    ) {
+                    match add_change_result {
                         Ok(AddChangeResult::Added) => {
                             tracing::info!("New change added");
 
@@ -302,12 +359,18 @@ impl DcpsDomainParticipant {
 
                             if subscriber_listener_mask.is_enabled(&StatusKind::DataOnReaders) {
                                 if let Some(l) = &subscriber_listener_sender {
-                                    l.send(ListenerMail::DataOnReaders { the_subscriber }).ok();
+                                    l.send(ListenerMail::DataOnReaders {
+                                        the_subscriber: the_subscriber.clone(),
+                                    })
+                                    .ok();
                                 }
                             } else if data_reader_on_data_available_active {
                                 if let Some(l) = &data_reader.listener_sender {
                                     info!("Triggering data reader DataAvailable listener");
-                                    l.send(ListenerMail::DataAvailable { the_reader }).ok();
+                                    l.send(ListenerMail::DataAvailable {
+                                        the_reader: the_reader.clone(),
+                                    })
+                                    .ok();
                                 }
                             }
 
@@ -317,6 +380,14 @@ impl DcpsDomainParticipant {
                             data_reader
                                 .status_condition
                                 .add_communication_state(StatusKind::DataAvailable);
+
+                            data_reader.notify_read_conditions_on_new_sample(
+                                change_kind,
+                                data_value.as_ref(),
+                                &change_instance_handle,
+                                previous_instance_state,
+                                *type_support,
+                            );
                         }
                         Ok(AddChangeResult::NotAdded) => (), // Do nothing
                         Ok(AddChangeResult::Rejected(
@@ -336,22 +407,31 @@ impl DcpsDomainParticipant {
                                 let status = data_reader.get_sample_rejected_status();
 
                                 if let Some(l) = &data_reader.listener_sender {
-                                    l.send(ListenerMail::SampleRejected { the_reader, status })
-                                        .ok();
+                                    l.send(ListenerMail::SampleRejected {
+                                        the_reader: the_reader.clone(),
+                                        status,
+                                    })
+                                    .ok();
                                 };
                             } else if subscriber_listener_mask
                                 .is_enabled(&StatusKind::SampleRejected)
                             {
                                 let status = data_reader.get_sample_rejected_status();
                                 if let Some(l) = &subscriber_listener_sender {
-                                    l.send(ListenerMail::SampleRejected { status, the_reader })
-                                        .ok();
+                                    l.send(ListenerMail::SampleRejected {
+                                        status,
+                                        the_reader: the_reader.clone(),
+                                    })
+                                    .ok();
                                 }
                             } else if dp_listener_mask.is_enabled(&StatusKind::SampleRejected) {
                                 let status = data_reader.get_sample_rejected_status();
                                 if let Some(l) = dp_listener_sender {
-                                    l.send(ListenerMail::SampleRejected { status, the_reader })
-                                        .ok();
+                                    l.send(ListenerMail::SampleRejected {
+                                        status,
+                                        the_reader: the_reader.clone(),
+                                    })
+                                    .ok();
                                 }
                             }
 
@@ -418,7 +498,11 @@ impl DcpsDomainParticipant {
                         self.handle_gap_submessage(&message_receiver, gap_submessage);
                     }
                     RtpsSubmessageReadKind::Heartbeat(heartbeat_submessage) => {
-                        self.handle_heartbeat_submessage(&message_receiver, heartbeat_submessage);
+                        self.handle_heartbeat_submessage(
+                            &message_receiver,
+                            heartbeat_submessage,
+                            runtime.clock().now(),
+                        );
                     }
                     RtpsSubmessageReadKind::HeartbeatFrag(heartbeat_frag_submessage) => {
                         for dr in self
@@ -451,32 +535,34 @@ impl DcpsDomainParticipant {
                         }
                     }
                     RtpsSubmessageReadKind::AckNack(ack_nack_submessage) => {
-                        for dw in self
-                            .domain_participant
-                            .user_defined_publisher_list
-                            .iter_mut()
-                            .flat_map(|p| p.data_writer_list.iter_mut())
-                        {
-                            if dw
-                                .transport_writer
-                                .on_acknack_submessage_received(
-                                    ack_nack_submessage,
-                                    message_receiver.source_guid_prefix(),
-                                    self.transport.message_writer.as_ref(),
-                                    &runtime.clock(),
-                                )
-                                .is_some()
-                            {
-                                if let Some(x) = dw.acknowledgement_notification.take() {
-                                    x.send(());
-                                }
-
+                        for publisher in &mut self.domain_participant.user_defined_publisher_list {
+                            let message_writer = publication_message_writer(
+                                &publisher.suspended_messages,
+                                self.transport.message_writer.as_ref(),
+                            );
+                            for dw in &mut publisher.data_writer_list {
                                 if dw
                                     .transport_writer
-                                    .is_change_acknowledged(dw.last_change_sequence_number)
+                                    .on_acknack_submessage_received(
+                                        ack_nack_submessage,
+                                        message_receiver.source_guid_prefix(),
+                                        message_writer,
+                                        &runtime.clock(),
+                                    )
+                                    .is_some()
                                 {
-                                    for n in dw.wait_for_acknowledgments_notification.drain(..) {
-                                        n.send(Ok(()));
+                                    if let Some(x) = dw.acknowledgement_notification.take() {
+                                        x.send(());
+                                    }
+
+                                    if dw
+                                        .transport_writer
+                                        .is_change_acknowledged(dw.last_change_sequence_number)
+                                    {
+                                        for n in dw.wait_for_acknowledgments_notification.drain(..)
+                                        {
+                                            n.send(Ok(()));
+                                        }
                                     }
                                 }
                             }
@@ -496,17 +582,19 @@ impl DcpsDomainParticipant {
                         self.process_pending_write_samples(runtime);
                     }
                     RtpsSubmessageReadKind::NackFrag(nack_frag_submessage) => {
-                        for dw in self
-                            .domain_participant
-                            .user_defined_publisher_list
-                            .iter_mut()
-                            .flat_map(|p| p.data_writer_list.iter_mut())
-                        {
-                            dw.transport_writer.on_nack_frag_submessage_received(
-                                nack_frag_submessage,
-                                message_receiver.source_guid_prefix(),
+                        for publisher in &mut self.domain_participant.user_defined_publisher_list {
+                            let message_writer = publication_message_writer(
+                                &publisher.suspended_messages,
                                 self.transport.message_writer.as_ref(),
                             );
+                            for dw in &mut publisher.data_writer_list {
+                                dw.transport_writer.on_nack_frag_submessage_received(
+                                    nack_frag_submessage,
+                                    message_receiver.source_guid_prefix(),
+                                    message_writer,
+                                    &runtime.clock(),
+                                );
+                            }
                         }
                         for dw in self
                             .domain_participant
@@ -517,6 +605,7 @@ impl DcpsDomainParticipant {
                                 nack_frag_submessage,
                                 message_receiver.source_guid_prefix(),
                                 self.transport.message_writer.as_ref(),
+                                &runtime.clock(),
                             );
                         }
                     }
@@ -596,6 +685,7 @@ impl DcpsDomainParticipant {
         &mut self,
         message_receiver: &MessageReceiver<'_>,
         heartbeat_submessage: &HeartbeatSubmessage,
+        now: Time,
     ) {
         for s in self
             .domain_participant
@@ -618,10 +708,15 @@ impl DcpsDomainParticipant {
                         let must_send_acknacks = !heartbeat_submessage.final_flag()
                             || (!heartbeat_submessage.liveliness_flag()
                                 && writer_proxy.missing_changes().count() > 0);
-                        writer_proxy.set_must_send_acknacks(must_send_acknacks);
+                        if must_send_acknacks {
+                            writer_proxy.schedule_acknack(now + self.heartbeat_response_delay);
+                        }
 
-                        writer_proxy
-                            .write_message(&reader_guid, self.transport.message_writer.as_ref());
+                        writer_proxy.write_message(
+                            &reader_guid,
+                            self.transport.message_writer.as_ref(),
+                            now,
+                        );
                     }
                 }
 
@@ -651,10 +746,15 @@ impl DcpsDomainParticipant {
                     let must_send_acknacks = !heartbeat_submessage.final_flag()
                         || (!heartbeat_submessage.liveliness_flag()
                             && writer_proxy.missing_changes().count() > 0);
-                    writer_proxy.set_must_send_acknacks(must_send_acknacks);
+                    if must_send_acknacks {
+                        writer_proxy.schedule_acknack(now + self.heartbeat_response_delay);
+                    }
 
-                    writer_proxy
-                        .write_message(&reader_guid, self.transport.message_writer.as_ref());
+                    writer_proxy.write_message(
+                        &reader_guid,
+                        self.transport.message_writer.as_ref(),
+                        now,
+                    );
                 }
             }
         }
@@ -685,14 +785,40 @@ impl DcpsDomainParticipant {
     }
 
     pub fn poke(&mut self, clock: &impl Clock) {
-        for dw in self
+        // Nothing is sent for the writers of a publisher whose publications are suspended
+        let mut data_writer_list: Vec<_> = self
             .domain_participant
             .user_defined_publisher_list
             .iter_mut()
+            .filter(|p| p.suspended_messages.is_none())
             .flat_map(|p| p.data_writer_list.iter_mut())
-        {
-            dw.transport_writer
-                .write_message(self.transport.message_writer.as_ref(), clock);
+            .collect();
+        // The writers sharing a flow controller get its bytes in the order of the scheduling policy
+        if let Some(flow_controller) = &self.flow_controller {
+            match flow_controller.scheduling_policy {
+                FlowControllerSchedulingPolicy::Fifo => data_writer_list.sort_by_key(|dw| {
+                    let timestamp = dw.transport_writer.oldest_unsent_change_timestamp();
+                    (timestamp.is_none(), timestamp)
+                }),
+                FlowControllerSchedulingPolicy::RoundRobin => {
+                    if !data_writer_list.is_empty() {
+                        let len = data_writer_list.len();
+                        data_writer_list.rotate_left(self.flow_controller_round % len);
+                    }
+                    self.flow_controller_round = self.flow_controller_round.wrapping_add(1);
+                }
+                FlowControllerSchedulingPolicy::Priority => data_writer_list
+                    .sort_by_key(|dw| core::cmp::Reverse(dw.qos.transport_priority.value)),
+            }
+        }
+        for dw in data_writer_list {
+            let message_writer: &dyn WriteMessage =
+                if dw.transport_writer.is_asynchronous_publication() {
+                    &self.asynchronous_sender
+                } else {
+                    self.transport.message_writer.as_ref()
+                };
+            dw.transport_writer.write_message(message_writer, clock);
         }
         for dw in self
             .domain_participant
@@ -702,5 +828,27 @@ impl DcpsDomainParticipant {
             dw.transport_writer
                 .write_message(self.transport.message_writer.as_ref(), clock);
         }
+        // The AckNacks delayed by the heartbeat response delay
+        let now = clock.now();
+        for dr in self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter_mut()
+            .flat_map(|s| s.data_reader_list.iter_mut().map(|dr| &mut dr.reader))
+            .chain(
+                self.domain_participant
+                    .builtin_subscriber
+                    .stateful_data_reader_list_mut(),
+            )
+        {
+            dr.transport_reader
+                .write_message(self.transport.message_writer.as_ref(), now);
+        }
     }
 }
+
+/// Sample to be added to the history of a data reader
+enum ReceivedSample {
+    Change(CacheChange),
+    TimeBasedFilterPending(ReaderSample),
+}
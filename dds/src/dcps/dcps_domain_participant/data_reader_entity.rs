@@ -7,9 +7,9 @@ use crate::{
         qos_policy::{DestinationOrderQosPolicyKind, HistoryQosPolicyKind, OwnershipQosPolicyKind},
         sample_info::{InstanceStateKind, SampleInfo, SampleStateKind, ViewStateKind},
         status::SampleRejectedStatusKind,
-        time::{DurationKind, TIME_INVALID_NSEC, TIME_INVALID_SEC, Time},
+        time::{Duration, DurationKind, TIME_INVALID_NSEC, TIME_INVALID_SEC, Time},
     },
-    transport::types::{ChangeKind, Guid},
+    transport::types::{ChangeKind, Guid, SequenceNumber},
 };
 use alloc::{
     string::{String, ToString},
@@ -99,18 +99,41 @@ impl InstanceState {
     }
 }
 
+/// Position of a sample in the changes made by the publisher of its writer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupOrder {
+    pub writer_group: Guid,
+    pub group_sequence_number: SequenceNumber,
+}
+
 #[derive(Debug)]
 pub struct ReaderSample {
     pub kind: ChangeKind,
     pub writer_guid: [u8; 16],
     pub instance_handle: InstanceHandle,
     pub source_timestamp: Option<Time>,
+    pub reception_timestamp: Time,
+    pub group_order: Option<GroupOrder>,
     pub data_value: Arc<[u8]>,
     pub sample_state: SampleStateKind,
     pub disposed_generation_count: i32,
     pub no_writers_generation_count: i32,
 }
 
+/// Sample held back by the time based filter until the minimum separation from the last
+/// sample of its instance elapses
+pub struct PendingSample {
+    pub sample: ReaderSample,
+    pub source_timestamp: Time,
+    pub delivery_time: Time,
+}
+
+pub struct TimeBasedFilterInstance {
+    pub instance_handle: InstanceHandle,
+    pub last_source_timestamp: Time,
+    pub pending_sample: Option<PendingSample>,
+}
+
 pub struct InstanceOwnership {
     pub instance_handle: InstanceHandle,
     pub owner_handle: [u8; 16],
@@ -126,6 +149,7 @@ pub struct DataReaderEntity<T> {
     pub enabled: bool,
     pub instances: Vec<InstanceState>,
     pub instance_ownership: Vec<InstanceOwnership>,
+    pub time_based_filter_instances: Vec<TimeBasedFilterInstance>,
     pub transport_reader: T,
 }
 
@@ -145,10 +169,12 @@ impl<T> DataReaderEntity<T> {
             enabled: false,
             instances: Vec::new(),
             instance_ownership: Vec::new(),
+            time_based_filter_instances: Vec::new(),
             transport_reader,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_sample_collection(
         &mut self,
         max_samples: i32,
@@ -157,6 +183,7 @@ impl<T> DataReaderEntity<T> {
         instance_states: &[InstanceStateKind],
         specific_instance_handle: &Option<InstanceHandle>,
         take: bool,
+        sample_filter: &dyn Fn(&ReaderSample) -> bool,
     ) -> DdsResult<SampleList> {
         if let Some(h) = specific_instance_handle {
             if !self.instances.iter().any(|x| x.handle() == h) {
@@ -193,6 +220,10 @@ impl<T> DataReaderEntity<T> {
                 return true;
             }
 
+            if !sample_filter(cache_change) {
+                return true;
+            }
+
             if !instances_in_collection
                 .iter()
                 .any(|x| x.handle() == &cache_change.instance_handle)
@@ -289,6 +320,50 @@ impl<T> DataReaderEntity<T> {
         }
     }
 
+    pub fn contains_sample(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: Option<&InstanceHandle>,
+        sample_filter: &dyn Fn(&ReaderSample) -> bool,
+    ) -> bool {
+        self.samples_in_states(sample_states, view_states, instance_states)
+            .any(|sample| {
+                specific_instance_handle.is_none_or(|h| h == &sample.instance_handle)
+                    && sample_filter(sample)
+            })
+    }
+
+    pub fn samples_in_states<'a>(
+        &'a self,
+        sample_states: &'a [SampleStateKind],
+        view_states: &'a [ViewStateKind],
+        instance_states: &'a [InstanceStateKind],
+    ) -> impl Iterator<Item = &'a ReaderSample> + 'a {
+        self.sample_list.iter().filter(|sample| {
+            sample_states.contains(&sample.sample_state)
+                && self
+                    .instances
+                    .iter()
+                    .find(|x| x.handle == sample.instance_handle)
+                    .is_some_and(|instance| {
+                        view_states.contains(&instance.view_state)
+                            && instance_states.contains(&instance.instance_state)
+                    })
+        })
+    }
+
+    pub fn get_instance_state(
+        &self,
+        instance_handle: &InstanceHandle,
+    ) -> Option<(ViewStateKind, InstanceStateKind)> {
+        self.instances
+            .iter()
+            .find(|x| x.handle() == instance_handle)
+            .map(|x| (x.view_state, x.instance_state))
+    }
+
     pub fn next_instance(
         &mut self,
         previous_handle: &Option<InstanceHandle>,
@@ -305,6 +380,7 @@ impl<T> DataReaderEntity<T> {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(skip(self))]
     pub fn add_reader_change(
         &mut self,
@@ -314,6 +390,7 @@ impl<T> DataReaderEntity<T> {
         change_instance_handle: [u8; 16],
         change_source_timestamp: Option<Time>,
         reception_timestamp: Time,
+        group_order: Option<GroupOrder>,
     ) -> DdsResult<AddChangeResult> {
         let instance_handle = InstanceHandle::new(change_instance_handle);
         // Update the state of the instance before creating since this has direct impact on
@@ -362,13 +439,14 @@ impl<T> DataReaderEntity<T> {
             writer_guid: writer_guid.into(),
             instance_handle,
             source_timestamp: change_source_timestamp,
+            reception_timestamp,
+            group_order,
             data_value,
             sample_state: SampleStateKind::NotRead,
             disposed_generation_count: instance.most_recent_disposed_generation_count,
             no_writers_generation_count: instance.most_recent_no_writers_generation_count,
         };
 
-        let change_instance_handle = sample.instance_handle;
         // data_reader exclusive access if the writer is not the allowed to write the sample do an early return
         if self.qos.ownership.kind == OwnershipQosPolicyKind::Exclusive {
             // Get the InstanceHandle of the data writer owning this instance
@@ -431,31 +509,90 @@ impl<T> DataReaderEntity<T> {
             }
         }
 
-        let is_sample_of_interest_based_on_time = {
-            let closest_timestamp_before_received_sample = self
-                .sample_list
-                .iter()
-                .filter(|cc| cc.instance_handle == sample.instance_handle)
-                .filter(|cc| cc.source_timestamp <= sample.source_timestamp)
-                .map(|cc| cc.source_timestamp)
-                .max();
-
-            if let Some(Some(t)) = closest_timestamp_before_received_sample {
-                if let Some(sample_source_time) = sample.source_timestamp {
-                    let sample_separation = sample_source_time - t;
-                    DurationKind::Finite(sample_separation)
-                        >= self.qos.time_based_filter.minimum_separation
-                } else {
-                    true
-                }
-            } else {
-                true
-            }
+        let Some(sample) = self.filter_sample_based_on_time(sample) else {
+            return Ok(AddChangeResult::NotAdded);
         };
 
-        if !is_sample_of_interest_based_on_time {
-            return Ok(AddChangeResult::NotAdded);
+        self.add_sample(sample)
+    }
+
+    /// Applies the time based filter to a received sample. A sample arriving before the minimum
+    /// separation from the last sample of its instance has elapsed is held back, replacing the one
+    /// held back before, so that the latest value is delivered once the separation elapses.
+    fn filter_sample_based_on_time(&mut self, sample: ReaderSample) -> Option<ReaderSample> {
+        let minimum_separation = self.qos.time_based_filter.minimum_separation;
+        if sample.kind != ChangeKind::Alive
+            || minimum_separation == DurationKind::Finite(Duration::new(0, 0))
+        {
+            return Some(sample);
+        }
+        let Some(instance) = self
+            .time_based_filter_instances
+            .iter_mut()
+            .find(|x| x.instance_handle == sample.instance_handle)
+        else {
+            return Some(sample);
+        };
+
+        let source_timestamp = sample
+            .source_timestamp
+            .unwrap_or(sample.reception_timestamp);
+        // Samples older than the last one delivered are not of interest anymore
+        if source_timestamp < instance.last_source_timestamp {
+            return None;
+        }
+        let separation = source_timestamp - instance.last_source_timestamp;
+        if DurationKind::Finite(separation) >= minimum_separation {
+            return Some(sample);
         }
+        if let DurationKind::Finite(minimum_separation) = minimum_separation {
+            let is_latest_sample = instance
+                .pending_sample
+                .as_ref()
+                .is_none_or(|p| p.source_timestamp <= source_timestamp);
+            if is_latest_sample {
+                instance.pending_sample = Some(PendingSample {
+                    delivery_time: sample.reception_timestamp + (minimum_separation - separation),
+                    source_timestamp,
+                    sample,
+                });
+            }
+        }
+        None
+    }
+
+    /// Takes the samples held back by the time based filter whose minimum separation has elapsed.
+    pub fn take_due_pending_samples(&mut self, now: Time) -> Vec<ReaderSample> {
+        self.time_based_filter_instances
+            .iter_mut()
+            .filter_map(|x| {
+                x.pending_sample
+                    .take_if(|p| p.delivery_time <= now)
+                    .map(|p| p.sample)
+            })
+            .collect()
+    }
+
+    pub fn time_until_pending_sample_delivery(&self, now: Time) -> Option<Duration> {
+        self.time_based_filter_instances
+            .iter()
+            .filter_map(|x| x.pending_sample.as_ref())
+            .map(|p| {
+                if p.delivery_time > now {
+                    p.delivery_time - now
+                } else {
+                    Duration::new(0, 0)
+                }
+            })
+            .min()
+    }
+
+    /// Adds a sample which already passed the time based filter to the history.
+    pub fn add_sample(&mut self, sample: ReaderSample) -> DdsResult<AddChangeResult> {
+        let change_instance_handle = sample.instance_handle;
+        let reception_timestamp = sample.reception_timestamp;
+        let change_kind = sample.kind;
+        let source_timestamp = sample.source_timestamp.unwrap_or(reception_timestamp);
 
         let is_max_samples_limit_reached = {
             let total_samples = self
@@ -593,6 +730,30 @@ impl<T> DataReaderEntity<T> {
                 owner_handle: sample_writer_guid,
             }),
         }
+
+        if change_kind == ChangeKind::Alive
+            && self.qos.time_based_filter.minimum_separation
+                != DurationKind::Finite(Duration::new(0, 0))
+        {
+            match self
+                .time_based_filter_instances
+                .iter_mut()
+                .find(|x| x.instance_handle == change_instance_handle)
+            {
+                Some(x) => {
+                    x.last_source_timestamp = source_timestamp;
+                    x.pending_sample
+                        .take_if(|p| p.source_timestamp <= source_timestamp);
+                }
+                None => self
+                    .time_based_filter_instances
+                    .push(TimeBasedFilterInstance {
+                        instance_handle: change_instance_handle,
+                        last_source_timestamp: source_timestamp,
+                        pending_sample: None,
+                    }),
+            }
+        }
         Ok(AddChangeResult::Added)
     }
 
@@ -622,6 +783,7 @@ impl<T> DataReaderEntity<T> {
             instance_states,
             specific_instance_handle,
             false,
+            &|_| true,
         )
     }
 
@@ -644,6 +806,7 @@ impl<T> DataReaderEntity<T> {
             instance_states,
             specific_instance_handle,
             true,
+            &|_| true,
         )
     }
 }
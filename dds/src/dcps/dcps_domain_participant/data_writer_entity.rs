@@ -1,7 +1,12 @@
 use crate::{
-    dcps::xtypes_glue::key_and_instance_handle::{
-        KeyHolderData, get_instance_handle_from_key_holder_data,
+    dcps::{
+        dcps_domain_participant::persistent_history::PersistentHistory,
+        xtypes_glue::key_and_instance_handle::{
+            KeyHolderData, get_instance_handle_from_key_holder_data,
+            get_instance_handle_from_serialized_key,
+        },
     },
+    dds_async::persistence::PersistentChangeKind,
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
@@ -13,17 +18,21 @@ use crate::{
         status::OfferedIncompatibleQosStatus,
         time::{Duration, DurationKind, Time},
     },
-    runtime::DdsRuntime,
+    rtps::stateful_writer::RtpsStatefulWriter,
+    runtime::{Clock, DdsRuntime},
     transport::{
         interface::WriteMessage,
-        types::{CacheChange, ChangeKind, TopicKind},
+        types::{CacheChange, ChangeKind, CoherentSet, SequenceNumber, TopicKind},
     },
     xtypes::{
         dynamic_type::{DynamicData, DynamicType},
         serializer::{serialize_cdr1_be, serialize_cdr1_le, serialize_cdr2_be, serialize_cdr2_le},
+        type_support::TypeSupport,
+        xcdr::{Endianness, XcdrVersion, serialize_key, serialize_top_level},
     },
 };
-use alloc::{collections::VecDeque, string::String, vec::Vec};
+use alloc::{collections::VecDeque, string::String, sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU8, Ordering};
 
 use super::rtps_traits::RtpsWriter;
 
@@ -33,6 +42,13 @@ pub struct RegisteredInstanceInfo {
     pub samples: VecDeque<i64>,
 }
 
+/// Presentation information attached to each change written by a data writer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChangePresentation {
+    pub coherent_set: Option<CoherentSet>,
+    pub group_sequence_number: Option<SequenceNumber>,
+}
+
 #[derive(Default)]
 pub struct IncompatibleSubscriptions {
     pub incompatible_subscription_list: Vec<InstanceHandle>,
@@ -47,6 +63,8 @@ pub struct DataWriterEntity<T> {
     pub last_change_sequence_number: i64,
     pub qos: DataWriterQos,
     pub registered_instance_info: Vec<RegisteredInstanceInfo>,
+    /// Changes kept by the persistence service when the durability kind is persistent
+    pub persistent_history: Option<PersistentHistory>,
 }
 
 impl<T: RtpsWriter> DataWriterEntity<T> {
@@ -64,15 +82,26 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
             last_change_sequence_number: 0,
             qos,
             registered_instance_info: Vec::new(),
+            persistent_history: None,
+        }
+    }
+
+    fn persist_change(&mut self, change: &CacheChange, now: Time) {
+        if let Some(persistent_history) = &mut self.persistent_history {
+            if let Err(e) = persistent_history.add_change(change, now) {
+                tracing::warn!("Change of topic {} was not persisted: {e}", self.topic_name);
+            }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn write_w_timestamp(
         &mut self,
         sample_instance_handle: InstanceHandle,
         serialized_data: Vec<u8>,
         sample_timestamp: Time,
         now: Time,
+        presentation: ChangePresentation,
         message_writer: &(impl WriteMessage + ?Sized),
         runtime: &impl DdsRuntime,
     ) -> DdsResult<()> {
@@ -134,6 +163,9 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
             source_timestamp: Some(sample_timestamp.into()),
             instance_handle: Some(sample_instance_handle.into()),
             data_value: serialized_data.into(),
+            filter_signatures: Vec::new(),
+            coherent_set: presentation.coherent_set,
+            group_sequence_number: presentation.group_sequence_number,
         };
 
         let instance_info = self
@@ -162,6 +194,7 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
             }
         }
 
+        self.persist_change(&change, now);
         self.transport_writer
             .add_change(change, message_writer, runtime);
 
@@ -173,6 +206,7 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
         dynamic_data: &DynamicData<'static>,
         type_support: &DynamicType<'static>,
         timestamp: Time,
+        presentation: ChangePresentation,
         message_writer: &(impl WriteMessage + ?Sized),
         runtime: &impl DdsRuntime,
     ) -> DdsResult<()> {
@@ -210,7 +244,11 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
             source_timestamp: Some(timestamp.into()),
             instance_handle: Some(instance_handle.into()),
             data_value: serialized_key.into(),
+            filter_signatures: Vec::new(),
+            coherent_set: presentation.coherent_set,
+            group_sequence_number: presentation.group_sequence_number,
         };
+        self.persist_change(&cache_change, runtime.clock().now());
         self.transport_writer
             .add_change(cache_change, message_writer, runtime);
 
@@ -260,6 +298,7 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
         dynamic_data: &DynamicData<'static>,
         type_support: &DynamicType<'static>,
         timestamp: Time,
+        presentation: ChangePresentation,
         message_writer: &(impl WriteMessage + ?Sized),
         runtime: &impl DdsRuntime,
     ) -> DdsResult<()> {
@@ -305,13 +344,96 @@ impl<T: RtpsWriter> DataWriterEntity<T> {
             source_timestamp: Some(timestamp.into()),
             instance_handle: Some(instance_handle.into()),
             data_value: serialized_key.into(),
+            filter_signatures: Vec::new(),
+            coherent_set: presentation.coherent_set,
+            group_sequence_number: presentation.group_sequence_number,
         };
+        self.persist_change(&cache_change, runtime.clock().now());
         self.transport_writer
             .add_change(cache_change, message_writer, runtime);
         Ok(())
     }
 }
 
+impl DataWriterEntity<RtpsStatefulWriter> {
+    /// Adds to the history the changes kept by the persistence service so that they
+    /// are sent to the late joining readers as if they had been written by this writer.
+    pub fn restore_persistent_history(
+        &mut self,
+        persistent_history: PersistentHistory,
+        message_writer: &(impl WriteMessage + ?Sized),
+        runtime: &impl DdsRuntime,
+    ) {
+        for change in persistent_history.changes() {
+            let instance_info = match self
+                .registered_instance_info
+                .iter()
+                .position(|x| x.instance_handle == change.instance_handle)
+            {
+                Some(index) => &mut self.registered_instance_info[index],
+                None if change.kind == PersistentChangeKind::Alive
+                    && self.registered_instance_info.len()
+                        < self.qos.resource_limits.max_instances =>
+                {
+                    self.registered_instance_info.push(RegisteredInstanceInfo {
+                        instance_handle: change.instance_handle,
+                        last_write_time: None,
+                        samples: VecDeque::new(),
+                    });
+                    self.registered_instance_info
+                        .last_mut()
+                        .expect("Instance info was just added")
+                }
+                None => continue,
+            };
+
+            self.last_change_sequence_number += 1;
+            let kind = match change.kind {
+                PersistentChangeKind::Alive => {
+                    if let HistoryQosPolicyKind::KeepLast(depth) = self.qos.history.kind {
+                        if instance_info.samples.len() >= depth as usize {
+                            if let Some(smallest_seq_num_instance) =
+                                instance_info.samples.pop_front()
+                            {
+                                self.transport_writer
+                                    .remove_change(smallest_seq_num_instance);
+                            }
+                        }
+                    }
+                    instance_info
+                        .samples
+                        .push_back(self.last_change_sequence_number);
+                    instance_info.last_write_time = Some(change.source_timestamp);
+                    ChangeKind::Alive
+                }
+                PersistentChangeKind::NotAliveDisposed => {
+                    instance_info.last_write_time = None;
+                    ChangeKind::NotAliveDisposed
+                }
+                PersistentChangeKind::NotAliveUnregistered => {
+                    instance_info.last_write_time = None;
+                    ChangeKind::NotAliveUnregistered
+                }
+                PersistentChangeKind::NotAliveDisposedUnregistered => {
+                    instance_info.last_write_time = None;
+                    ChangeKind::NotAliveDisposedUnregistered
+                }
+            };
+            let cache_change = CacheChange::new(
+                kind,
+                self.transport_writer.guid(),
+                self.last_change_sequence_number,
+                Some(change.source_timestamp.into()),
+                Some(change.instance_handle.into()),
+                change.serialized_data.as_slice().into(),
+            );
+            self.transport_writer
+                .add_change(cache_change, message_writer, &runtime.clock());
+        }
+        self.persistent_history = Some(persistent_history);
+    }
+}
+
 pub fn serialize<'a>(
     dynamic_data: &DynamicData<'a>,
     representation: &DataRepresentationQosPolicy,
@@ -334,3 +456,94 @@ pub fn serialize<'a>(
         }?,
     )
 }
+
+/// Data representation of a writer shared with its [`DataWriterAsync`](crate::dds_async::data_writer::DataWriterAsync)
+/// so that the samples can be serialized before being sent to the participant.
+#[derive(Clone)]
+pub struct SharedDataRepresentation(Arc<AtomicU8>);
+
+impl SharedDataRepresentation {
+    const XCDR1: u8 = 1;
+    const XCDR2: u8 = 2;
+    const UNSUPPORTED: u8 = 0;
+
+    pub fn new(representation: &DataRepresentationQosPolicy) -> Self {
+        let shared_representation = Self(Arc::new(AtomicU8::new(Self::UNSUPPORTED)));
+        shared_representation.set(representation);
+        shared_representation
+    }
+
+    pub fn set(&self, representation: &DataRepresentationQosPolicy) {
+        let value = match representation.value.first() {
+            None | Some(&XCDR_DATA_REPRESENTATION) => Self::XCDR1,
+            Some(&XCDR2_DATA_REPRESENTATION) => Self::XCDR2,
+            Some(_) => Self::UNSUPPORTED,
+        };
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    pub fn xcdr_version(&self) -> Option<XcdrVersion> {
+        match self.0.load(Ordering::Relaxed) {
+            Self::XCDR1 => Some(XcdrVersion::Xcdr1),
+            Self::XCDR2 => Some(XcdrVersion::Xcdr2),
+            _ => None,
+        }
+    }
+}
+
+/// Sample written by the user. Types deriving [`TypeSupport`] are serialized directly from the
+/// user type while the others are sent as [`DynamicData`] and serialized by the participant.
+#[derive(Debug)]
+pub enum WriterSample {
+    Dynamic(DynamicData<'static>),
+    Serialized {
+        serialized_data: Vec<u8>,
+        instance_handle: InstanceHandle,
+    },
+}
+
+impl WriterSample {
+    pub fn new<Foo: TypeSupport>(data: Foo, representation: &SharedDataRepresentation) -> Self {
+        let serialized = representation.xcdr_version().and_then(|version| {
+            let serialized_data = serialize_top_level(&data, version, Endianness::NATIVE).ok()?;
+            let key = serialize_key(&data).ok()?;
+            Some((
+                serialized_data,
+                get_instance_handle_from_serialized_key(&key),
+            ))
+        });
+        match serialized {
+            Some((serialized_data, instance_handle)) => Self::Serialized {
+                serialized_data,
+                instance_handle,
+            },
+            None => Self::Dynamic(data.create_dynamic_sample()),
+        }
+    }
+
+    pub fn instance_handle(&self) -> DdsResult<InstanceHandle> {
+        match self {
+            Self::Dynamic(dynamic_data) => {
+                let mut member_list = Vec::new();
+                let key_holder_data =
+                    KeyHolderData::from_dynamic_data(dynamic_data, &mut member_list)?;
+                Ok(get_instance_handle_from_key_holder_data(&key_holder_data)?)
+            }
+            Self::Serialized {
+                instance_handle, ..
+            } => Ok(*instance_handle),
+        }
+    }
+
+    pub fn into_serialized_data(
+        self,
+        representation: &DataRepresentationQosPolicy,
+    ) -> DdsResult<Vec<u8>> {
+        match self {
+            Self::Dynamic(dynamic_data) => serialize(&dynamic_data, representation),
+            Self::Serialized {
+                serialized_data, ..
+            } => Ok(serialized_data),
+        }
+    }
+}
@@ -4,8 +4,11 @@ use crate::{
         SubscriptionBuiltinTopicData, TopicBuiltinTopicData,
     },
     dcps::{
+        content_filter::ContentFilter,
         data_representation_builtin_endpoints::{
-            discovered_reader_data::{DiscoveredReaderData, ReaderProxy},
+            discovered_reader_data::{
+                ContentFilterProperty, DDSSQL_FILTER_CLASS_NAME, DiscoveredReaderData, ReaderProxy,
+            },
             discovered_topic_data::DiscoveredTopicData,
             discovered_writer_data::{DiscoveredWriterData, WriterProxy},
             spdp_discovered_participant_data::{
@@ -29,20 +32,21 @@ use crate::{
                 ENTITYID_TL_SVC_REQ_READER, ENTITYID_TL_SVC_REQ_WRITER,
             },
             data_reader_entity::DataReaderEntity,
-            data_writer_entity::IncompatibleSubscriptions,
+            data_writer_entity::{ChangePresentation, DataWriterEntity, IncompatibleSubscriptions},
             participant_entity::{
                 BUILT_IN_TOPIC_NAME_LIST, BuiltInKeyHolder, DcpsDomainParticipant,
                 DiscoveredParticipantInfo,
             },
-            rtps_traits::RtpsReader,
+            rtps_traits::{RtpsReader, RtpsWriter},
             topic_entity::DiscoveredTypeRepresentationState,
             user_defined_data_reader::UserDefinedDataReader,
             user_defined_data_writer::UserDefinedDataWriter,
         },
+        infrastructure::sample_info::SampleInfo,
         listeners::domain_participant_listener::ListenerMail,
     },
     dds_async::{
-        data_reader::DataReaderAsync, data_writer::DataWriterAsync,
+        configuration::DiscoveryMode, data_reader::DataReaderAsync, data_writer::DataWriterAsync,
         domain_participant::DomainParticipantAsync, publisher::PublisherAsync,
         subscriber::SubscriberAsync, topic::TopicAsync,
     },
@@ -66,15 +70,19 @@ use crate::{
         },
         time::{Duration, DurationKind, Time},
     },
-    rtps::types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    rtps::{
+        reader_proxy::ReaderContentFilter,
+        types::{PROTOCOLVERSION, VENDOR_ID_S2E},
+    },
     runtime::{Clock, DdsRuntime},
     transport::{
         self,
+        interface::WriteMessage,
         types::{DurabilityKind, ENTITYID_UNKNOWN, Guid, GuidPrefix, ReliabilityKind},
     },
     xtypes::{
         deserializer::deserialize_top_level_type,
-        dynamic_type::DynamicDataFactory,
+        dynamic_type::{DynamicDataFactory, DynamicType},
         serializer::serialize_cdr2_le,
         type_object::{
             CompleteTypeObject, MinimalTypeObject, TypeIdentifier, TypeIdentifierTypeObjectPair,
@@ -135,13 +143,14 @@ impl DcpsDomainParticipant {
             let spdp_discovered_participant_data = SpdpDiscoveredParticipantData {
                 dds_participant_data: participant_builtin_topic_data,
                 participant_proxy,
-                lease_duration: Duration::new(100, 0),
+                lease_duration: self.participant_lease_duration,
                 discovered_participant_list: self
                     .domain_participant
                     .discovered_participant_list
                     .iter()
                     .map(|p| InstanceHandle::new(p.dds_participant_data.key().value))
                     .collect(),
+                is_discovery_server: self.discovery_mode == DiscoveryMode::Server,
             };
 
             {
@@ -159,6 +168,7 @@ impl DcpsDomainParticipant {
                     serialized_data,
                     sample_timestamp,
                     now,
+                    ChangePresentation::default(),
                     self.transport.message_writer.as_ref(),
                     runtime,
                 )
@@ -188,6 +198,7 @@ impl DcpsDomainParticipant {
                 &dynamic_data,
                 &BuiltInKeyHolder::TYPE,
                 timestamp,
+                ChangePresentation::default(),
                 self.transport.message_writer.as_ref(),
                 runtime,
             )
@@ -407,6 +418,7 @@ impl DcpsDomainParticipant {
                             data_writer.instance_handle,
                             the_publisher,
                             the_topic,
+                            data_writer.data_representation.clone(),
                         );
                         data_writer
                             .offered_deadline_missed_status
@@ -539,7 +551,8 @@ impl DcpsDomainParticipant {
         };
         let writer_proxy = WriterProxy {
             remote_writer_guid: data_writer.transport_writer.guid(),
-            remote_group_entity_id: ENTITYID_UNKNOWN,
+            remote_group_entity_id: Guid::from(<[u8; 16]>::from(publisher.instance_handle))
+                .entity_id(),
             unicast_locator_list: vec![],
             multicast_locator_list: vec![],
         };
@@ -563,6 +576,7 @@ impl DcpsDomainParticipant {
                 serialized_data,
                 sample_timestamp,
                 now,
+                ChangePresentation::default(),
                 message_writer,
                 runtime,
             )
@@ -593,6 +607,7 @@ impl DcpsDomainParticipant {
                 &dynamic_data,
                 &BuiltInKeyHolder::TYPE,
                 timestamp,
+                ChangePresentation::default(),
                 self.transport.message_writer.as_ref(),
                 runtime,
             )
@@ -623,7 +638,7 @@ impl DcpsDomainParticipant {
             return;
         };
 
-        let topic = if let Some(content_filtered_topic) = self
+        let (topic, content_filter) = if let Some(content_filtered_topic) = self
             .domain_participant
             .content_filtered_topic_list
             .iter()
@@ -637,7 +652,7 @@ impl DcpsDomainParticipant {
             else {
                 return;
             };
-            t
+            (t, Some(content_filtered_topic.content_filter_property()))
         } else {
             let Some(t) = self
                 .domain_participant
@@ -647,7 +662,7 @@ impl DcpsDomainParticipant {
             else {
                 return;
             };
-            t
+            (t, None)
         };
         let guid = data_reader.transport_reader.guid();
         let dds_subscription_data = SubscriptionBuiltinTopicData {
@@ -680,7 +695,8 @@ impl DcpsDomainParticipant {
         };
         let reader_proxy = ReaderProxy {
             remote_reader_guid: data_reader.transport_reader.guid(),
-            remote_group_entity_id: ENTITYID_UNKNOWN,
+            remote_group_entity_id: Guid::from(<[u8; 16]>::from(subscriber.instance_handle))
+                .entity_id(),
             unicast_locator_list: vec![],
             multicast_locator_list: vec![],
             expects_inline_qos: false,
@@ -688,6 +704,7 @@ impl DcpsDomainParticipant {
         let discovered_reader_data = DiscoveredReaderData {
             dds_subscription_data,
             reader_proxy,
+            content_filter,
         };
 
         {
@@ -705,6 +722,7 @@ impl DcpsDomainParticipant {
                 serialized_data,
                 sample_timestamp,
                 now,
+                ChangePresentation::default(),
                 message_writer,
                 runtime,
             )
@@ -712,6 +730,36 @@ impl DcpsDomainParticipant {
         }
     }
 
+    /// Updates the filter used by the writers of this participant for a matched local reader.
+    pub(super) fn update_local_writers_reader_content_filter(
+        &mut self,
+        reader_guid: Guid,
+        content_filter_property: &ContentFilterProperty,
+    ) {
+        for publisher in &mut self.domain_participant.user_defined_publisher_list {
+            for data_writer in &mut publisher.data_writer_list {
+                if data_writer
+                    .transport_writer
+                    .matched_reader_content_filter_signature(reader_guid)
+                    .is_none()
+                {
+                    continue;
+                }
+                let reader_content_filter = self
+                    .domain_participant
+                    .locally_created_topic_list
+                    .iter()
+                    .find(|x| x.topic_name == data_writer.topic_name)
+                    .and_then(|topic| {
+                        create_reader_content_filter(content_filter_property, topic.type_support)
+                    });
+                data_writer
+                    .transport_writer
+                    .set_matched_reader_content_filter(reader_guid, reader_content_filter);
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self, data_reader, runtime))]
     pub(super) fn announce_deleted_data_reader(
         &mut self,
@@ -735,6 +783,7 @@ impl DcpsDomainParticipant {
                 &dynamic_data,
                 &BuiltInKeyHolder::TYPE,
                 timestamp,
+                ChangePresentation::default(),
                 self.transport.message_writer.as_ref(),
                 runtime,
             )
@@ -789,6 +838,7 @@ impl DcpsDomainParticipant {
                 serialized_data,
                 sample_timestamp,
                 now,
+                ChangePresentation::default(),
                 message_writer,
                 runtime,
             )
@@ -811,6 +861,41 @@ impl DcpsDomainParticipant {
                         .matched_subscription_list
                         .contains(&discovered_reader_data.dds_subscription_data)
                     {
+                        // The content filter is not part of the subscription data so a change
+                        // of the expression parameters only updates the matched reader filter
+                        let remote_reader_guid =
+                            discovered_reader_data.reader_proxy.remote_reader_guid;
+                        let content_filter_signature = discovered_reader_data
+                            .content_filter
+                            .as_ref()
+                            .map(|p| p.signature());
+                        if data_writer
+                            .transport_writer
+                            .matched_reader_content_filter_signature(remote_reader_guid)
+                            != content_filter_signature
+                        {
+                            let reader_content_filter = discovered_reader_data
+                                .content_filter
+                                .as_ref()
+                                .zip(
+                                    self.domain_participant
+                                        .locally_created_topic_list
+                                        .iter()
+                                        .find(|x| x.topic_name == writer_topic_name),
+                                )
+                                .and_then(|(content_filter_property, topic)| {
+                                    create_reader_content_filter(
+                                        content_filter_property,
+                                        topic.type_support,
+                                    )
+                                });
+                            data_writer
+                                .transport_writer
+                                .set_matched_reader_content_filter(
+                                    remote_reader_guid,
+                                    reader_content_filter,
+                                );
+                        }
                         continue;
                     }
 
@@ -1000,6 +1085,7 @@ impl DcpsDomainParticipant {
                                                 serialized_data,
                                                 runtime.clock().now(),
                                                 runtime.clock().now(),
+                                                ChangePresentation::default(),
                                                 self.transport.message_writer.as_ref(),
                                                 runtime,
                                             )
@@ -1039,6 +1125,7 @@ impl DcpsDomainParticipant {
                             data_writer.instance_handle,
                             the_publisher,
                             the_topic,
+                            data_writer.data_representation.clone(),
                         );
 
                         if is_matched_topic_name {
@@ -1144,6 +1231,21 @@ impl DcpsDomainParticipant {
                                     data_writer
                                         .transport_writer
                                         .add_matched_reader(reader_proxy);
+                                    let reader_content_filter = discovered_reader_data
+                                        .content_filter
+                                        .as_ref()
+                                        .and_then(|content_filter_property| {
+                                            create_reader_content_filter(
+                                                content_filter_property,
+                                                writer_associated_topic.type_support,
+                                            )
+                                        });
+                                    data_writer
+                                        .transport_writer
+                                        .set_matched_reader_content_filter(
+                                            discovered_reader_data.reader_proxy.remote_reader_guid,
+                                            reader_content_filter,
+                                        );
 
                                     if data_writer
                                         .listener_mask
@@ -1576,6 +1678,7 @@ impl DcpsDomainParticipant {
                                                 serialized_data,
                                                 runtime.clock().now(),
                                                 runtime.clock().now(),
+                                                ChangePresentation::default(),
                                                 self.transport.message_writer.as_ref(),
                                                 runtime,
                                             )
@@ -1899,12 +2002,27 @@ impl DcpsDomainParticipant {
                     } else {
                         self.remove_discovered_participant(&sample_info.instance_handle);
                     }
+                    if self.is_forwarded_by_discovery_server(&sample_info) {
+                        forward_discovery_sample(
+                            &mut self
+                                .domain_participant
+                                .builtin_publisher
+                                .dcps_participant_writer,
+                            &sample,
+                            &sample_info,
+                            self.transport.message_writer.as_ref(),
+                            runtime,
+                        );
+                    }
                 }
             }
         }
     }
 
-    pub fn process_builtin_publications_detector_cache_change(&mut self) {
+    pub fn process_builtin_publications_detector_cache_change(
+        &mut self,
+        runtime: &impl DdsRuntime,
+    ) {
         let sedp_publication = &mut self
             .domain_participant
             .builtin_subscriber
@@ -1986,12 +2104,27 @@ impl DcpsDomainParticipant {
                             );
                         }
                     }
+                    if self.is_forwarded_by_discovery_server(&sample_info) {
+                        forward_discovery_sample(
+                            &mut self
+                                .domain_participant
+                                .builtin_publisher
+                                .dcps_publications_writer,
+                            &sample,
+                            &sample_info,
+                            self.transport.message_writer.as_ref(),
+                            runtime,
+                        );
+                    }
                 }
             }
         }
     }
 
-    pub fn process_builtin_subscriptions_detector_cache_change(&mut self) {
+    pub fn process_builtin_subscriptions_detector_cache_change(
+        &mut self,
+        runtime: &impl DdsRuntime,
+    ) {
         let sedp_subscriptions = &mut self
             .domain_participant
             .builtin_subscriber
@@ -2108,12 +2241,24 @@ impl DcpsDomainParticipant {
                             );
                         }
                     }
+                    if self.is_forwarded_by_discovery_server(&sample_info) {
+                        forward_discovery_sample(
+                            &mut self
+                                .domain_participant
+                                .builtin_publisher
+                                .dcps_subscriptions_writer,
+                            &sample,
+                            &sample_info,
+                            self.transport.message_writer.as_ref(),
+                            runtime,
+                        );
+                    }
                 }
             }
         }
     }
 
-    pub fn process_builtin_topics_detector_cache_change(&mut self) {
+    pub fn process_builtin_topics_detector_cache_change(&mut self, runtime: &impl DdsRuntime) {
         let sedp_topics = &mut self.domain_participant.builtin_subscriber.dcps_topic_reader;
         {
             if let Ok(samples) = sedp_topics.read(
@@ -2134,6 +2279,15 @@ impl DcpsDomainParticipant {
                                 .add_discovered_topic(topic_builtin_topic_data.clone());
                         }
                     }
+                    if self.is_forwarded_by_discovery_server(&sample_info) {
+                        forward_discovery_sample(
+                            &mut self.domain_participant.builtin_publisher.dcps_topics_writer,
+                            &sample,
+                            &sample_info,
+                            self.transport.message_writer.as_ref(),
+                            runtime,
+                        );
+                    }
                 }
             }
         }
@@ -2216,6 +2370,7 @@ impl DcpsDomainParticipant {
                                                     serialized_data,
                                                     runtime.clock().now(),
                                                     runtime.clock().now(),
+                                                    ChangePresentation::default(),
                                                     self.transport.message_writer.as_ref(),
                                                     runtime,
                                                 )
@@ -2530,6 +2685,7 @@ impl DcpsDomainParticipant {
                                     serialized_data,
                                     runtime.clock().now(),
                                     runtime.clock().now(),
+                                    ChangePresentation::default(),
                                     self.transport.message_writer.as_ref(),
                                     runtime,
                                 )
@@ -2584,22 +2740,48 @@ impl DcpsDomainParticipant {
             .iter()
             .any(|handle| handle == &discovered_participant_data.dds_participant_data.key.value);
 
+        let is_own_participant = discovered_participant_data.dds_participant_data.key.value
+            == <[u8; 16]>::from(self.domain_participant.instance_handle);
+        // Discovery clients only exchange the discovery data with the servers
+        let is_discovery_data_exchanged = match self.discovery_mode {
+            DiscoveryMode::Simple | DiscoveryMode::Server => true,
+            DiscoveryMode::Client => {
+                discovered_participant_data.is_discovery_server || is_own_participant
+            }
+        };
+
         if is_domain_id_matching
             && is_domain_tag_matching
             && !is_participant_discovered
             && !is_participant_ignored
         {
-            self.add_matched_publications_detector(discovered_participant_data);
-            self.add_matched_publications_announcer(discovered_participant_data);
-            self.add_matched_subscriptions_detector(discovered_participant_data);
-            self.add_matched_subscriptions_announcer(discovered_participant_data);
-            self.add_matched_topics_detector(discovered_participant_data);
-            self.add_matched_topics_announcer(discovered_participant_data);
-
-            self.add_matched_service_request_data_reader(discovered_participant_data);
-            self.add_matched_service_request_data_writer(discovered_participant_data);
-            self.add_matched_service_reply_data_reader(discovered_participant_data);
-            self.add_matched_service_reply_data_writer(discovered_participant_data);
+            if is_discovery_data_exchanged {
+                self.add_matched_publications_detector(discovered_participant_data);
+                self.add_matched_publications_announcer(discovered_participant_data);
+                self.add_matched_subscriptions_detector(discovered_participant_data);
+                self.add_matched_subscriptions_announcer(discovered_participant_data);
+                self.add_matched_topics_detector(discovered_participant_data);
+                self.add_matched_topics_announcer(discovered_participant_data);
+
+                self.add_matched_service_request_data_reader(discovered_participant_data);
+                self.add_matched_service_request_data_writer(discovered_participant_data);
+                self.add_matched_service_reply_data_reader(discovered_participant_data);
+                self.add_matched_service_reply_data_writer(discovered_participant_data);
+            }
+
+            // Discovery servers announce all the known participants to the discovered ones
+            if self.discovery_mode == DiscoveryMode::Server && !is_own_participant {
+                let dw = &mut self
+                    .domain_participant
+                    .builtin_publisher
+                    .dcps_participant_writer;
+                for &locator in &discovered_participant_data
+                    .participant_proxy
+                    .metatraffic_unicast_locator_list
+                {
+                    dw.transport_writer.reader_locator_add(locator);
+                }
+            }
 
             self.announce_participant(runtime);
 
@@ -2614,6 +2796,10 @@ impl DcpsDomainParticipant {
                     .participant_proxy
                     .default_multicast_locator_list
                     .clone(),
+                metatraffic_unicast_locator_list: discovered_participant_data
+                    .participant_proxy
+                    .metatraffic_unicast_locator_list
+                    .clone(),
                 lease_duration: discovered_participant_data.lease_duration,
                 last_communication_timestamp: runtime.clock().now(),
             };
@@ -2631,16 +2817,50 @@ impl DcpsDomainParticipant {
                     .discovered_participant_list
                     .push(discovered_participant_info),
             }
+        } else if let Some(discovered_participant) = self
+            .domain_participant
+            .discovered_participant_list
+            .iter_mut()
+            .find(|p| {
+                p.dds_participant_data.key().value
+                    == discovered_participant_data.dds_participant_data.key.value
+            })
+        {
+            // Participants forwarded by a discovery server might not communicate directly
+            // so their announcements keep them alive
+            discovered_participant.last_communication_timestamp = runtime.clock().now();
         }
     }
 
     /// Remove discovered [domain participant](SpdpDiscoveredParticipantData) with the speficied [handle](InstanceHandle).
     pub fn remove_discovered_participant(&mut self, handle: &InstanceHandle) {
-        self.domain_participant
+        let removed_participant_list: Vec<_> = self
+            .domain_participant
             .discovered_participant_list
-            .retain(|domain_participant| {
-                &domain_participant.dds_participant_data.key.value != handle
-            });
+            .extract_if(.., |domain_participant| {
+                &domain_participant.dds_participant_data.key.value == handle
+            })
+            .collect();
+
+        if self.discovery_mode == DiscoveryMode::Server {
+            let dw = &mut self
+                .domain_participant
+                .builtin_publisher
+                .dcps_participant_writer;
+            for &locator in removed_participant_list
+                .iter()
+                .flat_map(|p| &p.metatraffic_unicast_locator_list)
+            {
+                if !self.transport.initial_peer_locator_list.contains(&locator)
+                    && !self
+                        .transport
+                        .metatraffic_multicast_locator_list
+                        .contains(&locator)
+                {
+                    dw.transport_writer.reader_locator_remove(locator);
+                }
+            }
+        }
 
         let prefix = Guid::from(<[u8; 16]>::from(*handle)).prefix();
 
@@ -3190,6 +3410,16 @@ impl DcpsDomainParticipant {
         dr.transport_reader.delete_matched_writer(guid);
     }
 
+    /// Discovery servers forward the discovery data received from the participant owning it.
+    /// The data forwarded by other servers is not forwarded again.
+    fn is_forwarded_by_discovery_server(&self, sample_info: &SampleInfo) -> bool {
+        let owner_prefix = &sample_info.instance_handle.as_ref()[..12];
+        let sender_prefix = &sample_info.publication_handle.as_ref()[..12];
+        self.discovery_mode == DiscoveryMode::Server
+            && owner_prefix == sender_prefix
+            && owner_prefix != &self.domain_participant.instance_handle.as_ref()[..12]
+    }
+
     #[tracing::instrument(skip(self, runtime))]
     pub fn _request_type_lookup(
         &mut self,
@@ -3225,6 +3455,7 @@ impl DcpsDomainParticipant {
                 serialized_data,
                 sample_timestamp,
                 now,
+                ChangePresentation::default(),
                 self.transport.message_writer.as_ref(),
                 runtime,
             )
@@ -3233,6 +3464,46 @@ impl DcpsDomainParticipant {
     }
 }
 
+fn forward_discovery_sample<W: RtpsWriter>(
+    data_writer: &mut DataWriterEntity<W>,
+    sample: &[u8],
+    sample_info: &SampleInfo,
+    message_writer: &(impl WriteMessage + ?Sized),
+    runtime: &impl DdsRuntime,
+) {
+    let now = runtime.clock().now();
+    if sample_info.valid_data {
+        data_writer
+            .write_w_timestamp(
+                sample_info.instance_handle,
+                sample.to_vec(),
+                now,
+                now,
+                ChangePresentation::default(),
+                message_writer,
+                runtime,
+            )
+            .ok();
+    } else {
+        let mut dynamic_data = DynamicDataFactory::create_data(BuiltInKeyHolder::TYPE);
+        let topic_key_data = BuiltInTopicKey {
+            value: sample_info.instance_handle.into(),
+        }
+        .create_dynamic_sample();
+        dynamic_data.set_complex_value(0, topic_key_data).unwrap();
+        data_writer
+            .unregister_w_timestamp(
+                &dynamic_data,
+                &BuiltInKeyHolder::TYPE,
+                now,
+                ChangePresentation::default(),
+                message_writer,
+                runtime,
+            )
+            .ok();
+    }
+}
+
 #[tracing::instrument]
 fn get_discovered_reader_incompatible_qos_policy_list(
     writer_qos: &DataWriterQos,
@@ -3244,10 +3515,10 @@ fn get_discovered_reader_incompatible_qos_policy_list(
         incompatible_qos_policy_list.push(DURABILITY_QOS_POLICY_ID);
     }
     if publisher_qos.presentation.access_scope < discovered_reader_data.presentation().access_scope
-        || publisher_qos.presentation.coherent_access
-            != discovered_reader_data.presentation().coherent_access
-        || publisher_qos.presentation.ordered_access
-            != discovered_reader_data.presentation().ordered_access
+        || (discovered_reader_data.presentation().coherent_access
+            && !publisher_qos.presentation.coherent_access)
+        || (discovered_reader_data.presentation().ordered_access
+            && !publisher_qos.presentation.ordered_access)
     {
         incompatible_qos_policy_list.push(PRESENTATION_QOS_POLICY_ID);
     }
@@ -3298,12 +3569,12 @@ fn get_discovered_writer_incompatible_qos_policy_list(
 
     if subscriber_qos.presentation.access_scope
         > publication_builtin_topic_data.presentation().access_scope
-        || subscriber_qos.presentation.coherent_access
-            != publication_builtin_topic_data
+        || (subscriber_qos.presentation.coherent_access
+            && !publication_builtin_topic_data
                 .presentation()
-                .coherent_access
-        || subscriber_qos.presentation.ordered_access
-            != publication_builtin_topic_data.presentation().ordered_access
+                .coherent_access)
+        || (subscriber_qos.presentation.ordered_access
+            && !publication_builtin_topic_data.presentation().ordered_access)
     {
         incompatible_qos_policy_list.push(PRESENTATION_QOS_POLICY_ID);
     }
@@ -3351,6 +3622,35 @@ fn get_discovered_writer_incompatible_qos_policy_list(
     incompatible_qos_policy_list
 }
 
+fn create_reader_content_filter(
+    content_filter_property: &ContentFilterProperty,
+    type_support: DynamicType<'static>,
+) -> Option<ReaderContentFilter> {
+    if content_filter_property.filter_class_name != DDSSQL_FILTER_CLASS_NAME {
+        return None;
+    }
+    // A filter which can not be evaluated on the writer side is left to the reader
+    let content_filter = match ContentFilter::new(
+        &content_filter_property.filter_expression,
+        &type_support,
+        &content_filter_property.expression_parameters,
+    ) {
+        Ok(content_filter) => content_filter,
+        Err(e) => {
+            tracing::warn!(?e, filter_expression = ?content_filter_property.filter_expression, "Failed to compile reader content filter");
+            return None;
+        }
+    };
+    Some(ReaderContentFilter::new(
+        content_filter_property.signature(),
+        move |cache_change| {
+            deserialize_top_level_type(type_support, cache_change.data_value.as_ref())
+                .map(|data| content_filter.evaluate(&data))
+                .unwrap_or(true)
+        },
+    ))
+}
+
 fn fnmatch_to_regex(pattern: &str) -> String {
     fn flush_literal(out: &mut String, lit: &mut String) {
         if !lit.is_empty() {
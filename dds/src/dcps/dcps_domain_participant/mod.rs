@@ -1,14 +1,18 @@
+pub mod asynchronous_sender;
 pub mod builtin_constants;
 pub mod builtin_data_reader;
 pub mod builtin_publisher;
 pub mod builtin_subscriber;
+pub mod coherent_set_buffer;
 pub mod communication_methods;
 pub mod data_reader_entity;
 pub mod data_writer_entity;
 pub mod discovery_methods;
 pub mod participant_entity;
 pub mod participant_methods;
+pub mod persistent_history;
 pub mod publisher_methods;
+pub mod read_condition_methods;
 pub mod reader_methods;
 pub mod rtps_traits;
 pub mod status_condition_methods;
@@ -1,4 +1,5 @@
 use super::{
+    asynchronous_sender::AsynchronousSender,
     builtin_constants::{TYPE_LOOKUP_REPLY_TOPIC_NAME, TYPE_LOOKUP_REQUEST_TOPIC_NAME},
     builtin_publisher::BuiltinPublisher,
     builtin_subscriber::BuiltinSubscriber,
@@ -21,27 +22,34 @@ use crate::{
         status_condition::DcpsStatusCondition,
         status_mask::StatusMask,
     },
-    dds_async::domain_participant_factory::DcpsSender,
+    dds_async::{
+        configuration::{DiscoveryMode, FlowControllerConfiguration},
+        domain_participant_factory::DcpsSender,
+        persistence::PersistenceService,
+    },
     infrastructure::{
         domain::DomainId,
         error::DdsResult,
         instance::InstanceHandle,
         qos::{DomainParticipantQos, PublisherQos, SubscriberQos, TopicQos},
+        qos_policy::DurabilityServiceQosPolicy,
         time::{Duration, DurationKind, Time},
     },
+    rtps::{flow_controller::FlowController, stateful_writer::WriterTiming},
     transport::{
-        interface::RtpsTransportParticipant,
+        interface::{RtpsTransportParticipant, WriteMessage},
         types::{ENTITYID_PARTICIPANT, Guid, GuidPrefix, Locator, USER_DEFINED_TOPIC},
     },
     xtypes::{dynamic_type::DynamicType, type_support::TypeSupport},
 };
-use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, string::String, sync::Arc, vec::Vec};
 
 pub struct DiscoveredParticipantInfo {
     pub dds_participant_data: ParticipantBuiltinTopicData,
     pub guid_prefix: GuidPrefix,
     pub default_unicast_locator_list: Vec<Locator>,
     pub default_multicast_locator_list: Vec<Locator>,
+    pub metatraffic_unicast_locator_list: Vec<Locator>,
     pub lease_duration: Duration,
     pub last_communication_timestamp: Time,
 }
@@ -61,6 +69,18 @@ pub struct DcpsDomainParticipant {
     pub subscriber_counter: u8,
     pub domain_participant: DomainParticipantEntity,
     pub dcps_sender: DcpsSender,
+    pub persistence_service: Option<Arc<dyn PersistenceService>>,
+    pub discovery_mode: DiscoveryMode,
+    pub writer_timing: WriterTiming,
+    pub heartbeat_response_delay: Duration,
+    pub participant_lease_duration: Duration,
+    pub flow_controller: Option<FlowControllerConfiguration>,
+    /// Flow controller shared by all the data writers of the participant
+    pub participant_flow_controller: Option<FlowController>,
+    /// Turn of the writers sharing a flow controller with round robin scheduling
+    pub flow_controller_round: usize,
+    /// Sender of the messages of the data writers with an asynchronous publish mode
+    pub asynchronous_sender: AsynchronousSender,
 }
 
 impl DcpsDomainParticipant {
@@ -72,16 +92,29 @@ impl DcpsDomainParticipant {
         domain_participant_qos: DomainParticipantQos,
         listener_sender: Option<MpscSender<ListenerMail>>,
         listener_mask: StatusMask,
-        transport: RtpsTransportParticipant,
+        mut transport: RtpsTransportParticipant,
         dcps_sender: DcpsSender,
         participant_announcement_interval: core::time::Duration,
+        persistence_service: Option<Arc<dyn PersistenceService>>,
+        discovery_mode: DiscoveryMode,
+        writer_timing: WriterTiming,
+        heartbeat_response_delay: core::time::Duration,
+        participant_lease_duration: core::time::Duration,
+        flow_controller: Option<FlowControllerConfiguration>,
     ) -> Self {
         let guid = Guid::new(guid_prefix, ENTITYID_PARTICIPANT);
 
         let participant_handle = InstanceHandle::new(guid.into());
 
+        // The transport is also used by the sender task of the asynchronous writers
+        let message_writer: Arc<dyn WriteMessage + Send + Sync> =
+            Arc::from(transport.message_writer);
+        transport.message_writer = Box::new(message_writer.clone());
+        let asynchronous_sender = AsynchronousSender::new(message_writer, transport.fragment_size);
+
         let builtin_subscriber = BuiltinSubscriber::new(guid_prefix);
-        let builtin_publisher = BuiltinPublisher::new(guid_prefix, &transport);
+        let builtin_publisher =
+            BuiltinPublisher::new(guid_prefix, &transport, discovery_mode, writer_timing);
 
         let domain_participant = DomainParticipantEntity::new(
             domain_id,
@@ -103,6 +136,15 @@ impl DcpsDomainParticipant {
             subscriber_counter: 0,
             domain_participant,
             dcps_sender,
+            persistence_service,
+            discovery_mode,
+            writer_timing,
+            heartbeat_response_delay: Duration::from(heartbeat_response_delay),
+            participant_lease_duration: Duration::from(participant_lease_duration),
+            flow_controller,
+            participant_flow_controller: None,
+            flow_controller_round: 0,
+            asynchronous_sender,
         }
     }
 
@@ -115,6 +157,20 @@ impl DcpsDomainParticipant {
             .time_until_participant_announcement(now)
     }
 
+    /// Shortest of the periods and delays of the reliable protocol, which are handled when the
+    /// participant is poked.
+    pub fn poke_period(&self) -> Option<Duration> {
+        [
+            Duration::from(self.writer_timing.heartbeat_period),
+            Duration::from(self.writer_timing.nack_response_delay),
+            self.heartbeat_response_delay,
+        ]
+        .into_iter()
+        .chain(self.flow_controller.map(|f| Duration::from(f.period)))
+        .filter(|d| d > &Duration::new(0, 0))
+        .min()
+    }
+
     pub fn time_until_stale_participant(&self, now: Time) -> Option<Duration> {
         self.domain_participant
             .discovered_participant_list
@@ -206,6 +262,30 @@ impl DcpsDomainParticipant {
             .min()
     }
 
+    pub fn time_until_pending_acknowledgments_timeout(&self, now: Time) -> Option<Duration> {
+        self.domain_participant
+            .user_defined_publisher_list
+            .iter()
+            .flat_map(|publisher| publisher.pending_acknowledgments.iter())
+            .map(|pending| {
+                if pending.expiration_time > now {
+                    pending.expiration_time - now
+                } else {
+                    Duration::new(0, 0)
+                }
+            })
+            .min()
+    }
+
+    pub fn time_until_pending_sample_delivery(&self, now: Time) -> Option<Duration> {
+        self.domain_participant
+            .user_defined_subscriber_list
+            .iter()
+            .flat_map(|subscriber| subscriber.data_reader_list.iter())
+            .filter_map(|data_reader| data_reader.time_until_pending_sample_delivery(now))
+            .min()
+    }
+
     pub fn get_instance_handle(&self) -> &InstanceHandle {
         &self.domain_participant.instance_handle
     }
@@ -231,6 +311,7 @@ pub struct DomainParticipantEntity {
     pub domain_id: DomainId,
     pub domain_tag: String,
     pub topic_counter: u16,
+    pub read_condition_counter: u16,
     pub instance_handle: InstanceHandle,
     pub qos: DomainParticipantQos,
     pub builtin_subscriber: BuiltinSubscriber,
@@ -275,6 +356,7 @@ impl DomainParticipantEntity {
             domain_id,
             instance_handle,
             topic_counter: 0,
+            read_condition_counter: 0,
             qos: domain_participant_qos,
             builtin_subscriber,
             builtin_publisher,
@@ -365,6 +447,7 @@ impl DomainParticipantEntity {
             let qos = TopicQos {
                 topic_data: discovered_topic_data.topic_data().clone(),
                 durability: discovered_topic_data.durability().clone(),
+                durability_service: DurabilityServiceQosPolicy::const_default(),
                 deadline: discovered_topic_data.deadline().clone(),
                 latency_budget: discovered_topic_data.latency_budget().clone(),
                 liveliness: discovered_topic_data.liveliness().clone(),
@@ -508,6 +591,7 @@ mod tests {
             metatraffic_unicast_locator_list: Vec::new(),
             metatraffic_multicast_locator_list: Vec::new(),
             default_multicast_locator_list: Vec::new(),
+            initial_peer_locator_list: Vec::new(),
             fragment_size: 65536,
         };
 
@@ -517,7 +601,12 @@ mod tests {
             None,
             StatusMask::default(),
             InstanceHandle::new([0; 16]),
-            BuiltinPublisher::new(GuidPrefix::default(), &transport),
+            BuiltinPublisher::new(
+                GuidPrefix::default(),
+                &transport,
+                DiscoveryMode::Simple,
+                WriterTiming::default(),
+            ),
             BuiltinSubscriber::new(GuidPrefix::default()),
             String::new(),
             Duration::new(5, 0),
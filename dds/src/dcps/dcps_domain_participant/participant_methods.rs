@@ -8,6 +8,7 @@ use crate::{
     builtin_topics::{ParticipantBuiltinTopicData, TopicBuiltinTopicData},
     dcps::{
         channels::oneshot::OneshotSender,
+        content_filter::ContentFilter,
         dcps_domain_participant::{
             participant_entity::{
                 BUILT_IN_TOPIC_NAME_LIST, DcpsDomainParticipant, FindTopicNotification,
@@ -31,7 +32,9 @@ use crate::{
         time::{Duration, Time},
     },
     runtime::DdsRuntime,
-    transport::types::{USER_DEFINED_READER_GROUP, USER_DEFINED_TOPIC, USER_DEFINED_WRITER_GROUP},
+    transport::types::{
+        Guid, USER_DEFINED_READER_GROUP, USER_DEFINED_TOPIC, USER_DEFINED_WRITER_GROUP,
+    },
     xtypes::dynamic_type::DynamicType,
 };
 
@@ -360,16 +363,21 @@ impl DcpsDomainParticipant {
         filter_expression: String,
         expression_parameters: Vec<String>,
     ) -> DdsResult<InstanceHandle> {
-        if !self
+        let Some(related_topic) = self
             .domain_participant
             .locally_created_topic_list
             .iter()
-            .any(|x| x.topic_name == related_topic_name)
-        {
+            .find(|x| x.topic_name == related_topic_name)
+        else {
             return Err(DdsError::PreconditionNotMet(format!(
                 "Related topic with name {related_topic_name} does not exist."
             )));
-        }
+        };
+        let content_filter = ContentFilter::new(
+            &filter_expression,
+            &related_topic.type_support,
+            &expression_parameters,
+        )?;
 
         let topic_handle = InstanceHandle::new([
             self.domain_participant.instance_handle[0],
@@ -396,6 +404,7 @@ impl DcpsDomainParticipant {
             related_topic_name,
             filter_expression,
             expression_parameters,
+            content_filter,
         );
         self.domain_participant
             .content_filtered_topic_list
@@ -413,6 +422,62 @@ impl DcpsDomainParticipant {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn get_expression_parameters(&mut self, name: String) -> DdsResult<Vec<String>> {
+        let Some(content_filtered_topic) = self
+            .domain_participant
+            .content_filtered_topic_list
+            .iter()
+            .find(|x| x.topic_name == name)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+
+        Ok(content_filtered_topic.expression_parameters.clone())
+    }
+
+    #[tracing::instrument(skip(self, runtime))]
+    pub fn set_expression_parameters(
+        &mut self,
+        name: String,
+        expression_parameters: Vec<String>,
+        runtime: &impl DdsRuntime,
+    ) -> DdsResult<()> {
+        let Some(content_filtered_topic) = self
+            .domain_participant
+            .content_filtered_topic_list
+            .iter_mut()
+            .find(|x| x.topic_name == name)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        content_filtered_topic.set_expression_parameters(expression_parameters)?;
+        let content_filter_property = content_filtered_topic.content_filter_property();
+
+        let enabled_reader_list: Vec<_> = self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter()
+            .flat_map(|s| {
+                s.data_reader_list
+                    .iter()
+                    .filter(|dr| dr.enabled && dr.topic_name == name)
+                    .map(|dr| (s.instance_handle, dr.instance_handle))
+            })
+            .collect();
+        for (subscriber_handle, data_reader_handle) in enabled_reader_list {
+            // Writers of this participant would otherwise keep filtering with the previous
+            // parameters until they receive the announcement of the reader
+            self.update_local_writers_reader_content_filter(
+                Guid::from(<[u8; 16]>::from(data_reader_handle)),
+                &content_filter_property,
+            );
+            self.announce_data_reader(&subscriber_handle, &data_reader_handle, runtime);
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self, type_support, reply_sender))]
     pub fn find_topic(
         &mut self,
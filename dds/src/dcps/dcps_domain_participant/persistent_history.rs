@@ -0,0 +1,424 @@
+use crate::{
+    dds_async::persistence::{PersistenceService, PersistentChange, PersistentChangeKind},
+    infrastructure::{
+        domain::DomainId,
+        error::DdsResult,
+        instance::InstanceHandle,
+        qos_policy::{DurabilityServiceQosPolicy, HistoryQosPolicyKind, Length},
+        time::Time,
+    },
+    transport::types::{CacheChange, ChangeKind},
+};
+use alloc::{string::String, sync::Arc, vec::Vec};
+
+/// Number of changes the persistence service can hold before it is compacted even if
+/// most of them are no longer needed
+const MIN_COMPACTION_CHANGES: usize = 32;
+
+/// Change of the history together with whether it was written by the writer owning the
+/// history or loaded from the changes kept for the other writers of the topic
+struct HistoryChange {
+    change: PersistentChange,
+    is_written: bool,
+}
+
+/// Changes of a topic written with the persistent durability kind which are kept by
+/// the persistence service according to the durability service QoS.
+pub struct PersistentHistory {
+    persistence_service: Arc<dyn PersistenceService>,
+    domain_id: DomainId,
+    topic_name: String,
+    writer_handle: InstanceHandle,
+    qos: DurabilityServiceQosPolicy,
+    changes: Vec<HistoryChange>,
+    /// Number of changes of the writer held by the persistence service since it was last compacted
+    stored_changes: usize,
+}
+
+impl PersistentHistory {
+    pub fn load(
+        persistence_service: Arc<dyn PersistenceService>,
+        domain_id: DomainId,
+        topic_name: String,
+        writer_handle: InstanceHandle,
+        qos: DurabilityServiceQosPolicy,
+        now: Time,
+    ) -> DdsResult<Self> {
+        let stored_changes = persistence_service.load(domain_id, &topic_name)?;
+        let mut history = Self {
+            persistence_service,
+            domain_id,
+            topic_name,
+            writer_handle,
+            qos,
+            changes: Vec::new(),
+            stored_changes: 0,
+        };
+        for change in stored_changes {
+            history.changes.push(HistoryChange {
+                change,
+                is_written: false,
+            });
+            history.apply_limits(now);
+        }
+        Ok(history)
+    }
+
+    pub fn changes(&self) -> impl Iterator<Item = &PersistentChange> {
+        self.changes.iter().map(|c| &c.change)
+    }
+
+    pub fn add_change(&mut self, change: &CacheChange, now: Time) -> DdsResult<()> {
+        let change = PersistentChange {
+            kind: match change.kind {
+                ChangeKind::Alive | ChangeKind::AliveFiltered => PersistentChangeKind::Alive,
+                ChangeKind::NotAliveDisposed => PersistentChangeKind::NotAliveDisposed,
+                ChangeKind::NotAliveUnregistered => PersistentChangeKind::NotAliveUnregistered,
+                ChangeKind::NotAliveDisposedUnregistered => {
+                    PersistentChangeKind::NotAliveDisposedUnregistered
+                }
+            },
+            instance_handle: InstanceHandle::new(change.instance_handle.unwrap_or_default()),
+            source_timestamp: change.source_timestamp.map(Time::from).unwrap_or(now),
+            serialized_data: change.data_value.to_vec(),
+        };
+        self.persistence_service.append(
+            self.domain_id,
+            &self.topic_name,
+            self.writer_handle,
+            &change,
+        )?;
+        self.stored_changes += 1;
+        self.changes.push(HistoryChange {
+            change,
+            is_written: true,
+        });
+        self.apply_limits(now);
+
+        // Only the changes written by this writer are compacted. The ones loaded from the other
+        // writers are kept by the service for those writers.
+        let written_changes = self.changes.iter().filter(|c| c.is_written).count();
+        if self.stored_changes > (2 * written_changes).max(MIN_COMPACTION_CHANGES) {
+            let written_changes: Vec<PersistentChange> = self
+                .changes
+                .iter()
+                .filter(|c| c.is_written)
+                .map(|c| c.change.clone())
+                .collect();
+            self.persistence_service.compact(
+                self.domain_id,
+                &self.topic_name,
+                self.writer_handle,
+                &written_changes,
+            )?;
+            self.stored_changes = written_changes.len();
+        }
+        Ok(())
+    }
+
+    /// Discards the oldest changes exceeding the limits of the durability service QoS and
+    /// the instances which are no longer alive for longer than the service cleanup delay.
+    fn apply_limits(&mut self, now: Time) {
+        // The instances are ordered by their last change so that the first ones are the
+        // ones which were not written for the longest time
+        let mut instances: Vec<InstanceHandle> = Vec::new();
+        for HistoryChange { change, .. } in self.changes.iter().rev() {
+            if !instances.contains(&change.instance_handle) {
+                instances.insert(0, change.instance_handle);
+            }
+        }
+
+        let cleanup_delay = self.qos.service_cleanup_delay;
+        instances.retain(|instance| {
+            let last_change = self
+                .changes
+                .iter()
+                .rev()
+                .find(|c| &c.change.instance_handle == instance)
+                .expect("Instance has changes");
+            let is_cleaned_up = last_change.change.kind != PersistentChangeKind::Alive
+                && last_change.change.source_timestamp + cleanup_delay <= now;
+            if is_cleaned_up {
+                self.changes
+                    .retain(|c| &c.change.instance_handle != instance);
+            }
+            !is_cleaned_up
+        });
+
+        let max_samples_per_instance =
+            match (self.qos.history_kind, self.qos.max_samples_per_instance) {
+                (HistoryQosPolicyKind::KeepLast(depth), Length::Limited(max)) => {
+                    Some((depth as usize).min(max as usize))
+                }
+                (HistoryQosPolicyKind::KeepLast(depth), Length::Unlimited) => Some(depth as usize),
+                (HistoryQosPolicyKind::KeepAll, Length::Limited(max)) => Some(max as usize),
+                (HistoryQosPolicyKind::KeepAll, Length::Unlimited) => None,
+            };
+        if let Some(max_samples_per_instance) = max_samples_per_instance {
+            for instance in &instances {
+                let mut instance_samples = self
+                    .changes
+                    .iter()
+                    .filter(|c| &c.change.instance_handle == instance)
+                    .count();
+                self.changes.retain(|c| {
+                    if &c.change.instance_handle == instance
+                        && instance_samples > max_samples_per_instance
+                    {
+                        instance_samples -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+        }
+
+        if let Length::Limited(max_instances) = self.qos.max_instances {
+            while instances.len() > max_instances as usize {
+                let instance = instances.remove(0);
+                self.changes
+                    .retain(|c| c.change.instance_handle != instance);
+            }
+        }
+
+        if let Length::Limited(max_samples) = self.qos.max_samples {
+            if self.changes.len() > max_samples as usize {
+                self.changes
+                    .drain(..self.changes.len() - max_samples as usize);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{infrastructure::time::Duration, transport::types::GUID_UNKNOWN};
+    use std::sync::Mutex;
+
+    const WRITER: InstanceHandle = InstanceHandle::new([1; 16]);
+
+    #[derive(Default)]
+    struct MockPersistenceService {
+        changes: Mutex<Vec<(InstanceHandle, PersistentChange)>>,
+        compactions: Mutex<usize>,
+    }
+
+    impl PersistenceService for MockPersistenceService {
+        fn load(
+            &self,
+            _domain_id: DomainId,
+            _topic_name: &str,
+        ) -> DdsResult<Vec<PersistentChange>> {
+            Ok(self
+                .changes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(_, c)| c.clone())
+                .collect())
+        }
+
+        fn append(
+            &self,
+            _domain_id: DomainId,
+            _topic_name: &str,
+            writer_handle: InstanceHandle,
+            change: &PersistentChange,
+        ) -> DdsResult<()> {
+            self.changes
+                .lock()
+                .unwrap()
+                .push((writer_handle, change.clone()));
+            Ok(())
+        }
+
+        fn compact(
+            &self,
+            _domain_id: DomainId,
+            _topic_name: &str,
+            writer_handle: InstanceHandle,
+            changes: &[PersistentChange],
+        ) -> DdsResult<()> {
+            let mut stored_changes = self.changes.lock().unwrap();
+            stored_changes.retain(|(w, _)| w != &writer_handle);
+            stored_changes.extend(changes.iter().map(|c| (writer_handle, c.clone())));
+            *self.compactions.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    fn cache_change(kind: ChangeKind, instance: u8, value: u8) -> CacheChange {
+        CacheChange::new(
+            kind,
+            GUID_UNKNOWN,
+            1,
+            Some(crate::transport::types::Time::new(value as i32, 0)),
+            Some([instance; 16]),
+            alloc::vec![value].into(),
+        )
+    }
+
+    fn values(history: &PersistentHistory) -> Vec<u8> {
+        history.changes().map(|c| c.serialized_data[0]).collect()
+    }
+
+    fn history(
+        service: &Arc<MockPersistenceService>,
+        qos: DurabilityServiceQosPolicy,
+    ) -> PersistentHistory {
+        PersistentHistory::load(
+            service.clone(),
+            0,
+            String::from("Topic"),
+            WRITER,
+            qos,
+            Time::new(0, 0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn keep_last_history_is_applied_per_instance() {
+        let service = Arc::new(MockPersistenceService::default());
+        let mut history = history(
+            &service,
+            DurabilityServiceQosPolicy {
+                history_kind: HistoryQosPolicyKind::KeepLast(2),
+                ..Default::default()
+            },
+        );
+        let now = Time::new(0, 0);
+
+        for (instance, value) in [(1, 1), (1, 2), (2, 3), (1, 4)] {
+            history
+                .add_change(&cache_change(ChangeKind::Alive, instance, value), now)
+                .unwrap();
+        }
+
+        assert_eq!(values(&history), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn oldest_instances_and_samples_exceeding_the_limits_are_discarded() {
+        let service = Arc::new(MockPersistenceService::default());
+        let mut history = history(
+            &service,
+            DurabilityServiceQosPolicy {
+                history_kind: HistoryQosPolicyKind::KeepAll,
+                max_instances: Length::Limited(2),
+                max_samples: Length::Limited(3),
+                ..Default::default()
+            },
+        );
+        let now = Time::new(0, 0);
+
+        for (instance, value) in [(1, 1), (2, 2), (3, 3), (3, 4), (2, 5)] {
+            history
+                .add_change(&cache_change(ChangeKind::Alive, instance, value), now)
+                .unwrap();
+        }
+
+        assert_eq!(values(&history), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn instances_which_are_not_alive_are_removed_after_the_cleanup_delay() {
+        let service = Arc::new(MockPersistenceService::default());
+        let mut history = history(
+            &service,
+            DurabilityServiceQosPolicy {
+                service_cleanup_delay: Duration::new(10, 0),
+                ..Default::default()
+            },
+        );
+
+        history
+            .add_change(&cache_change(ChangeKind::Alive, 1, 1), Time::new(1, 0))
+            .unwrap();
+        history
+            .add_change(
+                &cache_change(ChangeKind::NotAliveDisposed, 1, 2),
+                Time::new(2, 0),
+            )
+            .unwrap();
+        assert_eq!(values(&history), vec![2]);
+
+        history
+            .add_change(&cache_change(ChangeKind::Alive, 2, 20), Time::new(20, 0))
+            .unwrap();
+        assert_eq!(values(&history), vec![20]);
+    }
+
+    #[test]
+    fn service_is_compacted_once_it_holds_too_many_unneeded_changes() {
+        let service = Arc::new(MockPersistenceService::default());
+        let mut history = history(&service, DurabilityServiceQosPolicy::default());
+        let now = Time::new(0, 0);
+
+        for value in 0..MIN_COMPACTION_CHANGES as u8 {
+            history
+                .add_change(&cache_change(ChangeKind::Alive, 1, value), now)
+                .unwrap();
+        }
+        assert_eq!(*service.compactions.lock().unwrap(), 0);
+
+        history
+            .add_change(&cache_change(ChangeKind::Alive, 1, 100), now)
+            .unwrap();
+        assert_eq!(*service.compactions.lock().unwrap(), 1);
+        assert_eq!(service.changes.lock().unwrap().len(), 1);
+
+        let loaded = PersistentHistory::load(
+            service.clone(),
+            0,
+            String::from("Topic"),
+            WRITER,
+            DurabilityServiceQosPolicy::default(),
+            now,
+        )
+        .unwrap();
+        assert_eq!(values(&loaded), vec![100]);
+    }
+
+    #[test]
+    fn changes_of_other_writers_are_not_compacted() {
+        let other_writer = InstanceHandle::new([2; 16]);
+        let service = Arc::new(MockPersistenceService::default());
+        let now = Time::new(0, 0);
+        service
+            .append(
+                0,
+                "Topic",
+                other_writer,
+                &PersistentChange {
+                    kind: PersistentChangeKind::Alive,
+                    instance_handle: InstanceHandle::new([2; 16]),
+                    source_timestamp: now,
+                    serialized_data: alloc::vec![200],
+                },
+            )
+            .unwrap();
+        let mut history = history(&service, DurabilityServiceQosPolicy::default());
+
+        for value in 0..=MIN_COMPACTION_CHANGES as u8 {
+            history
+                .add_change(&cache_change(ChangeKind::Alive, 1, value), now)
+                .unwrap();
+        }
+
+        assert_eq!(*service.compactions.lock().unwrap(), 1);
+        assert_eq!(values(&history), vec![200, MIN_COMPACTION_CHANGES as u8]);
+        assert_eq!(
+            service
+                .changes
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(w, c)| (*w, c.serialized_data[0]))
+                .collect::<Vec<_>>(),
+            vec![(other_writer, 200), (WRITER, MIN_COMPACTION_CHANGES as u8)]
+        );
+    }
+}
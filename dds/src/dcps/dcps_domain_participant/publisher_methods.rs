@@ -2,24 +2,34 @@ use alloc::string::String;
 
 use crate::{
     dcps::{
+        channels::oneshot::OneshotSender,
         dcps_domain_participant::{
+            data_writer_entity::SharedDataRepresentation,
             participant_entity::DcpsDomainParticipant,
             user_defined_data_writer::UserDefinedDataWriter,
+            user_defined_publisher::{PendingAcknowledgments, publication_message_writer},
         },
         listeners::{
             data_writer_listener::DcpsDataWriterListener, publisher_listener::DcpsPublisherListener,
         },
         status_mask::StatusMask,
     },
+    dds_async::configuration::FlowControllerScope,
     infrastructure::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos, QosKind},
+        qos_policy::PresentationQosPolicyAccessScopeKind,
+        time::{Duration, Time},
+    },
+    rtps::{
+        cache_change::writer_group_digest, flow_controller::FlowController,
+        message_creator::RtpsMessageBatch, stateful_writer::RtpsStatefulWriter,
     },
-    rtps::stateful_writer::RtpsStatefulWriter,
     runtime::DdsRuntime,
     transport::types::{
-        EntityId, Guid, TopicKind, USER_DEFINED_WRITER_NO_KEY, USER_DEFINED_WRITER_WITH_KEY,
+        CoherentSet, EntityId, Guid, TopicKind, USER_DEFINED_WRITER_NO_KEY,
+        USER_DEFINED_WRITER_WITH_KEY,
     },
 };
 
@@ -34,7 +44,7 @@ impl DcpsDomainParticipant {
         dcps_listener: Option<DcpsDataWriterListener>,
         listener_mask: StatusMask,
         runtime: &impl DdsRuntime,
-    ) -> DdsResult<InstanceHandle> {
+    ) -> DdsResult<(InstanceHandle, SharedDataRepresentation)> {
         let Some(topic) = self
             .domain_participant
             .locally_created_topic_list
@@ -100,10 +110,28 @@ impl DcpsDomainParticipant {
             }
         };
         let guid = Guid::from(*self.domain_participant.instance_handle.as_ref());
-        let transport_writer = RtpsStatefulWriter::new(
+        let mut transport_writer = RtpsStatefulWriter::new(
             Guid::new(guid.prefix(), entity_id),
             self.transport.fragment_size,
+            self.writer_timing,
         );
+        // The writers sharing a flow controller get handles to the same token bucket
+        let flow_controller = self.flow_controller.map(|f| {
+            let new_flow_controller =
+                || FlowController::new(f.max_bytes_per_period, f.period.into());
+            match f.scope {
+                FlowControllerScope::Writer => new_flow_controller(),
+                FlowControllerScope::Publisher => publisher
+                    .flow_controller
+                    .get_or_insert_with(new_flow_controller)
+                    .clone(),
+                FlowControllerScope::Participant => self
+                    .participant_flow_controller
+                    .get_or_insert_with(new_flow_controller)
+                    .clone(),
+            }
+        });
+        transport_writer.set_flow_controller(flow_controller);
         let listener_sender = dcps_listener.map(|l| l.spawn(&runtime.spawner()));
         let data_writer = UserDefinedDataWriter::new(
             writer_handle,
@@ -114,6 +142,7 @@ impl DcpsDomainParticipant {
             qos,
         );
         let data_writer_handle = data_writer.instance_handle;
+        let data_representation = data_writer.data_representation.clone();
 
         publisher.data_writer_list.push(data_writer);
 
@@ -121,7 +150,7 @@ impl DcpsDomainParticipant {
             self.enable_data_writer(publisher_handle, &writer_handle, runtime)?;
         }
 
-        Ok(data_writer_handle)
+        Ok((data_writer_handle, data_representation))
     }
 
     #[tracing::instrument(skip(self, runtime))]
@@ -254,4 +283,176 @@ impl DcpsDomainParticipant {
         publisher.listener_mask = listener_mask;
         Ok(())
     }
+
+    #[tracing::instrument(skip(self))]
+    pub fn suspend_publications(&mut self, publisher_handle: &InstanceHandle) -> DdsResult<()> {
+        let Some(publisher) = self
+            .domain_participant
+            .user_defined_publisher_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == publisher_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        if publisher.suspended_messages.is_none() {
+            publisher.suspended_messages = Some(RtpsMessageBatch::new());
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, runtime))]
+    pub fn resume_publications(
+        &mut self,
+        publisher_handle: &InstanceHandle,
+        runtime: &impl DdsRuntime,
+    ) -> DdsResult<()> {
+        let Some(publisher) = self
+            .domain_participant
+            .user_defined_publisher_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == publisher_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        let Some(suspended_messages) = publisher.suspended_messages.take() else {
+            return Err(DdsError::PreconditionNotMet(String::from(
+                "No matching call to suspend_publications",
+            )));
+        };
+        // Heartbeats are not sent while suspended so they are added with the held changes
+        for data_writer in &mut publisher.data_writer_list {
+            data_writer
+                .transport_writer
+                .write_message(&suspended_messages, &runtime.clock());
+        }
+        suspended_messages.flush(
+            self.transport.message_writer.as_ref(),
+            self.transport.fragment_size,
+        );
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, reply_sender))]
+    pub fn wait_for_acknowledgments(
+        &mut self,
+        publisher_handle: &InstanceHandle,
+        max_wait: Duration,
+        now: Time,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    ) {
+        let Some(publisher) = self
+            .domain_participant
+            .user_defined_publisher_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == publisher_handle)
+        else {
+            return reply_sender.send(Err(DdsError::AlreadyDeleted));
+        };
+
+        if publisher.is_acknowledged() {
+            reply_sender.send(Ok(()));
+        } else {
+            publisher
+                .pending_acknowledgments
+                .push(PendingAcknowledgments {
+                    expiration_time: now + max_wait,
+                    reply_sender,
+                });
+        }
+    }
+
+    /// Replies to the calls to wait_for_acknowledgments of the publishers whose data
+    /// got acknowledged or whose maximum wait has elapsed.
+    pub fn check_pending_acknowledgments(&mut self, now: Time) {
+        for publisher in &mut self.domain_participant.user_defined_publisher_list {
+            if publisher.pending_acknowledgments.is_empty() {
+                continue;
+            }
+            if publisher.is_acknowledged() {
+                for pending in publisher.pending_acknowledgments.drain(..) {
+                    pending.reply_sender.send(Ok(()));
+                }
+            } else {
+                for pending in publisher
+                    .pending_acknowledgments
+                    .extract_if(.., |p| now >= p.expiration_time)
+                {
+                    pending.reply_sender.send(Err(DdsError::Timeout));
+                }
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn begin_coherent_changes(&mut self, publisher_handle: &InstanceHandle) -> DdsResult<()> {
+        let Some(publisher) = self
+            .domain_participant
+            .user_defined_publisher_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == publisher_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        publisher.presentation.begin_coherent_changes();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self, runtime))]
+    pub fn end_coherent_changes(
+        &mut self,
+        publisher_handle: &InstanceHandle,
+        runtime: &impl DdsRuntime,
+    ) -> DdsResult<()> {
+        let Some(publisher) = self
+            .domain_participant
+            .user_defined_publisher_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == publisher_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        let Some(finished_set) = publisher.presentation.end_coherent_changes()? else {
+            return Ok(());
+        };
+
+        let writer_set = (publisher.qos.presentation.access_scope
+            == PresentationQosPolicyAccessScopeKind::Group)
+            .then(|| {
+                writer_group_digest(
+                    publisher
+                        .data_writer_list
+                        .iter()
+                        .filter(|dw| {
+                            finished_set
+                                .writers
+                                .iter()
+                                .any(|(h, _)| h == &dw.instance_handle)
+                        })
+                        .map(|dw| dw.transport_writer.guid()),
+                )
+            });
+        for (data_writer_handle, sequence_number) in finished_set.writers {
+            // Writers deleted in the middle of the set are not waited for
+            if let Some(data_writer) = publisher
+                .data_writer_list
+                .iter_mut()
+                .find(|x| x.instance_handle == data_writer_handle)
+            {
+                data_writer.end_coherent_set(
+                    CoherentSet {
+                        sequence_number,
+                        group_sequence_number: finished_set.group_sequence_number,
+                        writer_set,
+                        end_of_set: true,
+                    },
+                    publication_message_writer(
+                        &publisher.suspended_messages,
+                        self.transport.message_writer.as_ref(),
+                    ),
+                    runtime,
+                );
+            }
+        }
+        Ok(())
+    }
 }
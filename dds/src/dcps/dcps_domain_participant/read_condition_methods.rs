@@ -0,0 +1,177 @@
+use crate::{
+    dcps::{
+        channels::notification::NotificationSender,
+        dcps_domain_participant::{
+            participant_entity::DcpsDomainParticipant, topic_entity::get_topic_type_support,
+            user_defined_data_reader::UserDefinedDataReader,
+        },
+        read_condition::ReadConditionEntity,
+    },
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    },
+    transport::types::USER_DEFINED_UNKNOWN,
+    xtypes::dynamic_type::DynamicType,
+};
+use alloc::{string::String, vec::Vec};
+
+impl DcpsDomainParticipant {
+    fn find_user_defined_data_reader(
+        &mut self,
+        subscriber_handle: &InstanceHandle,
+        data_reader_handle: &InstanceHandle,
+    ) -> DdsResult<(&mut UserDefinedDataReader, DynamicType<'static>)> {
+        let Some(subscriber) = self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == subscriber_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        let Some(data_reader) = subscriber
+            .data_reader_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == data_reader_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        let Some(type_support) = get_topic_type_support(
+            &data_reader.topic_name,
+            &self.domain_participant.content_filtered_topic_list,
+            &self.domain_participant.locally_created_topic_list,
+        ) else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+        Ok((data_reader, *type_support))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(skip(self))]
+    pub fn create_read_condition(
+        &mut self,
+        subscriber_handle: &InstanceHandle,
+        data_reader_handle: &InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        query_expression: Option<String>,
+        query_parameters: Vec<String>,
+    ) -> DdsResult<InstanceHandle> {
+        if subscriber_handle == &self.domain_participant.instance_handle {
+            return Err(DdsError::Unsupported);
+        }
+        let condition_handle = InstanceHandle::new([
+            self.domain_participant.instance_handle[0],
+            self.domain_participant.instance_handle[1],
+            self.domain_participant.instance_handle[2],
+            self.domain_participant.instance_handle[3],
+            self.domain_participant.instance_handle[4],
+            self.domain_participant.instance_handle[5],
+            self.domain_participant.instance_handle[6],
+            self.domain_participant.instance_handle[7],
+            self.domain_participant.instance_handle[8],
+            self.domain_participant.instance_handle[9],
+            self.domain_participant.instance_handle[10],
+            self.domain_participant.instance_handle[11],
+            0,
+            self.domain_participant.read_condition_counter.to_ne_bytes()[0],
+            self.domain_participant.read_condition_counter.to_ne_bytes()[1],
+            USER_DEFINED_UNKNOWN,
+        ]);
+        let (data_reader, type_support) =
+            self.find_user_defined_data_reader(subscriber_handle, data_reader_handle)?;
+        data_reader.create_read_condition(
+            condition_handle,
+            sample_states,
+            view_states,
+            instance_states,
+            query_expression
+                .as_deref()
+                .map(|query_expression| (query_expression, query_parameters)),
+            &type_support,
+        )?;
+        self.domain_participant.read_condition_counter += 1;
+        Ok(condition_handle)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn delete_read_condition(
+        &mut self,
+        subscriber_handle: &InstanceHandle,
+        data_reader_handle: &InstanceHandle,
+        condition_handle: &InstanceHandle,
+    ) -> DdsResult<()> {
+        let (data_reader, _) =
+            self.find_user_defined_data_reader(subscriber_handle, data_reader_handle)?;
+        data_reader.delete_read_condition(condition_handle)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn delete_data_reader_contained_entities(
+        &mut self,
+        subscriber_handle: &InstanceHandle,
+        data_reader_handle: &InstanceHandle,
+    ) -> DdsResult<()> {
+        // Conditions can not be created on the readers of the builtin subscriber
+        if subscriber_handle == &self.domain_participant.instance_handle {
+            return Ok(());
+        }
+        let (data_reader, _) =
+            self.find_user_defined_data_reader(subscriber_handle, data_reader_handle)?;
+        data_reader.read_conditions.clear();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_read_condition_trigger_value(
+        &mut self,
+        entity: &ReadConditionEntity,
+    ) -> DdsResult<bool> {
+        let (data_reader, type_support) =
+            self.find_user_defined_data_reader(&entity.subscriber_handle, &entity.reader_handle)?;
+        data_reader.get_read_condition_trigger_value(&entity.condition_handle, type_support)
+    }
+
+    #[tracing::instrument(skip(self, notification_sender))]
+    pub fn register_read_condition_notification(
+        &mut self,
+        entity: &ReadConditionEntity,
+        notification_sender: NotificationSender,
+    ) -> DdsResult<()> {
+        let (data_reader, type_support) =
+            self.find_user_defined_data_reader(&entity.subscriber_handle, &entity.reader_handle)?;
+        data_reader.register_read_condition_notification(
+            &entity.condition_handle,
+            notification_sender,
+            type_support,
+        )
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_parameters(&mut self, entity: &ReadConditionEntity) -> DdsResult<Vec<String>> {
+        let (data_reader, _) =
+            self.find_user_defined_data_reader(&entity.subscriber_handle, &entity.reader_handle)?;
+        Ok(data_reader
+            .get_read_condition(&entity.condition_handle)?
+            .get_query_parameters())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_query_parameters(
+        &mut self,
+        entity: &ReadConditionEntity,
+        query_parameters: Vec<String>,
+    ) -> DdsResult<()> {
+        let (data_reader, type_support) =
+            self.find_user_defined_data_reader(&entity.subscriber_handle, &entity.reader_handle)?;
+        data_reader
+            .get_read_condition(&entity.condition_handle)?
+            .set_query_parameters(query_parameters)?;
+        // The new parameters can make samples already in the reader match the query
+        data_reader.notify_read_conditions(type_support);
+        Ok(())
+    }
+}
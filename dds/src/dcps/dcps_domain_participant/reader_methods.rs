@@ -1,4 +1,4 @@
-use alloc::vec::Vec;
+use alloc::{string::String, sync::Arc, vec::Vec};
 
 use crate::{
     builtin_topics::{
@@ -31,7 +31,9 @@ use crate::{
     xtypes::{
         deserializer::deserialize_top_level_type,
         dynamic_type::{DynamicData, DynamicType},
+        error::XTypesError,
         type_support::TypeSupport,
+        xcdr::deserialize_top_level,
     },
 };
 
@@ -53,7 +55,7 @@ pub fn deserialize_topic_type<'a>(
         DCPS_TOPIC => DiscoveredTopicData::from_bytes(data)
             .map(|x| x.topic_builtin_topic_data.create_dynamic_sample())
             .ok(),
-        _ => deserialize_top_level_type(type_support, data).ok(),
+        _ => return deserialize_user_type(type_support, data),
     };
     if let Some(dynamic_data) = dynamic_data.as_mut() {
         if !dynamic_data.validate_dynamic_data() {
@@ -63,6 +65,61 @@ pub fn deserialize_topic_type<'a>(
     dynamic_data
 }
 
+fn deserialize_user_type<'a>(
+    type_support: DynamicType<'a>,
+    data: &[u8],
+) -> Option<DynamicData<'a>> {
+    let mut dynamic_data = deserialize_top_level_type(type_support, data).ok()?;
+    dynamic_data.validate_dynamic_data().then_some(dynamic_data)
+}
+
+/// Sample returned to the user. The samples of the user topics are kept serialized until they
+/// are converted to the user type, directly when it supports it or else through [`DynamicData`].
+pub enum ReaderSample {
+    Dynamic(DynamicData<'static>),
+    Serialized {
+        serialized_data: Arc<[u8]>,
+        type_support: DynamicType<'static>,
+    },
+}
+
+impl ReaderSample {
+    pub fn new(
+        topic_name: &str,
+        type_support: DynamicType<'static>,
+        serialized_data: Arc<[u8]>,
+    ) -> Option<Self> {
+        match topic_name {
+            DCPS_PARTICIPANT | DCPS_PUBLICATION | DCPS_SUBSCRIPTION | DCPS_TOPIC => {
+                deserialize_topic_type(topic_name, type_support, &serialized_data)
+                    .map(Self::Dynamic)
+            }
+            _ => Some(Self::Serialized {
+                serialized_data,
+                type_support,
+            }),
+        }
+    }
+
+    pub fn into_sample<Foo: TypeSupport>(self) -> Option<Foo> {
+        match self {
+            Self::Dynamic(mut dynamic_data) => Foo::create_sample(&mut dynamic_data),
+            Self::Serialized {
+                serialized_data,
+                type_support,
+            } => match deserialize_top_level(&serialized_data) {
+                Ok(sample) => Some(sample),
+                // Data rejected by the direct deserialization is not valid for the type either
+                Err(XTypesError::InvalidData) => None,
+                Err(_) => {
+                    let mut dynamic_data = deserialize_user_type(type_support, &serialized_data)?;
+                    Foo::create_sample(&mut dynamic_data)
+                }
+            },
+        }
+    }
+}
+
 impl DcpsDomainParticipant {
     #[allow(clippy::too_many_arguments, clippy::type_complexity)]
     #[tracing::instrument(skip(self))]
@@ -75,9 +132,15 @@ impl DcpsDomainParticipant {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: &Option<InstanceHandle>,
-    ) -> DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>> {
+        condition_handle: &Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>> {
         let (sample_list, topic_name) =
             if subscriber_handle == &self.domain_participant.instance_handle {
+                if condition_handle.is_some() {
+                    return Err(DdsError::PreconditionNotMet(String::from(
+                        "Condition not created by this data reader",
+                    )));
+                }
                 let bs = &mut self.domain_participant.builtin_subscriber;
                 if &bs.dcps_participant_reader.instance_handle == data_reader_handle {
                     let sample_list = bs.dcps_participant_reader.read(
@@ -116,12 +179,21 @@ impl DcpsDomainParticipant {
                 else {
                     return Err(DdsError::AlreadyDeleted);
                 };
+                let Some(type_support) = get_topic_type_support(
+                    &data_reader.topic_name,
+                    &self.domain_participant.content_filtered_topic_list,
+                    &self.domain_participant.locally_created_topic_list,
+                ) else {
+                    return Err(DdsError::AlreadyDeleted);
+                };
                 let sample_list = data_reader.read(
                     max_samples,
                     sample_states,
                     view_states,
                     instance_states,
                     specific_instance_handle,
+                    condition_handle,
+                    *type_support,
                 )?;
                 (sample_list, data_reader.topic_name.clone())
             };
@@ -139,7 +211,7 @@ impl DcpsDomainParticipant {
             .map(|(data, info)| {
                 (
                     if info.valid_data {
-                        deserialize_topic_type(&topic_name, *type_support, data.as_ref())
+                        ReaderSample::new(&topic_name, *type_support, data)
                     } else {
                         None
                     },
@@ -160,7 +232,8 @@ impl DcpsDomainParticipant {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: &Option<InstanceHandle>,
-    ) -> DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>> {
+        condition_handle: &Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>> {
         let Some(subscriber) = self
             .domain_participant
             .user_defined_subscriber_list
@@ -176,14 +249,6 @@ impl DcpsDomainParticipant {
         else {
             return Err(DdsError::AlreadyDeleted);
         };
-        let sample_list = data_reader.take(
-            max_samples,
-            sample_states,
-            view_states,
-            instance_states,
-            specific_instance_handle,
-        )?;
-
         let Some(type_support) = get_topic_type_support(
             &data_reader.topic_name,
             &self.domain_participant.content_filtered_topic_list,
@@ -191,17 +256,22 @@ impl DcpsDomainParticipant {
         ) else {
             return Err(DdsError::AlreadyDeleted);
         };
+        let sample_list = data_reader.take(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            specific_instance_handle,
+            condition_handle,
+            *type_support,
+        )?;
 
         Ok(sample_list
             .into_iter()
             .map(|(data, info)| {
                 (
                     if info.valid_data {
-                        deserialize_topic_type(
-                            &data_reader.topic_name,
-                            *type_support,
-                            data.as_ref(),
-                        )
+                        ReaderSample::new(&data_reader.topic_name, *type_support, data)
                     } else {
                         None
                     },
@@ -222,7 +292,8 @@ impl DcpsDomainParticipant {
         sample_states: &[SampleStateKind],
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
-    ) -> DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>> {
+        condition_handle: &Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>> {
         let Some(subscriber) = self
             .domain_participant
             .user_defined_subscriber_list
@@ -238,14 +309,6 @@ impl DcpsDomainParticipant {
         else {
             return Err(DdsError::AlreadyDeleted);
         };
-        let sample_list = data_reader.read_next_instance(
-            max_samples,
-            previous_handle,
-            sample_states,
-            view_states,
-            instance_states,
-        )?;
-
         let Some(type_support) = get_topic_type_support(
             &data_reader.topic_name,
             &self.domain_participant.content_filtered_topic_list,
@@ -253,17 +316,22 @@ impl DcpsDomainParticipant {
         ) else {
             return Err(DdsError::AlreadyDeleted);
         };
+        let sample_list = data_reader.read_next_instance(
+            max_samples,
+            previous_handle,
+            sample_states,
+            view_states,
+            instance_states,
+            condition_handle,
+            *type_support,
+        )?;
 
         Ok(sample_list
             .into_iter()
             .map(|(data, info)| {
                 (
                     if info.valid_data {
-                        deserialize_topic_type(
-                            &data_reader.topic_name,
-                            *type_support,
-                            data.as_ref(),
-                        )
+                        ReaderSample::new(&data_reader.topic_name, *type_support, data)
                     } else {
                         None
                     },
@@ -284,7 +352,8 @@ impl DcpsDomainParticipant {
         sample_states: &[SampleStateKind],
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
-    ) -> DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>> {
+        condition_handle: &Option<InstanceHandle>,
+    ) -> DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>> {
         let Some(subscriber) = self
             .domain_participant
             .user_defined_subscriber_list
@@ -300,14 +369,6 @@ impl DcpsDomainParticipant {
         else {
             return Err(DdsError::AlreadyDeleted);
         };
-        let sample_list = data_reader.take_next_instance(
-            max_samples,
-            previous_handle,
-            sample_states,
-            view_states,
-            instance_states,
-        )?;
-
         let Some(type_support) = get_topic_type_support(
             &data_reader.topic_name,
             &self.domain_participant.content_filtered_topic_list,
@@ -315,17 +376,22 @@ impl DcpsDomainParticipant {
         ) else {
             return Err(DdsError::AlreadyDeleted);
         };
+        let sample_list = data_reader.take_next_instance(
+            max_samples,
+            previous_handle,
+            sample_states,
+            view_states,
+            instance_states,
+            condition_handle,
+            *type_support,
+        )?;
 
         Ok(sample_list
             .into_iter()
             .map(|(data, info)| {
                 (
                     if info.valid_data {
-                        deserialize_topic_type(
-                            &data_reader.topic_name,
-                            *type_support,
-                            data.as_ref(),
-                        )
+                        ReaderSample::new(&data_reader.topic_name, *type_support, data)
                     } else {
                         None
                     },
@@ -1,4 +1,4 @@
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 use crate::{
     builtin_topics::{DCPS_PARTICIPANT, DCPS_PUBLICATION, DCPS_SUBSCRIPTION, DCPS_TOPIC},
@@ -18,7 +18,8 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos},
-        qos_policy::ReliabilityQosPolicyKind,
+        qos_policy::{PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicyKind},
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
     },
     rtps::stateful_reader::RtpsStatefulReader,
     runtime::DdsRuntime,
@@ -170,7 +171,18 @@ impl DcpsDomainParticipant {
             .iter()
             .position(|x| &x.instance_handle == datareader_handle)
         {
+            if !subscriber.data_reader_list[index]
+                .read_conditions
+                .is_empty()
+            {
+                return Err(DdsError::PreconditionNotMet(String::from(
+                    "Data reader still has read conditions",
+                )));
+            }
             let data_reader = subscriber.data_reader_list.remove(index);
+            subscriber
+                .coherent_set_buffer
+                .remove_reader(&data_reader.instance_handle);
             self.announce_deleted_data_reader(data_reader, runtime);
         } else {
             return Err(DdsError::AlreadyDeleted);
@@ -358,4 +370,132 @@ impl DcpsDomainParticipant {
         subscriber.listener_mask = listener_mask;
         Ok(())
     }
+
+    #[tracing::instrument(skip(self))]
+    pub fn begin_access(&mut self, subscriber_handle: &InstanceHandle) -> DdsResult<()> {
+        let Some(subscriber) = self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == subscriber_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+
+        subscriber.access_count += 1;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn end_access(&mut self, subscriber_handle: &InstanceHandle) -> DdsResult<()> {
+        let Some(subscriber) = self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter_mut()
+            .find(|x| &x.instance_handle == subscriber_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+
+        if subscriber.access_count == 0 {
+            return Err(DdsError::PreconditionNotMet(String::from(
+                "No matching call to begin_access",
+            )));
+        }
+        subscriber.access_count -= 1;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_data_readers(
+        &mut self,
+        subscriber_handle: &InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        let Some(subscriber) = self
+            .domain_participant
+            .user_defined_subscriber_list
+            .iter()
+            .find(|x| &x.instance_handle == subscriber_handle)
+        else {
+            return Err(DdsError::AlreadyDeleted);
+        };
+
+        let presentation = &subscriber.qos.presentation;
+        if !(presentation.ordered_access
+            && presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group)
+        {
+            return Ok(subscriber
+                .data_reader_list
+                .iter()
+                .filter(|dr| {
+                    dr.contains_sample(
+                        &sample_states,
+                        &view_states,
+                        &instance_states,
+                        None,
+                        &|_| true,
+                    )
+                })
+                .map(|dr| dr.instance_handle)
+                .collect());
+        }
+
+        // The order of the samples is only kept while the application is accessing them
+        if subscriber.access_count == 0 {
+            return Err(DdsError::PreconditionNotMet(String::from(
+                "Readers with GROUP ordered access must be retrieved after begin_access",
+            )));
+        }
+
+        // Each reader appears once for every sample to read from it. The samples are
+        // sorted by reception and the samples of each publisher are then put in the
+        // order in which they were written.
+        let mut samples: Vec<_> = subscriber
+            .data_reader_list
+            .iter()
+            .flat_map(|dr| {
+                dr.samples_in_states(&sample_states, &view_states, &instance_states)
+                    .map(|sample| {
+                        (
+                            sample.reception_timestamp,
+                            sample.group_order,
+                            dr.instance_handle,
+                        )
+                    })
+            })
+            .collect();
+        samples.sort_by_key(|(reception_timestamp, _, _)| *reception_timestamp);
+        let mut writer_groups = Vec::new();
+        for group_order in samples
+            .iter()
+            .filter_map(|(_, group_order, _)| *group_order)
+        {
+            if !writer_groups.contains(&group_order.writer_group) {
+                writer_groups.push(group_order.writer_group);
+            }
+        }
+        for writer_group in writer_groups {
+            let positions: Vec<usize> = samples
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, group_order, _))| {
+                    group_order.is_some_and(|g| g.writer_group == writer_group)
+                })
+                .map(|(position, _)| position)
+                .collect();
+            let mut group_samples: Vec<_> = positions.iter().map(|&p| samples[p]).collect();
+            group_samples
+                .sort_by_key(|(_, group_order, _)| group_order.map(|g| g.group_sequence_number));
+            for (position, sample) in positions.into_iter().zip(group_samples) {
+                samples[position] = sample;
+            }
+        }
+        Ok(samples
+            .into_iter()
+            .map(|(_, _, data_reader_handle)| data_reader_handle)
+            .collect())
+    }
 }
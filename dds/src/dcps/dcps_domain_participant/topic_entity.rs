@@ -7,12 +7,18 @@ use crate::{
     },
     dcps::{
         channels::mpsc::MpscSender,
-        data_representation_builtin_endpoints::type_lookup::{TypeLookupReply, TypeLookupRequest},
+        content_filter::ContentFilter,
+        data_representation_builtin_endpoints::{
+            discovered_reader_data::{ContentFilterProperty, DDSSQL_FILTER_CLASS_NAME},
+            type_lookup::{TypeLookupReply, TypeLookupRequest},
+        },
         listeners::domain_participant_listener::ListenerMail,
         status_condition::DcpsStatusCondition,
         status_mask::StatusMask,
     },
-    infrastructure::{instance::InstanceHandle, qos::TopicQos, status::InconsistentTopicStatus},
+    infrastructure::{
+        error::DdsResult, instance::InstanceHandle, qos::TopicQos, status::InconsistentTopicStatus,
+    },
     xtypes::{
         dynamic_type::DynamicType,
         type_object::{TypeInformation, TypeObject},
@@ -26,6 +32,9 @@ pub struct ContentFilteredTopicEntity {
     pub related_topic_name: String,
     pub filter_expression: String,
     pub expression_parameters: Vec<String>,
+    pub content_filter: ContentFilter,
+    /// Signature of the content filter property, kept up to date with the expression parameters
+    pub filter_signature: [u8; 16],
 }
 
 impl ContentFilteredTopicEntity {
@@ -34,12 +43,39 @@ impl ContentFilteredTopicEntity {
         related_topic_name: String,
         filter_expression: String,
         expression_parameters: Vec<String>,
+        content_filter: ContentFilter,
     ) -> Self {
-        Self {
+        let mut content_filtered_topic = Self {
             topic_name: name,
             related_topic_name,
             filter_expression,
             expression_parameters,
+            content_filter,
+            filter_signature: [0; 16],
+        };
+        content_filtered_topic.filter_signature =
+            content_filtered_topic.content_filter_property().signature();
+        content_filtered_topic
+    }
+
+    pub fn set_expression_parameters(
+        &mut self,
+        expression_parameters: Vec<String>,
+    ) -> DdsResult<()> {
+        self.content_filter
+            .set_expression_parameters(&expression_parameters)?;
+        self.expression_parameters = expression_parameters;
+        self.filter_signature = self.content_filter_property().signature();
+        Ok(())
+    }
+
+    pub fn content_filter_property(&self) -> ContentFilterProperty {
+        ContentFilterProperty {
+            content_filtered_topic_name: self.topic_name.clone(),
+            related_topic_name: self.related_topic_name.clone(),
+            filter_class_name: String::from(DDSSQL_FILTER_CLASS_NAME),
+            filter_expression: self.filter_expression.clone(),
+            expression_parameters: self.expression_parameters.clone(),
         }
     }
 }
@@ -1,8 +1,9 @@
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     dcps::{
-        channels::{mpsc::MpscSender, oneshot::OneshotSender},
+        channels::{mpsc::MpscSender, notification::NotificationSender, oneshot::OneshotSender},
         listeners::domain_participant_listener::ListenerMail,
+        read_condition::DcpsReadCondition,
         status_condition::DcpsStatusCondition,
         status_mask::StatusMask,
     },
@@ -18,6 +19,8 @@ use crate::{
         },
     },
     rtps::stateful_reader::RtpsStatefulReader,
+    transport::types::ChangeKind,
+    xtypes::dynamic_type::DynamicType,
 };
 use alloc::{string::String, vec::Vec};
 use core::ops::{Deref, DerefMut};
@@ -35,6 +38,7 @@ pub struct UserDefinedDataReader {
     pub subscription_matched_status: SubscriptionMatchedStatus,
     pub incompatible_writer_list: Vec<InstanceHandle>,
     pub wait_for_historical_data_notification: Vec<OneshotSender<DdsResult<()>>>,
+    pub read_conditions: Vec<DcpsReadCondition>,
 }
 
 impl Deref for UserDefinedDataReader {
@@ -70,6 +74,7 @@ impl UserDefinedDataReader {
             subscription_matched_status: SubscriptionMatchedStatus::const_default(),
             incompatible_writer_list: Vec::new(),
             wait_for_historical_data_notification: Vec::new(),
+            read_conditions: Vec::new(),
         }
     }
 
@@ -169,6 +174,7 @@ impl UserDefinedDataReader {
         status
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn read(
         &mut self,
         max_samples: i32,
@@ -176,18 +182,22 @@ impl UserDefinedDataReader {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: &Option<InstanceHandle>,
+        condition_handle: &Option<InstanceHandle>,
+        type_support: DynamicType<'static>,
     ) -> DdsResult<SampleList> {
-        self.status_condition
-            .remove_communication_state(StatusKind::DataAvailable);
-        self.reader.read(
+        self.create_sample_collection_w_condition(
             max_samples,
             sample_states,
             view_states,
             instance_states,
             specific_instance_handle,
+            condition_handle,
+            type_support,
+            false,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn take(
         &mut self,
         max_samples: i32,
@@ -195,18 +205,193 @@ impl UserDefinedDataReader {
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
         specific_instance_handle: &Option<InstanceHandle>,
+        condition_handle: &Option<InstanceHandle>,
+        type_support: DynamicType<'static>,
     ) -> DdsResult<SampleList> {
+        self.create_sample_collection_w_condition(
+            max_samples,
+            sample_states,
+            view_states,
+            instance_states,
+            specific_instance_handle,
+            condition_handle,
+            type_support,
+            true,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_sample_collection_w_condition(
+        &mut self,
+        max_samples: i32,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        specific_instance_handle: &Option<InstanceHandle>,
+        condition_handle: &Option<InstanceHandle>,
+        type_support: DynamicType<'static>,
+        take: bool,
+    ) -> DdsResult<SampleList> {
+        if !self.enabled {
+            return Err(DdsError::NotEnabled);
+        }
+        let condition = match condition_handle {
+            Some(h) => Some(
+                self.read_conditions
+                    .iter()
+                    .find(|x| x.instance_handle() == h)
+                    .ok_or_else(|| {
+                        DdsError::PreconditionNotMet(String::from(
+                            "Condition not created by this data reader",
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
+
         self.status_condition
             .remove_communication_state(StatusKind::DataAvailable);
-        self.reader.take(
+        self.reader.create_sample_collection(
             max_samples,
             sample_states,
             view_states,
             instance_states,
             specific_instance_handle,
+            take,
+            &|sample| condition.is_none_or(|c| c.matches_query(sample, type_support)),
         )
     }
 
+    pub fn create_read_condition(
+        &mut self,
+        instance_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        query: Option<(&str, Vec<String>)>,
+        type_support: &DynamicType<'static>,
+    ) -> DdsResult<()> {
+        let read_condition = match query {
+            Some((query_expression, query_parameters)) => DcpsReadCondition::new_query(
+                instance_handle,
+                sample_states,
+                view_states,
+                instance_states,
+                query_expression,
+                query_parameters,
+                type_support,
+            )?,
+            None => {
+                DcpsReadCondition::new(instance_handle, sample_states, view_states, instance_states)
+            }
+        };
+        self.read_conditions.push(read_condition);
+        Ok(())
+    }
+
+    pub fn delete_read_condition(&mut self, condition_handle: &InstanceHandle) -> DdsResult<()> {
+        let Some(index) = self
+            .read_conditions
+            .iter()
+            .position(|x| x.instance_handle() == condition_handle)
+        else {
+            return Err(DdsError::PreconditionNotMet(String::from(
+                "Condition not created by this data reader",
+            )));
+        };
+        self.read_conditions.remove(index);
+        Ok(())
+    }
+
+    pub fn get_read_condition(
+        &mut self,
+        condition_handle: &InstanceHandle,
+    ) -> DdsResult<&mut DcpsReadCondition> {
+        self.read_conditions
+            .iter_mut()
+            .find(|x| x.instance_handle() == condition_handle)
+            .ok_or(DdsError::AlreadyDeleted)
+    }
+
+    pub fn get_read_condition_trigger_value(
+        &self,
+        condition_handle: &InstanceHandle,
+        type_support: DynamicType<'static>,
+    ) -> DdsResult<bool> {
+        let read_condition = self
+            .read_conditions
+            .iter()
+            .find(|x| x.instance_handle() == condition_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        Ok(read_condition.get_trigger_value(&self.reader, type_support))
+    }
+
+    pub fn register_read_condition_notification(
+        &mut self,
+        condition_handle: &InstanceHandle,
+        notification_sender: NotificationSender,
+        type_support: DynamicType<'static>,
+    ) -> DdsResult<()> {
+        let read_condition = self
+            .read_conditions
+            .iter_mut()
+            .find(|x| x.instance_handle() == condition_handle)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        let trigger_value = read_condition.get_trigger_value(&self.reader, type_support);
+        read_condition.register_notification(notification_sender, trigger_value);
+        Ok(())
+    }
+
+    pub fn notify_read_conditions(&mut self, type_support: DynamicType<'static>) {
+        for read_condition in &mut self.read_conditions {
+            if read_condition.has_registered_notifications()
+                && read_condition.get_trigger_value(&self.reader, type_support)
+            {
+                read_condition.notify();
+            }
+        }
+    }
+
+    /// Notifies the read conditions which are triggered by a newly added sample. Only the new
+    /// sample is evaluated unless it changed the state of its instance, in which case the other
+    /// samples of that instance can also start matching the conditions.
+    pub fn notify_read_conditions_on_new_sample(
+        &mut self,
+        change_kind: ChangeKind,
+        data_value: &[u8],
+        instance_handle: &InstanceHandle,
+        previous_instance_state: Option<(ViewStateKind, InstanceStateKind)>,
+        type_support: DynamicType<'static>,
+    ) {
+        let Some((view_state, instance_state)) = self.reader.get_instance_state(instance_handle)
+        else {
+            return;
+        };
+        let is_instance_state_changed =
+            previous_instance_state != Some((view_state, instance_state));
+        for read_condition in &mut self.read_conditions {
+            if !read_condition.has_registered_notifications() {
+                continue;
+            }
+            if read_condition.matches_new_sample(
+                change_kind,
+                data_value,
+                view_state,
+                instance_state,
+                type_support,
+            ) || is_instance_state_changed
+                && read_condition.get_instance_trigger_value(
+                    &self.reader,
+                    instance_handle,
+                    type_support,
+                )
+            {
+                read_condition.notify();
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn take_next_instance(
         &mut self,
         max_samples: i32,
@@ -214,6 +399,8 @@ impl UserDefinedDataReader {
         sample_states: &[SampleStateKind],
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
+        condition_handle: &Option<InstanceHandle>,
+        type_support: DynamicType<'static>,
     ) -> DdsResult<SampleList> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -226,11 +413,14 @@ impl UserDefinedDataReader {
                 view_states,
                 instance_states,
                 &Some(next_handle),
+                condition_handle,
+                type_support,
             ),
             None => Err(DdsError::NoData),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn read_next_instance(
         &mut self,
         max_samples: i32,
@@ -238,6 +428,8 @@ impl UserDefinedDataReader {
         sample_states: &[SampleStateKind],
         view_states: &[ViewStateKind],
         instance_states: &[InstanceStateKind],
+        condition_handle: &Option<InstanceHandle>,
+        type_support: DynamicType<'static>,
     ) -> DdsResult<SampleList> {
         if !self.enabled {
             return Err(DdsError::NotEnabled);
@@ -250,6 +442,8 @@ impl UserDefinedDataReader {
                 view_states,
                 instance_states,
                 &Some(next_handle),
+                condition_handle,
+                type_support,
             ),
             None => Err(DdsError::NoData),
         }
@@ -3,7 +3,7 @@ use crate::{
     dcps::{
         channels::{mpsc::MpscSender, oneshot::OneshotSender},
         dcps_domain_participant::data_writer_entity::{
-            DataWriterEntity, IncompatibleSubscriptions,
+            DataWriterEntity, IncompatibleSubscriptions, SharedDataRepresentation, WriterSample,
         },
         listeners::domain_participant_listener::ListenerMail,
         status_condition::DcpsStatusCondition,
@@ -17,13 +17,14 @@ use crate::{
         time::Time,
     },
     rtps::stateful_writer::RtpsStatefulWriter,
-    xtypes::dynamic_type::DynamicData,
+    runtime::DdsRuntime,
+    transport::{interface::WriteMessage, types::CoherentSet},
 };
 use alloc::{string::String, vec::Vec};
 use core::ops::{Deref, DerefMut};
 
 pub struct PendingWriteSample {
-    pub dynamic_data: DynamicData<'static>,
+    pub sample: WriterSample,
     pub timestamp: Time,
     pub reply_sender: OneshotSender<DdsResult<()>>,
     pub expiration_time: Option<Time>,
@@ -45,6 +46,7 @@ pub struct UserDefinedDataWriter {
     /// wait_for_acknowledgments method
     pub wait_for_acknowledgments_notification: Vec<OneshotSender<DdsResult<()>>>,
     pub pending_write_sample: Option<PendingWriteSample>,
+    pub data_representation: SharedDataRepresentation,
 }
 
 impl Deref for UserDefinedDataWriter {
@@ -70,6 +72,7 @@ impl UserDefinedDataWriter {
         listener_mask: StatusMask,
         qos: DataWriterQos,
     ) -> Self {
+        let data_representation = SharedDataRepresentation::new(&qos.representation);
         Self {
             writer: DataWriterEntity::new(instance_handle, transport_writer, topic_name, qos),
             listener_sender,
@@ -82,6 +85,7 @@ impl UserDefinedDataWriter {
             acknowledgement_notification: None,
             wait_for_acknowledgments_notification: Vec::new(),
             pending_write_sample: None,
+            data_representation,
         }
     }
 
@@ -99,6 +103,24 @@ impl UserDefinedDataWriter {
         self.publication_matched_status.current_count_change -= 1;
     }
 
+    /// Tells the readers that the coherent set is complete. The marker takes the next
+    /// sequence number of the writer but it is not stored in the history.
+    pub fn end_coherent_set(
+        &mut self,
+        coherent_set: CoherentSet,
+        message_writer: &(impl WriteMessage + ?Sized),
+        runtime: &impl DdsRuntime,
+    ) {
+        self.last_change_sequence_number += 1;
+        let sequence_number = self.last_change_sequence_number;
+        self.transport_writer.end_coherent_set(
+            sequence_number,
+            coherent_set,
+            message_writer,
+            &runtime.clock(),
+        );
+    }
+
     pub fn get_offered_deadline_missed_status(&mut self) -> OfferedDeadlineMissedStatus {
         let status = self.offered_deadline_missed_status.clone();
         self.offered_deadline_missed_status.total_count_change = 0;
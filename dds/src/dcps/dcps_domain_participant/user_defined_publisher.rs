@@ -1,15 +1,150 @@
 use crate::{
     dcps::{
-        channels::mpsc::MpscSender,
-        dcps_domain_participant::user_defined_data_writer::UserDefinedDataWriter,
-        listeners::domain_participant_listener::ListenerMail, status_mask::StatusMask,
+        channels::{mpsc::MpscSender, oneshot::OneshotSender},
+        dcps_domain_participant::{
+            data_writer_entity::ChangePresentation, user_defined_data_writer::UserDefinedDataWriter,
+        },
+        listeners::domain_participant_listener::ListenerMail,
+        status_mask::StatusMask,
     },
     infrastructure::{
+        error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, PublisherQos},
+        qos_policy::{PresentationQosPolicy, PresentationQosPolicyAccessScopeKind},
+        time::Time,
+    },
+    rtps::{flow_controller::FlowController, message_creator::RtpsMessageBatch},
+    transport::{
+        interface::WriteMessage,
+        types::{CoherentSet, SequenceNumber},
     },
 };
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
+
+/// Coherent set of changes started with begin_coherent_changes
+struct CoherentChanges {
+    depth: u32,
+    group_sequence_number: Option<SequenceNumber>,
+    writers: Vec<(InstanceHandle, SequenceNumber)>,
+}
+
+/// The writers which took part in a finished coherent set together with the
+/// information needed to mark the end of the set on each of them.
+pub struct FinishedCoherentSet {
+    pub group_sequence_number: Option<SequenceNumber>,
+    pub writers: Vec<(InstanceHandle, SequenceNumber)>,
+}
+
+pub struct PublisherPresentation {
+    coherent_changes: Option<CoherentChanges>,
+    last_group_sequence_number: SequenceNumber,
+}
+
+impl PublisherPresentation {
+    pub const fn new() -> Self {
+        Self {
+            coherent_changes: None,
+            last_group_sequence_number: 0,
+        }
+    }
+
+    pub fn begin_coherent_changes(&mut self) {
+        match &mut self.coherent_changes {
+            Some(coherent_changes) => coherent_changes.depth += 1,
+            None => {
+                self.coherent_changes = Some(CoherentChanges {
+                    depth: 1,
+                    group_sequence_number: None,
+                    writers: Vec::new(),
+                })
+            }
+        }
+    }
+
+    /// Returns the finished set when the outermost coherent set is ended.
+    pub fn end_coherent_changes(&mut self) -> DdsResult<Option<FinishedCoherentSet>> {
+        let Some(coherent_changes) = &mut self.coherent_changes else {
+            return Err(DdsError::PreconditionNotMet(String::from(
+                "No matching call to begin_coherent_changes",
+            )));
+        };
+        coherent_changes.depth -= 1;
+        if coherent_changes.depth > 0 {
+            return Ok(None);
+        }
+        Ok(self
+            .coherent_changes
+            .take()
+            .map(|coherent_changes| FinishedCoherentSet {
+                group_sequence_number: coherent_changes.group_sequence_number,
+                writers: coherent_changes.writers,
+            }))
+    }
+
+    /// Returns the presentation information of the next change of the data writer,
+    /// which is going to be written with the given sequence number.
+    pub fn next_change(
+        &mut self,
+        presentation: &PresentationQosPolicy,
+        data_writer_handle: InstanceHandle,
+        sequence_number: SequenceNumber,
+    ) -> ChangePresentation {
+        let is_group_scope =
+            presentation.access_scope == PresentationQosPolicyAccessScopeKind::Group;
+        let group_sequence_number =
+            if is_group_scope && (presentation.coherent_access || presentation.ordered_access) {
+                self.last_group_sequence_number += 1;
+                Some(self.last_group_sequence_number)
+            } else {
+                None
+            };
+
+        let coherent_set = match &mut self.coherent_changes {
+            Some(coherent_changes)
+                if presentation.coherent_access
+                    && presentation.access_scope
+                        != PresentationQosPolicyAccessScopeKind::Instance =>
+            {
+                if is_group_scope && coherent_changes.group_sequence_number.is_none() {
+                    coherent_changes.group_sequence_number = group_sequence_number;
+                }
+                let set_sequence_number = match coherent_changes
+                    .writers
+                    .iter()
+                    .find(|(h, _)| h == &data_writer_handle)
+                {
+                    Some((_, first_sequence_number)) => *first_sequence_number,
+                    None => {
+                        coherent_changes
+                            .writers
+                            .push((data_writer_handle, sequence_number));
+                        sequence_number
+                    }
+                };
+                Some(CoherentSet {
+                    sequence_number: set_sequence_number,
+                    group_sequence_number: coherent_changes.group_sequence_number,
+                    writer_set: None,
+                    end_of_set: false,
+                })
+            }
+            _ => None,
+        };
+
+        ChangePresentation {
+            coherent_set,
+            group_sequence_number,
+        }
+    }
+}
+
+/// Call to wait_for_acknowledgments waiting for the data of all the writers of the
+/// publisher to be acknowledged
+pub struct PendingAcknowledgments {
+    pub expiration_time: Time,
+    pub reply_sender: OneshotSender<DdsResult<()>>,
+}
 
 pub struct PublisherEntity {
     pub qos: PublisherQos,
@@ -19,6 +154,12 @@ pub struct PublisherEntity {
     pub default_datawriter_qos: DataWriterQos,
     pub listener_sender: Option<MpscSender<ListenerMail>>,
     pub listener_mask: StatusMask,
+    pub presentation: PublisherPresentation,
+    /// Messages of the data writers held since publications were suspended
+    pub suspended_messages: Option<RtpsMessageBatch>,
+    pub pending_acknowledgments: Vec<PendingAcknowledgments>,
+    /// Flow controller shared by the data writers of the publisher
+    pub flow_controller: Option<FlowController>,
 }
 
 impl PublisherEntity {
@@ -37,6 +178,31 @@ impl PublisherEntity {
             default_datawriter_qos: DataWriterQos::const_default(),
             listener_sender,
             listener_mask,
+            presentation: PublisherPresentation::new(),
+            suspended_messages: None,
+            pending_acknowledgments: Vec::new(),
+            flow_controller: None,
         }
     }
+
+    /// Whether all the changes of the data writers have been acknowledged by all
+    /// their matched reliable readers
+    pub fn is_acknowledged(&self) -> bool {
+        self.data_writer_list.iter().all(|dw| {
+            dw.transport_writer
+                .is_change_acknowledged(dw.last_change_sequence_number)
+        })
+    }
+}
+
+/// Returns where the messages of the data writers of a publisher are written, which
+/// is the batch of held messages while its publications are suspended.
+pub fn publication_message_writer<'a>(
+    suspended_messages: &'a Option<RtpsMessageBatch>,
+    message_writer: &'a (dyn WriteMessage + Send + Sync),
+) -> &'a dyn WriteMessage {
+    match suspended_messages {
+        Some(suspended_messages) => suspended_messages,
+        None => message_writer,
+    }
 }
@@ -2,7 +2,8 @@ use crate::{
     dcps::{
         channels::mpsc::MpscSender,
         dcps_domain_participant::{
-            subscriber_entity::SubscriberEntity, user_defined_data_reader::UserDefinedDataReader,
+            coherent_set_buffer::CoherentSetBuffer, subscriber_entity::SubscriberEntity,
+            user_defined_data_reader::UserDefinedDataReader,
         },
         listeners::domain_participant_listener::ListenerMail,
         status_condition::DcpsStatusCondition,
@@ -23,6 +24,9 @@ pub struct UserDefinedSubscriber {
     pub listener_sender: Option<MpscSender<ListenerMail>>,
     pub listener_mask: StatusMask,
     pub data_reader_list: Vec<UserDefinedDataReader>,
+    pub coherent_set_buffer: CoherentSetBuffer,
+    /// Number of begin_access calls not yet matched by an end_access
+    pub access_count: u32,
 }
 
 impl UserDefinedSubscriber {
@@ -39,6 +43,8 @@ impl UserDefinedSubscriber {
             listener_sender,
             listener_mask,
             data_reader_list: Vec::new(),
+            coherent_set_buffer: CoherentSetBuffer::new(),
+            access_count: 0,
         }
     }
 }
@@ -5,8 +5,9 @@ use crate::{
     dcps::{
         channels::oneshot::OneshotSender,
         dcps_domain_participant::{
-            data_writer_entity::serialize, participant_entity::DcpsDomainParticipant,
-            user_defined_data_writer::PendingWriteSample,
+            data_writer_entity::WriterSample, participant_entity::DcpsDomainParticipant,
+            persistent_history::PersistentHistory, user_defined_data_writer::PendingWriteSample,
+            user_defined_publisher::publication_message_writer,
         },
         listeners::data_writer_listener::DcpsDataWriterListener,
         status_mask::StatusMask,
@@ -18,7 +19,10 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataWriterQos, QosKind},
-        qos_policy::{HistoryQosPolicyKind, ReliabilityQosPolicyKind},
+        qos_policy::{
+            DurabilityQosPolicyKind, HistoryQosPolicyKind, PublishModeQosPolicyKind,
+            ReliabilityQosPolicyKind,
+        },
         status::{OfferedDeadlineMissedStatus, PublicationMatchedStatus, StatusKind},
         time::{DurationKind, Time},
     },
@@ -236,11 +240,20 @@ impl DcpsDomainParticipant {
             .find(|x| x.topic_name == data_writer.topic_name)
             .expect("Writer topic must exist");
 
+        let presentation = publisher.presentation.next_change(
+            &publisher.qos.presentation,
+            data_writer.instance_handle,
+            data_writer.last_change_sequence_number + 1,
+        );
         data_writer.unregister_w_timestamp(
             dynamic_data,
             &topic.type_support,
             timestamp,
-            self.transport.message_writer.as_ref(),
+            presentation,
+            publication_message_writer(
+                &publisher.suspended_messages,
+                self.transport.message_writer.as_ref(),
+            ),
             runtime,
         )
     }
@@ -300,7 +313,7 @@ impl DcpsDomainParticipant {
         &mut self,
         publisher_handle: &InstanceHandle,
         data_writer_handle: &InstanceHandle,
-        dynamic_data: &DynamicData<'static>,
+        sample: WriterSample,
         timestamp: Time,
         runtime: &impl DdsRuntime,
         reply_sender: OneshotSender<DdsResult<()>>,
@@ -329,27 +342,10 @@ impl DcpsDomainParticipant {
             return;
         }
 
-        let serialized_data = match serialize(dynamic_data, &data_writer.qos.representation) {
-            Ok(s) => s,
-            Err(e) => {
-                reply_sender.send(Err(e));
-                return;
-            }
-        };
-
-        let mut member_list = Vec::new();
-        let key_holder_data = match KeyHolderData::from_dynamic_data(dynamic_data, &mut member_list)
-        {
-            Ok(h) => h,
-            Err(e) => {
-                reply_sender.send(Err(e.into()));
-                return;
-            }
-        };
-        let instance_handle = match get_instance_handle_from_key_holder_data(&key_holder_data) {
+        let instance_handle = match sample.instance_handle() {
             Ok(h) => h,
             Err(e) => {
-                reply_sender.send(Err(e.into()));
+                reply_sender.send(Err(e));
                 return;
             }
         };
@@ -368,7 +364,10 @@ impl DcpsDomainParticipant {
                 });
 
             if let Some(smallest_seq_num_instance) = smallest_seq_num_instance {
+                // The changes of a suspended publisher have not been sent yet so they are
+                // replaced without waiting for them to be acknowledged
                 if data_writer.qos.reliability.kind == ReliabilityQosPolicyKind::Reliable
+                    && publisher.suspended_messages.is_none()
                     && !data_writer
                         .transport_writer
                         .is_change_acknowledged(smallest_seq_num_instance)
@@ -384,7 +383,7 @@ impl DcpsDomainParticipant {
                         DurationKind::Infinite => None,
                     };
                     data_writer.pending_write_sample = Some(PendingWriteSample {
-                        dynamic_data: dynamic_data.clone(),
+                        sample,
                         timestamp,
                         reply_sender,
                         expiration_time,
@@ -406,12 +405,29 @@ impl DcpsDomainParticipant {
             }
         }
 
+        let serialized_data = match sample.into_serialized_data(&data_writer.qos.representation) {
+            Ok(s) => s,
+            Err(e) => {
+                reply_sender.send(Err(e));
+                return;
+            }
+        };
+
+        let presentation = publisher.presentation.next_change(
+            &publisher.qos.presentation,
+            data_writer.instance_handle,
+            data_writer.last_change_sequence_number + 1,
+        );
         let write_result = data_writer.write_w_timestamp(
             instance_handle,
             serialized_data,
             timestamp,
             now,
-            self.transport.message_writer.as_ref(),
+            presentation,
+            publication_message_writer(
+                &publisher.suspended_messages,
+                self.transport.message_writer.as_ref(),
+            ),
             runtime,
         );
         if write_result.is_err() {
@@ -453,11 +469,20 @@ impl DcpsDomainParticipant {
             .find(|x| x.topic_name == data_writer.topic_name)
             .expect("Writer topic must exist");
 
+        let presentation = publisher.presentation.next_change(
+            &publisher.qos.presentation,
+            data_writer.instance_handle,
+            data_writer.last_change_sequence_number + 1,
+        );
         data_writer.dispose_w_timestamp(
             dynamic_data,
             &topic.type_support,
             timestamp,
-            self.transport.message_writer.as_ref(),
+            presentation,
+            publication_message_writer(
+                &publisher.suspended_messages,
+                self.transport.message_writer.as_ref(),
+            ),
             runtime,
         )
     }
@@ -510,6 +535,32 @@ impl DcpsDomainParticipant {
             return Err(DdsError::AlreadyDeleted);
         };
         if !data_writer.enabled {
+            if data_writer.qos.publish_mode.kind == PublishModeQosPolicyKind::Asynchronous {
+                data_writer
+                    .transport_writer
+                    .set_asynchronous_publication(true);
+                self.asynchronous_sender.start(&runtime.sender_spawner());
+            }
+            if let (DurabilityQosPolicyKind::Persistent, Some(persistence_service)) =
+                (&data_writer.qos.durability.kind, &self.persistence_service)
+            {
+                let persistent_history = PersistentHistory::load(
+                    persistence_service.clone(),
+                    self.domain_participant.domain_id,
+                    data_writer.topic_name.clone(),
+                    data_writer.instance_handle,
+                    data_writer.qos.durability_service.clone(),
+                    runtime.clock().now(),
+                )?;
+                data_writer.restore_persistent_history(
+                    persistent_history,
+                    publication_message_writer(
+                        &publisher.suspended_messages,
+                        self.transport.message_writer.as_ref(),
+                    ),
+                    runtime,
+                );
+            }
             data_writer.enabled = true;
 
             self.announce_data_writer(publisher_handle, data_writer_handle, runtime);
@@ -550,6 +601,9 @@ impl DcpsDomainParticipant {
             data_writer.qos.check_immutability(&qos)?;
         }
         data_writer.qos = qos;
+        data_writer
+            .data_representation
+            .set(&data_writer.qos.representation);
 
         if data_writer.enabled {
             self.announce_data_writer(publisher_handle, data_writer_handle, runtime);
@@ -604,15 +658,7 @@ impl DcpsDomainParticipant {
                     if !data_writer.enabled {
                         continue;
                     }
-                    let mut member_list = Vec::new();
-                    let Ok(key_holder_data) =
-                        KeyHolderData::from_dynamic_data(&pending.dynamic_data, &mut member_list)
-                    else {
-                        continue;
-                    };
-                    let Ok(instance_handle) =
-                        get_instance_handle_from_key_holder_data(&key_holder_data)
-                    else {
+                    let Ok(instance_handle) = pending.sample.instance_handle() else {
                         continue;
                     };
 
@@ -646,15 +692,16 @@ impl DcpsDomainParticipant {
 
                     if can_write {
                         let pending = data_writer.pending_write_sample.take().unwrap();
-                        let serialized_data =
-                            match serialize(&pending.dynamic_data, &data_writer.qos.representation)
-                            {
-                                Ok(s) => s,
-                                Err(e) => {
-                                    pending.reply_sender.send(Err(e));
-                                    continue;
-                                }
-                            };
+                        let serialized_data = match pending
+                            .sample
+                            .into_serialized_data(&data_writer.qos.representation)
+                        {
+                            Ok(s) => s,
+                            Err(e) => {
+                                pending.reply_sender.send(Err(e));
+                                continue;
+                            }
+                        };
 
                         if let HistoryQosPolicyKind::KeepLast(depth) = data_writer.qos.history.kind
                         {
@@ -673,12 +720,21 @@ impl DcpsDomainParticipant {
                             }
                         }
 
+                        let presentation = publisher.presentation.next_change(
+                            &publisher.qos.presentation,
+                            data_writer.instance_handle,
+                            data_writer.last_change_sequence_number + 1,
+                        );
                         let write_result = data_writer.write_w_timestamp(
                             instance_handle,
                             serialized_data,
                             pending.timestamp,
                             now,
-                            self.transport.message_writer.as_ref(),
+                            presentation,
+                            publication_message_writer(
+                                &publisher.suspended_messages,
+                                self.transport.message_writer.as_ref(),
+                            ),
                             runtime,
                         );
                         if write_result.is_err() {
@@ -1,4 +1,4 @@
-use alloc::string::String;
+use alloc::{string::String, sync::Arc};
 
 use crate::{
     builtin_topics::{
@@ -7,6 +7,10 @@ use crate::{
     },
     dcps::{
         channels::{notification::NotificationSender, oneshot::OneshotSender},
+        dcps_domain_participant::{
+            data_writer_entity::{SharedDataRepresentation, WriterSample},
+            reader_methods::ReaderSample,
+        },
         listeners::{
             data_reader_listener::DcpsDataReaderListener,
             data_writer_listener::DcpsDataWriterListener,
@@ -14,9 +18,14 @@ use crate::{
             publisher_listener::DcpsPublisherListener, subscriber_listener::DcpsSubscriberListener,
             topic_listener::DcpsTopicListener,
         },
+        read_condition::ReadConditionEntity,
         status_condition::StatusConditionEntity,
         status_mask::StatusMask,
     },
+    dds_async::{
+        configuration::{DiscoveryMode, FlowControllerConfiguration},
+        persistence::PersistenceService,
+    },
     infrastructure::{
         domain::DomainId,
         error::DdsResult,
@@ -32,6 +41,7 @@ use crate::{
         },
         time::{Duration, Time},
     },
+    rtps::stateful_writer::WriterTiming,
     transport::{interface::RtpsTransportParticipant, types::GuidPrefix},
     xtypes::dynamic_type::{DynamicData, DynamicType},
 };
@@ -46,6 +56,7 @@ pub enum DcpsMail {
     Subscriber(SubscriberServiceMail),
     Reader(ReaderServiceMail),
     StatusCondition(StatusConditionMail),
+    ReadCondition(ReadConditionMail),
     Message(MessageServiceMail),
 }
 
@@ -61,6 +72,12 @@ pub enum ParticipantFactoryMail {
         transport_participant: RtpsTransportParticipant,
         domain_tag: String,
         participant_announcement_interval: core::time::Duration,
+        persistence_service: Option<Arc<dyn PersistenceService>>,
+        discovery_mode: DiscoveryMode,
+        writer_timing: WriterTiming,
+        heartbeat_response_delay: core::time::Duration,
+        participant_lease_duration: core::time::Duration,
+        flow_controller: Option<FlowControllerConfiguration>,
     },
     DeleteParticipant {
         participant_handle: InstanceHandle,
@@ -142,6 +159,17 @@ pub enum ParticipantServiceMail {
         name: String,
         reply_sender: OneshotSender<DdsResult<()>>,
     },
+    GetExpressionParameters {
+        participant_handle: InstanceHandle,
+        name: String,
+        reply_sender: OneshotSender<DdsResult<Vec<String>>>,
+    },
+    SetExpressionParameters {
+        participant_handle: InstanceHandle,
+        name: String,
+        expression_parameters: Vec<String>,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
     FindTopic {
         participant_handle: InstanceHandle,
         topic_name: String,
@@ -280,7 +308,7 @@ pub enum PublisherServiceMail {
         qos: QosKind<DataWriterQos>,
         dcps_listener: Option<DcpsDataWriterListener>,
         listener_mask: StatusMask,
-        reply_sender: OneshotSender<DdsResult<InstanceHandle>>,
+        reply_sender: OneshotSender<DdsResult<(InstanceHandle, SharedDataRepresentation)>>,
     },
     DeleteDataWriter {
         participant_handle: InstanceHandle,
@@ -317,6 +345,32 @@ pub enum PublisherServiceMail {
         listener_mask: StatusMask,
         reply_sender: OneshotSender<DdsResult<()>>,
     },
+    SuspendPublications {
+        participant_handle: InstanceHandle,
+        publisher_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    ResumePublications {
+        participant_handle: InstanceHandle,
+        publisher_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    WaitForAcknowledgments {
+        participant_handle: InstanceHandle,
+        publisher_handle: InstanceHandle,
+        max_wait: Duration,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    BeginCoherentChanges {
+        participant_handle: InstanceHandle,
+        publisher_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    EndCoherentChanges {
+        participant_handle: InstanceHandle,
+        publisher_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
 }
 
 pub enum SubscriberServiceMail {
@@ -370,6 +424,24 @@ pub enum SubscriberServiceMail {
         listener_mask: StatusMask,
         reply_sender: OneshotSender<DdsResult<()>>,
     },
+    BeginAccess {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    EndAccess {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    GetDataReaders {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        reply_sender: OneshotSender<DdsResult<Vec<InstanceHandle>>>,
+    },
 }
 
 pub enum WriterServiceMail {
@@ -433,7 +505,7 @@ pub enum WriterServiceMail {
         participant_handle: InstanceHandle,
         publisher_handle: InstanceHandle,
         data_writer_handle: InstanceHandle,
-        dynamic_data: DynamicData<'static>,
+        sample: WriterSample,
         timestamp: Time,
         reply_sender: OneshotSender<DdsResult<()>>,
     },
@@ -482,8 +554,9 @@ pub enum ReaderServiceMail {
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
         specific_instance_handle: Option<InstanceHandle>,
+        condition_handle: Option<InstanceHandle>,
         #[allow(clippy::type_complexity)]
-        reply_sender: OneshotSender<DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>>>,
+        reply_sender: OneshotSender<DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>>>,
     },
     Take {
         participant_handle: InstanceHandle,
@@ -494,8 +567,9 @@ pub enum ReaderServiceMail {
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
         specific_instance_handle: Option<InstanceHandle>,
+        condition_handle: Option<InstanceHandle>,
         #[allow(clippy::type_complexity)]
-        reply_sender: OneshotSender<DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>>>,
+        reply_sender: OneshotSender<DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>>>,
     },
     ReadNextInstance {
         participant_handle: InstanceHandle,
@@ -506,8 +580,9 @@ pub enum ReaderServiceMail {
         sample_states: Vec<SampleStateKind>,
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
+        condition_handle: Option<InstanceHandle>,
         #[allow(clippy::type_complexity)]
-        reply_sender: OneshotSender<DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>>>,
+        reply_sender: OneshotSender<DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>>>,
     },
     TakeNextInstance {
         participant_handle: InstanceHandle,
@@ -518,8 +593,9 @@ pub enum ReaderServiceMail {
         sample_states: Vec<SampleStateKind>,
         view_states: Vec<ViewStateKind>,
         instance_states: Vec<InstanceStateKind>,
+        condition_handle: Option<InstanceHandle>,
         #[allow(clippy::type_complexity)]
-        reply_sender: OneshotSender<DdsResult<Vec<(Option<DynamicData<'static>>, SampleInfo)>>>,
+        reply_sender: OneshotSender<DdsResult<Vec<(Option<ReaderSample>, SampleInfo)>>>,
     },
     GetSubscriptionMatchedStatus {
         participant_handle: InstanceHandle,
@@ -561,6 +637,30 @@ pub enum ReaderServiceMail {
         listener_mask: StatusMask,
         reply_sender: OneshotSender<DdsResult<()>>,
     },
+    CreateReadCondition {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        query_expression: Option<String>,
+        query_parameters: Vec<String>,
+        reply_sender: OneshotSender<DdsResult<InstanceHandle>>,
+    },
+    DeleteReadCondition {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        condition_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    DeleteContainedEntities {
+        participant_handle: InstanceHandle,
+        subscriber_handle: InstanceHandle,
+        data_reader_handle: InstanceHandle,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
 }
 
 pub enum StatusConditionMail {
@@ -584,6 +684,27 @@ pub enum StatusConditionMail {
     },
 }
 
+pub enum ReadConditionMail {
+    GetTriggerValue {
+        entity: ReadConditionEntity,
+        reply_sender: OneshotSender<DdsResult<bool>>,
+    },
+    RegisterNotification {
+        entity: ReadConditionEntity,
+        notification_sender: NotificationSender,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+    GetQueryParameters {
+        entity: ReadConditionEntity,
+        reply_sender: OneshotSender<DdsResult<Vec<String>>>,
+    },
+    SetQueryParameters {
+        entity: ReadConditionEntity,
+        query_parameters: Vec<String>,
+        reply_sender: OneshotSender<DdsResult<()>>,
+    },
+}
+
 pub enum MessageServiceMail {
     NotifyAcknowledgments {
         participant_handle: InstanceHandle,
@@ -2,8 +2,8 @@ use crate::{
     dcps::{
         dcps_mail::{
             DcpsMail, MessageServiceMail, ParticipantFactoryMail, ParticipantServiceMail,
-            PublisherServiceMail, ReaderServiceMail, StatusConditionMail, SubscriberServiceMail,
-            TopicServiceMail, WriterServiceMail,
+            PublisherServiceMail, ReadConditionMail, ReaderServiceMail, StatusConditionMail,
+            SubscriberServiceMail, TopicServiceMail, WriterServiceMail,
         },
         dcps_participant_factory::DcpsParticipantFactory,
     },
@@ -24,6 +24,12 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 transport_participant,
                 domain_tag,
                 participant_announcement_interval,
+                persistence_service,
+                discovery_mode,
+                writer_timing,
+                heartbeat_response_delay,
+                participant_lease_duration,
+                flow_controller,
             }) => reply_sender.send(self.create_participant(
                 guid_prefix,
                 domain_id,
@@ -33,6 +39,12 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 transport_participant,
                 domain_tag,
                 participant_announcement_interval,
+                persistence_service,
+                discovery_mode,
+                writer_timing,
+                heartbeat_response_delay,
+                participant_lease_duration,
+                flow_controller,
             )),
             DcpsMail::ParticipantFactory(ParticipantFactoryMail::DeleteParticipant {
                 participant_handle,
@@ -177,6 +189,32 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 self.find_participant(&participant_handle)
                     .and_then(|p| p.delete_content_filtered_topic(&participant_handle, name)),
             ),
+            DcpsMail::Participant(ParticipantServiceMail::GetExpressionParameters {
+                participant_handle,
+                name,
+                reply_sender,
+            }) => reply_sender.send(
+                self.find_participant(&participant_handle)
+                    .and_then(|p| p.get_expression_parameters(name)),
+            ),
+            DcpsMail::Participant(ParticipantServiceMail::SetExpressionParameters {
+                participant_handle,
+                name,
+                expression_parameters,
+                reply_sender,
+            }) => match self
+                .domain_participant_list
+                .iter_mut()
+                .find(|x| x.get_instance_handle() == &participant_handle)
+                .ok_or(DdsError::AlreadyDeleted)
+            {
+                Ok(p) => reply_sender.send(p.set_expression_parameters(
+                    name,
+                    expression_parameters,
+                    &self.runtime,
+                )),
+                Err(e) => reply_sender.send(Err(e)),
+            },
             DcpsMail::Participant(ParticipantServiceMail::FindTopic {
                 participant_handle,
                 topic_name,
@@ -516,6 +554,58 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                         )
                     }),
             ),
+            DcpsMail::Publisher(PublisherServiceMail::SuspendPublications {
+                participant_handle,
+                publisher_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.find_participant(&participant_handle)
+                    .and_then(|p| p.suspend_publications(&publisher_handle)),
+            ),
+            DcpsMail::Publisher(PublisherServiceMail::ResumePublications {
+                participant_handle,
+                publisher_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.domain_participant_list
+                    .iter_mut()
+                    .find(|x| x.get_instance_handle() == &participant_handle)
+                    .ok_or(DdsError::AlreadyDeleted)
+                    .and_then(|p| p.resume_publications(&publisher_handle, &self.runtime)),
+            ),
+            DcpsMail::Publisher(PublisherServiceMail::WaitForAcknowledgments {
+                participant_handle,
+                publisher_handle,
+                max_wait,
+                reply_sender,
+            }) => {
+                let now = self.runtime.clock().now();
+                match self.find_participant(&participant_handle) {
+                    Ok(p) => {
+                        p.wait_for_acknowledgments(&publisher_handle, max_wait, now, reply_sender)
+                    }
+                    Err(e) => reply_sender.send(Err(e)),
+                }
+            }
+            DcpsMail::Publisher(PublisherServiceMail::BeginCoherentChanges {
+                participant_handle,
+                publisher_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.find_participant(&participant_handle)
+                    .and_then(|p| p.begin_coherent_changes(&publisher_handle)),
+            ),
+            DcpsMail::Publisher(PublisherServiceMail::EndCoherentChanges {
+                participant_handle,
+                publisher_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.domain_participant_list
+                    .iter_mut()
+                    .find(|x| x.get_instance_handle() == &participant_handle)
+                    .ok_or(DdsError::AlreadyDeleted)
+                    .and_then(|p| p.end_coherent_changes(&publisher_handle, &self.runtime)),
+            ),
             DcpsMail::Writer(WriterServiceMail::SetListener {
                 participant_handle,
                 publisher_handle,
@@ -636,7 +726,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 participant_handle,
                 publisher_handle,
                 data_writer_handle,
-                dynamic_data,
+                sample,
                 timestamp,
                 reply_sender,
             }) => match self
@@ -648,7 +738,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 Ok(p) => p.write_w_timestamp(
                     &publisher_handle,
                     &data_writer_handle,
-                    &dynamic_data,
+                    sample,
                     timestamp,
                     &self.runtime,
                     reply_sender,
@@ -832,6 +922,37 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                         )
                     }),
             ),
+            DcpsMail::Subscriber(SubscriberServiceMail::BeginAccess {
+                participant_handle,
+                subscriber_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.find_participant(&participant_handle)
+                    .and_then(|p| p.begin_access(&subscriber_handle)),
+            ),
+            DcpsMail::Subscriber(SubscriberServiceMail::EndAccess {
+                participant_handle,
+                subscriber_handle,
+                reply_sender,
+            }) => reply_sender.send(
+                self.find_participant(&participant_handle)
+                    .and_then(|p| p.end_access(&subscriber_handle)),
+            ),
+            DcpsMail::Subscriber(SubscriberServiceMail::GetDataReaders {
+                participant_handle,
+                subscriber_handle,
+                sample_states,
+                view_states,
+                instance_states,
+                reply_sender,
+            }) => reply_sender.send(self.find_participant(&participant_handle).and_then(|p| {
+                p.get_data_readers(
+                    &subscriber_handle,
+                    sample_states,
+                    view_states,
+                    instance_states,
+                )
+            })),
             DcpsMail::Reader(ReaderServiceMail::Read {
                 participant_handle,
                 subscriber_handle,
@@ -841,6 +962,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 view_states,
                 instance_states,
                 specific_instance_handle,
+                condition_handle,
                 reply_sender,
             }) => match self.find_participant(&participant_handle) {
                 Ok(p) => reply_sender.send(p.read(
@@ -851,6 +973,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                     &view_states,
                     &instance_states,
                     &specific_instance_handle,
+                    &condition_handle,
                 )),
                 Err(e) => reply_sender.send(Err(e)),
             },
@@ -863,6 +986,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 view_states,
                 instance_states,
                 specific_instance_handle,
+                condition_handle,
                 reply_sender,
             }) => match self.find_participant(&participant_handle) {
                 Ok(p) => reply_sender.send(p.take(
@@ -873,6 +997,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                     &view_states,
                     &instance_states,
                     &specific_instance_handle,
+                    &condition_handle,
                 )),
                 Err(e) => reply_sender.send(Err(e)),
             },
@@ -885,6 +1010,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 sample_states,
                 view_states,
                 instance_states,
+                condition_handle,
                 reply_sender,
             }) => match self.find_participant(&participant_handle) {
                 Ok(p) => reply_sender.send(p.read_next_instance(
@@ -895,6 +1021,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                     &sample_states,
                     &view_states,
                     &instance_states,
+                    &condition_handle,
                 )),
                 Err(e) => reply_sender.send(Err(e)),
             },
@@ -907,6 +1034,7 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                 sample_states,
                 view_states,
                 instance_states,
+                condition_handle,
                 reply_sender,
             }) => match self.find_participant(&participant_handle) {
                 Ok(p) => reply_sender.send(p.take_next_instance(
@@ -917,6 +1045,55 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
                     &sample_states,
                     &view_states,
                     &instance_states,
+                    &condition_handle,
+                )),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::Reader(ReaderServiceMail::CreateReadCondition {
+                participant_handle,
+                subscriber_handle,
+                data_reader_handle,
+                sample_states,
+                view_states,
+                instance_states,
+                query_expression,
+                query_parameters,
+                reply_sender,
+            }) => match self.find_participant(&participant_handle) {
+                Ok(p) => reply_sender.send(p.create_read_condition(
+                    &subscriber_handle,
+                    &data_reader_handle,
+                    sample_states,
+                    view_states,
+                    instance_states,
+                    query_expression,
+                    query_parameters,
+                )),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::Reader(ReaderServiceMail::DeleteReadCondition {
+                participant_handle,
+                subscriber_handle,
+                data_reader_handle,
+                condition_handle,
+                reply_sender,
+            }) => match self.find_participant(&participant_handle) {
+                Ok(p) => reply_sender.send(p.delete_read_condition(
+                    &subscriber_handle,
+                    &data_reader_handle,
+                    &condition_handle,
+                )),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::Reader(ReaderServiceMail::DeleteContainedEntities {
+                participant_handle,
+                subscriber_handle,
+                data_reader_handle,
+                reply_sender,
+            }) => match self.find_participant(&participant_handle) {
+                Ok(p) => reply_sender.send(p.delete_data_reader_contained_entities(
+                    &subscriber_handle,
+                    &data_reader_handle,
                 )),
                 Err(e) => reply_sender.send(Err(e)),
             },
@@ -1049,6 +1226,37 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
             }) => {
                 reply_sender.send(self.set_status_condition_enabled_statuses(entity, status_mask));
             }
+            DcpsMail::ReadCondition(ReadConditionMail::GetTriggerValue {
+                entity,
+                reply_sender,
+            }) => match self.find_participant(&entity.participant_handle) {
+                Ok(p) => reply_sender.send(p.get_read_condition_trigger_value(&entity)),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::ReadCondition(ReadConditionMail::RegisterNotification {
+                entity,
+                notification_sender,
+                reply_sender,
+            }) => match self.find_participant(&entity.participant_handle) {
+                Ok(p) => reply_sender
+                    .send(p.register_read_condition_notification(&entity, notification_sender)),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::ReadCondition(ReadConditionMail::GetQueryParameters {
+                entity,
+                reply_sender,
+            }) => match self.find_participant(&entity.participant_handle) {
+                Ok(p) => reply_sender.send(p.get_query_parameters(&entity)),
+                Err(e) => reply_sender.send(Err(e)),
+            },
+            DcpsMail::ReadCondition(ReadConditionMail::SetQueryParameters {
+                entity,
+                query_parameters,
+                reply_sender,
+            }) => match self.find_participant(&entity.participant_handle) {
+                Ok(p) => reply_sender.send(p.set_query_parameters(&entity, query_parameters)),
+                Err(e) => reply_sender.send(Err(e)),
+            },
             DcpsMail::Message(MessageServiceMail::NotifyAcknowledgments {
                 participant_handle,
                 publisher_handle,
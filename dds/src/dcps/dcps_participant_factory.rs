@@ -4,7 +4,11 @@ use crate::{
         listeners::domain_participant_listener::DcpsDomainParticipantListener,
         status_mask::StatusMask,
     },
-    dds_async::domain_participant_factory::DcpsSender,
+    dds_async::{
+        configuration::{DiscoveryMode, FlowControllerConfiguration},
+        domain_participant_factory::DcpsSender,
+        persistence::PersistenceService,
+    },
     infrastructure::{
         domain::DomainId,
         error::{DdsError, DdsResult},
@@ -12,10 +16,11 @@ use crate::{
         qos::{DomainParticipantFactoryQos, DomainParticipantQos, QosKind},
         time::Duration,
     },
+    rtps::stateful_writer::WriterTiming,
     runtime::{Clock, DdsRuntime},
     transport::{interface::RtpsTransportParticipant, types::GuidPrefix},
 };
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 
 pub struct DcpsParticipantFactory<R: DdsRuntime> {
     pub domain_participant_list: Vec<DcpsDomainParticipant>,
@@ -47,6 +52,12 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
         transport_participant: RtpsTransportParticipant,
         domain_tag: String,
         participant_announcement_interval: core::time::Duration,
+        persistence_service: Option<Arc<dyn PersistenceService>>,
+        discovery_mode: DiscoveryMode,
+        writer_timing: WriterTiming,
+        heartbeat_response_delay: core::time::Duration,
+        participant_lease_duration: core::time::Duration,
+        flow_controller: Option<FlowControllerConfiguration>,
     ) -> DdsResult<InstanceHandle> {
         let domain_participant_qos = match qos {
             QosKind::Default => self.default_participant_qos.clone(),
@@ -65,6 +76,12 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
             transport_participant,
             self.dcps_sender,
             participant_announcement_interval,
+            persistence_service,
+            discovery_mode,
+            writer_timing,
+            heartbeat_response_delay,
+            participant_lease_duration,
+            flow_controller,
         );
         let participant_handle = *dcps_participant.get_instance_handle();
 
@@ -135,6 +152,13 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
         self.qos.clone()
     }
 
+    pub(crate) fn poke_period(&self) -> Option<Duration> {
+        self.domain_participant_list
+            .iter()
+            .filter_map(|x| x.poke_period())
+            .min()
+    }
+
     pub(crate) fn time_until_stale_participant(&self) -> Option<Duration> {
         let now = self.runtime.clock().now();
         self.domain_participant_list
@@ -175,6 +199,22 @@ impl<R: DdsRuntime> DcpsParticipantFactory<R> {
             .min()
     }
 
+    pub(crate) fn time_until_pending_acknowledgments_timeout(&self) -> Option<Duration> {
+        let now = self.runtime.clock().now();
+        self.domain_participant_list
+            .iter()
+            .filter_map(|x| x.time_until_pending_acknowledgments_timeout(now))
+            .min()
+    }
+
+    pub(crate) fn time_until_pending_sample_delivery(&self) -> Option<Duration> {
+        let now = self.runtime.clock().now();
+        self.domain_participant_list
+            .iter()
+            .filter_map(|x| x.time_until_pending_sample_delivery(now))
+            .min()
+    }
+
     pub(crate) fn time_until_participant_announcement(&self) -> Option<Duration> {
         let now = self.runtime.clock().now();
         self.domain_participant_list
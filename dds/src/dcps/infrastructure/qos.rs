@@ -6,9 +6,10 @@ use crate::{
 use super::{
     qos_policy::{
         DataRepresentationQosPolicy, DeadlineQosPolicy, DestinationOrderQosPolicy,
-        DurabilityQosPolicy, EntityFactoryQosPolicy, GroupDataQosPolicy, HistoryQosPolicy,
-        HistoryQosPolicyKind, LatencyBudgetQosPolicy, LifespanQosPolicy, LivelinessQosPolicy,
-        OwnershipQosPolicy, OwnershipStrengthQosPolicy, PartitionQosPolicy, PresentationQosPolicy,
+        DurabilityQosPolicy, DurabilityServiceQosPolicy, EntityFactoryQosPolicy,
+        GroupDataQosPolicy, HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy,
+        LifespanQosPolicy, LivelinessQosPolicy, OwnershipQosPolicy, OwnershipStrengthQosPolicy,
+        PartitionQosPolicy, PresentationQosPolicy, PublishModeQosPolicy,
         ReaderDataLifecycleQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
         ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, TopicDataQosPolicy,
         TransportPriorityQosPolicy, UserDataQosPolicy, WriterDataLifecycleQosPolicy,
@@ -77,6 +78,8 @@ impl Default for PublisherQos {
 pub struct DataWriterQos {
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -105,6 +108,8 @@ pub struct DataWriterQos {
     pub writer_data_lifecycle: WriterDataLifecycleQosPolicy,
     /// Value of the data representation QoS policy.
     pub representation: DataRepresentationQosPolicy,
+    /// Value of the publish mode QoS policy.
+    pub publish_mode: PublishModeQosPolicy,
 }
 
 impl DataWriterQos {
@@ -119,6 +124,7 @@ impl DataWriterQos {
                 )),
             },
             durability: DurabilityQosPolicy::const_default(),
+            durability_service: DurabilityServiceQosPolicy::const_default(),
             deadline: DeadlineQosPolicy::const_default(),
             latency_budget: LatencyBudgetQosPolicy::const_default(),
             liveliness: LivelinessQosPolicy::const_default(),
@@ -132,6 +138,7 @@ impl DataWriterQos {
             transport_priority: TransportPriorityQosPolicy::const_default(),
             writer_data_lifecycle: WriterDataLifecycleQosPolicy::const_default(),
             representation: DataRepresentationQosPolicy::const_default(),
+            publish_mode: PublishModeQosPolicy::const_default(),
         }
     }
 }
@@ -155,6 +162,8 @@ impl DataWriterQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        self.durability_service.is_consistent()?;
+
         // The setting of RESOURCE_LIMITS max_samples_per_instance must be consistent with the HISTORY depth. For these two
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
@@ -171,12 +180,14 @@ impl DataWriterQos {
 
     pub(crate) fn check_immutability(&self, other: &Self) -> DdsResult<()> {
         if self.durability != other.durability
+            || self.durability_service != other.durability_service
             || self.liveliness != other.liveliness
             || self.reliability != other.reliability
             || self.destination_order != other.destination_order
             || self.history != other.history
             || self.resource_limits != other.resource_limits
             || self.ownership != other.ownership
+            || self.publish_mode != other.publish_mode
         {
             Err(DdsError::ImmutablePolicy)
         } else {
@@ -341,6 +352,8 @@ pub struct TopicQos {
     pub topic_data: TopicDataQosPolicy,
     /// Value of the durability QoS policy.
     pub durability: DurabilityQosPolicy,
+    /// Value of the durability service QoS policy.
+    pub durability_service: DurabilityServiceQosPolicy,
     /// Value of the deadline QoS policy.
     pub deadline: DeadlineQosPolicy,
     /// Value of the latency budget QoS policy.
@@ -378,6 +391,7 @@ impl TopicQos {
             },
             topic_data: TopicDataQosPolicy::const_default(),
             durability: DurabilityQosPolicy::const_default(),
+            durability_service: DurabilityServiceQosPolicy::const_default(),
             deadline: DeadlineQosPolicy::const_default(),
             latency_budget: LatencyBudgetQosPolicy::const_default(),
             liveliness: LivelinessQosPolicy::const_default(),
@@ -406,6 +420,8 @@ impl TopicQos {
             return Err(DdsError::InconsistentPolicy);
         }
 
+        self.durability_service.is_consistent()?;
+
         // The setting of RESOURCE_LIMITS max_samples_per_instance must be consistent with the HISTORY depth. For these two
         // QoS to be consistent, they must verify that *depth <= max_samples_per_instance.*
         match self.history.kind {
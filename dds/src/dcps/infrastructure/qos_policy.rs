@@ -1,6 +1,9 @@
 use super::time::{DURATION_ZERO_NSEC, DURATION_ZERO_SEC};
 use crate::{
-    infrastructure::time::{Duration, DurationKind},
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        time::{Duration, DurationKind},
+    },
     transport::types::{DurabilityKind, ReliabilityKind},
     xtypes::{
         dynamic_type::{
@@ -121,6 +124,7 @@ pub trait QosPolicy {
 
 const USERDATA_QOS_POLICY_NAME: &str = "UserData";
 const DURABILITY_QOS_POLICY_NAME: &str = "Durability";
+const DURABILITYSERVICE_QOS_POLICY_NAME: &str = "DurabilityService";
 const PRESENTATION_QOS_POLICY_NAME: &str = "Presentation";
 const DEADLINE_QOS_POLICY_NAME: &str = "Deadline";
 const LATENCYBUDGET_QOS_POLICY_NAME: &str = "LatencyBudget";
@@ -142,6 +146,7 @@ const GROUPDATA_QOS_POLICY_NAME: &str = "GroupData";
 const LIFESPAN_QOS_POLICY_NAME: &str = "Lifespan";
 const DATA_REPRESENTATION_QOS_POLICY_NAME: &str = "DataRepresentation";
 const TYPE_CONSISTENCY_ENFORCEMENT_QOS_POLICY_NAME: &str = "TypeConsistencyEnforcement";
+const PUBLISHMODE_QOS_POLICY_NAME: &str = "PublishMode";
 
 /// QosPolicy Id representing an invalid QoS policy
 pub const INVALID_QOS_POLICY_ID: QosPolicyId = 0;
@@ -442,6 +447,71 @@ impl Default for DurabilityQosPolicy {
     }
 }
 
+/// This policy is used to configure the history and resource limits used by the persistence service which keeps
+/// the data written with the [`DurabilityQosPolicyKind::Persistent`] durability kind.
+///
+/// The persistence service keeps the data of each instance according to the [`DurabilityServiceQosPolicy::history_kind`]
+/// and, once a limit is reached, discards the oldest data to make room for the new one. The data of an instance which is
+/// no longer alive is removed once the [`DurabilityServiceQosPolicy::service_cleanup_delay`] has elapsed since it stopped
+/// being alive.
+/// The setting of [`DurabilityServiceQosPolicy::max_samples`] must be consistent with the
+/// [`DurabilityServiceQosPolicy::max_samples_per_instance`] and the depth of the [`DurabilityServiceQosPolicy::history_kind`]
+/// in the same way as for the [`ResourceLimitsQosPolicy`] and the [`HistoryQosPolicy`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DurabilityServiceQosPolicy {
+    /// Time the data of an instance which is no longer alive is kept by the service.
+    pub service_cleanup_delay: Duration,
+    /// Kind of history kept by the service.
+    pub history_kind: HistoryQosPolicyKind,
+    /// Maximum number of samples limit.
+    pub max_samples: Length,
+    /// Maximum number of instances limit.
+    pub max_instances: Length,
+    /// Maximum number of samples per instance limit.
+    pub max_samples_per_instance: Length,
+}
+
+impl DurabilityServiceQosPolicy {
+    /// Default constructor usable in const contexts
+    pub const fn const_default() -> Self {
+        Self {
+            service_cleanup_delay: Duration::new(DURATION_ZERO_SEC, DURATION_ZERO_NSEC),
+            history_kind: HistoryQosPolicyKind::KeepLast(1),
+            max_samples: Length::Unlimited,
+            max_instances: Length::Unlimited,
+            max_samples_per_instance: Length::Unlimited,
+        }
+    }
+}
+
+impl DurabilityServiceQosPolicy {
+    pub(crate) fn is_consistent(&self) -> DdsResult<()> {
+        if self.max_samples < self.max_samples_per_instance {
+            return Err(DdsError::InconsistentPolicy);
+        }
+        match self.history_kind {
+            HistoryQosPolicyKind::KeepLast(depth)
+                if depth as usize > self.max_samples_per_instance =>
+            {
+                Err(DdsError::InconsistentPolicy)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl QosPolicy for DurabilityServiceQosPolicy {
+    fn name(&self) -> &str {
+        DURABILITYSERVICE_QOS_POLICY_NAME
+    }
+}
+
+impl Default for DurabilityServiceQosPolicy {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, TypeSupport)]
 /// Enumeration representing the different types of Presentation QoS policy access scope.
 pub enum PresentationQosPolicyAccessScopeKind {
@@ -449,6 +519,8 @@ pub enum PresentationQosPolicyAccessScopeKind {
     Instance,
     /// Access scope per topic
     Topic,
+    /// Access scope per group of entities belonging to the same publisher or subscriber
+    Group,
 }
 
 impl PartialOrd for PresentationQosPolicyAccessScopeKind {
@@ -456,11 +528,18 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
         match self {
             PresentationQosPolicyAccessScopeKind::Instance => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Equal),
-                PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Less),
+                PresentationQosPolicyAccessScopeKind::Topic
+                | PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
             },
             PresentationQosPolicyAccessScopeKind::Topic => match other {
                 PresentationQosPolicyAccessScopeKind::Instance => Some(Ordering::Greater),
                 PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Equal),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Less),
+            },
+            PresentationQosPolicyAccessScopeKind::Group => match other {
+                PresentationQosPolicyAccessScopeKind::Instance
+                | PresentationQosPolicyAccessScopeKind::Topic => Some(Ordering::Greater),
+                PresentationQosPolicyAccessScopeKind::Group => Some(Ordering::Equal),
             },
         }
     }
@@ -483,7 +562,10 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 ///   independently. That is, changes made to instances within each individual DataWriter will be available as coherent with
 ///   respect to other changes to instances in that same DataWriter, but will not be grouped with changes made to instances
 ///   belonging to a different DataWriter.
-///   If ordered_access is set, then the access_scope controls the maximum extent for which order will be preserved by the Service.
+/// - If access_scope is set to GROUP, then coherent changes made to instances through DataWriter entities attached to a
+///   common Publisher are made available as a unit to remote subscribers.
+///
+/// If ordered_access is set, then the access_scope controls the maximum extent for which order will be preserved by the Service.
 /// - If access_scope is set to INSTANCE (the lowest level), then changes to each instance are considered unordered relative
 ///   to changes to any other instance. That means that changes (creations, deletions, modifications) made to two instances
 ///   are not necessarily seen in the order they occur. This is the case even if it is the same application thread making the
@@ -492,6 +574,8 @@ impl PartialOrd for PresentationQosPolicyAccessScopeKind {
 ///   available to subscribers in the same order they occur. Changes made to instances through different DataWriter entities
 ///   are not necessarily seen in the order they occur. This is the case, even if the changes are made by a single application
 ///   thread using DataWriter objects attached to the same Publisher.
+/// - If access_scope is set to GROUP, changes made to instances via DataWriter entities attached to the same Publisher are
+///   made available to subscribers in the same order they occur.
 ///
 /// Note that this QoS policy controls the scope at which related changes are made available to the subscriber. This means the
 /// subscriber can access the changes in a coherent manner and in the proper order; however, it does not necessarily imply that the
@@ -1629,6 +1713,51 @@ impl Default for TypeConsistencyEnforcementQosPolicy {
     }
 }
 
+/// Enumeration representing the different types of Publish Mode QoS policies.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PublishModeQosPolicyKind {
+    /// The data is sent by the write operation.
+    Synchronous,
+    /// The data is only stored by the write operation and sent afterwards by a separate sender task.
+    Asynchronous,
+}
+
+/// This policy is an extension of the DDS specification which controls whether the data of a
+/// [`DataWriter`](crate::publication::data_writer::DataWriter) is sent by the write operation or in the background.
+///
+/// With [`PublishModeQosPolicyKind::Synchronous`] the write operation sends the data to the matched readers before returning.
+/// With [`PublishModeQosPolicyKind::Asynchronous`] the write operation only adds the change to the history of the writer.
+/// The data is then sent by a sender task of the participant which writes the messages of all the asynchronous writers
+/// to the transport, combining the ones going to the same destinations. The application threads writing the data therefore
+/// never wait for the transport, at the cost of some latency.
+/// This policy is local to the writer and it is not sent with the publication data.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PublishModeQosPolicy {
+    /// Kind of publish mode of the writer
+    pub kind: PublishModeQosPolicyKind,
+}
+
+impl PublishModeQosPolicy {
+    /// Default constructor usable in const contexts
+    pub const fn const_default() -> Self {
+        Self {
+            kind: PublishModeQosPolicyKind::Synchronous,
+        }
+    }
+}
+
+impl QosPolicy for PublishModeQosPolicy {
+    fn name(&self) -> &str {
+        PUBLISHMODE_QOS_POLICY_NAME
+    }
+}
+
+impl Default for PublishModeQosPolicy {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
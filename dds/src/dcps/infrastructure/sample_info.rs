@@ -1,6 +1,7 @@
 use crate::{
+    dcps::dcps_domain_participant::reader_methods::ReaderSample,
     infrastructure::{instance::InstanceHandle, time::Time},
-    xtypes::{dynamic_type::DynamicData, type_support::TypeSupport},
+    xtypes::type_support::TypeSupport,
 };
 
 /// A [`Sample`] contains the data and [`SampleInfo`] read by the [`DataReader`].
@@ -16,9 +17,9 @@ impl<Foo> Sample<Foo>
 where
     Foo: TypeSupport,
 {
-    pub(crate) fn new(mut data: Option<DynamicData<'static>>, sample_info: SampleInfo) -> Self {
+    pub(crate) fn new(data: Option<ReaderSample>, sample_info: SampleInfo) -> Self {
         Self {
-            data: data.as_mut().and_then(Foo::create_sample),
+            data: data.and_then(ReaderSample::into_sample),
             sample_info,
         }
     }
@@ -1,9 +1,11 @@
+pub mod content_filter;
 pub mod data_representation_builtin_endpoints;
 pub mod dcps_domain_participant;
 pub mod dcps_mail;
 pub mod dcps_mail_handler;
 pub mod dcps_participant_factory;
 pub mod listeners;
+pub mod read_condition;
 pub mod status_condition;
 pub mod status_mask;
 pub mod xtypes_glue;
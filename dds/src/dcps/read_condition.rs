@@ -0,0 +1,205 @@
+use crate::{
+    dcps::{
+        channels::notification::NotificationSender,
+        content_filter::ContentFilter,
+        dcps_domain_participant::data_reader_entity::{DataReaderEntity, ReaderSample},
+        xtypes_glue::key_and_instance_handle::KeyHolderType,
+    },
+    infrastructure::{
+        error::DdsResult,
+        instance::InstanceHandle,
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+    },
+    transport::types::ChangeKind,
+    xtypes::{deserializer::deserialize_top_level_type, dynamic_type::DynamicType},
+};
+use alloc::{string::String, vec::Vec};
+
+#[derive(Clone, Debug)]
+pub struct ReadConditionEntity {
+    pub participant_handle: InstanceHandle,
+    pub subscriber_handle: InstanceHandle,
+    pub reader_handle: InstanceHandle,
+    pub condition_handle: InstanceHandle,
+}
+
+struct DcpsQuery {
+    content_filter: ContentFilter,
+    query_parameters: Vec<String>,
+}
+
+pub struct DcpsReadCondition {
+    instance_handle: InstanceHandle,
+    sample_states: Vec<SampleStateKind>,
+    view_states: Vec<ViewStateKind>,
+    instance_states: Vec<InstanceStateKind>,
+    query: Option<DcpsQuery>,
+    registered_notifications: Vec<NotificationSender>,
+}
+
+impl DcpsReadCondition {
+    pub fn new(
+        instance_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> Self {
+        Self {
+            instance_handle,
+            sample_states,
+            view_states,
+            instance_states,
+            query: None,
+            registered_notifications: Vec::new(),
+        }
+    }
+
+    pub fn new_query(
+        instance_handle: InstanceHandle,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+        query_expression: &str,
+        query_parameters: Vec<String>,
+        type_support: &DynamicType<'static>,
+    ) -> DdsResult<Self> {
+        let content_filter = ContentFilter::new(query_expression, type_support, &query_parameters)?;
+        Ok(Self {
+            query: Some(DcpsQuery {
+                content_filter,
+                query_parameters,
+            }),
+            ..Self::new(instance_handle, sample_states, view_states, instance_states)
+        })
+    }
+
+    pub fn instance_handle(&self) -> &InstanceHandle {
+        &self.instance_handle
+    }
+
+    pub fn get_query_parameters(&self) -> Vec<String> {
+        self.query
+            .as_ref()
+            .map(|q| q.query_parameters.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_query_parameters(&mut self, query_parameters: Vec<String>) -> DdsResult<()> {
+        if let Some(query) = &mut self.query {
+            query
+                .content_filter
+                .set_expression_parameters(&query_parameters)?;
+            query.query_parameters = query_parameters;
+        }
+        Ok(())
+    }
+
+    /// Checks the sample against the query of the condition. Samples without valid data
+    /// only carry the key of the instance so they can only match queries on key members.
+    pub fn matches_query(&self, sample: &ReaderSample, type_support: DynamicType<'static>) -> bool {
+        self.matches_query_data(sample.kind, sample.data_value.as_ref(), type_support)
+    }
+
+    fn matches_query_data(
+        &self,
+        kind: ChangeKind,
+        data_value: &[u8],
+        type_support: DynamicType<'static>,
+    ) -> bool {
+        let Some(query) = &self.query else {
+            return true;
+        };
+        match kind {
+            ChangeKind::Alive | ChangeKind::AliveFiltered => {
+                deserialize_top_level_type(type_support, data_value)
+                    .map(|data| query.content_filter.evaluate(&data))
+                    .unwrap_or(false)
+            }
+            ChangeKind::NotAliveDisposed
+            | ChangeKind::NotAliveUnregistered
+            | ChangeKind::NotAliveDisposedUnregistered => {
+                if !query.content_filter.is_key_only() {
+                    return false;
+                }
+                let mut member_list = Vec::new();
+                let Ok(key_holder) =
+                    KeyHolderType::from_dynamic_type(&type_support, &mut member_list)
+                else {
+                    return false;
+                };
+                deserialize_top_level_type(*key_holder.as_dynamic_type(), data_value)
+                    .map(|data| query.content_filter.evaluate(&data))
+                    .unwrap_or(false)
+            }
+        }
+    }
+
+    pub fn get_trigger_value<T>(
+        &self,
+        data_reader: &DataReaderEntity<T>,
+        type_support: DynamicType<'static>,
+    ) -> bool {
+        data_reader.contains_sample(
+            &self.sample_states,
+            &self.view_states,
+            &self.instance_states,
+            None,
+            &|sample| self.matches_query(sample, type_support),
+        )
+    }
+
+    /// Trigger value restricted to the samples of a single instance.
+    pub fn get_instance_trigger_value<T>(
+        &self,
+        data_reader: &DataReaderEntity<T>,
+        instance_handle: &InstanceHandle,
+        type_support: DynamicType<'static>,
+    ) -> bool {
+        data_reader.contains_sample(
+            &self.sample_states,
+            &self.view_states,
+            &self.instance_states,
+            Some(instance_handle),
+            &|sample| self.matches_query(sample, type_support),
+        )
+    }
+
+    /// Checks whether a newly received sample, which is always not read, satisfies the condition
+    /// given the state of its instance.
+    pub fn matches_new_sample(
+        &self,
+        kind: ChangeKind,
+        data_value: &[u8],
+        view_state: ViewStateKind,
+        instance_state: InstanceStateKind,
+        type_support: DynamicType<'static>,
+    ) -> bool {
+        self.sample_states.contains(&SampleStateKind::NotRead)
+            && self.view_states.contains(&view_state)
+            && self.instance_states.contains(&instance_state)
+            && self.matches_query_data(kind, data_value, type_support)
+    }
+
+    pub fn has_registered_notifications(&self) -> bool {
+        !self.registered_notifications.is_empty()
+    }
+
+    pub fn register_notification(
+        &mut self,
+        notification_sender: NotificationSender,
+        trigger_value: bool,
+    ) {
+        if trigger_value {
+            notification_sender.notify();
+        } else {
+            self.registered_notifications.push(notification_sender);
+        }
+    }
+
+    pub fn notify(&mut self) {
+        for w in self.registered_notifications.drain(..) {
+            // Do not care if there is no channel waiting for response
+            w.notify();
+        }
+    }
+}
@@ -113,15 +113,19 @@ pub fn get_instance_handle_from_key_holder_data<'a>(
     key_holder_data: &KeyHolderData<'a>,
 ) -> Result<InstanceHandle, XTypesError> {
     let data = serialize_final_without_header(Vec::new(), &key_holder_data.0)?;
+    Ok(get_instance_handle_from_serialized_key(&data))
+}
+
+pub fn get_instance_handle_from_serialized_key(data: &[u8]) -> InstanceHandle {
     let key = if data.len() <= 16 {
         let mut key = [0; 16];
-        key[0..data.len()].copy_from_slice(&data);
+        key[0..data.len()].copy_from_slice(data);
         key
     } else {
         md5::compute(data).into()
     };
 
-    Ok(InstanceHandle::new(key))
+    InstanceHandle::new(key)
 }
 
 pub fn get_instance_handle_from_dynamic_data<'a>(
@@ -1,8 +1,14 @@
 use crate::{
-    dds_async::condition::StatusConditionAsync,
-    infrastructure::{error::DdsResult, status::StatusKind},
+    dds_async::condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    infrastructure::{
+        error::DdsResult,
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+        status::StatusKind,
+    },
     std_runtime::executor::block_on,
 };
+use alloc::{string::String, vec::Vec};
+use core::ops::Deref;
 
 /// A [`StatusCondition`] object is a specific Condition that is associated with each Entity.
 /// The *trigger_value* of the [`StatusCondition`] depends on the communication status of that entity (e.g., arrival of data, loss of
@@ -53,3 +59,107 @@ impl StatusCondition {
         block_on(self.condition_async.get_trigger_value())
     }
 }
+
+/// A [`ReadCondition`] object is a specific Condition that is associated with a
+/// [`DataReader`](crate::subscription::data_reader::DataReader). Its *trigger_value* is [`true`] whenever the
+/// [`DataReader`](crate::subscription::data_reader::DataReader) contains at least one sample with a
+/// [`SampleStateKind`], [`ViewStateKind`] and [`InstanceStateKind`] matching those of the [`ReadCondition`].
+/// A [`ReadCondition`] is created with [`DataReader::create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition)
+/// and can be used with [`DataReader::read_w_condition`](crate::subscription::data_reader::DataReader::read_w_condition)
+/// and [`DataReader::take_w_condition`](crate::subscription::data_reader::DataReader::take_w_condition) or attached to a
+/// [`WaitSet`](crate::wait_set::WaitSet).
+#[derive(Clone)]
+pub struct ReadCondition {
+    condition_async: ReadConditionAsync,
+}
+
+impl ReadCondition {
+    pub(crate) fn new(condition_async: ReadConditionAsync) -> Self {
+        Self { condition_async }
+    }
+
+    pub(crate) fn condition_async(&self) -> &ReadConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl ReadCondition {
+    /// This operation returns the set of sample states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the sample states specified when the [`ReadCondition`] was created.
+    pub fn get_sample_state_mask(&self) -> &[SampleStateKind] {
+        self.condition_async.get_sample_state_mask()
+    }
+
+    /// This operation returns the set of view states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the view states specified when the [`ReadCondition`] was created.
+    pub fn get_view_state_mask(&self) -> &[ViewStateKind] {
+        self.condition_async.get_view_state_mask()
+    }
+
+    /// This operation returns the set of instance states that are taken into account to determine the *trigger_value* of the [`ReadCondition`].
+    /// These are the instance states specified when the [`ReadCondition`] was created.
+    pub fn get_instance_state_mask(&self) -> &[InstanceStateKind] {
+        self.condition_async.get_instance_state_mask()
+    }
+}
+
+/// This implementation block contains the Condition operations for the [`ReadCondition`].
+impl ReadCondition {
+    /// This operation retrieves the *trigger_value* of the [`ReadCondition`].
+    pub fn get_trigger_value(&self) -> DdsResult<bool> {
+        block_on(self.condition_async.get_trigger_value())
+    }
+}
+
+/// A [`QueryCondition`] is a specialized [`ReadCondition`] that additionally filters the samples of the
+/// [`DataReader`](crate::subscription::data_reader::DataReader) with a query expression written in the SQL subset
+/// also used by [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic), e.g. `"priority > %0"`.
+/// Its *trigger_value* is [`true`] whenever there is at least one sample matching both the state masks and the query.
+/// All the [`ReadCondition`] operations are available on a [`QueryCondition`] through [`Deref`].
+#[derive(Clone)]
+pub struct QueryCondition {
+    read_condition: ReadCondition,
+    condition_async: QueryConditionAsync,
+}
+
+impl QueryCondition {
+    pub(crate) fn new(condition_async: QueryConditionAsync) -> Self {
+        Self {
+            read_condition: ReadCondition::new(condition_async.deref().clone()),
+            condition_async,
+        }
+    }
+
+    pub(crate) fn condition_async(&self) -> &QueryConditionAsync {
+        &self.condition_async
+    }
+}
+
+impl Deref for QueryCondition {
+    type Target = ReadCondition;
+
+    fn deref(&self) -> &Self::Target {
+        &self.read_condition
+    }
+}
+
+impl QueryCondition {
+    /// This operation returns the query expression specified when the [`QueryCondition`] was created.
+    pub fn get_query_expression(&self) -> String {
+        self.condition_async.get_query_expression()
+    }
+
+    /// This operation returns the query parameters associated with the [`QueryCondition`]. That is, the parameters specified on the last
+    /// successful call to [`QueryCondition::set_query_parameters`], or if it was never called, the arguments specified when the
+    /// [`QueryCondition`] was created.
+    pub fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        block_on(self.condition_async.get_query_parameters())
+    }
+
+    /// This operation changes the query parameters associated with the [`QueryCondition`]. The parameters must be valid for the query expression,
+    /// otherwise the operation returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError::BadParameter) and the previous
+    /// parameters are kept.
+    pub fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        block_on(self.condition_async.set_query_parameters(query_parameters))
+    }
+}
@@ -21,6 +21,12 @@ pub mod configuration {
     pub use crate::dds_async::configuration::*;
 }
 
+/// Contains the [`PersistenceService`](crate::persistence::PersistenceService) trait used to keep the data written
+/// with the persistent durability kind
+pub mod persistence {
+    pub use crate::dds_async::persistence::*;
+}
+
 /// Classes related to the status conditions.
 pub mod condition;
 
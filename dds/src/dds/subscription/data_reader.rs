@@ -1,7 +1,7 @@
 use super::subscriber::Subscriber;
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
-    condition::StatusCondition,
+    condition::{QueryCondition, ReadCondition, StatusCondition},
     dds_async::data_reader::DataReaderAsync,
     infrastructure::{
         error::DdsResult,
@@ -19,7 +19,7 @@ use crate::{
     topic_definition::topic_description::TopicDescription,
     xtypes::type_support::TypeSupport,
 };
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 /// A [`DataReader`] allows the application (1) to declare the data it wishes to receive (i.e., make a subscription) and (2) to access the
 /// data received by the attached [`Subscriber`].
@@ -262,6 +262,82 @@ impl<Foo: TypeSupport> DataReader<Foo> {
         ))
     }
 
+    /// This operation accesses via 'read' the samples that match the criteria specified in the [`ReadCondition`]. This operation is
+    /// especially useful in combination with [`QueryCondition`] to filter data samples based on the content.
+    /// The specified [`ReadCondition`] must be attached to the [`DataReader`]; otherwise the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// In case the [`ReadCondition`] is a 'plain' [`ReadCondition`] and not the specialized [`QueryCondition`], the
+    /// operation is equivalent to calling [`DataReader::read`] and passing as `sample_states`, `view_states` and `instance_states`
+    /// the value of the corresponding attributes in `a_condition`. Using this operation the application can avoid repeating the same
+    /// parameters specified when creating the [`ReadCondition`].
+    /// The samples are accessed with the same semantics as the [`DataReader::read`] operation.
+    /// If the [`DataReader`] has no samples that meet the constraints, the return value will be
+    /// [`DdsError::NoData`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .read_w_condition(max_samples, a_condition.condition_async()),
+        )
+    }
+
+    /// This operation is analogous to [`DataReader::read_w_condition`] except it accesses samples via the 'take' operation.
+    /// The specified [`ReadCondition`] must be attached to the [`DataReader`]; otherwise the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// The samples are accessed with the same semantics as the [`DataReader::take`] operation.
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(
+            self.reader_async
+                .take_w_condition(max_samples, a_condition.condition_async()),
+        )
+    }
+
+    /// This operation accesses a collection of [`Sample`] values from the [`DataReader`]. The behavior is identical to
+    /// [`DataReader::read_next_instance`] except that all samples returned satisfy the specified condition. In other words, on success
+    /// all returned samples belong to the same instance, and the instance is the instance with 'smallest' `instance_handle` among the
+    /// ones that verify (a) `instance_handle` >= `previous_handle` and (b) have samples for which the specified [`ReadCondition`] evaluates to [`true`].
+    /// The specified [`ReadCondition`] must be attached to the [`DataReader`]; otherwise the operation will fail and return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn read_next_instance_w_condition(
+        &self,
+        max_samples: i32,
+        previous_handle: Option<InstanceHandle>,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(self.reader_async.read_next_instance_w_condition(
+            max_samples,
+            previous_handle,
+            a_condition.condition_async(),
+        ))
+    }
+
+    /// This operation accesses a collection of [`Sample`] values from the [`DataReader`] and removes them from the [`DataReader`].
+    /// This operation has the same behavior as [`DataReader::read_next_instance_w_condition`] except that the samples are 'taken' from the
+    /// [`DataReader`] such that they are no longer accessible via subsequent 'read' or 'take' operations.
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn take_next_instance_w_condition(
+        &self,
+        max_samples: i32,
+        previous_handle: Option<InstanceHandle>,
+        a_condition: &ReadCondition,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        block_on(self.reader_async.take_next_instance_w_condition(
+            max_samples,
+            previous_handle,
+            a_condition.condition_async(),
+        ))
+    }
+
     /// This operation can be used to retrieve the instance key that corresponds to an `handle`.
     /// The operation will only fill the fields that form the key inside the `key_holder` instance.
     /// This operation may return [`DdsError::BadParameter`](crate::infrastructure::error::DdsError)
@@ -409,6 +485,69 @@ impl<Foo> DataReader<Foo> {
         block_on(self.reader_async.get_qos())
     }
 
+    /// This operation creates a [`ReadCondition`]. The returned [`ReadCondition`] will be attached and belong to the [`DataReader`].
+    /// Its *trigger_value* is [`true`] whenever the [`DataReader`] contains at least one sample matching the `sample_states`,
+    /// `view_states` and `instance_states`.
+    #[tracing::instrument(skip(self))]
+    pub fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadCondition> {
+        block_on(self.reader_async.create_readcondition(
+            sample_states,
+            view_states,
+            instance_states,
+        ))
+        .map(ReadCondition::new)
+    }
+
+    /// This operation creates a [`QueryCondition`]. The returned [`QueryCondition`] will be attached and belong to the [`DataReader`].
+    /// The `query_expression` uses the same SQL subset as the filter expression of a
+    /// [`ContentFilteredTopic`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic) and is evaluated with the
+    /// `query_parameters` replacing the `%n` tokens. If the expression can not be compiled for the type of the [`DataReader`] or
+    /// the parameters do not match it, the operation returns [`DdsError::BadParameter`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: String,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryCondition> {
+        block_on(self.reader_async.create_querycondition(
+            sample_states,
+            view_states,
+            instance_states,
+            query_expression,
+            query_parameters,
+        ))
+        .map(QueryCondition::new)
+    }
+
+    /// This operation deletes a [`ReadCondition`] attached to the [`DataReader`]. Since [`QueryCondition`] specializes
+    /// [`ReadCondition`] it can also be used to delete a [`QueryCondition`].
+    /// If the [`ReadCondition`] is not attached to the [`DataReader`], the operation will return the error
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub fn delete_readcondition(&self, a_condition: &ReadCondition) -> DdsResult<()> {
+        block_on(
+            self.reader_async
+                .delete_readcondition(a_condition.condition_async()),
+        )
+    }
+
+    /// This operation deletes all the entities that were created by means of the “create” operations on the [`DataReader`]. That is,
+    /// it deletes all contained [`ReadCondition`] and [`QueryCondition`] objects.
+    /// Once this operation returns successfully, the application may delete the [`DataReader`] knowing that it has no
+    /// contained [`ReadCondition`] and [`QueryCondition`] objects.
+    #[tracing::instrument(skip(self))]
+    pub fn delete_contained_entities(&self) -> DdsResult<()> {
+        block_on(self.reader_async.delete_contained_entities())
+    }
+
     /// This operation allows access to the [`StatusCondition`] associated with the Entity. The returned
     /// condition can then be added to a [`WaitSet`](crate::infrastructure::wait_set::WaitSet) so that the application can wait for specific status changes
     /// that affect the Entity.
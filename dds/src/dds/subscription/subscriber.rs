@@ -10,6 +10,7 @@ use crate::{
         error::DdsResult,
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos, TopicQos},
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
         status::{SampleLostStatus, StatusKind},
     },
     std_runtime::executor::block_on,
@@ -106,6 +107,48 @@ impl Subscriber {
         block_on(self.subscriber_async.notify_datareaders())
     }
 
+    /// This operation indicates that the application is about to access the data samples in any of the [`DataReader`] objects attached to
+    /// the [`Subscriber`].
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has
+    /// `coherent_access` set to `true` with an access scope of `TOPIC` or `GROUP`, the Service will not modify the data in the
+    /// [`DataReader`] objects until the matching call to [`Subscriber::end_access`], so that the application sees complete coherent sets.
+    /// These calls can be nested. In that case, the access terminates only with the last call to [`Subscriber::end_access`].
+    #[tracing::instrument(skip(self))]
+    pub fn begin_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.begin_access())
+    }
+
+    /// This operation indicates that the application has finished accessing the data samples in [`DataReader`] objects managed by the
+    /// [`Subscriber`]. The call must close a previous call to [`Subscriber::begin_access`] otherwise the operation will return
+    /// [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    #[tracing::instrument(skip(self))]
+    pub fn end_access(&self) -> DdsResult<()> {
+        block_on(self.subscriber_async.end_access())
+    }
+
+    /// This operation returns the instance handles of the [`DataReader`] objects that contain samples with the specified
+    /// `sample_states`, `view_states` and `instance_states`. The handles can be compared with
+    /// [`DataReader::get_instance_handle`] to select the reader to read from.
+    /// If the [`PresentationQosPolicy`](crate::infrastructure::qos_policy::PresentationQosPolicy) of the [`Subscriber`] has
+    /// `ordered_access` set to `true` with an access scope of `GROUP`, the returned list contains a [`DataReader`] once for
+    /// each of its samples, in the order in which the samples were written by their [`Publisher`](crate::publication::publisher::Publisher).
+    /// The application should then read or take exactly one sample from each [`DataReader`] in the order of the list.
+    /// In that case the operation must be called between [`Subscriber::begin_access`] and [`Subscriber::end_access`], otherwise
+    /// it returns [`DdsError::PreconditionNotMet`](crate::infrastructure::error::DdsError).
+    /// Otherwise each [`DataReader`] appears at most once in the list and the order is not specified.
+    #[tracing::instrument(skip(self))]
+    pub fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        block_on(
+            self.subscriber_async
+                .get_datareaders(sample_states, view_states, instance_states),
+        )
+    }
+
     /// This operation returns the [`DomainParticipant`] to which the [`Subscriber`] belongs.
     #[tracing::instrument(skip(self))]
     pub fn get_participant(&self) -> DomainParticipant {
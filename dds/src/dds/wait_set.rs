@@ -1,4 +1,4 @@
-use super::condition::StatusCondition;
+use super::condition::{QueryCondition, ReadCondition, StatusCondition};
 use crate::{
     dds_async::wait_set::{ConditionAsync, WaitSetAsync},
     infrastructure::{error::DdsResult, time::Duration},
@@ -11,6 +11,10 @@ use alloc::vec::Vec;
 pub enum Condition {
     /// Status condition variant
     StatusCondition(StatusCondition),
+    /// Read condition variant
+    ReadCondition(ReadCondition),
+    /// Query condition variant
+    QueryCondition(QueryCondition),
 }
 impl Condition {
     #[tracing::instrument(skip(self))]
@@ -18,6 +22,8 @@ impl Condition {
     pub fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             Condition::StatusCondition(c) => c.get_trigger_value(),
+            Condition::ReadCondition(c) => c.get_trigger_value(),
+            Condition::QueryCondition(c) => c.get_trigger_value(),
         }
     }
 }
@@ -54,6 +60,12 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::QueryCondition(qc) => {
+                    Condition::QueryCondition(QueryCondition::new(qc))
+                }
             })
             .collect())
     }
@@ -68,6 +80,14 @@ impl WaitSet {
             Condition::StatusCondition(sc) => block_on(self.waitset_async.attach_condition(
                 ConditionAsync::StatusCondition(sc.condition_async().clone()),
             )),
+            Condition::ReadCondition(rc) => block_on(
+                self.waitset_async
+                    .attach_condition(ConditionAsync::ReadCondition(rc.condition_async().clone())),
+            ),
+            Condition::QueryCondition(qc) => block_on(
+                self.waitset_async
+                    .attach_condition(ConditionAsync::QueryCondition(qc.condition_async().clone())),
+            ),
         }
     }
 
@@ -87,6 +107,12 @@ impl WaitSet {
                 ConditionAsync::StatusCondition(sc) => {
                     Condition::StatusCondition(StatusCondition::new(sc))
                 }
+                ConditionAsync::ReadCondition(rc) => {
+                    Condition::ReadCondition(ReadCondition::new(rc))
+                }
+                ConditionAsync::QueryCondition(qc) => {
+                    Condition::QueryCondition(QueryCondition::new(qc))
+                }
             })
             .collect())
     }
@@ -1,12 +1,19 @@
 use crate::{
     dcps::{
         channels::{notification::NotificationSender, oneshot::oneshot},
-        dcps_mail::{DcpsMail, StatusConditionMail},
+        dcps_mail::{DcpsMail, ReadConditionMail, StatusConditionMail},
+        read_condition::ReadConditionEntity,
         status_condition::StatusConditionEntity,
     },
     dds_async::domain_participant_factory::DcpsSender,
-    infrastructure::{error::DdsResult, status::StatusKind},
+    infrastructure::{
+        error::DdsResult,
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
+        status::StatusKind,
+    },
 };
+use alloc::{string::String, vec::Vec};
+use core::ops::Deref;
 
 /// Async version of [`StatusCondition`](crate::infrastructure::condition::StatusCondition).
 #[derive(Clone)]
@@ -96,3 +103,154 @@ impl StatusConditionAsync {
         reply_receiver.await?
     }
 }
+
+/// Async version of [`ReadCondition`](crate::condition::ReadCondition).
+#[derive(Clone)]
+pub struct ReadConditionAsync {
+    dcps_sender: DcpsSender,
+    entity: ReadConditionEntity,
+    sample_states: Vec<SampleStateKind>,
+    view_states: Vec<ViewStateKind>,
+    instance_states: Vec<InstanceStateKind>,
+}
+
+impl ReadConditionAsync {
+    pub(crate) fn new(
+        dcps_sender: DcpsSender,
+        entity: ReadConditionEntity,
+        sample_states: Vec<SampleStateKind>,
+        view_states: Vec<ViewStateKind>,
+        instance_states: Vec<InstanceStateKind>,
+    ) -> Self {
+        Self {
+            dcps_sender,
+            entity,
+            sample_states,
+            view_states,
+            instance_states,
+        }
+    }
+
+    pub(crate) fn entity(&self) -> &ReadConditionEntity {
+        &self.entity
+    }
+
+    pub(crate) async fn register_notification(
+        &self,
+        notification_sender: NotificationSender,
+    ) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender
+            .send(DcpsMail::ReadCondition(
+                ReadConditionMail::RegisterNotification {
+                    entity: self.entity.clone(),
+                    notification_sender,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_sample_state_mask`](crate::condition::ReadCondition::get_sample_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_sample_state_mask(&self) -> &[SampleStateKind] {
+        &self.sample_states
+    }
+
+    /// Async version of [`get_view_state_mask`](crate::condition::ReadCondition::get_view_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_view_state_mask(&self) -> &[ViewStateKind] {
+        &self.view_states
+    }
+
+    /// Async version of [`get_instance_state_mask`](crate::condition::ReadCondition::get_instance_state_mask).
+    #[tracing::instrument(skip(self))]
+    pub fn get_instance_state_mask(&self) -> &[InstanceStateKind] {
+        &self.instance_states
+    }
+}
+
+impl ReadConditionAsync {
+    /// Async version of [`get_trigger_value`](crate::condition::ReadCondition::get_trigger_value).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_trigger_value(&self) -> DdsResult<bool> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender
+            .send(DcpsMail::ReadCondition(
+                ReadConditionMail::GetTriggerValue {
+                    entity: self.entity.clone(),
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+}
+
+/// Async version of [`QueryCondition`](crate::condition::QueryCondition).
+#[derive(Clone)]
+pub struct QueryConditionAsync {
+    read_condition: ReadConditionAsync,
+    query_expression: String,
+}
+
+impl QueryConditionAsync {
+    pub(crate) fn new(read_condition: ReadConditionAsync, query_expression: String) -> Self {
+        Self {
+            read_condition,
+            query_expression,
+        }
+    }
+}
+
+impl Deref for QueryConditionAsync {
+    type Target = ReadConditionAsync;
+
+    fn deref(&self) -> &Self::Target {
+        &self.read_condition
+    }
+}
+
+impl QueryConditionAsync {
+    /// Async version of [`get_query_expression`](crate::condition::QueryCondition::get_query_expression).
+    #[tracing::instrument(skip(self))]
+    pub fn get_query_expression(&self) -> String {
+        self.query_expression.clone()
+    }
+
+    /// Async version of [`get_query_parameters`](crate::condition::QueryCondition::get_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_query_parameters(&self) -> DdsResult<Vec<String>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.read_condition
+            .dcps_sender
+            .send(DcpsMail::ReadCondition(
+                ReadConditionMail::GetQueryParameters {
+                    entity: self.read_condition.entity.clone(),
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+
+    /// Async version of [`set_query_parameters`](crate::condition::QueryCondition::set_query_parameters).
+    #[tracing::instrument(skip(self))]
+    pub async fn set_query_parameters(&self, query_parameters: &[String]) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.read_condition
+            .dcps_sender
+            .send(DcpsMail::ReadCondition(
+                ReadConditionMail::SetQueryParameters {
+                    entity: self.read_condition.entity.clone(),
+                    query_parameters: query_parameters.to_vec(),
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+}
@@ -1,13 +1,103 @@
-use crate::infrastructure::error::DdsResult;
-use alloc::string::{String, ToString};
+use crate::{
+    dds_async::persistence::PersistenceService,
+    infrastructure::error::{DdsError, DdsResult},
+};
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+};
 use core::time::Duration;
 
+/// Persistence service shared by the configuration. Two configurations use the same service
+/// only if they share the same instance.
+#[derive(Clone)]
+struct PersistenceServiceHandle(Arc<dyn PersistenceService>);
+
+impl core::fmt::Debug for PersistenceServiceHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("PersistenceService")
+    }
+}
+
+impl PartialEq for PersistenceServiceHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PersistenceServiceHandle {}
+
+/// Role of the participants in the discovery of the other participants of the domain.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum DiscoveryMode {
+    /// The participants are announced to all the participants reachable through the transport
+    /// and exchange their endpoints with all the discovered participants.
+    #[default]
+    Simple,
+    /// The participants act as discovery servers. They exchange their endpoints with all the
+    /// discovered participants and forward the participants and endpoints announced to them
+    /// to all the other participants.
+    Server,
+    /// The participants are only announced to the initial peers of the transport, which are
+    /// expected to be discovery servers, and only exchange their endpoints with the servers.
+    /// Several servers can be used for redundancy.
+    Client,
+}
+
+/// Writers sharing the bandwidth of a flow controller.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FlowControllerScope {
+    /// Each data writer has its own flow controller.
+    #[default]
+    Writer,
+    /// The data writers of a publisher share a flow controller.
+    Publisher,
+    /// All the data writers of a participant share a flow controller.
+    Participant,
+}
+
+/// Order in which the data writers sharing a flow controller send their pending data.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FlowControllerSchedulingPolicy {
+    /// The writers with the oldest pending sample, according to its source timestamp, send first.
+    #[default]
+    Fifo,
+    /// The writers take turns to be the first to send.
+    RoundRobin,
+    /// The writers with the highest [`TransportPriorityQosPolicy`](crate::infrastructure::qos_policy::TransportPriorityQosPolicy)
+    /// value send first.
+    Priority,
+}
+
+/// Flow controller limiting the bandwidth used by the user data writers. The DATA and DATA_FRAG submessages
+/// are paced with a token bucket which allows sending up to `max_bytes_per_period` bytes in each period, the
+/// rest of the data being sent in the following periods. The discovery data is not paced.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FlowControllerConfiguration {
+    /// Maximum number of bytes sent in a period.
+    pub max_bytes_per_period: usize,
+    /// Period after which the bytes can be sent again.
+    pub period: Duration,
+    /// Writers sharing the bandwidth.
+    pub scope: FlowControllerScope,
+    /// Order in which the writers sharing the bandwidth send their data.
+    pub scheduling_policy: FlowControllerSchedulingPolicy,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// This struct specifies the high-level configuration for the DustDDS library. The configuration can be set for use by the
 /// [`DomainParticipantFactory::set_configuration`](dust_dds::domain::domain_participant_factory::DomainParticipantFactory::set_configuration) method.
 pub struct DustDdsConfiguration {
     domain_tag: String,
     participant_announcement_interval: Duration,
+    persistence_service: Option<PersistenceServiceHandle>,
+    discovery_mode: DiscoveryMode,
+    heartbeat_period: Duration,
+    nack_response_delay: Duration,
+    nack_suppression_duration: Duration,
+    heartbeat_response_delay: Duration,
+    participant_lease_duration: Duration,
+    flow_controller: Option<FlowControllerConfiguration>,
 }
 
 impl DustDdsConfiguration {
@@ -20,6 +110,46 @@ impl DustDdsConfiguration {
     pub fn participant_announcement_interval(&self) -> Duration {
         self.participant_announcement_interval
     }
+
+    /// Service keeping the data written with the persistent durability kind.
+    pub fn persistence_service(&self) -> Option<&Arc<dyn PersistenceService>> {
+        self.persistence_service.as_ref().map(|s| &s.0)
+    }
+
+    /// Role of the participants in the discovery.
+    pub fn discovery_mode(&self) -> DiscoveryMode {
+        self.discovery_mode
+    }
+
+    /// Period at which the reliable writers announce the changes not yet acknowledged by the readers.
+    pub fn heartbeat_period(&self) -> Duration {
+        self.heartbeat_period
+    }
+
+    /// Delay of the reliable writers before repairing the changes requested by a reader.
+    pub fn nack_response_delay(&self) -> Duration {
+        self.nack_response_delay
+    }
+
+    /// Duration during which the reliable writers ignore the requests of a change after sending it.
+    pub fn nack_suppression_duration(&self) -> Duration {
+        self.nack_suppression_duration
+    }
+
+    /// Delay of the reliable readers before answering a heartbeat.
+    pub fn heartbeat_response_delay(&self) -> Duration {
+        self.heartbeat_response_delay
+    }
+
+    /// Duration during which the participants are considered alive by the others without being announced.
+    pub fn participant_lease_duration(&self) -> Duration {
+        self.participant_lease_duration
+    }
+
+    /// Flow controller limiting the bandwidth used by the user data writers.
+    pub fn flow_controller(&self) -> Option<&FlowControllerConfiguration> {
+        self.flow_controller.as_ref()
+    }
 }
 
 impl Default for DustDdsConfiguration {
@@ -27,6 +157,14 @@ impl Default for DustDdsConfiguration {
         Self {
             domain_tag: "".to_string(),
             participant_announcement_interval: Duration::from_secs(5),
+            persistence_service: None,
+            discovery_mode: DiscoveryMode::default(),
+            heartbeat_period: Duration::from_millis(200),
+            nack_response_delay: Duration::ZERO,
+            nack_suppression_duration: Duration::ZERO,
+            heartbeat_response_delay: Duration::ZERO,
+            participant_lease_duration: Duration::from_secs(100),
+            flow_controller: None,
         }
     }
 }
@@ -47,6 +185,11 @@ impl DustDdsConfigurationBuilder {
 
     /// Build a new configuration
     pub fn build(self) -> DdsResult<DustDdsConfiguration> {
+        if let Some(flow_controller) = &self.configuration.flow_controller {
+            if flow_controller.max_bytes_per_period == 0 || flow_controller.period.is_zero() {
+                return Err(DdsError::BadParameter);
+            }
+        }
         Ok(self.configuration)
     }
 
@@ -65,4 +208,68 @@ impl DustDdsConfigurationBuilder {
         self.configuration.participant_announcement_interval = participant_announcement_interval;
         self
     }
+
+    /// Set the service keeping the data written with the persistent durability kind, for example a
+    /// [`FilePersistenceService`](crate::dds_async::persistence::FilePersistenceService). No service is set by
+    /// default, in which case the persistent durability kind behaves as the transient local one.
+    pub fn persistence_service(
+        mut self,
+        persistence_service: Option<Arc<dyn PersistenceService>>,
+    ) -> Self {
+        self.configuration.persistence_service = persistence_service.map(PersistenceServiceHandle);
+        self
+    }
+
+    /// Set the role of the participants in the discovery. By default the simple discovery is used in which
+    /// every participant exchanges the discovery data with all the others.
+    pub fn discovery_mode(mut self, discovery_mode: DiscoveryMode) -> Self {
+        self.configuration.discovery_mode = discovery_mode;
+        self
+    }
+
+    /// Set the period at which all the reliable writers of the participants send a heartbeat to the readers which
+    /// have not acknowledged all the changes. Shorter periods repair the lost changes sooner at the cost of more
+    /// traffic.
+    pub fn heartbeat_period(mut self, heartbeat_period: Duration) -> Self {
+        self.configuration.heartbeat_period = heartbeat_period;
+        self
+    }
+
+    /// Set the delay of all the reliable writers of the participants before repairing the changes requested by a
+    /// reader, which allows the requests of several readers to be served together. By default the changes are
+    /// repaired immediately.
+    pub fn nack_response_delay(mut self, nack_response_delay: Duration) -> Self {
+        self.configuration.nack_response_delay = nack_response_delay;
+        self
+    }
+
+    /// Set the duration after sending a change during which all the reliable writers of the participants ignore the
+    /// requests of the same change, which are likely to have been sent before the change was received. By default
+    /// no request is ignored.
+    pub fn nack_suppression_duration(mut self, nack_suppression_duration: Duration) -> Self {
+        self.configuration.nack_suppression_duration = nack_suppression_duration;
+        self
+    }
+
+    /// Set the delay of all the reliable readers of the participants before answering a heartbeat with the
+    /// acknowledgment of the received changes. By default heartbeats are answered immediately.
+    pub fn heartbeat_response_delay(mut self, heartbeat_response_delay: Duration) -> Self {
+        self.configuration.heartbeat_response_delay = heartbeat_response_delay;
+        self
+    }
+
+    /// Set the duration announced to the other participants during which they must consider the participants
+    /// alive without receiving any announcement. It should be longer than the participant announcement interval.
+    pub fn participant_lease_duration(mut self, participant_lease_duration: Duration) -> Self {
+        self.configuration.participant_lease_duration = participant_lease_duration;
+        self
+    }
+
+    /// Set the flow controller limiting the bandwidth used by the user data writers. By default the data is sent
+    /// as soon as it is written. Building the configuration fails if the flow controller allows no byte to be sent
+    /// or has a zero period.
+    pub fn flow_controller(mut self, flow_controller: Option<FlowControllerConfiguration>) -> Self {
+        self.configuration.flow_controller = flow_controller;
+        self
+    }
 }
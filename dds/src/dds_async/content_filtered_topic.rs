@@ -1,4 +1,8 @@
 use crate::{
+    dcps::{
+        channels::oneshot::oneshot,
+        dcps_mail::{DcpsMail, ParticipantServiceMail},
+    },
     dds_async::{
         domain_participant::DomainParticipantAsync, topic::TopicAsync,
         topic_description::TopicDescriptionAsync,
@@ -27,16 +31,43 @@ impl ContentFilteredTopicAsync {
     }
 
     /// Async version of [`get_expression_parameters`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::get_expression_parameters).
+    #[tracing::instrument(skip(self))]
     pub async fn get_expression_parameters(&self) -> DdsResult<Vec<String>> {
-        todo!()
+        let participant = self.topic.get_participant();
+        let (reply_sender, reply_receiver) = oneshot();
+        participant
+            .dcps_sender()
+            .send(DcpsMail::Participant(
+                ParticipantServiceMail::GetExpressionParameters {
+                    participant_handle: participant.get_instance_handle(),
+                    name: self.name.clone(),
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
     /// Async version of [`set_expression_parameters`](crate::topic_definition::content_filtered_topic::ContentFilteredTopic::set_expression_parameters).
+    #[tracing::instrument(skip(self))]
     pub async fn set_expression_parameters(
         &self,
-        _expression_parameters: Vec<String>,
+        expression_parameters: Vec<String>,
     ) -> DdsResult<()> {
-        todo!()
+        let participant = self.topic.get_participant();
+        let (reply_sender, reply_receiver) = oneshot();
+        participant
+            .dcps_sender()
+            .send(DcpsMail::Participant(
+                ParticipantServiceMail::SetExpressionParameters {
+                    participant_handle: participant.get_instance_handle(),
+                    name: self.name.clone(),
+                    expression_parameters,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 }
 
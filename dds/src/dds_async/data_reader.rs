@@ -1,12 +1,16 @@
 use tracing::warn;
 
-use super::{condition::StatusConditionAsync, subscriber::SubscriberAsync};
+use super::{
+    condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync},
+    subscriber::SubscriberAsync,
+};
 use crate::{
     builtin_topics::PublicationBuiltinTopicData,
     dcps::{
         channels::oneshot::oneshot,
         dcps_mail::{DcpsMail, MessageServiceMail, ReaderServiceMail},
         listeners::data_reader_listener::DcpsDataReaderListener,
+        read_condition::ReadConditionEntity,
         status_condition::StatusConditionEntity,
     },
     dds_async::{
@@ -126,6 +130,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: None,
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -157,6 +162,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: None,
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -182,6 +188,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: ANY_VIEW_STATE.to_vec(),
                 instance_states: ANY_INSTANCE_STATE.to_vec(),
                 specific_instance_handle: None,
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -204,6 +211,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: ANY_VIEW_STATE.to_vec(),
                 instance_states: ANY_INSTANCE_STATE.to_vec(),
                 specific_instance_handle: None,
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -233,6 +241,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: Some(a_handle),
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -264,6 +273,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
                 specific_instance_handle: Some(a_handle),
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -296,6 +306,7 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 sample_states: sample_states.to_vec(),
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
+                condition_handle: None,
                 reply_sender,
             }))
             .await;
@@ -327,6 +338,127 @@ impl<Foo: TypeSupport> DataReaderAsync<Foo> {
                 sample_states: sample_states.to_vec(),
                 view_states: view_states.to_vec(),
                 instance_states: instance_states.to_vec(),
+                condition_handle: None,
+                reply_sender,
+            }))
+            .await;
+        let samples = reply_receiver.await??;
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`read_w_condition`](crate::subscription::data_reader::DataReader::read_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn read_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::Read {
+                participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                subscriber_handle: self.subscriber.get_instance_handle(),
+                data_reader_handle: self.handle,
+                max_samples,
+                sample_states: a_condition.get_sample_state_mask().to_vec(),
+                view_states: a_condition.get_view_state_mask().to_vec(),
+                instance_states: a_condition.get_instance_state_mask().to_vec(),
+                specific_instance_handle: None,
+                condition_handle: Some(a_condition.entity().condition_handle),
+                reply_sender,
+            }))
+            .await;
+        let samples = reply_receiver.await??;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`take_w_condition`](crate::subscription::data_reader::DataReader::take_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn take_w_condition(
+        &self,
+        max_samples: i32,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::Take {
+                participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                subscriber_handle: self.subscriber.get_instance_handle(),
+                data_reader_handle: self.handle,
+                max_samples,
+                sample_states: a_condition.get_sample_state_mask().to_vec(),
+                view_states: a_condition.get_view_state_mask().to_vec(),
+                instance_states: a_condition.get_instance_state_mask().to_vec(),
+                specific_instance_handle: None,
+                condition_handle: Some(a_condition.entity().condition_handle),
+                reply_sender,
+            }))
+            .await;
+        let samples = reply_receiver.await??;
+
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`read_next_instance_w_condition`](crate::subscription::data_reader::DataReader::read_next_instance_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn read_next_instance_w_condition(
+        &self,
+        max_samples: i32,
+        previous_handle: Option<InstanceHandle>,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::ReadNextInstance {
+                participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                subscriber_handle: self.subscriber.get_instance_handle(),
+                data_reader_handle: self.handle,
+                max_samples,
+                previous_handle,
+                sample_states: a_condition.get_sample_state_mask().to_vec(),
+                view_states: a_condition.get_view_state_mask().to_vec(),
+                instance_states: a_condition.get_instance_state_mask().to_vec(),
+                condition_handle: Some(a_condition.entity().condition_handle),
+                reply_sender,
+            }))
+            .await;
+        let samples = reply_receiver.await??;
+        Ok(samples
+            .into_iter()
+            .map(|(data, sample_info)| Sample::new(data, sample_info))
+            .collect())
+    }
+
+    /// Async version of [`take_next_instance_w_condition`](crate::subscription::data_reader::DataReader::take_next_instance_w_condition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn take_next_instance_w_condition(
+        &self,
+        max_samples: i32,
+        previous_handle: Option<InstanceHandle>,
+        a_condition: &ReadConditionAsync,
+    ) -> DdsResult<Vec<Sample<Foo>>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::TakeNextInstance {
+                participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                subscriber_handle: self.subscriber.get_instance_handle(),
+                data_reader_handle: self.handle,
+                max_samples,
+                previous_handle,
+                sample_states: a_condition.get_sample_state_mask().to_vec(),
+                view_states: a_condition.get_view_state_mask().to_vec(),
+                instance_states: a_condition.get_instance_state_mask().to_vec(),
+                condition_handle: Some(a_condition.entity().condition_handle),
                 reply_sender,
             }))
             .await;
@@ -508,6 +640,112 @@ impl<Foo> DataReaderAsync<Foo> {
         reply_receiver.await?
     }
 
+    /// Async version of [`create_readcondition`](crate::subscription::data_reader::DataReader::create_readcondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_readcondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<ReadConditionAsync> {
+        self.create_read_condition(sample_states, view_states, instance_states, None, &[])
+            .await
+    }
+
+    /// Async version of [`create_querycondition`](crate::subscription::data_reader::DataReader::create_querycondition).
+    #[tracing::instrument(skip(self))]
+    pub async fn create_querycondition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: String,
+        query_parameters: &[String],
+    ) -> DdsResult<QueryConditionAsync> {
+        let read_condition = self
+            .create_read_condition(
+                sample_states,
+                view_states,
+                instance_states,
+                Some(query_expression.clone()),
+                query_parameters,
+            )
+            .await?;
+        Ok(QueryConditionAsync::new(read_condition, query_expression))
+    }
+
+    async fn create_read_condition(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+        query_expression: Option<String>,
+        query_parameters: &[String],
+    ) -> DdsResult<ReadConditionAsync> {
+        let participant_handle = self.subscriber.get_participant().get_instance_handle();
+        let subscriber_handle = self.subscriber.get_instance_handle();
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::CreateReadCondition {
+                participant_handle,
+                subscriber_handle,
+                data_reader_handle: self.handle,
+                sample_states: sample_states.to_vec(),
+                view_states: view_states.to_vec(),
+                instance_states: instance_states.to_vec(),
+                query_expression,
+                query_parameters: query_parameters.to_vec(),
+                reply_sender,
+            }))
+            .await;
+        let condition_handle = reply_receiver.await??;
+        Ok(ReadConditionAsync::new(
+            *self.dcps_sender(),
+            ReadConditionEntity {
+                participant_handle,
+                subscriber_handle,
+                reader_handle: self.handle,
+                condition_handle,
+            },
+            sample_states.to_vec(),
+            view_states.to_vec(),
+            instance_states.to_vec(),
+        ))
+    }
+
+    /// Async version of [`delete_readcondition`](crate::subscription::data_reader::DataReader::delete_readcondition).
+    #[tracing::instrument(skip(self, a_condition))]
+    pub async fn delete_readcondition(&self, a_condition: &ReadConditionAsync) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(ReaderServiceMail::DeleteReadCondition {
+                participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                subscriber_handle: self.subscriber.get_instance_handle(),
+                data_reader_handle: self.handle,
+                condition_handle: a_condition.entity().condition_handle,
+                reply_sender,
+            }))
+            .await;
+        reply_receiver.await?
+    }
+
+    /// Async version of [`delete_contained_entities`](crate::subscription::data_reader::DataReader::delete_contained_entities).
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_contained_entities(&self) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Reader(
+                ReaderServiceMail::DeleteContainedEntities {
+                    participant_handle: self.subscriber.get_participant().get_instance_handle(),
+                    subscriber_handle: self.subscriber.get_instance_handle(),
+                    data_reader_handle: self.handle,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+
     /// Async version of [`get_statuscondition`](crate::subscription::data_reader::DataReader::get_statuscondition).
     #[tracing::instrument(skip(self))]
     pub fn get_statuscondition(&self) -> StatusConditionAsync {
@@ -5,6 +5,7 @@ use crate::{
     builtin_topics::SubscriptionBuiltinTopicData,
     dcps::{
         channels::oneshot::oneshot,
+        dcps_domain_participant::data_writer_entity::{SharedDataRepresentation, WriterSample},
         dcps_mail::{DcpsMail, MessageServiceMail, WriterServiceMail},
         listeners::data_writer_listener::DcpsDataWriterListener,
         status_condition::StatusConditionEntity,
@@ -33,6 +34,7 @@ pub struct DataWriterAsync<Foo> {
     handle: InstanceHandle,
     publisher: PublisherAsync,
     topic: TopicAsync,
+    data_representation: SharedDataRepresentation,
     phantom: PhantomData<Foo>,
 }
 
@@ -42,6 +44,7 @@ impl<Foo> Clone for DataWriterAsync<Foo> {
             handle: self.handle,
             publisher: self.publisher.clone(),
             topic: self.topic.clone(),
+            data_representation: self.data_representation.clone(),
             phantom: self.phantom,
         }
     }
@@ -52,11 +55,13 @@ impl<Foo> DataWriterAsync<Foo> {
         handle: InstanceHandle,
         publisher: PublisherAsync,
         topic: TopicAsync,
+        data_representation: SharedDataRepresentation,
     ) -> Self {
         Self {
             handle,
             publisher,
             topic,
+            data_representation,
             phantom: PhantomData,
         }
     }
@@ -70,6 +75,7 @@ impl<Foo> DataWriterAsync<Foo> {
             handle: self.handle,
             publisher: self.publisher,
             topic: self.topic,
+            data_representation: self.data_representation,
             phantom: PhantomData,
         }
     }
@@ -199,13 +205,13 @@ where
         timestamp: Time,
     ) -> DdsResult<()> {
         let (reply_sender, reply_receiver) = oneshot();
-        let dynamic_data = data.create_dynamic_sample();
+        let sample = WriterSample::new(data, &self.data_representation);
         self.dcps_sender()
             .send(DcpsMail::Writer(WriterServiceMail::WriteWTimestamp {
                 participant_handle: self.publisher.get_participant().get_instance_handle(),
                 publisher_handle: self.publisher.get_instance_handle(),
                 data_writer_handle: self.handle,
-                dynamic_data,
+                sample,
                 timestamp,
                 reply_sender,
             }))
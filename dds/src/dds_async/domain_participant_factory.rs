@@ -21,6 +21,7 @@ use crate::{
         status::StatusKind,
         time::Duration,
     },
+    rtps::stateful_writer::WriterTiming,
     runtime::{Clock, DdsRuntime, Either, Spawner, TaskHandle, Timer, select_future},
     transport::{
         interface::{TransportDataReceiver, TransportParticipantFactory},
@@ -87,6 +88,17 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
 
         let domain_tag = configuration.domain_tag().to_owned();
         let participant_announcement_interval = configuration.participant_announcement_interval();
+        let persistence_service = configuration.persistence_service().cloned();
+        let discovery_mode = configuration.discovery_mode();
+        let writer_timing = WriterTiming {
+            heartbeat_period: Duration::from(configuration.heartbeat_period()).into(),
+            nack_response_delay: Duration::from(configuration.nack_response_delay()).into(),
+            nack_suppression_duration: Duration::from(configuration.nack_suppression_duration())
+                .into(),
+        };
+        let heartbeat_response_delay = configuration.heartbeat_response_delay();
+        let participant_lease_duration = configuration.participant_lease_duration();
+        let flow_controller = configuration.flow_controller().copied();
         let listener_mask = mask.iter().collect();
         let dcps_listener = a_listener.map(DcpsDomainParticipantListener::new);
         let (reply_sender, reply_receiver) = oneshot();
@@ -102,6 +114,12 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
                     transport_participant,
                     domain_tag,
                     participant_announcement_interval,
+                    persistence_service,
+                    discovery_mode,
+                    writer_timing,
+                    heartbeat_response_delay,
+                    participant_lease_duration,
+                    flow_controller,
                 },
             ))
             .await;
@@ -232,19 +250,24 @@ impl
             let executor = crate::std_runtime::executor::Executor::new();
             let timer_driver = crate::std_runtime::timer::TimerDriver::new();
             let runtime = crate::std_runtime::StdRuntime::new(executor, timer_driver);
-            let interface_address = NetworkInterface::show()
+            let interface_address_list: Vec<_> = NetworkInterface::show()
                 .expect("Could not scan interfaces")
                 .into_iter()
-                .flat_map(|i| {
-                    i.addr
-                        .into_iter()
-                        .filter(|a| matches!(a, Addr::V4(v4) if !v4.ip.is_loopback()))
-                })
-                .next();
+                .flat_map(|i| i.addr.into_iter().filter(|a| !a.ip().is_loopback()))
+                .collect();
+            // IPv4 addresses are preferred and IPv6 is used only on hosts without one
+            let interface_address = interface_address_list
+                .iter()
+                .find(|a| matches!(a, Addr::V4(_)))
+                .or_else(|| interface_address_list.first());
             let host_id = if let Some(interface) = interface_address {
                 match interface.ip() {
                     IpAddr::V4(a) => a.octets(),
-                    IpAddr::V6(_) => unimplemented!("IPv6 not yet implemented"),
+                    IpAddr::V6(a) => {
+                        // Fold the 16 octets of the address into the 4 octets of the host id
+                        let o = a.octets();
+                        core::array::from_fn(|i| o[i] ^ o[i + 4] ^ o[i + 8] ^ o[i + 12])
+                    }
                 }
             } else {
                 warn!("Failed to get Host ID from IP address, use 0 instead");
@@ -284,7 +307,12 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
             let span = tracing::trace_span!("dds_actor_loop");
             let _enter = span.enter();
             while run_loop_clone.load(core::sync::atomic::Ordering::Relaxed) {
-                let poke_time = Duration::new(0, 50_000_000);
+                let default_poke_time = Duration::new(0, 50_000_000);
+                let poke_time = default_poke_time.min(
+                    domain_participant_factory
+                        .poke_period()
+                        .unwrap_or(default_poke_time),
+                );
                 let time_until_missed_reader_deadline =
                     domain_participant_factory.time_until_missed_reader_deadline();
                 let time_until_missed_writer_deadline =
@@ -295,6 +323,10 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
                     domain_participant_factory.time_until_stale_writer_sample();
                 let time_until_pending_writer_sample_timeout =
                     domain_participant_factory.time_until_pending_writer_sample_timeout();
+                let time_until_pending_acknowledgments_timeout =
+                    domain_participant_factory.time_until_pending_acknowledgments_timeout();
+                let time_until_pending_sample_delivery =
+                    domain_participant_factory.time_until_pending_sample_delivery();
                 let time_until_participant_announcement =
                     domain_participant_factory.time_until_participant_announcement();
                 let next_task_time = poke_time
@@ -303,6 +335,8 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
                     .min(time_until_stale_participant.unwrap_or(poke_time))
                     .min(time_until_stale_writer_sample.unwrap_or(poke_time))
                     .min(time_until_pending_writer_sample_timeout.unwrap_or(poke_time))
+                    .min(time_until_pending_acknowledgments_timeout.unwrap_or(poke_time))
+                    .min(time_until_pending_sample_delivery.unwrap_or(poke_time))
                     .min(time_until_participant_announcement.unwrap_or(poke_time));
 
                 match select_future(
@@ -325,9 +359,15 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
                     dp.process_discovered_participants_detector_cache_change(
                         &domain_participant_factory.runtime,
                     );
-                    dp.process_builtin_publications_detector_cache_change();
-                    dp.process_builtin_subscriptions_detector_cache_change();
-                    dp.process_builtin_topics_detector_cache_change();
+                    dp.process_builtin_publications_detector_cache_change(
+                        &domain_participant_factory.runtime,
+                    );
+                    dp.process_builtin_subscriptions_detector_cache_change(
+                        &domain_participant_factory.runtime,
+                    );
+                    dp.process_builtin_topics_detector_cache_change(
+                        &domain_participant_factory.runtime,
+                    );
                     dp.process_builtin_type_lookup_request_cache_change(
                         &domain_participant_factory.runtime,
                     );
@@ -351,6 +391,9 @@ impl<T: TransportParticipantFactory> DomainParticipantFactoryAsync<T> {
                         domain_participant_factory.runtime.clock().now(),
                     );
                     dp.process_pending_write_samples(&domain_participant_factory.runtime);
+                    dp.check_pending_acknowledgments(
+                        domain_participant_factory.runtime.clock().now(),
+                    );
                     dp.announce_participant_if_needed(&domain_participant_factory.runtime);
                     dp.notify_find_topic_senders(domain_participant_factory.runtime.clock().now());
                     dp.poke(&domain_participant_factory.runtime.clock());
@@ -19,6 +19,9 @@ pub mod domain_participant;
 pub mod domain_participant_factory;
 /// Traits related to the async domain participant listener.
 pub mod domain_participant_listener;
+/// Contains the [`PersistenceService`](crate::persistence::PersistenceService) trait used to keep the data written
+/// with the persistent durability kind
+pub mod persistence;
 /// Classes related to the async publisher.
 pub mod publisher;
 /// Traits related to the async publisher listener.
@@ -0,0 +1,439 @@
+use crate::infrastructure::{
+    domain::DomainId, error::DdsResult, instance::InstanceHandle, time::Time,
+};
+use alloc::vec::Vec;
+
+/// Kind of a change kept by a [`PersistenceService`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersistentChangeKind {
+    /// A new value of the instance was written.
+    Alive,
+    /// The instance was disposed.
+    NotAliveDisposed,
+    /// The instance was unregistered.
+    NotAliveUnregistered,
+    /// The instance was disposed and unregistered.
+    NotAliveDisposedUnregistered,
+}
+
+/// Change written by a [`DataWriter`](crate::publication::data_writer::DataWriter) with the
+/// [`DurabilityQosPolicyKind::Persistent`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Persistent) durability kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistentChange {
+    /// Kind of the change.
+    pub kind: PersistentChangeKind,
+    /// Instance the change belongs to.
+    pub instance_handle: InstanceHandle,
+    /// Source timestamp of the change.
+    pub source_timestamp: Time,
+    /// Serialized data of the change. For changes which are not alive this is the serialized key.
+    pub serialized_data: Vec<u8>,
+}
+
+/// Storage used to keep the data written with the
+/// [`DurabilityQosPolicyKind::Persistent`](crate::infrastructure::qos_policy::DurabilityQosPolicyKind::Persistent) durability kind
+/// so that it outlives the application which wrote it.
+///
+/// The changes are kept separately for each writer identified by its instance handle. The changes of a writer are
+/// given to the service as they are written. The service can keep more changes than needed since the limits of the
+/// [`DurabilityServiceQosPolicy`](crate::infrastructure::qos_policy::DurabilityServiceQosPolicy) are applied again
+/// to the loaded changes. From time to time the service is given the changes of the writer which are still needed
+/// so that it can discard the rest.
+pub trait PersistenceService: Send + Sync {
+    /// Returns the changes kept for the topic by all its writers ordered by their source timestamp.
+    fn load(&self, domain_id: DomainId, topic_name: &str) -> DdsResult<Vec<PersistentChange>>;
+
+    /// Keeps a new change written by the writer.
+    fn append(
+        &self,
+        domain_id: DomainId,
+        topic_name: &str,
+        writer_handle: InstanceHandle,
+        change: &PersistentChange,
+    ) -> DdsResult<()>;
+
+    /// Replaces all the changes kept for the writer with the given ones. The changes of
+    /// the other writers of the topic are left untouched.
+    fn compact(
+        &self,
+        domain_id: DomainId,
+        topic_name: &str,
+        writer_handle: InstanceHandle,
+        changes: &[PersistentChange],
+    ) -> DdsResult<()>;
+}
+
+#[cfg(feature = "std")]
+pub use file_persistence_service::FilePersistenceService;
+
+#[cfg(feature = "std")]
+mod file_persistence_service {
+    use super::{PersistenceService, PersistentChange, PersistentChangeKind};
+    use crate::infrastructure::{
+        domain::DomainId,
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+        time::Time,
+    };
+    use std::{
+        fmt::Write as _,
+        fs::{self, OpenOptions},
+        io::{self, Write},
+        path::{Path, PathBuf},
+        sync::{Arc, OnceLock, mpsc},
+    };
+
+    const RECORD_HEADER_SIZE: usize = 4;
+    const CHANGE_HEADER_SIZE: usize = 1 + 16 + 4 + 4;
+
+    /// [`PersistenceService`] which keeps the changes of each writer in an append-only log file
+    /// named after the writer inside a subdirectory of the domain and topic.
+    ///
+    /// The log files are written by a background thread so that writing a change does not wait
+    /// for the file system. The changes written meanwhile are appended to the logs together.
+    #[derive(Debug, Clone)]
+    pub struct FilePersistenceService {
+        directory: PathBuf,
+        log_writer: Arc<OnceLock<mpsc::Sender<LogCommand>>>,
+    }
+
+    #[derive(Debug)]
+    enum LogCommand {
+        Append { path: PathBuf, record: Vec<u8> },
+        Compact { path: PathBuf, log: Vec<u8> },
+        Flush(mpsc::Sender<()>),
+    }
+
+    impl FilePersistenceService {
+        /// Creates a service which keeps the log files inside the given directory. The
+        /// directory is created when the first change is written.
+        pub fn new(directory: impl Into<PathBuf>) -> Self {
+            Self {
+                directory: directory.into(),
+                log_writer: Arc::new(OnceLock::new()),
+            }
+        }
+
+        /// Directory in which the log files are kept.
+        pub fn directory(&self) -> &Path {
+            &self.directory
+        }
+
+        fn topic_path(&self, domain_id: DomainId, topic_name: &str) -> PathBuf {
+            // Topic names can contain characters which are not valid in file names
+            let mut directory_name = String::new();
+            for b in topic_name.bytes() {
+                if b.is_ascii_alphanumeric() || b == b'_' || b == b'-' {
+                    directory_name.push(b as char);
+                } else {
+                    write!(directory_name, "%{b:02X}").expect("Write to string must succeed");
+                }
+            }
+            self.directory
+                .join(domain_id.to_string())
+                .join(directory_name)
+        }
+
+        fn log_path(
+            &self,
+            domain_id: DomainId,
+            topic_name: &str,
+            writer_handle: InstanceHandle,
+        ) -> PathBuf {
+            let mut file_name = String::new();
+            for b in <[u8; 16]>::from(writer_handle) {
+                write!(file_name, "{b:02x}").expect("Write to string must succeed");
+            }
+            file_name.push_str(".log");
+            self.topic_path(domain_id, topic_name).join(file_name)
+        }
+
+        fn send(&self, command: LogCommand) -> DdsResult<()> {
+            self.log_writer
+                .get_or_init(|| {
+                    let (sender, receiver) = mpsc::channel();
+                    std::thread::Builder::new()
+                        .name("Dust DDS Persistence".to_string())
+                        .spawn(move || write_logs(receiver))
+                        .expect("Failed to spawn thread");
+                    sender
+                })
+                .send(command)
+                .map_err(|_| DdsError::Error("Persistence service stopped".to_string()))
+        }
+
+        /// Waits until the changes given to the service are written to the log files.
+        fn flush(&self) -> DdsResult<()> {
+            if self.log_writer.get().is_some() {
+                let (done_sender, done_receiver) = mpsc::channel();
+                self.send(LogCommand::Flush(done_sender))?;
+                done_receiver
+                    .recv()
+                    .map_err(|_| DdsError::Error("Persistence service stopped".to_string()))?;
+            }
+            Ok(())
+        }
+    }
+
+    fn write_logs(commands: mpsc::Receiver<LogCommand>) {
+        while let Ok(command) = commands.recv() {
+            // The records appended to the same log are written together
+            let mut appended_records: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+            for command in core::iter::once(command).chain(commands.try_iter()) {
+                match command {
+                    LogCommand::Append { path, record } => {
+                        match appended_records.iter_mut().find(|(p, _)| p == &path) {
+                            Some((_, records)) => records.extend_from_slice(&record),
+                            None => appended_records.push((path, record)),
+                        }
+                    }
+                    LogCommand::Compact { path, log } => {
+                        append_records(core::mem::take(&mut appended_records));
+                        if let Err(e) = compact_log(&path, &log) {
+                            tracing::warn!("Log {} was not compacted: {e}", path.display());
+                        }
+                    }
+                    LogCommand::Flush(done) => {
+                        append_records(core::mem::take(&mut appended_records));
+                        done.send(()).ok();
+                    }
+                }
+            }
+            append_records(appended_records);
+        }
+    }
+
+    fn append_records(appended_records: Vec<(PathBuf, Vec<u8>)>) {
+        for (path, records) in appended_records {
+            let result = create_parent_directory(&path).and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .and_then(|mut file| file.write_all(&records))
+            });
+            if let Err(e) = result {
+                tracing::warn!("Changes were not appended to log {}: {e}", path.display());
+            }
+        }
+    }
+
+    fn compact_log(path: &Path, log: &[u8]) -> io::Result<()> {
+        create_parent_directory(path)?;
+        // The new log replaces the old one only once completely written so that
+        // the changes are not lost if the application stops in the meantime
+        let compacted_path = path.with_extension("log.tmp");
+        let mut file = fs::File::create(&compacted_path)?;
+        file.write_all(log)?;
+        file.sync_all()?;
+        fs::rename(&compacted_path, path)
+    }
+
+    fn create_parent_directory(path: &Path) -> io::Result<()> {
+        match path.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        }
+    }
+
+    fn encode_change(change: &PersistentChange, buf: &mut Vec<u8>) {
+        let record_size = CHANGE_HEADER_SIZE + change.serialized_data.len();
+        buf.extend_from_slice(&(record_size as u32).to_le_bytes());
+        buf.push(match change.kind {
+            PersistentChangeKind::Alive => 0,
+            PersistentChangeKind::NotAliveDisposed => 1,
+            PersistentChangeKind::NotAliveUnregistered => 2,
+            PersistentChangeKind::NotAliveDisposedUnregistered => 3,
+        });
+        buf.extend_from_slice(&<[u8; 16]>::from(change.instance_handle));
+        buf.extend_from_slice(&change.source_timestamp.sec().to_le_bytes());
+        buf.extend_from_slice(&change.source_timestamp.nanosec().to_le_bytes());
+        buf.extend_from_slice(&change.serialized_data);
+    }
+
+    fn decode_change(record: &[u8]) -> Option<PersistentChange> {
+        let kind = match record.first()? {
+            0 => PersistentChangeKind::Alive,
+            1 => PersistentChangeKind::NotAliveDisposed,
+            2 => PersistentChangeKind::NotAliveUnregistered,
+            3 => PersistentChangeKind::NotAliveDisposedUnregistered,
+            _ => return None,
+        };
+        let instance_handle = InstanceHandle::new(record.get(1..17)?.try_into().ok()?);
+        let sec = i32::from_le_bytes(record.get(17..21)?.try_into().ok()?);
+        let nanosec = u32::from_le_bytes(record.get(21..25)?.try_into().ok()?);
+        Some(PersistentChange {
+            kind,
+            instance_handle,
+            source_timestamp: Time::new(sec, nanosec),
+            serialized_data: record.get(CHANGE_HEADER_SIZE..)?.to_vec(),
+        })
+    }
+
+    fn decode_log(log: &[u8], changes: &mut Vec<PersistentChange>) {
+        let mut remaining = log;
+        // A record which was not completely written before the application stopped is ignored
+        while let Some(record_size) = remaining
+            .get(..RECORD_HEADER_SIZE)
+            .map(|size| u32::from_le_bytes(size.try_into().expect("Size has 4 bytes")) as usize)
+        {
+            let Some(record) = remaining.get(RECORD_HEADER_SIZE..RECORD_HEADER_SIZE + record_size)
+            else {
+                break;
+            };
+            let Some(change) = decode_change(record) else {
+                break;
+            };
+            changes.push(change);
+            remaining = &remaining[RECORD_HEADER_SIZE + record_size..];
+        }
+    }
+
+    fn io_error(error: io::Error) -> DdsError {
+        DdsError::Error(format!("Persistence service error: {error}"))
+    }
+
+    impl PersistenceService for FilePersistenceService {
+        fn load(&self, domain_id: DomainId, topic_name: &str) -> DdsResult<Vec<PersistentChange>> {
+            self.flush()?;
+            let entries = match fs::read_dir(self.topic_path(domain_id, topic_name)) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => return Err(io_error(e)),
+            };
+            let mut log_paths = Vec::new();
+            for entry in entries {
+                let path = entry.map_err(io_error)?.path();
+                if path.extension().is_some_and(|extension| extension == "log") {
+                    log_paths.push(path);
+                }
+            }
+            log_paths.sort();
+
+            let mut changes = Vec::new();
+            for path in log_paths {
+                decode_log(&fs::read(path).map_err(io_error)?, &mut changes);
+            }
+            changes.sort_by_key(|change| change.source_timestamp);
+            Ok(changes)
+        }
+
+        fn append(
+            &self,
+            domain_id: DomainId,
+            topic_name: &str,
+            writer_handle: InstanceHandle,
+            change: &PersistentChange,
+        ) -> DdsResult<()> {
+            let mut record = Vec::new();
+            encode_change(change, &mut record);
+            self.send(LogCommand::Append {
+                path: self.log_path(domain_id, topic_name, writer_handle),
+                record,
+            })
+        }
+
+        fn compact(
+            &self,
+            domain_id: DomainId,
+            topic_name: &str,
+            writer_handle: InstanceHandle,
+            changes: &[PersistentChange],
+        ) -> DdsResult<()> {
+            let mut log = Vec::new();
+            for change in changes {
+                encode_change(change, &mut log);
+            }
+            self.send(LogCommand::Compact {
+                path: self.log_path(domain_id, topic_name, writer_handle),
+                log,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const WRITER_1: InstanceHandle = InstanceHandle::new([1; 16]);
+        const WRITER_2: InstanceHandle = InstanceHandle::new([2; 16]);
+
+        fn change(value: u8) -> PersistentChange {
+            PersistentChange {
+                kind: PersistentChangeKind::Alive,
+                instance_handle: InstanceHandle::new([value; 16]),
+                source_timestamp: Time::new(10, value as u32),
+                serialized_data: vec![value; value as usize],
+            }
+        }
+
+        fn test_directory(name: &str) -> PathBuf {
+            let directory = std::env::temp_dir().join(format!(
+                "dust_dds_persistence_{}_{name}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&directory);
+            directory
+        }
+
+        #[test]
+        fn appended_changes_are_loaded_in_order() {
+            let directory = test_directory("appended");
+            let service = FilePersistenceService::new(&directory);
+
+            assert_eq!(service.load(0, "Topic::Name"), Ok(vec![]));
+            service
+                .append(0, "Topic::Name", WRITER_1, &change(1))
+                .unwrap();
+            service
+                .append(0, "Topic::Name", WRITER_2, &change(2))
+                .unwrap();
+            service
+                .append(0, "Topic::Name", WRITER_1, &change(3))
+                .unwrap();
+            service
+                .append(1, "Topic::Name", WRITER_1, &change(4))
+                .unwrap();
+
+            assert_eq!(
+                service.load(0, "Topic::Name"),
+                Ok(vec![change(1), change(2), change(3)])
+            );
+            assert_eq!(service.load(1, "Topic::Name"), Ok(vec![change(4)]));
+            fs::remove_dir_all(directory).unwrap();
+        }
+
+        #[test]
+        fn incomplete_record_is_ignored() {
+            let directory = test_directory("incomplete");
+            let service = FilePersistenceService::new(&directory);
+            service.append(0, "Topic", WRITER_1, &change(1)).unwrap();
+            service.append(0, "Topic", WRITER_1, &change(2)).unwrap();
+            service.flush().unwrap();
+            let path = service.log_path(0, "Topic", WRITER_1);
+            let log = fs::read(&path).unwrap();
+            fs::write(&path, &log[..log.len() - 1]).unwrap();
+
+            assert_eq!(service.load(0, "Topic"), Ok(vec![change(1)]));
+            fs::remove_dir_all(directory).unwrap();
+        }
+
+        #[test]
+        fn compact_replaces_only_the_changes_of_the_writer() {
+            let directory = test_directory("compact");
+            let service = FilePersistenceService::new(&directory);
+            service.append(0, "Topic", WRITER_1, &change(1)).unwrap();
+            service.append(0, "Topic", WRITER_2, &change(2)).unwrap();
+            service.append(0, "Topic", WRITER_1, &change(3)).unwrap();
+
+            service.compact(0, "Topic", WRITER_1, &[change(3)]).unwrap();
+            service.append(0, "Topic", WRITER_1, &change(4)).unwrap();
+
+            assert_eq!(
+                service.load(0, "Topic"),
+                Ok(vec![change(2), change(3), change(4)])
+            );
+            fs::remove_dir_all(directory).unwrap();
+        }
+    }
+}
@@ -70,9 +70,14 @@ impl PublisherAsync {
                 },
             ))
             .await;
-        let guid = reply_receiver.await??;
+        let (guid, data_representation) = reply_receiver.await??;
 
-        Ok(DataWriterAsync::new(guid, self.clone(), a_topic.clone()))
+        Ok(DataWriterAsync::new(
+            guid,
+            self.clone(),
+            a_topic.clone(),
+            data_representation,
+        ))
     }
 
     /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::delete_datawriter).
@@ -104,34 +109,85 @@ impl PublisherAsync {
         todo!()
     }
 
-    /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::suspend_publications).
+    /// Async version of [`suspend_publications`](crate::publication::publisher::Publisher::suspend_publications).
     #[tracing::instrument(skip(self))]
     pub async fn suspend_publications(&self) -> DdsResult<()> {
-        todo!()
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Publisher(
+                PublisherServiceMail::SuspendPublications {
+                    participant_handle: self.participant.get_instance_handle(),
+                    publisher_handle: self.handle,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
-    /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::resume_publications).
+    /// Async version of [`resume_publications`](crate::publication::publisher::Publisher::resume_publications).
     #[tracing::instrument(skip(self))]
     pub async fn resume_publications(&self) -> DdsResult<()> {
-        todo!()
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Publisher(
+                PublisherServiceMail::ResumePublications {
+                    participant_handle: self.participant.get_instance_handle(),
+                    publisher_handle: self.handle,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
-    /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::begin_coherent_changes).
+    /// Async version of [`begin_coherent_changes`](crate::publication::publisher::Publisher::begin_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn begin_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Publisher(
+                PublisherServiceMail::BeginCoherentChanges {
+                    participant_handle: self.participant.get_instance_handle(),
+                    publisher_handle: self.handle,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
-    /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::end_coherent_changes).
+    /// Async version of [`end_coherent_changes`](crate::publication::publisher::Publisher::end_coherent_changes).
     #[tracing::instrument(skip(self))]
     pub async fn end_coherent_changes(&self) -> DdsResult<()> {
-        todo!()
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Publisher(
+                PublisherServiceMail::EndCoherentChanges {
+                    participant_handle: self.participant.get_instance_handle(),
+                    publisher_handle: self.handle,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
-    /// Async version of [`delete_datawriter`](crate::publication::publisher::Publisher::wait_for_acknowledgments).
+    /// Async version of [`wait_for_acknowledgments`](crate::publication::publisher::Publisher::wait_for_acknowledgments).
     #[tracing::instrument(skip(self))]
-    pub async fn wait_for_acknowledgments(&self, _max_wait: Duration) -> DdsResult<()> {
-        todo!()
+    pub async fn wait_for_acknowledgments(&self, max_wait: Duration) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Publisher(
+                PublisherServiceMail::WaitForAcknowledgments {
+                    participant_handle: self.participant.get_instance_handle(),
+                    publisher_handle: self.handle,
+                    max_wait,
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
     }
 
     /// Async version of [`get_participant`](crate::publication::publisher::Publisher::get_participant).
@@ -18,6 +18,7 @@ use crate::{
         error::{DdsError, DdsResult},
         instance::InstanceHandle,
         qos::{DataReaderQos, QosKind, SubscriberQos, TopicQos},
+        sample_info::{InstanceStateKind, SampleStateKind, ViewStateKind},
         status::{SampleLostStatus, StatusKind},
     },
 };
@@ -137,6 +138,58 @@ impl SubscriberAsync {
         todo!()
     }
 
+    /// Async version of [`begin_access`](crate::subscription::subscriber::Subscriber::begin_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn begin_access(&self) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Subscriber(SubscriberServiceMail::BeginAccess {
+                participant_handle: self.participant.get_instance_handle(),
+                subscriber_handle: self.handle,
+                reply_sender,
+            }))
+            .await;
+        reply_receiver.await?
+    }
+
+    /// Async version of [`end_access`](crate::subscription::subscriber::Subscriber::end_access).
+    #[tracing::instrument(skip(self))]
+    pub async fn end_access(&self) -> DdsResult<()> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Subscriber(SubscriberServiceMail::EndAccess {
+                participant_handle: self.participant.get_instance_handle(),
+                subscriber_handle: self.handle,
+                reply_sender,
+            }))
+            .await;
+        reply_receiver.await?
+    }
+
+    /// Async version of [`get_datareaders`](crate::subscription::subscriber::Subscriber::get_datareaders).
+    #[tracing::instrument(skip(self))]
+    pub async fn get_datareaders(
+        &self,
+        sample_states: &[SampleStateKind],
+        view_states: &[ViewStateKind],
+        instance_states: &[InstanceStateKind],
+    ) -> DdsResult<Vec<InstanceHandle>> {
+        let (reply_sender, reply_receiver) = oneshot();
+        self.dcps_sender()
+            .send(DcpsMail::Subscriber(
+                SubscriberServiceMail::GetDataReaders {
+                    participant_handle: self.participant.get_instance_handle(),
+                    subscriber_handle: self.handle,
+                    sample_states: sample_states.to_vec(),
+                    view_states: view_states.to_vec(),
+                    instance_states: instance_states.to_vec(),
+                    reply_sender,
+                },
+            ))
+            .await;
+        reply_receiver.await?
+    }
+
     /// Async version of [`get_participant`](crate::subscription::subscriber::Subscriber::get_participant).
     #[tracing::instrument(skip(self))]
     pub fn get_participant(&self) -> DomainParticipantAsync {
@@ -1,4 +1,4 @@
-use super::condition::StatusConditionAsync;
+use super::condition::{QueryConditionAsync, ReadConditionAsync, StatusConditionAsync};
 use crate::{
     dcps::channels::notification::notification,
     infrastructure::error::{DdsError, DdsResult},
@@ -10,6 +10,10 @@ use alloc::{string::String, vec::Vec};
 pub enum ConditionAsync {
     /// Status condition variant
     StatusCondition(StatusConditionAsync),
+    /// Read condition variant
+    ReadCondition(ReadConditionAsync),
+    /// Query condition variant
+    QueryCondition(QueryConditionAsync),
 }
 
 impl ConditionAsync {
@@ -18,6 +22,8 @@ impl ConditionAsync {
     pub async fn get_trigger_value(&self) -> DdsResult<bool> {
         match self {
             ConditionAsync::StatusCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::ReadCondition(c) => c.get_trigger_value().await,
+            ConditionAsync::QueryCondition(c) => c.get_trigger_value().await,
         }
     }
 }
@@ -66,6 +72,16 @@ impl WaitSetAsync {
                         .register_notification(notification_sender.clone())
                         .await?;
                 }
+                ConditionAsync::ReadCondition(read_condition_async) => {
+                    read_condition_async
+                        .register_notification(notification_sender.clone())
+                        .await?;
+                }
+                ConditionAsync::QueryCondition(query_condition_async) => {
+                    query_condition_async
+                        .register_notification(notification_sender.clone())
+                        .await?;
+                }
             }
         }
 
@@ -1,4 +1,4 @@
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![forbid(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
@@ -28,6 +28,18 @@ pub mod rtps_messages;
 #[doc(hidden)]
 pub mod rtps_udp_transport;
 
+#[cfg(feature = "rtps_shared_memory_transport")]
+#[doc(hidden)]
+pub mod rtps_shared_memory_transport;
+
+#[cfg(feature = "rtps_tcp_transport")]
+#[doc(hidden)]
+pub mod rtps_tcp_transport;
+
+#[cfg(feature = "rtps_loopback_transport")]
+#[doc(hidden)]
+pub mod rtps_loopback_transport;
+
 #[doc(hidden)]
 /// Contains the Dust DDS transport interface definition.
 pub mod transport;
@@ -2,16 +2,24 @@ use super::error::RtpsError;
 use crate::{
     rtps_messages::{
         self,
+        overall_structure::Endianness,
         submessage_elements::{Parameter, ParameterList, SerializedDataFragment},
         submessages::{data::DataSubmessage, data_frag::DataFragSubmessage},
         types::ParameterId,
     },
-    transport::types::{CacheChange, ChangeKind, EntityId, Guid, GuidPrefix},
+    transport::types::{
+        CacheChange, ChangeKind, CoherentSet, EntityId, Guid, GuidPrefix, SequenceNumber,
+    },
 };
 use alloc::{sync::Arc, vec::Vec};
 
 pub const PID_KEY_HASH: ParameterId = 0x0070;
 pub const PID_STATUS_INFO: ParameterId = 0x0071;
+pub const PID_CONTENT_FILTER_INFO: ParameterId = 0x0055;
+pub const PID_COHERENT_SET: ParameterId = 0x0056;
+pub const PID_GROUP_COHERENT_SET: ParameterId = 0x0063;
+pub const PID_GROUP_SEQ_NUM: ParameterId = 0x0064;
+pub const PID_WRITER_GROUP_INFO: ParameterId = 0x0065;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 struct StatusInfo(pub [u8; 4]);
@@ -20,16 +28,130 @@ const STATUS_INFO_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000010]);
 const STATUS_INFO_DISPOSED_UNREGISTERED: StatusInfo = StatusInfo([0, 0, 0, 0b00000011]);
 const STATUS_INFO_FILTERED: StatusInfo = StatusInfo([0, 0, 0, 0b0000100]);
 
+// ContentFilterInfo_t: the filterResult bitmap sequence followed by the
+// sequence of 16 byte filter signatures the result bits refer to.
+fn serialize_content_filter_info(filter_signatures: &[[u8; 16]]) -> Vec<u8> {
+    let number_of_bitmaps = filter_signatures.len().div_ceil(32);
+    let mut bytes = Vec::with_capacity(8 + number_of_bitmaps * 4 + filter_signatures.len() * 16);
+    bytes.extend_from_slice(&(number_of_bitmaps as u32).to_le_bytes());
+    for bitmap_index in 0..number_of_bitmaps {
+        let passed_filters = core::cmp::min(filter_signatures.len() - bitmap_index * 32, 32);
+        let bitmap = !u32::MAX.checked_shr(passed_filters as u32).unwrap_or(0);
+        bytes.extend_from_slice(&bitmap.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(filter_signatures.len() as u32).to_le_bytes());
+    for filter_signature in filter_signatures {
+        bytes.extend_from_slice(filter_signature);
+    }
+    bytes
+}
+
+fn read_u32(value: &[u8], offset: usize, endianness: Endianness) -> Option<u32> {
+    let bytes = value.get(offset..offset + 4)?.try_into().ok()?;
+    Some(match endianness {
+        Endianness::BigEndian => u32::from_be_bytes(bytes),
+        Endianness::LittleEndian => u32::from_le_bytes(bytes),
+    })
+}
+
+fn deserialize_content_filter_info(value: &[u8], endianness: Endianness) -> Option<Vec<[u8; 16]>> {
+    let read = |offset: usize| read_u32(value, offset, endianness);
+    let number_of_bitmaps = read(0)? as usize;
+    let signatures_offset = 4 + number_of_bitmaps.checked_mul(4)?;
+    let number_of_signatures = read(signatures_offset)? as usize;
+    if value.len() != signatures_offset + 4 + number_of_signatures.checked_mul(16)? {
+        return None;
+    }
+    let mut filter_signatures = Vec::new();
+    for index in 0..number_of_signatures {
+        let bitmap = read(4 + (index / 32) * 4)?;
+        if bitmap & (0x8000_0000 >> (index % 32)) != 0 {
+            let start = signatures_offset + 4 + index * 16;
+            filter_signatures.push(value[start..start + 16].try_into().ok()?);
+        }
+    }
+    Some(filter_signatures)
+}
+
+/// GroupDigest_t identifying the set of writers taking part in a group
+/// coherent set: the first four bytes of the MD5 of their sorted GUIDs.
+pub fn writer_group_digest(writer_guids: impl IntoIterator<Item = Guid>) -> [u8; 4] {
+    let mut guids: Vec<[u8; 16]> = writer_guids.into_iter().map(<[u8; 16]>::from).collect();
+    guids.sort_unstable();
+    guids.dedup();
+    let digest = md5::compute(guids.concat());
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+// SequenceNumber_t: the high signed word followed by the low unsigned word.
+fn serialize_sequence_number(sequence_number: SequenceNumber) -> Arc<[u8]> {
+    let mut bytes = Vec::with_capacity(8);
+    bytes.extend_from_slice(&((sequence_number >> 32) as i32).to_le_bytes());
+    bytes.extend_from_slice(&(sequence_number as u32).to_le_bytes());
+    Arc::from(bytes)
+}
+
+fn deserialize_sequence_number(value: &[u8], endianness: Endianness) -> Option<SequenceNumber> {
+    if value.len() != 8 {
+        return None;
+    }
+    let high = read_u32(value, 0, endianness)? as i32;
+    let low = read_u32(value, 4, endianness)?;
+    Some(((high as i64) << 32) + low as i64)
+}
+
 impl CacheChange {
+    /// Returns whether the change only marks the end of a coherent set and
+    /// carries no data for the reader.
+    pub fn is_end_of_coherent_set(&self) -> bool {
+        self.coherent_set.is_some_and(|c| c.end_of_set)
+    }
+
+    fn coherent_set_parameters(&self, parameters: &mut Vec<Parameter>) {
+        if let Some(coherent_set) = &self.coherent_set {
+            parameters.push(Parameter::new(
+                PID_COHERENT_SET,
+                serialize_sequence_number(coherent_set.sequence_number),
+            ));
+            if let Some(group_sequence_number) = coherent_set.group_sequence_number {
+                parameters.push(Parameter::new(
+                    PID_GROUP_COHERENT_SET,
+                    serialize_sequence_number(group_sequence_number),
+                ));
+            }
+            if let Some(writer_set) = coherent_set.writer_set {
+                parameters.push(Parameter::new(PID_WRITER_GROUP_INFO, Arc::from(writer_set)));
+            }
+        }
+        if let Some(group_sequence_number) = self.group_sequence_number {
+            parameters.push(Parameter::new(
+                PID_GROUP_SEQ_NUM,
+                serialize_sequence_number(group_sequence_number),
+            ));
+        }
+    }
+
     pub fn as_data_submessage(&self, reader_id: EntityId, writer_id: EntityId) -> DataSubmessage {
+        self.as_filtered_data_submessage(reader_id, writer_id, &[])
+    }
+
+    /// Create a DATA submessage announcing in its inline QoS that the change passed the
+    /// content filters with the given signatures.
+    pub fn as_filtered_data_submessage(
+        &self,
+        reader_id: EntityId,
+        writer_id: EntityId,
+        filter_signatures: &[[u8; 16]],
+    ) -> DataSubmessage {
         let (data_flag, key_flag) = match self.kind {
+            _ if self.is_end_of_coherent_set() => (false, false),
             ChangeKind::Alive | ChangeKind::AliveFiltered => (true, false),
             ChangeKind::NotAliveDisposed
             | ChangeKind::NotAliveUnregistered
             | ChangeKind::NotAliveDisposedUnregistered => (false, true),
         };
 
-        let mut parameters = Vec::with_capacity(2);
+        let mut parameters = Vec::with_capacity(3);
         match self.kind {
             ChangeKind::Alive | ChangeKind::AliveFiltered => (),
             ChangeKind::NotAliveDisposed => parameters.push(Parameter::new(
@@ -49,6 +171,13 @@ impl CacheChange {
         if let Some(i) = self.instance_handle {
             parameters.push(Parameter::new(PID_KEY_HASH, Arc::from(i)));
         }
+        if !filter_signatures.is_empty() {
+            parameters.push(Parameter::new(
+                PID_CONTENT_FILTER_INFO,
+                Arc::from(serialize_content_filter_info(filter_signatures)),
+            ));
+        }
+        self.coherent_set_parameters(&mut parameters);
         let parameter_list = ParameterList::new(parameters);
 
         DataSubmessage::new(
@@ -106,6 +235,40 @@ impl CacheChange {
             None => None,
         };
 
+        let endianness = data_submessage.inline_qos_endianness();
+        let filter_signatures = match data_submessage
+            .inline_qos()
+            .parameter()
+            .iter()
+            .find(|&x| x.parameter_id() == PID_CONTENT_FILTER_INFO)
+        {
+            Some(p) => deserialize_content_filter_info(p.value(), endianness).unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let find_parameter = |parameter_id| {
+            data_submessage
+                .inline_qos()
+                .parameter()
+                .iter()
+                .find(move |&x| x.parameter_id() == parameter_id)
+        };
+        // A DATA with neither data nor key can only carry inline QoS and is
+        // used to mark the end of a coherent set
+        let end_of_set = !data_submessage._data_flag() && !data_submessage._key_flag();
+        let coherent_set = find_parameter(PID_COHERENT_SET)
+            .and_then(|p| deserialize_sequence_number(p.value(), endianness))
+            .map(|sequence_number| CoherentSet {
+                sequence_number,
+                group_sequence_number: find_parameter(PID_GROUP_COHERENT_SET)
+                    .and_then(|p| deserialize_sequence_number(p.value(), endianness)),
+                writer_set: find_parameter(PID_WRITER_GROUP_INFO)
+                    .and_then(|p| <[u8; 4]>::try_from(p.value()).ok()),
+                end_of_set,
+            });
+        let group_sequence_number = find_parameter(PID_GROUP_SEQ_NUM)
+            .and_then(|p| deserialize_sequence_number(p.value(), endianness));
+
         Ok(CacheChange {
             kind,
             writer_guid: Guid::new(source_guid_prefix, data_submessage.writer_id()),
@@ -113,6 +276,9 @@ impl CacheChange {
             instance_handle,
             sequence_number: data_submessage.writer_sn(),
             data_value: data_submessage.serialized_payload().clone().into(),
+            filter_signatures,
+            coherent_set,
+            group_sequence_number,
         })
     }
 
@@ -141,6 +307,9 @@ impl CacheChange {
         let serialized_payload =
             SerializedDataFragment::new(self.data_value.clone().into(), start..end);
 
+        let mut parameters = Vec::new();
+        self.coherent_set_parameters(&mut parameters);
+
         DataFragSubmessage::new(
             inline_qos_flag,
             non_standard_payload_flag,
@@ -152,8 +321,118 @@ impl CacheChange {
             fragments_in_submessage,
             fragment_size,
             data_size,
-            ParameterList::new(Vec::new()),
+            ParameterList::new(parameters),
             serialized_payload,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rtps_messages::overall_structure::{SubmessageHeaderRead, write_submessage_into_bytes_vec},
+        transport::types::{USER_DEFINED_READER_NO_KEY, USER_DEFINED_WRITER_NO_KEY},
+    };
+
+    fn read_data_submessage(mut bytes: &[u8]) -> DataSubmessage {
+        let submessage_header = SubmessageHeaderRead::try_read_from_bytes(&mut bytes).unwrap();
+        DataSubmessage::try_from_bytes(&submessage_header, bytes).unwrap()
+    }
+
+    #[test]
+    fn coherent_set_and_filter_info_round_trip() {
+        let writer_guid = Guid::new(
+            [1; 12],
+            EntityId::new([1, 2, 3], USER_DEFINED_WRITER_NO_KEY),
+        );
+        let cache_change = CacheChange {
+            kind: ChangeKind::Alive,
+            writer_guid,
+            sequence_number: 7,
+            source_timestamp: None,
+            instance_handle: None,
+            data_value: Arc::from([1, 2, 3, 4]),
+            filter_signatures: alloc::vec![[9; 16]],
+            coherent_set: Some(CoherentSet {
+                sequence_number: 5,
+                group_sequence_number: Some(3),
+                writer_set: None,
+                end_of_set: false,
+            }),
+            group_sequence_number: Some(3),
+        };
+        let data_submessage = cache_change.as_filtered_data_submessage(
+            EntityId::new([4, 5, 6], USER_DEFINED_READER_NO_KEY),
+            writer_guid.entity_id(),
+            &[[9; 16]],
+        );
+        let bytes = write_submessage_into_bytes_vec(&data_submessage);
+
+        let received = CacheChange::try_from_data_submessage(
+            &read_data_submessage(&bytes),
+            writer_guid.prefix(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(received, cache_change);
+    }
+
+    #[test]
+    fn big_endian_content_filter_info_is_decoded_with_submessage_byte_order() {
+        #[rustfmt::skip]
+        let bytes = [
+            0x15, 0b_0000_0110, 0, 60, // Submessage header: big endian, inline QoS, data
+            0, 0, 0, 16, // extraFlags, octetsToInlineQos
+            4, 5, 6, 4, // readerId
+            1, 2, 3, 3, // writerId
+            0, 0, 0, 0, // writerSN: high
+            0, 0, 0, 7, // writerSN: low
+            0x00, 0x55, 0, 28, // PID_CONTENT_FILTER_INFO
+            0, 0, 0, 1, // numBitmaps
+            0x80, 0, 0, 0, // filterResult
+            0, 0, 0, 1, // numSignatures
+            9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9,
+            0x00, 0x01, 0, 0, // PID_SENTINEL
+            1, 2, 3, 4, // SerializedPayload
+        ];
+        let data_submessage = read_data_submessage(&bytes);
+        assert_eq!(
+            data_submessage.inline_qos_endianness(),
+            Endianness::BigEndian
+        );
+
+        let cache_change =
+            CacheChange::try_from_data_submessage(&data_submessage, [1; 12], None).unwrap();
+        assert_eq!(cache_change.filter_signatures, alloc::vec![[9; 16]]);
+    }
+    #[test]
+    fn big_endian_coherent_set_is_decoded_with_submessage_byte_order() {
+        #[rustfmt::skip]
+        let bytes = [
+            0x15, 0b_0000_0010, 0, 48, // Submessage header: big endian, inline QoS
+            0, 0, 0, 16, // extraFlags, octetsToInlineQos
+            4, 5, 6, 4, // readerId
+            1, 2, 3, 3, // writerId
+            0, 0, 0, 0, // writerSN: high
+            0, 0, 0, 7, // writerSN: low
+            0x00, 0x56, 0, 8, // PID_COHERENT_SET
+            0, 0, 0, 0, 0, 0, 0, 5,
+            0x00, 0x63, 0, 8, // PID_GROUP_COHERENT_SET
+            0, 0, 0, 0, 0, 0, 0, 3,
+            0x00, 0x01, 0, 0, // PID_SENTINEL
+        ];
+        let cache_change =
+            CacheChange::try_from_data_submessage(&read_data_submessage(&bytes), [1; 12], None)
+                .unwrap();
+        assert_eq!(
+            cache_change.coherent_set,
+            Some(CoherentSet {
+                sequence_number: 5,
+                group_sequence_number: Some(3),
+                writer_set: None,
+                end_of_set: true,
+            })
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::infrastructure::time::{Duration, Time};
+use alloc::sync::Arc;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Token bucket limiting the number of bytes of DATA and DATA_FRAG submessages sent in each
+/// period by the writers sharing it.
+struct TokenBucket {
+    max_bytes_per_period: usize,
+    period: Duration,
+    available_bytes: usize,
+    period_start: Time,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: Time) {
+        if now - self.period_start >= self.period {
+            self.available_bytes = self.max_bytes_per_period;
+            self.period_start = now;
+        }
+    }
+}
+
+/// Flow controller shared by the writers whose output it paces. Cloning it gives another
+/// handle to the same token bucket.
+#[derive(Clone)]
+pub struct FlowController {
+    bucket: Arc<Mutex<RefCell<TokenBucket>>>,
+}
+
+impl FlowController {
+    pub fn new(max_bytes_per_period: usize, period: Duration) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(RefCell::new(TokenBucket {
+                max_bytes_per_period,
+                period,
+                available_bytes: max_bytes_per_period,
+                period_start: Time::new(0, 0),
+            }))),
+        }
+    }
+
+    pub fn period(&self) -> Duration {
+        critical_section::with(|cs| self.bucket.borrow(cs).borrow().period)
+    }
+
+    /// Takes the bytes of a message from the bucket if there are enough of them. A message
+    /// bigger than the bytes of a period can only be sent with a full bucket so that it is
+    /// not blocked forever.
+    pub fn try_send(&self, message_size: usize, now: Time) -> bool {
+        critical_section::with(|cs| {
+            let mut bucket = self.bucket.borrow(cs).borrow_mut();
+            bucket.refill(now);
+            if message_size <= bucket.available_bytes
+                || bucket.available_bytes == bucket.max_bytes_per_period
+            {
+                bucket.available_bytes = bucket.available_bytes.saturating_sub(message_size);
+                true
+            } else {
+                false
+            }
+        })
+    }
+}
+
+/// Whether a message can be sent by a writer with an optional flow controller
+pub fn is_send_allowed(
+    flow_controller: Option<&FlowController>,
+    message_size: usize,
+    now: Time,
+) -> bool {
+    flow_controller.is_none_or(|f| f.try_send(message_size, now))
+}
@@ -1,10 +1,21 @@
 use crate::{
-    rtps_messages::overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
-    transport::types::GuidPrefix,
+    rtps_messages::{
+        overall_structure::{RtpsMessageHeader, RtpsMessageWrite, Submessage},
+        submessages::info_destination::InfoDestinationSubmessage,
+        types::INFO_DST,
+    },
+    transport::{
+        interface::WriteMessage,
+        types::{GUIDPREFIX_UNKNOWN, GuidPrefix, Locator},
+    },
 };
+use alloc::vec::Vec;
+use core::cell::RefCell;
 
 use super::types::{PROTOCOLVERSION_2_4, VENDOR_ID_S2E};
 
+const RTPS_MESSAGE_HEADER_SIZE: usize = 20;
+
 impl RtpsMessageWrite {
     pub fn from_submessages(
         submessages: &[&(dyn Submessage + Send)],
@@ -14,3 +25,174 @@ impl RtpsMessageWrite {
         RtpsMessageWrite::new(&header, submessages)
     }
 }
+
+/// Messages which are held instead of being sent, for example while the publications
+/// of a publisher are suspended.
+#[derive(Default)]
+pub struct RtpsMessageBatch {
+    messages: RefCell<Vec<(Vec<Locator>, Vec<u8>)>>,
+}
+
+impl RtpsMessageBatch {
+    pub const fn new() -> Self {
+        Self {
+            messages: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Sends the held messages combining the ones going to the same locators into as
+    /// few RTPS messages as possible, each of at most `max_message_size` bytes unless a
+    /// single held message is already bigger. All the held messages must have been
+    /// created by the same participant since only the header of the first one is kept.
+    pub fn flush(self, message_writer: &(impl WriteMessage + ?Sized), max_message_size: usize) {
+        // The destination set by an INFO_DST of a previous message would otherwise
+        // also apply to the submessages of a message sent to all the participants
+        let reset_destination = RtpsMessageWrite::from_submessages(
+            &[&InfoDestinationSubmessage::new(GUIDPREFIX_UNKNOWN)],
+            GUIDPREFIX_UNKNOWN,
+        );
+        let reset_destination = &reset_destination.buffer()[RTPS_MESSAGE_HEADER_SIZE..];
+
+        let mut messages = self.messages.into_inner();
+        while let Some((locators, _)) = messages.first() {
+            let locators = locators.clone();
+            let mut combined_message: Vec<u8> = Vec::new();
+            messages.retain(|(message_locators, message)| {
+                if message_locators != &locators {
+                    return true;
+                }
+                let submessages = &message[RTPS_MESSAGE_HEADER_SIZE..];
+                let must_reset_destination = submessages.first() != Some(&INFO_DST);
+                let combined_size = combined_message.len()
+                    + submessages.len()
+                    + if must_reset_destination {
+                        reset_destination.len()
+                    } else {
+                        0
+                    };
+                if !combined_message.is_empty() && combined_size > max_message_size {
+                    message_writer.write_message(&combined_message, &locators);
+                    combined_message.clear();
+                }
+                if combined_message.is_empty() {
+                    combined_message.extend_from_slice(message);
+                } else {
+                    if must_reset_destination {
+                        combined_message.extend_from_slice(reset_destination);
+                    }
+                    combined_message.extend_from_slice(submessages);
+                }
+                false
+            });
+            message_writer.write_message(&combined_message, &locators);
+        }
+    }
+}
+
+impl WriteMessage for RtpsMessageBatch {
+    fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+        self.messages
+            .borrow_mut()
+            .push((locators.to_vec(), buf.to_vec()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        rtps_messages::{
+            overall_structure::{RtpsMessageRead, RtpsSubmessageReadKind},
+            submessage_elements::SequenceNumberSet,
+            submessages::gap::GapSubmessage,
+        },
+        transport::types::{ENTITYID_UNKNOWN, LOCATOR_KIND_UDP_V4},
+    };
+
+    struct MockWriter {
+        messages: Mutex<Vec<(Vec<Locator>, RtpsMessageRead)>>,
+    }
+
+    impl WriteMessage for MockWriter {
+        fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+            let message = RtpsMessageRead::try_from(buf).unwrap();
+            self.messages
+                .lock()
+                .unwrap()
+                .push((locators.to_vec(), message));
+        }
+    }
+
+    fn gap_message(sequence_number: i64) -> RtpsMessageWrite {
+        let gap = GapSubmessage::new(
+            ENTITYID_UNKNOWN,
+            ENTITYID_UNKNOWN,
+            sequence_number,
+            SequenceNumberSet::new(sequence_number + 1, []),
+        );
+        RtpsMessageWrite::from_submessages(&[&gap], [1; 12])
+    }
+
+    #[test]
+    fn messages_to_the_same_locators_are_combined() {
+        let locator1 = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [1; 16]);
+        let locator2 = Locator::new(LOCATOR_KIND_UDP_V4, 7401, [1; 16]);
+        let info_dst = InfoDestinationSubmessage::new([2; 12]);
+        let batch = RtpsMessageBatch::new();
+        batch.write_message(
+            RtpsMessageWrite::from_submessages(&[&info_dst], [1; 12]).buffer(),
+            &[locator1],
+        );
+        batch.write_message(gap_message(1).buffer(), &[locator2]);
+        batch.write_message(gap_message(2).buffer(), &[locator1]);
+        let writer = MockWriter {
+            messages: Mutex::new(Vec::new()),
+        };
+
+        batch.flush(&writer, 1000);
+
+        let messages = writer.messages.into_inner().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].0, vec![locator1]);
+        assert!(matches!(
+            messages[0].1.submessages(),
+            [
+                RtpsSubmessageReadKind::InfoDestination(d1),
+                RtpsSubmessageReadKind::InfoDestination(d2),
+                RtpsSubmessageReadKind::Gap(_),
+            ] if d1.guid_prefix() == [2; 12] && d2.guid_prefix() == GUIDPREFIX_UNKNOWN
+        ));
+        assert_eq!(messages[1].0, vec![locator2]);
+        assert_eq!(messages[1].1.submessages().len(), 1);
+    }
+
+    #[test]
+    fn combined_messages_do_not_exceed_the_maximum_size() {
+        let locator = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [1; 16]);
+        let batch = RtpsMessageBatch::new();
+        let message_size = gap_message(1).buffer().len();
+        for sequence_number in 1..=5 {
+            batch.write_message(gap_message(sequence_number).buffer(), &[locator]);
+        }
+        let writer = MockWriter {
+            messages: Mutex::new(Vec::new()),
+        };
+
+        batch.flush(&writer, message_size * 2);
+
+        let messages = writer.messages.into_inner().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(messages.iter().all(|(l, _)| l == &[locator]));
+        let gaps: Vec<_> = messages
+            .iter()
+            .flat_map(|(_, message)| message.submessages())
+            .filter_map(|s| match s {
+                RtpsSubmessageReadKind::Gap(gap) => Some(gap.gap_start()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(gaps, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod behavior_types;
 pub mod cache_change;
 pub mod error;
+pub mod flow_controller;
 pub mod message_creator;
 pub mod message_receiver;
 pub mod reader_locator;
@@ -1,14 +1,18 @@
 use crate::{
     infrastructure::time::{Duration, Time},
     rtps_messages::{
-        submessages::{heartbeat::HeartbeatSubmessage, heartbeat_frag::HeartbeatFragSubmessage},
+        submessages::{
+            data::DataSubmessage, heartbeat::HeartbeatSubmessage,
+            heartbeat_frag::HeartbeatFragSubmessage,
+        },
         types::{Count, FragmentNumber},
     },
     transport::types::{
-        CacheChange, DurabilityKind, EntityId, Guid, Locator, ReliabilityKind, SequenceNumber,
+        CacheChange, ChangeKind, DurabilityKind, EntityId, Guid, Locator, ReliabilityKind,
+        SequenceNumber,
     },
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct HeartbeatMachine {
@@ -80,6 +84,49 @@ impl HeartbeatFragMachine {
     }
 }
 
+/// Content filter evaluated by the writer on behalf of a matched reader. Changes rejected
+/// by the filter are sent to the reader as GAP instead of DATA.
+pub struct ReaderContentFilter {
+    signature: [u8; 16],
+    filter: Box<dyn Fn(&CacheChange) -> bool + Send + Sync>,
+}
+
+impl ReaderContentFilter {
+    pub fn new(
+        signature: [u8; 16],
+        filter: impl Fn(&CacheChange) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            signature,
+            filter: Box::new(filter),
+        }
+    }
+
+    pub fn signature(&self) -> [u8; 16] {
+        self.signature
+    }
+
+    pub fn accepts(&self, cache_change: &CacheChange) -> bool {
+        (self.filter)(cache_change)
+    }
+}
+
+impl core::fmt::Debug for ReaderContentFilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReaderContentFilter")
+            .field("signature", &self.signature)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ReaderContentFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.signature == other.signature
+    }
+}
+
+impl Eq for ReaderContentFilter {}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct RtpsReaderProxy {
     remote_reader_guid: Guid,
@@ -87,8 +134,11 @@ pub struct RtpsReaderProxy {
     unicast_locator_list: Vec<Locator>,
     multicast_locator_list: Vec<Locator>,
     highest_sent_seq_num: SequenceNumber,
+    sent_fragment_count: (SequenceNumber, usize),
     highest_acked_seq_num: SequenceNumber,
     requested_changes: Vec<SequenceNumber>,
+    requested_changes_response_time: Time,
+    suppressed_requests: Vec<(SequenceNumber, Time)>,
     expects_inline_qos: bool,
     is_active: bool,
     last_received_acknack_count: Count,
@@ -98,6 +148,7 @@ pub struct RtpsReaderProxy {
     reliability: ReliabilityKind,
     first_relevant_sample_seq_num: SequenceNumber,
     durability: DurabilityKind,
+    content_filter: Option<ReaderContentFilter>,
 }
 
 impl RtpsReaderProxy {
@@ -121,8 +172,11 @@ impl RtpsReaderProxy {
             unicast_locator_list: unicast_locator_list.to_vec(),
             multicast_locator_list: multicast_locator_list.to_vec(),
             highest_sent_seq_num: 0,
+            sent_fragment_count: (0, 0),
             highest_acked_seq_num: 0,
             requested_changes: Vec::new(),
+            requested_changes_response_time: Time::new(0, 0),
+            suppressed_requests: Vec::new(),
             expects_inline_qos,
             is_active,
             last_received_acknack_count: 0,
@@ -132,6 +186,7 @@ impl RtpsReaderProxy {
             reliability,
             first_relevant_sample_seq_num,
             durability,
+            content_filter: None,
         }
     }
 
@@ -159,6 +214,93 @@ impl RtpsReaderProxy {
         self.durability
     }
 
+    pub fn content_filter_signature(&self) -> Option<[u8; 16]> {
+        self.content_filter.as_ref().map(|f| f.signature())
+    }
+
+    pub fn set_content_filter(&mut self, content_filter: Option<ReaderContentFilter>) {
+        self.content_filter = content_filter;
+    }
+
+    /// Changes which are not alive only carry the key and are therefore never filtered out.
+    /// Neither are the changes marking the end of a coherent set since they carry no data.
+    pub fn is_change_relevant(&self, cache_change: &CacheChange) -> bool {
+        match &self.content_filter {
+            Some(content_filter)
+                if cache_change.kind == ChangeKind::Alive
+                    && !cache_change.is_end_of_coherent_set() =>
+            {
+                content_filter.accepts(cache_change)
+            }
+            _ => true,
+        }
+    }
+
+    pub fn data_submessage(
+        &self,
+        cache_change: &CacheChange,
+        writer_id: EntityId,
+    ) -> DataSubmessage {
+        let reader_id = self.remote_reader_guid.entity_id();
+        match &self.content_filter {
+            Some(content_filter)
+                if cache_change.kind == ChangeKind::Alive
+                    && !cache_change.is_end_of_coherent_set() =>
+            {
+                cache_change.as_filtered_data_submessage(
+                    reader_id,
+                    writer_id,
+                    &[content_filter.signature()],
+                )
+            }
+            _ => cache_change.as_data_submessage(reader_id, writer_id),
+        }
+    }
+
+    /// Number of fragments of a change already sent, which is only different from zero when
+    /// a flow controller stopped the sending of the fragments of the change
+    pub fn sent_fragment_count(&self, seq_num: SequenceNumber) -> usize {
+        match self.sent_fragment_count {
+            (sn, count) if sn == seq_num => count,
+            _ => 0,
+        }
+    }
+
+    pub fn set_sent_fragment_count(&mut self, seq_num: SequenceNumber, count: usize) {
+        self.sent_fragment_count = (seq_num, count);
+    }
+
+    /// Time from which the requested changes are sent to the reader
+    pub fn set_requested_changes_response_time(&mut self, response_time: Time) {
+        self.requested_changes_response_time = response_time;
+    }
+
+    pub fn is_time_for_requested_changes(&self, now: Time) -> bool {
+        now >= self.requested_changes_response_time
+    }
+
+    /// Ignore the requests of a change received within the nack suppression duration after
+    /// sending it since they were likely sent by the reader before receiving the change.
+    pub fn suppress_requests(
+        &mut self,
+        seq_num: SequenceNumber,
+        now: Time,
+        nack_suppression_duration: Duration,
+    ) {
+        self.suppressed_requests
+            .retain(|(sn, suppressed_until)| sn != &seq_num && suppressed_until > &now);
+        if nack_suppression_duration > Duration::new(0, 0) {
+            self.suppressed_requests
+                .push((seq_num, now + nack_suppression_duration));
+        }
+    }
+
+    pub fn is_request_suppressed(&self, seq_num: SequenceNumber, now: Time) -> bool {
+        self.suppressed_requests
+            .iter()
+            .any(|(sn, suppressed_until)| sn == &seq_num && suppressed_until > &now)
+    }
+
     // //////////////   ReaderProxy operations defined in the Rtps Standard
 
     pub fn acked_changes_set(&mut self, committed_seq_num: SequenceNumber) {
@@ -1,10 +1,14 @@
 use super::writer_proxy::RtpsWriterProxy;
 use crate::{
+    infrastructure,
     rtps_messages::{
         submessages::{data::DataSubmessage, data_frag::DataFragSubmessage},
         types::Time,
     },
-    transport::types::{CacheChange, Guid, GuidPrefix, ReliabilityKind, WriterProxy},
+    transport::{
+        interface::WriteMessage,
+        types::{CacheChange, Guid, GuidPrefix, ReliabilityKind, WriterProxy},
+    },
 };
 use alloc::vec::Vec;
 
@@ -53,6 +57,10 @@ impl RtpsStatefulReader {
             .retain(|writer_proxy| writer_proxy.remote_writer_guid() != writer_guid)
     }
 
+    pub fn matched_writers(&self) -> &[RtpsWriterProxy] {
+        &self.matched_writers
+    }
+
     pub fn matched_writer_lookup(&mut self, a_writer_guid: Guid) -> Option<&mut RtpsWriterProxy> {
         self.matched_writers
             .iter_mut()
@@ -154,6 +162,17 @@ impl RtpsStatefulReader {
 
 // The methods in this impl block are not defined by the standard
 impl RtpsStatefulReader {
+    /// Send the AckNacks whose heartbeat response delay has elapsed
+    pub fn write_message(
+        &mut self,
+        message_writer: &(impl WriteMessage + ?Sized),
+        now: infrastructure::time::Time,
+    ) {
+        for writer_proxy in &mut self.matched_writers {
+            writer_proxy.write_message(&self.guid, message_writer, now);
+        }
+    }
+
     pub fn is_historical_data_received(&self) -> bool {
         !self
             .matched_writers
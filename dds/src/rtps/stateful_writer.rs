@@ -1,4 +1,8 @@
-use super::{behavior_types::Duration, reader_proxy::RtpsReaderProxy};
+use super::{
+    behavior_types::{DURATION_ZERO, Duration},
+    flow_controller::{FlowController, is_send_allowed},
+    reader_proxy::{ReaderContentFilter, RtpsReaderProxy},
+};
 use crate::{
     rtps_messages::{
         overall_structure::RtpsMessageWrite,
@@ -14,29 +18,53 @@ use crate::{
     transport::{
         interface::WriteMessage,
         types::{
-            CacheChange, ChangeKind, DurabilityKind, ENTITYID_UNKNOWN, EntityId, Guid, GuidPrefix,
-            ReaderProxy, ReliabilityKind, SequenceNumber,
+            CacheChange, ChangeKind, CoherentSet, DurabilityKind, ENTITYID_UNKNOWN, EntityId, Guid,
+            GuidPrefix, ReaderProxy, ReliabilityKind, SequenceNumber,
         },
     },
 };
-use alloc::vec::Vec;
+use alloc::{sync::Arc, vec::Vec};
+
+/// Timing attributes of the reliable writers as defined by the RTPS standard
+#[derive(Clone, Copy)]
+pub struct WriterTiming {
+    pub heartbeat_period: Duration,
+    pub nack_response_delay: Duration,
+    pub nack_suppression_duration: Duration,
+}
+
+impl Default for WriterTiming {
+    fn default() -> Self {
+        Self {
+            heartbeat_period: Duration::from_millis(200),
+            nack_response_delay: DURATION_ZERO,
+            nack_suppression_duration: DURATION_ZERO,
+        }
+    }
+}
 
 pub struct RtpsStatefulWriter {
     guid: Guid,
     changes: Vec<CacheChange>,
+    coherent_set_end: Option<CacheChange>,
     matched_readers: Vec<RtpsReaderProxy>,
-    heartbeat_period: Duration,
+    timing: WriterTiming,
     data_max_size_serialized: usize,
+    flow_controller: Option<FlowController>,
+    asynchronous_publication: bool,
 }
 
 impl RtpsStatefulWriter {
-    pub fn new(guid: Guid, data_max_size_serialized: usize) -> Self {
+    pub fn new(guid: Guid, data_max_size_serialized: usize, timing: WriterTiming) -> Self {
         Self {
             guid,
             changes: Vec::new(),
+            coherent_set_end: None,
             matched_readers: Vec::new(),
-            heartbeat_period: Duration::from_millis(200),
+            timing,
             data_max_size_serialized,
+            flow_controller: None,
+            asynchronous_publication: false,
         }
     }
 
@@ -48,6 +76,46 @@ impl RtpsStatefulWriter {
         self.data_max_size_serialized
     }
 
+    /// Pace the DATA and DATA_FRAG submessages with a flow controller. The added changes are
+    /// sent as long as the controller allows it and the rest of them when [`Self::write_message`]
+    /// is called so that the writers sharing the controller can be scheduled.
+    pub fn set_flow_controller(&mut self, flow_controller: Option<FlowController>) {
+        self.flow_controller = flow_controller;
+    }
+
+    pub fn flow_controller(&self) -> Option<&FlowController> {
+        self.flow_controller.as_ref()
+    }
+
+    /// Leave the sending of the added changes to the caller of [`Self::write_message`] instead
+    /// of sending them when they are added.
+    pub fn set_asynchronous_publication(&mut self, asynchronous_publication: bool) {
+        self.asynchronous_publication = asynchronous_publication;
+    }
+
+    pub fn is_asynchronous_publication(&self) -> bool {
+        self.asynchronous_publication
+    }
+
+    fn sends_added_changes(&self) -> bool {
+        !self.asynchronous_publication
+    }
+
+    /// Source timestamp of the oldest change which has not been sent to all the matched readers
+    pub fn oldest_unsent_change_timestamp(&self) -> Option<crate::transport::types::Time> {
+        let history = self.history();
+        self.matched_readers
+            .iter()
+            .filter_map(|rp| rp.next_unsent_change(history.iter()))
+            .filter_map(|sn| {
+                history
+                    .iter()
+                    .find(|cc| cc.sequence_number == sn)
+                    .and_then(|cc| cc.source_timestamp)
+            })
+            .min()
+    }
+
     pub fn add_change(
         &mut self,
         cache_change: CacheChange,
@@ -55,7 +123,9 @@ impl RtpsStatefulWriter {
         clock: &impl Clock,
     ) {
         self.changes.push(cache_change);
-        self.write_message(message_writer, clock);
+        if self.sends_added_changes() {
+            self.write_message(message_writer, clock);
+        }
     }
 
     pub fn remove_change(&mut self, sequence_number: SequenceNumber) {
@@ -63,6 +133,42 @@ impl RtpsStatefulWriter {
             .retain(|cc| cc.sequence_number != sequence_number);
     }
 
+    /// Sends the DATA carrying only the inline QoS which marks the end of a coherent set.
+    /// The marker is not part of the history: it only replaces the marker of the previous
+    /// set so that it can be repaired like any other change.
+    pub fn end_coherent_set(
+        &mut self,
+        sequence_number: SequenceNumber,
+        coherent_set: CoherentSet,
+        message_writer: &(impl WriteMessage + ?Sized),
+        clock: &impl Clock,
+    ) {
+        self.coherent_set_end = Some(CacheChange {
+            coherent_set: Some(CoherentSet {
+                end_of_set: true,
+                ..coherent_set
+            }),
+            ..CacheChange::new(
+                ChangeKind::Alive,
+                self.guid,
+                sequence_number,
+                None,
+                None,
+                Arc::from([]),
+            )
+        });
+        if self.sends_added_changes() {
+            self.write_message(message_writer, clock);
+        }
+    }
+
+    fn history(&self) -> WriterHistory<'_> {
+        WriterHistory {
+            changes: &self.changes,
+            coherent_set_end: self.coherent_set_end.as_ref(),
+        }
+    }
+
     pub fn is_change_acknowledged(&self, sequence_number: SequenceNumber) -> bool {
         !self
             .matched_readers
@@ -74,7 +180,7 @@ impl RtpsStatefulWriter {
     pub fn add_matched_reader(&mut self, reader_proxy: ReaderProxy) {
         let first_relevant_sample_seq_num = match reader_proxy.durability_kind {
             DurabilityKind::Volatile => self
-                .changes
+                .history()
                 .iter()
                 .map(|cc| cc.sequence_number)
                 .max()
@@ -105,6 +211,27 @@ impl RtpsStatefulWriter {
         }
     }
 
+    pub fn matched_reader_content_filter_signature(&self, reader_guid: Guid) -> Option<[u8; 16]> {
+        self.matched_readers
+            .iter()
+            .find(|rp| rp.remote_reader_guid() == reader_guid)
+            .and_then(|rp| rp.content_filter_signature())
+    }
+
+    pub fn set_matched_reader_content_filter(
+        &mut self,
+        reader_guid: Guid,
+        content_filter: Option<ReaderContentFilter>,
+    ) {
+        if let Some(rp) = self
+            .matched_readers
+            .iter_mut()
+            .find(|rp| rp.remote_reader_guid() == reader_guid)
+        {
+            rp.set_content_filter(content_filter);
+        }
+    }
+
     pub fn delete_matched_reader(&mut self, reader_guid: Guid) {
         self.matched_readers
             .retain(|reader_proxy| reader_proxy.remote_reader_guid() != reader_guid);
@@ -115,12 +242,17 @@ impl RtpsStatefulWriter {
         message_writer: &(impl WriteMessage + ?Sized),
         clock: &impl Clock,
     ) {
+        let history = WriterHistory {
+            changes: &self.changes,
+            coherent_set_end: self.coherent_set_end.as_ref(),
+        };
         for reader_proxy in &mut self.matched_readers {
             reader_proxy.write_message(
                 self.guid.entity_id(),
-                &self.changes,
+                history,
                 self.data_max_size_serialized,
-                self.heartbeat_period,
+                self.timing,
+                self.flow_controller.as_ref(),
                 message_writer,
                 clock,
                 self.guid.prefix(),
@@ -150,15 +282,32 @@ impl RtpsStatefulWriter {
                 {
                     let acked_changes = acknack_submessage.reader_sn_state().base() - 1;
                     reader_proxy.acked_changes_set(acked_changes);
-                    reader_proxy.requested_changes_set(acknack_submessage.reader_sn_state().set());
+
+                    let now = clock.now();
+                    let requested_changes: Vec<SequenceNumber> = acknack_submessage
+                        .reader_sn_state()
+                        .set()
+                        .filter(|sn| !reader_proxy.is_request_suppressed(*sn, now))
+                        .collect();
+                    // Further requests do not postpone the response to the pending ones
+                    if reader_proxy.requested_changes().is_empty() {
+                        reader_proxy.set_requested_changes_response_time(
+                            now + self.timing.nack_response_delay.into(),
+                        );
+                    }
+                    reader_proxy.requested_changes_set(requested_changes.into_iter());
 
                     reader_proxy.set_last_received_acknack_count(acknack_submessage.count());
 
                     reader_proxy.write_message_reliable(
                         self.guid.entity_id(),
-                        &self.changes,
+                        WriterHistory {
+                            changes: &self.changes,
+                            coherent_set_end: self.coherent_set_end.as_ref(),
+                        },
                         self.data_max_size_serialized,
-                        self.heartbeat_period,
+                        self.timing,
+                        self.flow_controller.as_ref(),
                         message_writer,
                         clock,
                         self.guid.prefix(),
@@ -175,6 +324,7 @@ impl RtpsStatefulWriter {
         nackfrag_submessage: &NackFragSubmessage,
         source_guid_prefix: GuidPrefix,
         message_writer: &(impl WriteMessage + ?Sized),
+        clock: &impl Clock,
     ) {
         let reader_guid = Guid::new(source_guid_prefix, nackfrag_submessage.reader_id());
 
@@ -230,10 +380,18 @@ impl RtpsStatefulWriter {
                                 &[&info_dst, &info_timestamp, &data_frag],
                                 self.guid.prefix(),
                             );
-                            message_writer.write_message(
-                                rtps_message.buffer(),
-                                reader_proxy.unicast_locator_list(),
-                            )
+                            // The fragments not sent because of the flow controller are
+                            // requested again by the reader
+                            if is_send_allowed(
+                                self.flow_controller.as_ref(),
+                                rtps_message.buffer().len(),
+                                clock.now(),
+                            ) {
+                                message_writer.write_message(
+                                    rtps_message.buffer(),
+                                    reader_proxy.unicast_locator_list(),
+                                )
+                            }
                         }
                     }
                 } else {
@@ -267,14 +425,29 @@ impl RtpsStatefulWriter {
     }
 }
 
+/// The changes in the history of the writer followed by the marker of the end of the last
+/// coherent set, if any.
+#[derive(Clone, Copy)]
+struct WriterHistory<'a> {
+    changes: &'a [CacheChange],
+    coherent_set_end: Option<&'a CacheChange>,
+}
+
+impl<'a> WriterHistory<'a> {
+    fn iter(&self) -> impl Iterator<Item = &'a CacheChange> + 'a {
+        self.changes.iter().chain(self.coherent_set_end)
+    }
+}
+
 impl RtpsReaderProxy {
     #[allow(clippy::too_many_arguments)]
     fn write_message(
         &mut self,
         writer_id: EntityId,
-        changes: &[CacheChange],
+        changes: WriterHistory,
         data_max_size_serialized: usize,
-        heartbeat_period: Duration,
+        timing: WriterTiming,
+        flow_controller: Option<&FlowController>,
         message_writer: &(impl WriteMessage + ?Sized),
         clock: &impl Clock,
         guid_prefix: GuidPrefix,
@@ -284,14 +457,17 @@ impl RtpsReaderProxy {
                 writer_id,
                 changes,
                 data_max_size_serialized,
+                flow_controller,
                 message_writer,
+                clock,
                 guid_prefix,
             ),
             ReliabilityKind::Reliable => self.write_message_reliable(
                 writer_id,
                 changes,
                 data_max_size_serialized,
-                heartbeat_period,
+                timing,
+                flow_controller,
                 message_writer,
                 clock,
                 guid_prefix,
@@ -299,12 +475,15 @@ impl RtpsReaderProxy {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn write_message_best_effort(
         &mut self,
         writer_id: EntityId,
-        changes: &[CacheChange],
+        changes: WriterHistory,
         data_max_size_serialized: usize,
+        flow_controller: Option<&FlowController>,
         message_writer: &(impl WriteMessage + ?Sized),
+        clock: &impl Clock,
         guid_prefix: GuidPrefix,
     ) {
         // a_change_seq_num := the_reader_proxy.next_unsent_change();
@@ -346,10 +525,9 @@ impl RtpsReaderProxy {
                 message_writer.write_message(rtps_message.buffer(), self.unicast_locator_list());
 
                 self.set_highest_sent_seq_num(next_unsent_change_seq_num);
-            } else if let Some(cache_change) = changes
-                .iter()
-                .find(|cc| cc.sequence_number == next_unsent_change_seq_num)
-            {
+            } else if let Some(cache_change) = changes.iter().find(|cc| {
+                cc.sequence_number == next_unsent_change_seq_num && self.is_change_relevant(cc)
+            }) {
                 let number_of_fragments = cache_change
                     .data_value
                     .len()
@@ -364,7 +542,9 @@ impl RtpsReaderProxy {
                 };
                 // Either send a DATAFRAG submessages or send a single DATA submessage
                 if number_of_fragments > 1 {
-                    for fragment_number in 0..number_of_fragments {
+                    for fragment_number in
+                        self.sent_fragment_count(next_unsent_change_seq_num)..number_of_fragments
+                    {
                         let reader_id = self.remote_reader_guid().entity_id();
 
                         let data_frag = cache_change.as_data_frag_submessage(
@@ -377,17 +557,31 @@ impl RtpsReaderProxy {
                             &[&info_dst, &info_timestamp, &data_frag],
                             guid_prefix,
                         );
+                        if !is_send_allowed(
+                            flow_controller,
+                            rtps_message.buffer().len(),
+                            clock.now(),
+                        ) {
+                            self.set_sent_fragment_count(
+                                next_unsent_change_seq_num,
+                                fragment_number,
+                            );
+                            return;
+                        }
                         message_writer
                             .write_message(rtps_message.buffer(), self.unicast_locator_list())
                     }
+                    self.set_sent_fragment_count(next_unsent_change_seq_num, 0);
                 } else {
-                    let data_submessage = cache_change
-                        .as_data_submessage(self.remote_reader_guid().entity_id(), writer_id);
+                    let data_submessage = self.data_submessage(cache_change, writer_id);
 
                     let rtps_message = RtpsMessageWrite::from_submessages(
                         &[&info_dst, &info_timestamp, &data_submessage],
                         guid_prefix,
                     );
+                    if !is_send_allowed(flow_controller, rtps_message.buffer().len(), clock.now()) {
+                        return;
+                    }
                     message_writer.write_message(rtps_message.buffer(), self.unicast_locator_list())
                 }
             } else {
@@ -410,9 +604,10 @@ impl RtpsReaderProxy {
     fn write_message_reliable(
         &mut self,
         writer_id: EntityId,
-        changes: &[CacheChange],
+        changes: WriterHistory,
         data_max_size_serialized: usize,
-        heartbeat_period: Duration,
+        timing: WriterTiming,
+        flow_controller: Option<&FlowController>,
         message_writer: &(impl WriteMessage + ?Sized),
         clock: &impl Clock,
         guid_prefix: GuidPrefix,
@@ -450,6 +645,7 @@ impl RtpsReaderProxy {
                     if let Some(cache_change) = changes.iter().find(|cc| {
                         cc.sequence_number == next_unsent_change_seq_num
                             && next_unsent_change_seq_num > self.first_relevant_sample_seq_num()
+                            && self.is_change_relevant(cc)
                     }) {
                         let number_of_fragments = cache_change
                             .data_value
@@ -458,7 +654,10 @@ impl RtpsReaderProxy {
 
                         // Either send a DATAFRAG submessages or send a single DATA submessage
                         if number_of_fragments > 1 && cache_change.kind == ChangeKind::Alive {
-                            for fragment_number in 0..number_of_fragments {
+                            for fragment_number in self
+                                .sent_fragment_count(next_unsent_change_seq_num)
+                                ..number_of_fragments
+                            {
                                 let reader_id = self.remote_reader_guid().entity_id();
                                 let data_frag = cache_change.as_data_frag_submessage(
                                     reader_id,
@@ -494,11 +693,23 @@ impl RtpsReaderProxy {
                                         guid_prefix,
                                     )
                                 };
+                                if !is_send_allowed(
+                                    flow_controller,
+                                    rtps_message.buffer().len(),
+                                    now,
+                                ) {
+                                    self.set_sent_fragment_count(
+                                        next_unsent_change_seq_num,
+                                        fragment_number,
+                                    );
+                                    return;
+                                }
                                 message_writer.write_message(
                                     rtps_message.buffer(),
                                     self.unicast_locator_list(),
                                 )
                             }
+                            self.set_sent_fragment_count(next_unsent_change_seq_num, 0);
                         } else {
                             let info_dst =
                                 InfoDestinationSubmessage::new(self.remote_reader_guid().prefix());
@@ -510,10 +721,7 @@ impl RtpsReaderProxy {
                                     InfoTimestampSubmessage::new(true, TIME_INVALID)
                                 };
 
-                            let data_submessage = cache_change.as_data_submessage(
-                                self.remote_reader_guid().entity_id(),
-                                writer_id,
-                            );
+                            let data_submessage = self.data_submessage(cache_change, writer_id);
 
                             let first_sn = seq_num_min.unwrap_or(1);
                             let last_sn = seq_num_max.unwrap_or(0);
@@ -525,6 +733,9 @@ impl RtpsReaderProxy {
                                 &[&info_dst, &info_timestamp, &data_submessage, &heartbeat],
                                 guid_prefix,
                             );
+                            if !is_send_allowed(flow_controller, rtps_message.buffer().len(), now) {
+                                return;
+                            }
                             message_writer
                                 .write_message(rtps_message.buffer(), self.unicast_locator_list())
                         }
@@ -548,12 +759,17 @@ impl RtpsReaderProxy {
                     }
                 }
                 self.set_highest_sent_seq_num(next_unsent_change_seq_num);
+                self.suppress_requests(
+                    next_unsent_change_seq_num,
+                    now,
+                    timing.nack_suppression_duration.into(),
+                );
             }
         } else if !self.unacked_changes(seq_num_max) {
             // Idle
         } else if self
             .heartbeat_machine()
-            .is_time_for_heartbeat(now, heartbeat_period.into())
+            .is_time_for_heartbeat(now, timing.heartbeat_period.into())
         {
             let first_sn = seq_num_min.unwrap_or(1);
             let last_sn = seq_num_max.unwrap_or(0);
@@ -571,7 +787,7 @@ impl RtpsReaderProxy {
         }
 
         // Middle-part of the state-machine - Figure 8.19 RTPS standard
-        if !self.requested_changes().is_empty() {
+        if !self.requested_changes().is_empty() && self.is_time_for_requested_changes(now) {
             while let Some(next_requested_change_seq_num) = self.next_requested_change() {
                 // "a_change.status := UNDERWAY;" should be done by next_requested_change() as
                 // it's not done here to avoid the change being a mutable reference
@@ -584,6 +800,7 @@ impl RtpsReaderProxy {
                 if let Some(cache_change) = changes.iter().find(|cc| {
                     cc.sequence_number == next_requested_change_seq_num
                         && next_requested_change_seq_num > self.first_relevant_sample_seq_num()
+                        && self.is_change_relevant(cc)
                 }) {
                     let number_of_fragments = cache_change
                         .data_value
@@ -619,6 +836,12 @@ impl RtpsReaderProxy {
                             &[&info_dst, &info_timestamp, &data_frag, &heartbeat],
                             guid_prefix,
                         );
+                        if !is_send_allowed(flow_controller, rtps_message.buffer().len(), now) {
+                            self.requested_changes_set(core::iter::once(
+                                next_requested_change_seq_num,
+                            ));
+                            return;
+                        }
                         message_writer
                             .write_message(rtps_message.buffer(), self.unicast_locator_list());
                     } else {
@@ -632,8 +855,7 @@ impl RtpsReaderProxy {
                             InfoTimestampSubmessage::new(true, TIME_INVALID)
                         };
 
-                        let data_submessage = cache_change
-                            .as_data_submessage(self.remote_reader_guid().entity_id(), writer_id);
+                        let data_submessage = self.data_submessage(cache_change, writer_id);
 
                         let first_sn = seq_num_min.unwrap_or(1);
                         let last_sn = seq_num_max.unwrap_or(0);
@@ -645,6 +867,12 @@ impl RtpsReaderProxy {
                             &[&info_dst, &info_timestamp, &data_submessage, &heartbeat],
                             guid_prefix,
                         );
+                        if !is_send_allowed(flow_controller, rtps_message.buffer().len(), now) {
+                            self.requested_changes_set(core::iter::once(
+                                next_requested_change_seq_num,
+                            ));
+                            return;
+                        }
                         message_writer
                             .write_message(rtps_message.buffer(), self.unicast_locator_list());
                     }
@@ -666,6 +894,11 @@ impl RtpsReaderProxy {
                     message_writer
                         .write_message(rtps_message.buffer(), self.unicast_locator_list());
                 }
+                self.suppress_requests(
+                    next_requested_change_seq_num,
+                    now,
+                    timing.nack_suppression_duration.into(),
+                );
             }
         }
     }
@@ -685,35 +918,101 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    fn test_all_fragments_sent() {
-        struct MockClock {}
-        impl Clock for MockClock {
-            fn now(&self) -> crate::infrastructure::time::Time {
-                Time::new(1, 0)
+    /// Clock whose time is set by the test
+    struct MockClock {
+        now: Mutex<Time>,
+    }
+
+    impl MockClock {
+        fn new(now: Time) -> Self {
+            Self {
+                now: Mutex::new(now),
             }
         }
-        struct MockWriter {
-            total_fragments_sent: Mutex<usize>,
+
+        fn set(&self, now: Time) {
+            *self.now.lock().unwrap() = now;
         }
-        impl WriteMessage for MockWriter {
-            fn write_message(
-                &self,
-                datagram: &[u8],
-                _locator_list: &[crate::transport::types::Locator],
-            ) {
-                let message = RtpsMessageRead::try_from(datagram).unwrap();
-                assert!(matches!(
-                    message.submessages()[2],
-                    RtpsSubmessageReadKind::DataFrag(_)
-                ));
-                *self.total_fragments_sent.lock().unwrap() += 1;
-            }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Time {
+            *self.now.lock().unwrap()
+        }
+    }
+
+    /// Message writer keeping the messages written so that the tests can inspect their submessages
+    #[derive(Default)]
+    struct MockWriter {
+        messages: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl WriteMessage for MockWriter {
+        fn write_message(
+            &self,
+            datagram: &[u8],
+            _locator_list: &[crate::transport::types::Locator],
+        ) {
+            self.messages.lock().unwrap().push(datagram.to_vec());
+        }
+    }
+
+    impl MockWriter {
+        fn submessages<T>(&self, f: impl Fn(&RtpsSubmessageReadKind) -> Option<T>) -> Vec<T> {
+            self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .flat_map(|datagram| {
+                    let message = RtpsMessageRead::try_from(datagram.as_slice()).unwrap();
+                    message
+                        .submessages()
+                        .iter()
+                        .filter_map(&f)
+                        .collect::<Vec<_>>()
+                })
+                .collect()
         }
 
+        fn data_sent(&self) -> Vec<SequenceNumber> {
+            self.submessages(|submessage| match submessage {
+                RtpsSubmessageReadKind::Data(data) => Some(data.writer_sn()),
+                _ => None,
+            })
+        }
+
+        fn changes_sent(&self) -> Vec<CacheChange> {
+            self.submessages(|submessage| match submessage {
+                RtpsSubmessageReadKind::Data(data) => {
+                    Some(CacheChange::try_from_data_submessage(data, [1; 12], None).unwrap())
+                }
+                _ => None,
+            })
+        }
+
+        fn data_frags_sent(&self) -> usize {
+            self.submessages(|submessage| match submessage {
+                RtpsSubmessageReadKind::DataFrag(_) => Some(()),
+                _ => None,
+            })
+            .len()
+        }
+
+        fn gaps_sent(&self) -> Vec<SequenceNumber> {
+            self.submessages(|submessage| match submessage {
+                RtpsSubmessageReadKind::Gap(gap) => Some(gap.gap_start()),
+                _ => None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_all_fragments_sent() {
+        let clock = MockClock::new(Time::new(1, 0));
         let data_max_size_serialized = 500;
         let guid = Guid::new([1; 12], EntityId::new([1; 3], 1));
-        let mut writer = RtpsStatefulWriter::new(guid, data_max_size_serialized);
+        let mut writer =
+            RtpsStatefulWriter::new(guid, data_max_size_serialized, WriterTiming::default());
 
         let remote_reader_guid = Guid::new([2; 12], EntityId::new([2; 3], 2));
         writer.add_matched_reader(ReaderProxy {
@@ -726,9 +1025,7 @@ mod tests {
             expects_inline_qos: false,
         });
 
-        let message_writer = MockWriter {
-            total_fragments_sent: Mutex::new(0),
-        };
+        let message_writer = MockWriter::default();
         writer.add_change(
             CacheChange {
                 kind: ChangeKind::Alive,
@@ -737,43 +1034,24 @@ mod tests {
                 source_timestamp: None,
                 instance_handle: Some([10; 16]),
                 data_value: vec![8; 1300].into(),
+                filter_signatures: vec![],
+                coherent_set: None,
+                group_sequence_number: None,
             },
             &message_writer,
-            &MockClock {},
+            &clock,
         );
-        assert_eq!(*message_writer.total_fragments_sent.lock().unwrap(), 3);
+        assert_eq!(message_writer.data_frags_sent(), 3);
     }
 
     #[test]
     fn test_single_fragment_sent_after_acknack_frag() {
-        struct MockClock {}
-        impl Clock for MockClock {
-            fn now(&self) -> crate::infrastructure::time::Time {
-                Time::new(1, 0)
-            }
-        }
-        struct MockWriter {
-            total_fragments_sent: Mutex<usize>,
-        }
-        impl WriteMessage for MockWriter {
-            fn write_message(
-                &self,
-                datagram: &[u8],
-                _locator_list: &[crate::transport::types::Locator],
-            ) {
-                let message = RtpsMessageRead::try_from(datagram).unwrap();
-                assert!(matches!(
-                    message.submessages()[2],
-                    RtpsSubmessageReadKind::DataFrag(_)
-                ));
-                *self.total_fragments_sent.lock().unwrap() += 1;
-            }
-        }
-
+        let clock = MockClock::new(Time::new(1, 0));
         let data_max_size_serialized = 500;
         let writer_id = EntityId::new([1; 3], 1);
         let guid = Guid::new([1; 12], writer_id);
-        let mut writer = RtpsStatefulWriter::new(guid, data_max_size_serialized);
+        let mut writer =
+            RtpsStatefulWriter::new(guid, data_max_size_serialized, WriterTiming::default());
 
         let remote_reader_id = EntityId::new([2; 3], 2);
         let remote_reader_guid_prefix = [2; 12];
@@ -787,9 +1065,7 @@ mod tests {
             multicast_locator_list: vec![],
             expects_inline_qos: false,
         });
-        let message_writer = MockWriter {
-            total_fragments_sent: Mutex::new(0),
-        };
+        let message_writer = MockWriter::default();
         writer.add_change(
             CacheChange {
                 kind: ChangeKind::Alive,
@@ -798,9 +1074,12 @@ mod tests {
                 source_timestamp: None,
                 instance_handle: Some([10; 16]),
                 data_value: vec![8; 1300].into(),
+                filter_signatures: vec![],
+                coherent_set: None,
+                group_sequence_number: None,
             },
             &message_writer,
-            &MockClock {},
+            &clock,
         );
 
         let nackfrag_submessage = NackFragSubmessage::new(
@@ -810,15 +1089,324 @@ mod tests {
             FragmentNumberSet::new(1, []),
             1,
         );
-        let message_writer = MockWriter {
-            total_fragments_sent: Mutex::new(0),
-        };
+        let message_writer = MockWriter::default();
         writer.on_nack_frag_submessage_received(
             &nackfrag_submessage,
             remote_reader_guid_prefix,
             &message_writer,
+            &clock,
+        );
+
+        assert_eq!(message_writer.data_frags_sent(), 1);
+    }
+
+    #[test]
+    fn test_change_rejected_by_reader_content_filter_sent_as_gap() {
+        let clock = MockClock::new(Time::new(1, 0));
+        let writer_id = EntityId::new([1; 3], 1);
+        let guid = Guid::new([1; 12], writer_id);
+        let mut writer = RtpsStatefulWriter::new(guid, 500, WriterTiming::default());
+
+        let remote_reader_guid = Guid::new([2; 12], EntityId::new([2; 3], 2));
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid,
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+        let filter_signature = [7; 16];
+        writer.set_matched_reader_content_filter(
+            remote_reader_guid,
+            Some(ReaderContentFilter::new(filter_signature, |cache_change| {
+                cache_change.data_value[4] > 10
+            })),
+        );
+
+        let message_writer = MockWriter::default();
+        for (sequence_number, value) in [(1, 5), (2, 20)] {
+            writer.add_change(
+                CacheChange {
+                    kind: ChangeKind::Alive,
+                    writer_guid: guid,
+                    sequence_number,
+                    source_timestamp: None,
+                    instance_handle: Some([10; 16]),
+                    data_value: vec![0, 1, 0, 0, value].into(),
+                    filter_signatures: vec![],
+                    coherent_set: None,
+                    group_sequence_number: None,
+                },
+                &message_writer,
+                &clock,
+            );
+        }
+
+        assert_eq!(message_writer.gaps_sent(), vec![1]);
+        let data_sent = message_writer.changes_sent();
+        assert_eq!(data_sent.len(), 1);
+        assert_eq!(data_sent[0].sequence_number, 2);
+        assert_eq!(data_sent[0].filter_signatures, vec![filter_signature]);
+    }
+    #[test]
+    fn coherent_set_end_is_sent_without_being_stored_in_history() {
+        let clock = MockClock::new(Time::new(1, 0));
+        let writer_id = EntityId::new([1; 3], 1);
+        let guid = Guid::new([1; 12], writer_id);
+        let mut writer = RtpsStatefulWriter::new(guid, 500, WriterTiming::default());
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([2; 3], 2)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+
+        let message_writer = MockWriter::default();
+        let coherent_set = CoherentSet {
+            sequence_number: 1,
+            group_sequence_number: None,
+            writer_set: None,
+            end_of_set: false,
+        };
+        writer.add_change(
+            CacheChange {
+                coherent_set: Some(coherent_set),
+                ..CacheChange::new(
+                    ChangeKind::Alive,
+                    guid,
+                    1,
+                    None,
+                    Some([10; 16]),
+                    vec![0, 1, 0, 0, 5].into(),
+                )
+            },
+            &message_writer,
+            &clock,
+        );
+        writer.end_coherent_set(2, coherent_set, &message_writer, &clock);
+
+        assert_eq!(writer.changes().len(), 1);
+        let data_sent = message_writer.changes_sent();
+        assert_eq!(data_sent.len(), 2);
+        assert_eq!(data_sent[1].sequence_number, 2);
+        assert!(data_sent[1].is_end_of_coherent_set());
+        assert!(data_sent[1].data_value.is_empty());
+    }
+
+    #[test]
+    fn asynchronous_writer_sends_added_changes_only_when_written() {
+        let clock = MockClock::new(Time::new(1, 0));
+        let guid = Guid::new([1; 12], EntityId::new([1; 3], 1));
+        let mut writer = RtpsStatefulWriter::new(guid, 500, WriterTiming::default());
+        writer.set_asynchronous_publication(true);
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([2; 3], 2)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+
+        let message_writer = MockWriter::default();
+        for sequence_number in [1, 2] {
+            writer.add_change(
+                CacheChange::new(
+                    ChangeKind::Alive,
+                    guid,
+                    sequence_number,
+                    None,
+                    Some([10; 16]),
+                    vec![0, 1, 0, 0, 5].into(),
+                ),
+                &message_writer,
+                &clock,
+            );
+        }
+        assert!(message_writer.data_sent().is_empty());
+
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_sent(), vec![1, 2]);
+    }
+
+    #[test]
+    fn flow_controlled_fragments_are_sent_when_the_bucket_is_refilled() {
+        let guid = Guid::new([1; 12], EntityId::new([1; 3], 1));
+        let mut writer = RtpsStatefulWriter::new(guid, 500, WriterTiming::default());
+        // Room for two of the three fragments in each period
+        writer.set_flow_controller(Some(FlowController::new(
+            1200,
+            crate::infrastructure::time::Duration::new(1, 0),
+        )));
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid: Guid::new([2; 12], EntityId::new([2; 3], 2)),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+
+        let clock = MockClock::new(Time::new(1, 0));
+        let message_writer = MockWriter::default();
+        writer.add_change(
+            CacheChange::new(
+                ChangeKind::Alive,
+                guid,
+                1,
+                None,
+                Some([10; 16]),
+                vec![8; 1300].into(),
+            ),
+            &message_writer,
+            &clock,
+        );
+        assert_eq!(message_writer.data_frags_sent(), 2);
+
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_frags_sent(), 2);
+
+        clock.set(Time::new(1, 500_000_000));
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_frags_sent(), 2);
+
+        clock.set(Time::new(2, 0));
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_frags_sent(), 3);
+    }
+
+    #[test]
+    fn requested_changes_are_repaired_after_the_nack_response_delay() {
+        let writer_id = EntityId::new([1; 3], 1);
+        let guid = Guid::new([1; 12], writer_id);
+        let timing = WriterTiming {
+            nack_response_delay: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let mut writer = RtpsStatefulWriter::new(guid, 500, timing);
+        let remote_reader_id = EntityId::new([2; 3], 2);
+        let remote_reader_guid_prefix = [2; 12];
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid: Guid::new(remote_reader_guid_prefix, remote_reader_id),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+
+        let clock = MockClock::new(Time::new(1, 0));
+        let message_writer = MockWriter::default();
+        writer.add_change(
+            CacheChange::new(
+                ChangeKind::Alive,
+                guid,
+                1,
+                None,
+                Some([10; 16]),
+                vec![0, 1, 0, 0, 5].into(),
+            ),
+            &message_writer,
+            &clock,
+        );
+        let acknack_submessage = AckNackSubmessage::new(
+            false,
+            remote_reader_id,
+            writer_id,
+            SequenceNumberSet::new(1, [1]),
+            1,
+        );
+        writer.on_acknack_submessage_received(
+            &acknack_submessage,
+            remote_reader_guid_prefix,
+            &message_writer,
+            &clock,
         );
+        assert_eq!(message_writer.data_sent(), vec![1]);
+
+        clock.set(Time::new(1, 50_000_000));
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_sent(), vec![1]);
 
-        assert_eq!(*message_writer.total_fragments_sent.lock().unwrap(), 1);
+        clock.set(Time::new(1, 100_000_000));
+        writer.write_message(&message_writer, &clock);
+        assert_eq!(message_writer.data_sent(), vec![1, 1]);
+    }
+
+    #[test]
+    fn requests_within_the_nack_suppression_duration_are_ignored() {
+        let writer_id = EntityId::new([1; 3], 1);
+        let guid = Guid::new([1; 12], writer_id);
+        let timing = WriterTiming {
+            nack_suppression_duration: Duration::from_millis(100),
+            ..Default::default()
+        };
+        let mut writer = RtpsStatefulWriter::new(guid, 500, timing);
+        let remote_reader_id = EntityId::new([2; 3], 2);
+        let remote_reader_guid_prefix = [2; 12];
+        writer.add_matched_reader(ReaderProxy {
+            remote_reader_guid: Guid::new(remote_reader_guid_prefix, remote_reader_id),
+            remote_group_entity_id: ENTITYID_UNKNOWN,
+            reliability_kind: ReliabilityKind::Reliable,
+            durability_kind: DurabilityKind::Volatile,
+            unicast_locator_list: vec![],
+            multicast_locator_list: vec![],
+            expects_inline_qos: false,
+        });
+
+        let clock = MockClock::new(Time::new(1, 0));
+        let message_writer = MockWriter::default();
+        writer.add_change(
+            CacheChange::new(
+                ChangeKind::Alive,
+                guid,
+                1,
+                None,
+                Some([10; 16]),
+                vec![0, 1, 0, 0, 5].into(),
+            ),
+            &message_writer,
+            &clock,
+        );
+
+        clock.set(Time::new(1, 50_000_000));
+        let acknack_submessage = AckNackSubmessage::new(
+            false,
+            remote_reader_id,
+            writer_id,
+            SequenceNumberSet::new(1, [1]),
+            1,
+        );
+        writer.on_acknack_submessage_received(
+            &acknack_submessage,
+            remote_reader_guid_prefix,
+            &message_writer,
+            &clock,
+        );
+        assert_eq!(message_writer.data_sent(), vec![1]);
+
+        clock.set(Time::new(1, 200_000_000));
+        let acknack_submessage = AckNackSubmessage::new(
+            false,
+            remote_reader_id,
+            writer_id,
+            SequenceNumberSet::new(1, [1]),
+            2,
+        );
+        writer.on_acknack_submessage_received(
+            &acknack_submessage,
+            remote_reader_guid_prefix,
+            &message_writer,
+            &clock,
+        );
+        assert_eq!(message_writer.data_sent(), vec![1, 1]);
     }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    infrastructure::time::Time,
     rtps_messages::{
         overall_structure::RtpsMessageWrite,
         submessage_elements::{Data, FragmentNumberSet, SequenceNumberSet},
@@ -38,6 +39,7 @@ pub struct RtpsWriterProxy {
     last_available_seq_num: SequenceNumber,
     highest_received_change_sn: SequenceNumber,
     must_send_acknacks: bool,
+    acknack_response_time: Time,
     last_received_heartbeat_count: Count,
     last_received_heartbeat_frag_count: Count,
     acknack_count: Count,
@@ -63,6 +65,7 @@ impl RtpsWriterProxy {
             last_available_seq_num: 0,
             highest_received_change_sn: 0,
             must_send_acknacks: false,
+            acknack_response_time: Time::new(0, 0),
             last_received_heartbeat_count: 0,
             last_received_heartbeat_frag_count: 0,
             acknack_count: 0,
@@ -125,20 +128,24 @@ impl RtpsWriterProxy {
             let reader_id = frag.reader_id();
             let writer_sn = seq_num;
             let inline_qos = frag.inline_qos().clone();
+            let inline_qos_endianness = frag.inline_qos_endianness();
 
             self.frag_buffer.retain(|f| f.writer_sn() != seq_num);
 
-            Some(DataSubmessage::new(
-                inline_qos_flag,
-                data_flag,
-                key_flag,
-                non_standard_payload_flag,
-                reader_id,
-                writer_id,
-                writer_sn,
-                inline_qos,
-                Data::new(Arc::from(data)),
-            ))
+            Some(
+                DataSubmessage::new(
+                    inline_qos_flag,
+                    data_flag,
+                    key_flag,
+                    non_standard_payload_flag,
+                    reader_id,
+                    writer_id,
+                    writer_sn,
+                    inline_qos,
+                    Data::new(Arc::from(data)),
+                )
+                .with_inline_qos_endianness(inline_qos_endianness),
+            )
         } else {
             None
         }
@@ -148,6 +155,14 @@ impl RtpsWriterProxy {
         self.remote_writer_guid
     }
 
+    /// GUID of the publisher the remote writer belongs to
+    pub fn remote_group_guid(&self) -> Guid {
+        Guid::new(
+            self.remote_writer_guid.prefix(),
+            self.remote_group_entity_id,
+        )
+    }
+
     pub fn unicast_locator_list(&self) -> &[Locator] {
         self.unicast_locator_list.as_ref()
     }
@@ -233,6 +248,15 @@ impl RtpsWriterProxy {
         self.must_send_acknacks
     }
 
+    /// Send an AckNack from the response time on. Further heartbeats received before it is sent
+    /// do not postpone it.
+    pub fn schedule_acknack(&mut self, response_time: Time) {
+        if !self.must_send_acknacks {
+            self.must_send_acknacks = true;
+            self.acknack_response_time = response_time;
+        }
+    }
+
     pub fn last_received_heartbeat_count(&self) -> Count {
         self.last_received_heartbeat_count
     }
@@ -260,8 +284,11 @@ impl RtpsWriterProxy {
         &mut self,
         reader_guid: &Guid,
         message_writer: &(impl WriteMessage + ?Sized),
+        now: Time,
     ) {
-        if self.must_send_acknacks() || !self.missing_changes().count() == 0 {
+        if (self.must_send_acknacks() || !self.missing_changes().count() == 0)
+            && now >= self.acknack_response_time
+        {
             self.set_must_send_acknacks(false);
             self.increment_acknack_count();
 
@@ -0,0 +1,519 @@
+use crate::{
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+    },
+    std_runtime::{self},
+    transport::{
+        interface::{
+            RtpsTransportParticipant, TransportDataReceiver, TransportParticipantFactory,
+            WriteMessage,
+        },
+        types::{LOCATOR_KIND_LOOPBACK, Locator},
+    },
+};
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+const METATRAFFIC_UNICAST_PORT: u32 = 1;
+const DEFAULT_UNICAST_PORT: u32 = 2;
+const MULTICAST_ADDRESS: [u8; 16] = [0; 16];
+
+const PB: i32 = 7400;
+const DG: i32 = 250;
+fn port_builtin_multicast(domain_id: i32) -> u32 {
+    let d0 = 0;
+    (PB + DG * domain_id + d0) as u32
+}
+
+/// Faults injected in the messages sent to a locator of the [`LoopbackTransportParticipantFactory`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopbackFaults {
+    /// Probability between 0 and 1 of a message being dropped.
+    pub loss_probability: f64,
+    /// Probability between 0 and 1 of a message being delivered twice.
+    pub duplication_probability: f64,
+    /// Probability between 0 and 1 of a message being delivered after the reordering
+    /// delay so that the messages sent shortly after it overtake it.
+    pub reordering_probability: f64,
+    /// Additional delay of the reordered messages.
+    pub reordering_delay: Duration,
+    /// Delay of all the messages.
+    pub delay: Duration,
+}
+
+impl Default for LoopbackFaults {
+    fn default() -> Self {
+        Self {
+            loss_probability: 0.0,
+            duplication_probability: 0.0,
+            reordering_probability: 0.0,
+            reordering_delay: Duration::from_millis(10),
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+struct Endpoint {
+    domain_id: i32,
+    address: [u8; 16],
+    data_receiver: TransportDataReceiver,
+}
+
+struct ScheduledMessage {
+    /// Time since the creation of the network at which the message is delivered
+    delivery_time: Duration,
+    sequence_number: u64,
+    data_receiver: TransportDataReceiver,
+    message: Vec<u8>,
+}
+
+impl PartialEq for ScheduledMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScheduledMessage {}
+
+impl PartialOrd for ScheduledMessage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScheduledMessage {
+    // Reversed so that the binary heap returns the earliest message first
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.delivery_time, other.sequence_number)
+            .cmp(&(self.delivery_time, self.sequence_number))
+    }
+}
+
+struct NetworkState {
+    endpoint_list: Vec<Endpoint>,
+    default_faults: LoopbackFaults,
+    locator_faults: Vec<(Locator, LoopbackFaults)>,
+    random_state: u64,
+    message_queue: BinaryHeap<ScheduledMessage>,
+    sequence_number: u64,
+    is_delivery_thread_started: bool,
+    /// Time since the creation of the network when it is advanced manually instead of
+    /// following the system clock
+    manual_time: Option<Duration>,
+}
+
+impl NetworkState {
+    /// Uniformly distributed number in [0, 1) from a xorshift64* generator
+    fn random(&mut self) -> f64 {
+        let mut x = self.random_state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.random_state = x;
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn faults(&self, locator: &Locator) -> LoopbackFaults {
+        self.locator_faults
+            .iter()
+            .find(|(l, _)| l == locator)
+            .map_or(self.default_faults, |(_, faults)| *faults)
+    }
+
+    fn pop_due_message(&mut self, now: Duration) -> Option<ScheduledMessage> {
+        if self
+            .message_queue
+            .peek()
+            .is_some_and(|m| m.delivery_time <= now)
+        {
+            self.message_queue.pop()
+        } else {
+            None
+        }
+    }
+}
+
+struct LoopbackNetwork {
+    state: Mutex<NetworkState>,
+    message_available: Condvar,
+    start_instant: Instant,
+}
+
+impl LoopbackNetwork {
+    fn state(&self) -> MutexGuard<'_, NetworkState> {
+        self.state.lock().expect("Loopback network lock poisoned")
+    }
+
+    fn now(&self, state: &NetworkState) -> Duration {
+        state
+            .manual_time
+            .unwrap_or_else(|| self.start_instant.elapsed())
+    }
+
+    fn start_delivery_thread(self: &Arc<Self>, state: &mut NetworkState) {
+        if state.manual_time.is_none() && !state.is_delivery_thread_started {
+            state.is_delivery_thread_started = true;
+            let network = self.clone();
+            std::thread::Builder::new()
+                .name("LoopbackDelivery".to_string())
+                .spawn(move || network.deliver_messages())
+                .expect("failed to spawn thread");
+        }
+    }
+
+    /// Delivers the messages as they become due until the last participant is deleted
+    fn deliver_messages(&self) {
+        let mut state = self.state();
+        loop {
+            if state.endpoint_list.is_empty() {
+                // The thread is started again when a new participant is created
+                state.message_queue.clear();
+                state.is_delivery_thread_started = false;
+                return;
+            }
+            let now = self.now(&state);
+            if let Some(message) = state.pop_due_message(now) {
+                drop(state);
+                std_runtime::executor::block_on(
+                    message.data_receiver.receive_message(message.message),
+                );
+                state = self.state();
+            } else if let Some(m) = state.message_queue.peek() {
+                let timeout = m.delivery_time - now;
+                state = self
+                    .message_available
+                    .wait_timeout(state, timeout)
+                    .expect("Loopback network lock poisoned")
+                    .0;
+            } else {
+                state = self
+                    .message_available
+                    .wait(state)
+                    .expect("Loopback network lock poisoned");
+            }
+        }
+    }
+}
+
+/// Transport delivering the RTPS messages between the participants created by the same
+/// factory through in-memory queues, meant for testing.
+///
+/// Faults can be injected on the messages sent to any of the locators of the participants,
+/// which are obtained from the participant instance handle with [`Self::default_unicast_locator`]
+/// and [`Self::metatraffic_unicast_locator`], or to the discovery locator of a domain obtained
+/// with [`Self::metatraffic_multicast_locator`]. The faults are chosen with a pseudo-random
+/// generator with a configurable seed so that the same messages get the same faults when
+/// they are sent in the same order. The factory can be cloned to change the faults after
+/// it has been given to the domain participant factory.
+///
+/// The messages are delivered by a background thread which runs while there are participants
+/// using the factory. A factory created with [`Self::with_manual_time`] instead delivers the
+/// messages only when its time is advanced with [`Self::advance_time`], so that the delivery
+/// does not depend on the system clock nor on the scheduling of the threads.
+#[derive(Clone)]
+pub struct LoopbackTransportParticipantFactory {
+    network: Arc<LoopbackNetwork>,
+    fragment_size: usize,
+}
+
+impl LoopbackTransportParticipantFactory {
+    /// Create a factory whose messages are delivered only when [`Self::advance_time`] is called
+    pub fn with_manual_time() -> Self {
+        let factory = Self::default();
+        factory.network.state().manual_time = Some(Duration::ZERO);
+        factory
+    }
+
+    /// Advance the time of a factory created with [`Self::with_manual_time`] and deliver, from the
+    /// calling thread, the messages which are due in the meantime in the order of their delivery time.
+    /// Returns [`DdsError::PreconditionNotMet`] if the factory follows the system clock.
+    pub fn advance_time(&self, duration: Duration) -> DdsResult<()> {
+        let mut state = self.network.state();
+        let end_time = state.manual_time.ok_or(DdsError::PreconditionNotMet(
+            "Time of the loopback transport is not manual".to_string(),
+        ))? + duration;
+        while let Some(message) = state.pop_due_message(end_time) {
+            state.manual_time = state.manual_time.max(Some(message.delivery_time));
+            drop(state);
+            std_runtime::executor::block_on(message.data_receiver.receive_message(message.message));
+            state = self.network.state();
+        }
+        state.manual_time = Some(end_time);
+        Ok(())
+    }
+
+    /// Get the locator on which a participant receives the user data sent to it
+    pub fn default_unicast_locator(participant_handle: InstanceHandle) -> Locator {
+        Locator::new(
+            LOCATOR_KIND_LOOPBACK,
+            DEFAULT_UNICAST_PORT,
+            participant_handle.into(),
+        )
+    }
+
+    /// Get the locator on which a participant receives the discovery data sent to it
+    pub fn metatraffic_unicast_locator(participant_handle: InstanceHandle) -> Locator {
+        Locator::new(
+            LOCATOR_KIND_LOOPBACK,
+            METATRAFFIC_UNICAST_PORT,
+            participant_handle.into(),
+        )
+    }
+
+    /// Get the locator on which all the participants of a domain receive the participant announcements
+    pub fn metatraffic_multicast_locator(domain_id: i32) -> Locator {
+        Locator::new(
+            LOCATOR_KIND_LOOPBACK,
+            port_builtin_multicast(domain_id),
+            MULTICAST_ADDRESS,
+        )
+    }
+
+    /// Set the fragment size in a range between 8 to 65000. This value is the maximum size of the payload
+    /// transmitted in a single RTPS data submessage. Sizes larger than this value will be transmitted in
+    /// separate message using RTPS data fragments
+    pub fn set_fragment_size(&mut self, fragment_size: usize) -> DdsResult<&mut Self> {
+        if !(8..=65000).contains(&fragment_size) {
+            return Err(DdsError::BadParameter);
+        }
+        self.fragment_size = fragment_size;
+        Ok(self)
+    }
+
+    /// Set the seed of the pseudo-random generator choosing the faults of the messages
+    pub fn set_seed(&self, seed: u64) -> &Self {
+        // The xorshift generator never leaves the zero state
+        self.network.state().random_state = seed.max(1);
+        self
+    }
+
+    /// Set the faults of the messages sent to the locators without specific faults
+    pub fn set_default_faults(&self, faults: LoopbackFaults) -> &Self {
+        self.network.state().default_faults = faults;
+        self
+    }
+
+    /// Set the faults of the messages sent to a locator
+    pub fn set_locator_faults(&self, locator: Locator, faults: LoopbackFaults) -> &Self {
+        let mut state = self.network.state();
+        state.locator_faults.retain(|(l, _)| l != &locator);
+        state.locator_faults.push((locator, faults));
+        self
+    }
+
+    /// Remove all the faults so that messages are delivered immediately and only once
+    pub fn clear_faults(&self) -> &Self {
+        let mut state = self.network.state();
+        state.default_faults = LoopbackFaults::default();
+        state.locator_faults.clear();
+        self
+    }
+
+    /// Get the value of the currently configured fragment size
+    pub fn fragment_size(&self) -> usize {
+        self.fragment_size
+    }
+}
+
+impl Default for LoopbackTransportParticipantFactory {
+    fn default() -> Self {
+        Self {
+            network: Arc::new(LoopbackNetwork {
+                state: Mutex::new(NetworkState {
+                    endpoint_list: Vec::new(),
+                    default_faults: LoopbackFaults::default(),
+                    locator_faults: Vec::new(),
+                    random_state: 1,
+                    message_queue: BinaryHeap::new(),
+                    sequence_number: 0,
+                    is_delivery_thread_started: false,
+                    manual_time: None,
+                }),
+                message_available: Condvar::new(),
+                start_instant: Instant::now(),
+            }),
+            fragment_size: 1344,
+        }
+    }
+}
+
+impl TransportParticipantFactory for LoopbackTransportParticipantFactory {
+    fn create_participant(
+        &self,
+        domain_id: i32,
+        data_channel_sender: TransportDataReceiver,
+    ) -> RtpsTransportParticipant {
+        let participant_handle = data_channel_sender.participant_handle();
+        let mut state = self.network.state();
+        // Messages are never delivered from the sending thread since it might be the one
+        // which has to process them
+        self.network.start_delivery_thread(&mut state);
+        state.endpoint_list.push(Endpoint {
+            domain_id,
+            address: participant_handle.into(),
+            data_receiver: data_channel_sender,
+        });
+
+        RtpsTransportParticipant {
+            message_writer: Box::new(MessageWriter {
+                network: self.network.clone(),
+                address: participant_handle.into(),
+            }),
+            default_unicast_locator_list: vec![Self::default_unicast_locator(participant_handle)],
+            metatraffic_unicast_locator_list: vec![Self::metatraffic_unicast_locator(
+                participant_handle,
+            )],
+            metatraffic_multicast_locator_list: vec![Self::metatraffic_multicast_locator(
+                domain_id,
+            )],
+            default_multicast_locator_list: Vec::new(),
+            initial_peer_locator_list: Vec::new(),
+            fragment_size: self.fragment_size,
+        }
+    }
+}
+
+struct MessageWriter {
+    network: Arc<LoopbackNetwork>,
+    address: [u8; 16],
+}
+
+impl WriteMessage for MessageWriter {
+    fn write_message(&self, datagram: &[u8], locator_list: &[Locator]) {
+        let mut state = self.network.state();
+        let now = self.network.now(&state);
+        for locator in locator_list
+            .iter()
+            .filter(|l| l.kind() == LOCATOR_KIND_LOOPBACK)
+        {
+            let faults = state.faults(locator);
+            let data_receiver_list: Vec<_> = state
+                .endpoint_list
+                .iter()
+                .filter(|e| {
+                    if locator.address() == MULTICAST_ADDRESS {
+                        locator.port() == port_builtin_multicast(e.domain_id)
+                    } else {
+                        locator.address() == e.address
+                            && [METATRAFFIC_UNICAST_PORT, DEFAULT_UNICAST_PORT]
+                                .contains(&locator.port())
+                    }
+                })
+                .map(|e| e.data_receiver.clone())
+                .collect();
+
+            for data_receiver in data_receiver_list {
+                if state.random() < faults.loss_probability {
+                    continue;
+                }
+                let copies = if state.random() < faults.duplication_probability {
+                    2
+                } else {
+                    1
+                };
+                for _ in 0..copies {
+                    let mut delay = faults.delay;
+                    if state.random() < faults.reordering_probability {
+                        delay += faults.reordering_delay;
+                    }
+                    state.sequence_number += 1;
+                    let sequence_number = state.sequence_number;
+                    state.message_queue.push(ScheduledMessage {
+                        delivery_time: now + delay,
+                        sequence_number,
+                        data_receiver: data_receiver.clone(),
+                        message: datagram.to_vec(),
+                    });
+                }
+            }
+        }
+        self.network.message_available.notify_one();
+    }
+}
+
+impl Drop for MessageWriter {
+    fn drop(&mut self) {
+        self.network
+            .state()
+            .endpoint_list
+            .retain(|e| e.address != self.address);
+        // Wake up the delivery thread so that it stops if this was the last participant
+        self.network.message_available.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dcps::dcps_mail::{DcpsMail, MessageServiceMail},
+        dds_async::domain_participant_factory::DcpsChannel,
+    };
+
+    fn data_receiver(channel: &'static DcpsChannel, participant: u8) -> TransportDataReceiver {
+        TransportDataReceiver::new(InstanceHandle::new([participant; 16]), channel.sender())
+    }
+
+    #[test]
+    fn delivery_thread_stops_when_the_last_participant_is_deleted() {
+        static CHANNEL: DcpsChannel = DcpsChannel::new();
+        let factory = LoopbackTransportParticipantFactory::default();
+        let participant1 = factory.create_participant(0, data_receiver(&CHANNEL, 1));
+        let participant2 = factory.create_participant(0, data_receiver(&CHANNEL, 2));
+        assert_eq!(Arc::strong_count(&factory.network), 4);
+
+        drop(participant1);
+        drop(participant2);
+        let start = Instant::now();
+        while Arc::strong_count(&factory.network) > 1 {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!factory.network.state().is_delivery_thread_started);
+    }
+
+    #[test]
+    fn messages_are_delivered_when_the_manual_time_is_advanced() {
+        static CHANNEL: DcpsChannel = DcpsChannel::new();
+        let factory = LoopbackTransportParticipantFactory::with_manual_time();
+        factory.set_default_faults(LoopbackFaults {
+            delay: Duration::from_millis(10),
+            ..Default::default()
+        });
+        let sender = factory.create_participant(0, data_receiver(&CHANNEL, 1));
+        let _receiver = factory.create_participant(0, data_receiver(&CHANNEL, 2));
+        assert!(!factory.network.state().is_delivery_thread_started);
+
+        sender.message_writer.write_message(
+            &[1, 2, 3],
+            &[
+                LoopbackTransportParticipantFactory::default_unicast_locator(InstanceHandle::new(
+                    [2; 16],
+                )),
+            ],
+        );
+        factory.advance_time(Duration::from_millis(5)).unwrap();
+        assert!(CHANNEL.try_receive().is_err());
+
+        factory.advance_time(Duration::from_millis(5)).unwrap();
+        assert!(matches!(
+            CHANNEL.try_receive(),
+            Ok(DcpsMail::Message(MessageServiceMail::HandleData {
+                participant_handle,
+                data_message,
+            })) if participant_handle == InstanceHandle::new([2; 16]) && data_message == [1, 2, 3]
+        ));
+        assert!(CHANNEL.try_receive().is_err());
+    }
+
+    #[test]
+    fn time_of_the_system_clock_can_not_be_advanced() {
+        let factory = LoopbackTransportParticipantFactory::default();
+        assert!(factory.advance_time(Duration::from_millis(5)).is_err());
+    }
+}
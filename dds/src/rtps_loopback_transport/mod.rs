@@ -0,0 +1 @@
+pub mod loopback_transport;
@@ -19,6 +19,7 @@ use super::{
 };
 use alloc::{sync::Arc, vec::Vec};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Endianness {
     BigEndian,
     LittleEndian,
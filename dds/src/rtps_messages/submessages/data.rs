@@ -1,8 +1,8 @@
 use super::super::{
     error::{RtpsMessageError, RtpsMessageResult},
     overall_structure::{
-        Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes, Write,
-        WriteIntoBytes,
+        Endianness, Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
+        Write, WriteIntoBytes,
     },
     submessage_elements::{Data, ParameterList},
     types::{SubmessageFlag, SubmessageKind},
@@ -19,6 +19,7 @@ pub struct DataSubmessage {
     writer_id: EntityId,
     writer_sn: SequenceNumber,
     inline_qos: ParameterList,
+    inline_qos_endianness: Endianness,
     serialized_payload: Data,
 }
 
@@ -78,6 +79,7 @@ impl DataSubmessage {
             writer_id,
             writer_sn,
             inline_qos,
+            inline_qos_endianness: *endianness,
             serialized_payload,
         })
     }
@@ -103,10 +105,17 @@ impl DataSubmessage {
             writer_id,
             writer_sn,
             inline_qos,
+            inline_qos_endianness: Endianness::LittleEndian,
             serialized_payload,
         }
     }
 
+    /// Sets the byte order of the inline QoS, for a submessage reassembled from received fragments.
+    pub fn with_inline_qos_endianness(mut self, inline_qos_endianness: Endianness) -> Self {
+        self.inline_qos_endianness = inline_qos_endianness;
+        self
+    }
+
     pub fn _inline_qos_flag(&self) -> bool {
         self.inline_qos_flag
     }
@@ -139,6 +148,11 @@ impl DataSubmessage {
         &self.inline_qos
     }
 
+    /// Byte order of the values of the inline QoS parameters, which is the one of the submessage.
+    pub fn inline_qos_endianness(&self) -> Endianness {
+        self.inline_qos_endianness
+    }
+
     pub fn serialized_payload(&self) -> &Data {
         &self.serialized_payload
     }
@@ -3,8 +3,8 @@ use crate::transport::types::{EntityId, SequenceNumber};
 use super::super::{
     error::{RtpsMessageError, RtpsMessageResult},
     overall_structure::{
-        Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes, Write,
-        WriteIntoBytes,
+        Endianness, Submessage, SubmessageHeaderRead, SubmessageHeaderWrite, TryReadFromBytes,
+        Write, WriteIntoBytes,
     },
     submessage_elements::{ParameterList, SerializedDataFragment},
     types::{FragmentNumber, SubmessageFlag, SubmessageKind},
@@ -22,6 +22,7 @@ pub struct DataFragSubmessage {
     fragment_size: u16,
     data_size: u32,
     inline_qos: ParameterList,
+    inline_qos_endianness: Endianness,
     serialized_payload: SerializedDataFragment,
 }
 
@@ -84,6 +85,7 @@ impl DataFragSubmessage {
                 fragment_size,
                 data_size,
                 inline_qos,
+                inline_qos_endianness: *endianness,
                 serialized_payload,
             })
         } else {
@@ -135,6 +137,11 @@ impl DataFragSubmessage {
         &self.inline_qos
     }
 
+    /// Byte order of the values of the inline QoS parameters, which is the one of the submessage.
+    pub fn inline_qos_endianness(&self) -> Endianness {
+        self.inline_qos_endianness
+    }
+
     pub fn serialized_payload(&self) -> &SerializedDataFragment {
         &self.serialized_payload
     }
@@ -168,6 +175,7 @@ impl DataFragSubmessage {
             fragment_size,
             data_size,
             inline_qos,
+            inline_qos_endianness: Endianness::LittleEndian,
             serialized_payload,
         }
     }
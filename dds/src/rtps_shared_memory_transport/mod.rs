@@ -0,0 +1,2 @@
+mod ring_buffer;
+pub mod shared_memory_transport;
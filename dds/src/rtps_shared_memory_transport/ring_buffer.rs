@@ -0,0 +1,389 @@
+#![allow(unsafe_code)]
+//! Ring buffers mapped into the address space of the processes exchanging messages.
+//!
+//! All the accesses to the mapped memory are made through raw pointers in this module
+//! since the memory is concurrently modified by other processes.
+
+use memmap2::MmapRaw;
+use std::{
+    fs::OpenOptions,
+    path::Path,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+};
+
+// The ring starts with a header holding the capacity of the data, the read and write
+// positions, the lock of the writers, the notification counter and the state of the reader.
+// The positions only grow and are taken modulo the capacity to index the data. Many
+// processes write to a ring, taking turns with the lock, and its owner is the only reader.
+const CAPACITY_OFFSET: usize = 0;
+const READ_POSITION_OFFSET: usize = 8;
+const WRITE_POSITION_OFFSET: usize = 16;
+const WRITER_LOCK_OFFSET: usize = 24;
+const NOTIFICATION_OFFSET: usize = 32;
+const IS_READER_WAITING_OFFSET: usize = 36;
+const IS_CLOSED_OFFSET: usize = 40;
+const DATA_OFFSET: usize = 64;
+const MESSAGE_LENGTH_SIZE: u64 = 4;
+
+// Number of attempts to take the lock of the writers after which the liveness of the
+// process holding it is checked
+const LOCK_ATTEMPTS_BEFORE_OWNER_CHECK: u32 = 1000;
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const POLL_PERIOD: std::time::Duration = std::time::Duration::from_millis(1);
+
+pub fn is_process_alive(process_id: u32) -> bool {
+    // Without a process file system the state of other processes is unknown
+    !Path::new("/proc/self").exists() || Path::new(&format!("/proc/{process_id}")).exists()
+}
+
+/// Ring buffer in a file mapped in shared memory
+pub struct RingBuffer {
+    map: MmapRaw,
+    capacity: u64,
+}
+
+impl RingBuffer {
+    /// Creates the ring buffer owned by the reader
+    pub fn create(path: &Path, capacity: u64) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(DATA_OFFSET as u64 + capacity)?;
+        let ring_buffer = Self {
+            map: MmapRaw::map_raw(&file)?,
+            capacity,
+        };
+        // The capacity is written last since the writers ignore rings without one
+        ring_buffer
+            .atomic_u64(CAPACITY_OFFSET)
+            .store(capacity, Ordering::Release);
+        Ok(ring_buffer)
+    }
+
+    /// Opens the ring buffer of another participant for writing
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let length = file.metadata()?.len();
+        if length < DATA_OFFSET as u64 {
+            return Err(std::io::ErrorKind::InvalidData.into());
+        }
+        let mut ring_buffer = Self {
+            map: MmapRaw::map_raw(&file)?,
+            capacity: 0,
+        };
+        ring_buffer.capacity = ring_buffer
+            .atomic_u64(CAPACITY_OFFSET)
+            .load(Ordering::Acquire);
+        if ring_buffer.capacity == 0 || DATA_OFFSET as u64 + ring_buffer.capacity != length {
+            return Err(std::io::ErrorKind::InvalidData.into());
+        }
+        Ok(ring_buffer)
+    }
+
+    fn atomic_u64(&self, offset: usize) -> &AtomicU64 {
+        debug_assert!(offset + 8 <= DATA_OFFSET && offset.is_multiple_of(8));
+        // SAFETY: The offset is within the header of the mapping, which is page aligned and
+        // lives as long as the returned reference. The header words are only ever accessed
+        // atomically, by this and the other processes.
+        unsafe { AtomicU64::from_ptr(self.map.as_mut_ptr().add(offset).cast()) }
+    }
+
+    fn atomic_u32(&self, offset: usize) -> &AtomicU32 {
+        debug_assert!(offset + 4 <= DATA_OFFSET && offset.is_multiple_of(4));
+        // SAFETY: As for the atomic_u64 header words
+        unsafe { AtomicU32::from_ptr(self.map.as_mut_ptr().add(offset).cast()) }
+    }
+
+    /// Copies the bytes to the data at the position, wrapping around its end
+    fn write_data(&self, position: u64, buf: &[u8]) {
+        let offset = (position % self.capacity) as usize;
+        let (first, second) = buf.split_at(buf.len().min(self.capacity as usize - offset));
+        // SAFETY: Both copies stay within the data of the mapping. The writer holds the lock
+        // and the positions make sure the reader does not access this range until it is
+        // published by updating the write position.
+        unsafe {
+            let data = self.map.as_mut_ptr().add(DATA_OFFSET);
+            core::ptr::copy_nonoverlapping(first.as_ptr(), data.add(offset), first.len());
+            core::ptr::copy_nonoverlapping(second.as_ptr(), data, second.len());
+        }
+    }
+
+    /// Copies the bytes of the data at the position, wrapping around its end
+    fn read_data(&self, position: u64, buf: &mut [u8]) {
+        let offset = (position % self.capacity) as usize;
+        let (first, second) = buf.split_at_mut(buf.len().min(self.capacity as usize - offset));
+        // SAFETY: Both copies stay within the data of the mapping. The range was published by
+        // a writer and the writers do not access it again until the read position moves on.
+        unsafe {
+            let data = self.map.as_ptr().add(DATA_OFFSET);
+            core::ptr::copy_nonoverlapping(data.add(offset), first.as_mut_ptr(), first.len());
+            core::ptr::copy_nonoverlapping(data, second.as_mut_ptr(), second.len());
+        }
+    }
+
+    fn lock_writers(&self, writer_id: u64) {
+        let writer_lock = self.atomic_u64(WRITER_LOCK_OFFSET);
+        let mut attempts: u32 = 0;
+        loop {
+            match writer_lock.compare_exchange_weak(
+                0,
+                writer_id,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(0) => (),
+                Err(owner_id) => {
+                    attempts += 1;
+                    // The process of the owner terminated while writing, leaving the message
+                    // unpublished, so the lock can be taken over
+                    if attempts.is_multiple_of(LOCK_ATTEMPTS_BEFORE_OWNER_CHECK)
+                        && !is_process_alive((owner_id >> 32) as u32)
+                        && writer_lock
+                            .compare_exchange(
+                                owner_id,
+                                writer_id,
+                                Ordering::Acquire,
+                                Ordering::Relaxed,
+                            )
+                            .is_ok()
+                    {
+                        return;
+                    }
+                    std::thread::yield_now();
+                }
+            }
+        }
+    }
+
+    /// Writes the message if it fits in the free space. The writer id must be unique to
+    /// each writer and have the ID of its process in the upper 32 bits.
+    pub fn write(&self, message: &[u8], writer_id: u64) -> std::io::Result<()> {
+        let message_size = MESSAGE_LENGTH_SIZE + message.len() as u64;
+        if message_size > self.capacity {
+            return Err(std::io::ErrorKind::InvalidInput.into());
+        }
+        self.lock_writers(writer_id);
+        let read_position = self
+            .atomic_u64(READ_POSITION_OFFSET)
+            .load(Ordering::Acquire);
+        let write_position = self
+            .atomic_u64(WRITE_POSITION_OFFSET)
+            .load(Ordering::Relaxed);
+        let used_space = write_position.wrapping_sub(read_position);
+        let result = if used_space > self.capacity {
+            Err(std::io::ErrorKind::InvalidData.into())
+        } else if message_size > self.capacity - used_space {
+            Err(std::io::ErrorKind::WouldBlock.into())
+        } else {
+            self.write_data(write_position, &(message.len() as u32).to_le_bytes());
+            self.write_data(write_position + MESSAGE_LENGTH_SIZE, message);
+            // The message is only visible to the reader once the write position is updated
+            self.atomic_u64(WRITE_POSITION_OFFSET)
+                .store(write_position + message_size, Ordering::Release);
+            Ok(())
+        };
+        self.atomic_u64(WRITER_LOCK_OFFSET)
+            .store(0, Ordering::Release);
+        if result.is_ok() {
+            self.notify(false);
+        }
+        result
+    }
+
+    /// Reads the next message. Only the owner of the ring reads from it.
+    pub fn read(&self) -> Option<Vec<u8>> {
+        let read_position = self
+            .atomic_u64(READ_POSITION_OFFSET)
+            .load(Ordering::Relaxed);
+        let write_position = self
+            .atomic_u64(WRITE_POSITION_OFFSET)
+            .load(Ordering::Acquire);
+        let available_size = write_position.wrapping_sub(read_position);
+        if available_size == 0 {
+            return None;
+        }
+        let mut length = [0; MESSAGE_LENGTH_SIZE as usize];
+        if available_size <= self.capacity && available_size >= MESSAGE_LENGTH_SIZE {
+            self.read_data(read_position, &mut length);
+        }
+        let message_size = MESSAGE_LENGTH_SIZE + u32::from_le_bytes(length) as u64;
+        if available_size > self.capacity || message_size > available_size {
+            // Not a valid message so everything written so far is discarded
+            self.atomic_u64(READ_POSITION_OFFSET)
+                .store(write_position, Ordering::Release);
+            return None;
+        }
+        let mut message = vec![0; (message_size - MESSAGE_LENGTH_SIZE) as usize];
+        self.read_data(read_position + MESSAGE_LENGTH_SIZE, &mut message);
+        self.atomic_u64(READ_POSITION_OFFSET)
+            .store(read_position + message_size, Ordering::Release);
+        Some(message)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.atomic_u64(READ_POSITION_OFFSET)
+            .load(Ordering::Relaxed)
+            == self
+                .atomic_u64(WRITE_POSITION_OFFSET)
+                .load(Ordering::Acquire)
+    }
+
+    /// Blocks the reader until a message is written or the ring is closed. It can also
+    /// return before, so the reader must check the ring again.
+    pub fn wait(&self) {
+        let is_reader_waiting = self.atomic_u32(IS_READER_WAITING_OFFSET);
+        is_reader_waiting.store(1, Ordering::SeqCst);
+        let notification = self.atomic_u32(NOTIFICATION_OFFSET).load(Ordering::SeqCst);
+        if self.is_empty() && !self.is_closed() {
+            self.wait_notification(notification);
+        }
+        is_reader_waiting.store(0, Ordering::SeqCst);
+    }
+
+    fn notify(&self, is_forced: bool) {
+        self.atomic_u32(NOTIFICATION_OFFSET)
+            .fetch_add(1, Ordering::SeqCst);
+        if is_forced
+            || self
+                .atomic_u32(IS_READER_WAITING_OFFSET)
+                .load(Ordering::SeqCst)
+                != 0
+        {
+            self.wake_reader();
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn wait_notification(&self, notification: u32) {
+        // The futex is not private to the process since the other processes wake it
+        // SAFETY: The address is an aligned u32 within the mapping and the other arguments
+        // are the ones expected by FUTEX_WAIT without a timeout.
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                self.atomic_u32(NOTIFICATION_OFFSET).as_ptr(),
+                libc::FUTEX_WAIT,
+                notification,
+                core::ptr::null::<libc::timespec>(),
+            );
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn wake_reader(&self) {
+        // SAFETY: The address is an aligned u32 within the mapping and the other arguments
+        // are the ones expected by FUTEX_WAKE.
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                self.atomic_u32(NOTIFICATION_OFFSET).as_ptr(),
+                libc::FUTEX_WAKE,
+                1,
+            );
+        }
+    }
+
+    // Other systems have no waiting primitive shared between processes on any memory
+    // address so the reader checks the ring periodically
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn wait_notification(&self, _notification: u32) {
+        std::thread::sleep(POLL_PERIOD);
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn wake_reader(&self) {}
+
+    /// Marks the ring as no longer read, waking up its reader
+    pub fn close(&self) {
+        self.atomic_u32(IS_CLOSED_OFFSET).store(1, Ordering::SeqCst);
+        self.notify(true);
+    }
+
+    /// Whether the owner of the ring no longer reads from it
+    pub fn is_closed(&self) -> bool {
+        self.atomic_u32(IS_CLOSED_OFFSET).load(Ordering::SeqCst) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, time::Duration};
+
+    fn ring_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("dust_dds_ring_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn messages_wrap_around_the_end_of_the_data() {
+        let path = ring_path("wrap");
+        let reader = RingBuffer::create(&path, 32).unwrap();
+        let writer = RingBuffer::open(&path).unwrap();
+        let writer_id = (std::process::id() as u64) << 32 | 1;
+
+        for message in [[1; 10], [2; 10], [3; 10], [4; 10]] {
+            writer.write(&message, writer_id).unwrap();
+            assert_eq!(reader.read(), Some(message.to_vec()));
+        }
+        writer.write(&[5; 10], writer_id).unwrap();
+        writer.write(&[6; 10], writer_id).unwrap();
+        assert_eq!(
+            writer.write(&[7; 10], writer_id).unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+        assert_eq!(reader.read(), Some(vec![5; 10]));
+        assert_eq!(reader.read(), Some(vec![6; 10]));
+        assert_eq!(reader.read(), None);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn lock_of_a_terminated_writer_is_taken_over() {
+        let path = ring_path("lock");
+        let reader = RingBuffer::create(&path, 32).unwrap();
+        let writer = RingBuffer::open(&path).unwrap();
+        // No process has the maximum ID
+        writer
+            .atomic_u64(WRITER_LOCK_OFFSET)
+            .store((u32::MAX as u64) << 32, Ordering::Release);
+
+        writer
+            .write(&[1; 4], (std::process::id() as u64) << 32 | 1)
+            .unwrap();
+
+        assert_eq!(reader.read(), Some(vec![1; 4]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn waiting_reader_is_woken_up_by_a_write() {
+        let path = ring_path("wait");
+        let reader = Arc::new(RingBuffer::create(&path, 64).unwrap());
+        let writer = RingBuffer::open(&path).unwrap();
+        let reader_thread = std::thread::spawn({
+            let reader = reader.clone();
+            move || {
+                loop {
+                    if let Some(message) = reader.read() {
+                        return message;
+                    }
+                    reader.wait();
+                }
+            }
+        });
+
+        std::thread::sleep(Duration::from_millis(50));
+        writer
+            .write(&[1, 2, 3], (std::process::id() as u64) << 32 | 1)
+            .unwrap();
+
+        assert_eq!(reader_thread.join().unwrap(), vec![1, 2, 3]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,507 @@
+use super::ring_buffer::{RingBuffer, is_process_alive};
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    std_runtime::{self},
+    transport::{
+        interface::{
+            RtpsTransportParticipant, TransportDataReceiver, TransportParticipantFactory,
+            WriteMessage,
+        },
+        types::{LOCATOR_KIND_SHM, Locator},
+    },
+};
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+use tracing::info;
+
+// Every participant owns a ring buffer file for each of its unicast locators to which
+// the other participants write their messages. The participants of a domain register
+// their metatraffic ring in the domain directory to receive the messages sent to the
+// metatraffic multicast locator.
+
+const PB: i32 = 7400;
+const DG: i32 = 250;
+fn port_builtin_multicast(domain_id: i32) -> u32 {
+    let d0 = 0;
+    (PB + DG * domain_id + d0) as u32
+}
+
+static SEGMENT_COUNTER: AtomicU32 = AtomicU32::new(1);
+
+/// Identifies the machine so that locators of participants on other hosts are never
+/// resolved to a local segment. It is stored in the first 12 octets of the locator address.
+fn host_identifier() -> [u8; 12] {
+    let host = ["/proc/sys/kernel/random/boot_id", "/etc/machine-id"]
+        .into_iter()
+        .find_map(|path| std::fs::read(path).ok())
+        .or_else(|| {
+            std::env::var("COMPUTERNAME")
+                .or_else(|_| std::env::var("HOSTNAME"))
+                .ok()
+                .map(String::into_bytes)
+        })
+        .unwrap_or_default();
+    let digest = <[u8; 16]>::from(md5::compute(host));
+    let mut host_identifier = [0; 12];
+    host_identifier.copy_from_slice(&digest[..12]);
+    host_identifier
+}
+
+fn segment_name(process_id: u32, port: u32) -> String {
+    format!("{process_id}_{port}")
+}
+
+fn domain_directory(directory: &Path, port: u32) -> PathBuf {
+    directory.join(format!("domain_{port}"))
+}
+
+fn process_id_of(file_name: &str) -> Option<u32> {
+    file_name.split('_').next()?.parse().ok()
+}
+
+/// Removes the segments and domain registrations left behind by processes which
+/// terminated without deleting their participants.
+fn remove_stale_segments(directory: &Path) {
+    let is_stale = |path: &Path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(process_id_of)
+            .is_some_and(|process_id| !is_process_alive(process_id))
+    };
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if let Ok(entries) = std::fs::read_dir(&path) {
+            for path in entries.flatten().map(|e| e.path()) {
+                if is_stale(&path) {
+                    std::fs::remove_file(&path).ok();
+                }
+            }
+        } else if is_stale(&path) {
+            std::fs::remove_file(&path).ok();
+        }
+    }
+}
+
+fn spawn_receive_thread(
+    name: &str,
+    ring_buffer: Arc<RingBuffer>,
+    data_channel_sender: TransportDataReceiver,
+) {
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            loop {
+                while let Some(message) = ring_buffer.read() {
+                    std_runtime::executor::block_on(data_channel_sender.receive_message(message));
+                }
+                if ring_buffer.is_closed() {
+                    break;
+                }
+                ring_buffer.wait();
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+/// Transport exchanging the RTPS messages of participants running on the same host
+/// through ring buffers in shared memory.
+///
+/// The segments are files in a memory backed file system (`/dev/shm` when available)
+/// mapped into the address space of the participants. On Linux the receiving threads
+/// sleep on a futex in the segment until a message is written, on other systems they
+/// check their segments periodically. Participants sharing the segment directory must
+/// also share the process ID namespace.
+///
+/// A network transport can be combined with it using [`Self::set_network_transport`].
+/// Messages are then sent through shared memory to the shared memory locators and through
+/// the network transport to all the other locators. The readers discard the messages
+/// received twice from participants reachable both ways.
+pub struct RtpsSharedMemoryTransportParticipantFactory {
+    directory: PathBuf,
+    segment_size: usize,
+    fragment_size: usize,
+    network_transport: Option<Box<dyn TransportParticipantFactory>>,
+}
+
+impl RtpsSharedMemoryTransportParticipantFactory {
+    /// Set the directory in which the shared memory segments are created. It should be
+    /// in a memory backed file system to avoid the messages being written to disk.
+    pub fn set_directory(&mut self, directory: PathBuf) -> &mut Self {
+        self.directory = directory;
+        self
+    }
+
+    /// Set the size in bytes of the ring buffer in which each participant receives the
+    /// messages of all the others. It must be able to hold at least two messages with a full
+    /// fragment. Messages which do not fit in the free space of the ring buffer are dropped.
+    pub fn set_segment_size(&mut self, segment_size: usize) -> DdsResult<&mut Self> {
+        if segment_size < 2 * self.fragment_size {
+            return Err(DdsError::BadParameter);
+        }
+        self.segment_size = segment_size;
+        Ok(self)
+    }
+
+    /// Set the fragment size in a range between 8 to half of the segment size. This value
+    /// is the maximum size of the payload transmitted in a single RTPS data submessage.
+    /// When a network transport is combined the smallest of both fragment sizes is used.
+    pub fn set_fragment_size(&mut self, fragment_size: usize) -> DdsResult<&mut Self> {
+        if !(8..=self.segment_size / 2).contains(&fragment_size) {
+            return Err(DdsError::BadParameter);
+        }
+        self.fragment_size = fragment_size;
+        Ok(self)
+    }
+
+    /// Set the transport used to communicate with the participants which can not be
+    /// reached through shared memory, for example an [`RtpsUdpTransportParticipantFactory`](crate::rtps_udp_transport::udp_transport::RtpsUdpTransportParticipantFactory).
+    pub fn set_network_transport(
+        &mut self,
+        network_transport: impl TransportParticipantFactory,
+    ) -> &mut Self {
+        self.network_transport = Some(Box::new(network_transport));
+        self
+    }
+
+    /// Get the directory in which the shared memory segments are created
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the value of the currently configured segment size
+    pub fn segment_size(&self) -> usize {
+        self.segment_size
+    }
+
+    /// Get the value of the currently configured fragment size
+    pub fn fragment_size(&self) -> usize {
+        self.fragment_size
+    }
+}
+
+impl Default for RtpsSharedMemoryTransportParticipantFactory {
+    fn default() -> Self {
+        let shared_memory_directory = Path::new("/dev/shm");
+        let directory = if shared_memory_directory.is_dir() {
+            shared_memory_directory.to_path_buf()
+        } else {
+            std::env::temp_dir()
+        };
+        Self {
+            directory: directory.join("dust_dds"),
+            segment_size: 1024 * 1024,
+            fragment_size: 64000,
+            network_transport: None,
+        }
+    }
+}
+
+impl TransportParticipantFactory for RtpsSharedMemoryTransportParticipantFactory {
+    fn create_participant(
+        &self,
+        domain_id: i32,
+        data_channel_sender: TransportDataReceiver,
+    ) -> RtpsTransportParticipant {
+        std::fs::create_dir_all(&self.directory).expect("Could not create segment directory");
+        remove_stale_segments(&self.directory);
+
+        let host_identifier = host_identifier();
+        let process_id = std::process::id();
+        let mut owned_ring_list = Vec::new();
+        let mut owned_path_list = Vec::new();
+        let mut create_segment = |name: &str| {
+            let port = SEGMENT_COUNTER.fetch_add(1, Ordering::Relaxed);
+            let segment_path = self.directory.join(segment_name(process_id, port));
+            let ring_buffer = Arc::new(
+                RingBuffer::create(&segment_path, self.segment_size as u64)
+                    .expect("Could not create segment"),
+            );
+            owned_path_list.push(segment_path);
+            owned_ring_list.push(ring_buffer.clone());
+            spawn_receive_thread(name, ring_buffer, data_channel_sender.clone());
+            let mut address = [0; 16];
+            address[..12].copy_from_slice(&host_identifier);
+            address[12..].copy_from_slice(&process_id.to_be_bytes());
+            Locator::new(LOCATOR_KIND_SHM, port, address)
+        };
+        let default_unicast_locator = create_segment("SomethingOnDefaultSharedMemory");
+        let metatraffic_unicast_locator = create_segment("SomethingOnMetatrafficSharedMemory");
+
+        // The metatraffic multicast locator is delivered to the metatraffic unicast segments
+        // of all the participants registered in the domain directory
+        let metatraffic_multicast_port = port_builtin_multicast(domain_id);
+        let domain_directory = domain_directory(&self.directory, metatraffic_multicast_port);
+        std::fs::create_dir_all(&domain_directory).expect("Could not create domain directory");
+        let domain_registration =
+            domain_directory.join(segment_name(process_id, metatraffic_unicast_locator.port()));
+        File::create(&domain_registration).expect("Could not register in domain");
+        owned_path_list.push(domain_registration);
+        let mut multicast_address = [0; 16];
+        multicast_address[..12].copy_from_slice(&host_identifier);
+        let metatraffic_multicast_locator = Locator::new(
+            LOCATOR_KIND_SHM,
+            metatraffic_multicast_port,
+            multicast_address,
+        );
+
+        let mut default_unicast_locator_list = vec![default_unicast_locator];
+        let mut metatraffic_unicast_locator_list = vec![metatraffic_unicast_locator];
+        let mut metatraffic_multicast_locator_list = vec![metatraffic_multicast_locator];
+        let mut default_multicast_locator_list = Vec::new();
+        let mut initial_peer_locator_list = Vec::new();
+        let mut fragment_size = self.fragment_size;
+        let network_message_writer = self.network_transport.as_ref().map(|network_transport| {
+            let network_participant =
+                network_transport.create_participant(domain_id, data_channel_sender.clone());
+            default_unicast_locator_list.extend(network_participant.default_unicast_locator_list);
+            metatraffic_unicast_locator_list
+                .extend(network_participant.metatraffic_unicast_locator_list);
+            metatraffic_multicast_locator_list
+                .extend(network_participant.metatraffic_multicast_locator_list);
+            default_multicast_locator_list
+                .extend(network_participant.default_multicast_locator_list);
+            initial_peer_locator_list.extend(network_participant.initial_peer_locator_list);
+            fragment_size = fragment_size.min(network_participant.fragment_size);
+            network_participant.message_writer
+        });
+
+        let message_writer = MessageWriter {
+            directory: self.directory.clone(),
+            host_identifier,
+            writer_id: (process_id as u64) << 32
+                | SEGMENT_COUNTER.fetch_add(1, Ordering::Relaxed) as u64,
+            ring_buffer_list: Mutex::new(HashMap::new()),
+            owned_ring_list,
+            owned_path_list,
+            network_message_writer,
+        };
+
+        RtpsTransportParticipant {
+            message_writer: Box::new(message_writer),
+            default_unicast_locator_list,
+            metatraffic_unicast_locator_list,
+            metatraffic_multicast_locator_list,
+            default_multicast_locator_list,
+            initial_peer_locator_list,
+            fragment_size,
+        }
+    }
+}
+
+/// Ring buffers of the segments of the other participants indexed by name or None when
+/// they could not be opened
+type RingBufferList = HashMap<String, Option<Arc<RingBuffer>>>;
+
+struct MessageWriter {
+    directory: PathBuf,
+    host_identifier: [u8; 12],
+    writer_id: u64,
+    ring_buffer_list: Mutex<RingBufferList>,
+    owned_ring_list: Vec<Arc<RingBuffer>>,
+    owned_path_list: Vec<PathBuf>,
+    network_message_writer: Option<Box<dyn WriteMessage + Send + Sync>>,
+}
+
+impl MessageWriter {
+    /// Only the participant announcements are sent to the metatraffic multicast locator so
+    /// the segments of the domain are discovered again, and the segments which could not
+    /// be opened before are retried, each time a participant is announced.
+    fn discover_domain_segments(
+        &self,
+        ring_buffer_list: &mut RingBufferList,
+        port: u32,
+    ) -> Vec<String> {
+        ring_buffer_list
+            .retain(|_, ring_buffer| ring_buffer.as_ref().is_some_and(|r| !r.is_closed()));
+        std::fs::read_dir(domain_directory(&self.directory, port))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn segment_name_list(
+        &self,
+        ring_buffer_list: &mut RingBufferList,
+        locator: &Locator,
+    ) -> Vec<String> {
+        let address = locator.address();
+        if address[..12] != self.host_identifier {
+            return Vec::new();
+        }
+        let process_id = u32::from_be_bytes([address[12], address[13], address[14], address[15]]);
+        if process_id == 0 {
+            self.discover_domain_segments(ring_buffer_list, locator.port())
+        } else {
+            vec![segment_name(process_id, locator.port())]
+        }
+    }
+
+    fn ring_buffer(
+        &self,
+        ring_buffer_list: &mut RingBufferList,
+        segment_name: String,
+    ) -> Option<Arc<RingBuffer>> {
+        ring_buffer_list
+            .entry(segment_name)
+            .or_insert_with_key(|segment_name| {
+                let segment_path = self.directory.join(segment_name);
+                RingBuffer::open(&segment_path)
+                    .inspect_err(|e| {
+                        info!("Failed to open segment {:?} with error {}", segment_path, e)
+                    })
+                    .ok()
+                    .map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+impl WriteMessage for MessageWriter {
+    fn write_message(&self, datagram: &[u8], locator_list: &[Locator]) {
+        let (shared_memory_locator_list, network_locator_list): (Vec<Locator>, Vec<Locator>) =
+            locator_list
+                .iter()
+                .partition(|l| l.kind() == LOCATOR_KIND_SHM);
+
+        let mut destination_list: Vec<Arc<RingBuffer>> = Vec::new();
+        {
+            let mut ring_buffer_list = self
+                .ring_buffer_list
+                .lock()
+                .expect("Ring buffer list lock poisoned");
+            for locator in &shared_memory_locator_list {
+                for segment_name in self.segment_name_list(&mut ring_buffer_list, locator) {
+                    if let Some(ring_buffer) = self.ring_buffer(&mut ring_buffer_list, segment_name)
+                    {
+                        if !destination_list
+                            .iter()
+                            .any(|r| Arc::ptr_eq(r, &ring_buffer))
+                        {
+                            destination_list.push(ring_buffer);
+                        }
+                    }
+                }
+            }
+        }
+        // The messages are written outside of the lock of the ring buffer list. Messages
+        // which do not fit in a ring buffer are dropped as they would be by a network.
+        for ring_buffer in destination_list {
+            if let Err(e) = ring_buffer.write(datagram, self.writer_id) {
+                info!("Failed to write to ring buffer with error {}", e);
+            }
+        }
+
+        if let Some(network_message_writer) = &self.network_message_writer {
+            if !network_locator_list.is_empty() {
+                network_message_writer.write_message(datagram, &network_locator_list);
+            }
+        }
+    }
+}
+
+impl Drop for MessageWriter {
+    fn drop(&mut self) {
+        for ring_buffer in &self.owned_ring_list {
+            ring_buffer.close();
+        }
+        for path in &self.owned_path_list {
+            std::fs::remove_file(path).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dcps::dcps_mail::{DcpsMail, MessageServiceMail},
+        dds_async::domain_participant_factory::DcpsChannel,
+        infrastructure::instance::InstanceHandle,
+        transport::types::LOCATOR_KIND_UDP_V4,
+    };
+    use std::time::{Duration, Instant};
+
+    type WrittenLocatorList = Arc<Mutex<Vec<Vec<Locator>>>>;
+
+    struct MockWriter(WrittenLocatorList);
+    impl WriteMessage for MockWriter {
+        fn write_message(&self, _datagram: &[u8], locator_list: &[Locator]) {
+            self.0.lock().unwrap().push(locator_list.to_vec());
+        }
+    }
+
+    struct MockTransport(WrittenLocatorList);
+    impl TransportParticipantFactory for MockTransport {
+        fn create_participant(
+            &self,
+            _domain_id: i32,
+            _data_channel_sender: TransportDataReceiver,
+        ) -> RtpsTransportParticipant {
+            RtpsTransportParticipant {
+                message_writer: Box::new(MockWriter(self.0.clone())),
+                default_unicast_locator_list: Vec::new(),
+                metatraffic_unicast_locator_list: Vec::new(),
+                metatraffic_multicast_locator_list: Vec::new(),
+                default_multicast_locator_list: Vec::new(),
+                initial_peer_locator_list: Vec::new(),
+                fragment_size: 1000,
+            }
+        }
+    }
+
+    #[test]
+    fn each_locator_is_written_through_its_transport() {
+        static CHANNEL: DcpsChannel = DcpsChannel::new();
+        let written_locator_list = WrittenLocatorList::default();
+        let mut factory = RtpsSharedMemoryTransportParticipantFactory::default();
+        factory
+            .set_directory(std::env::temp_dir().join(format!("dust_dds_{}", std::process::id())))
+            .set_network_transport(MockTransport(written_locator_list.clone()));
+        let participant_handle = InstanceHandle::new([1; 16]);
+        let participant = factory.create_participant(
+            0,
+            TransportDataReceiver::new(participant_handle, CHANNEL.sender()),
+        );
+        let network_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7410, [1; 16]);
+
+        participant.message_writer.write_message(
+            &[1, 2, 3],
+            &[participant.default_unicast_locator_list[0], network_locator],
+        );
+
+        assert_eq!(
+            *written_locator_list.lock().unwrap(),
+            vec![vec![network_locator]]
+        );
+        let start = Instant::now();
+        let received_mail = loop {
+            if let Ok(mail) = CHANNEL.try_receive() {
+                break mail;
+            }
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(1));
+        };
+        assert!(matches!(
+            received_mail,
+            DcpsMail::Message(MessageServiceMail::HandleData {
+                participant_handle: handle,
+                data_message,
+            }) if handle == participant_handle && data_message == [1, 2, 3]
+        ));
+        drop(participant);
+        std::fs::remove_dir_all(factory.directory()).ok();
+    }
+}
@@ -0,0 +1,578 @@
+use crate::{
+    infrastructure::error::{DdsError, DdsResult},
+    std_runtime::{self},
+    transport::{
+        interface::{
+            RtpsTransportParticipant, TransportDataReceiver, TransportParticipantFactory,
+            WriteMessage,
+        },
+        types::{LOCATOR_KIND_TCP, Locator},
+    },
+};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{Receiver, SyncSender, TrySendError, sync_channel},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::info;
+
+// Every frame is made of its length as a little endian u32 followed by the frame kind
+// and the payload. A connection starts with both ends sending a hello frame with their
+// transport id and domain id. The locators of the participants are these transport ids
+// so that the replies to a participant behind a NAT use the connection it opened.
+const FRAME_KIND_HELLO: u8 = 0;
+const FRAME_KIND_RTPS_MESSAGE: u8 = 1;
+const FRAME_LENGTH_SIZE: usize = 4;
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+const ACCEPT_POLL_PERIOD: Duration = Duration::from_millis(50);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(1);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+// Frames sent to a connection which is not keeping up are dropped once its queue is full.
+// The reliable protocol repairs them as it does with lost UDP datagrams.
+const WRITE_QUEUE_CAPACITY: usize = 256;
+// The time waited between failed connection attempts doubles up to this many reconnect periods
+const MAX_RECONNECT_BACKOFF_FACTOR: u32 = 32;
+
+// The all-zero transport id addresses all the connected participants of the domain
+const MULTICAST_TRANSPORT_ID: TransportId = [0; 16];
+
+const PB: i32 = 7400;
+const DG: i32 = 250;
+fn port_builtin_multicast(domain_id: i32) -> u32 {
+    let d0 = 0;
+    (PB + DG * domain_id + d0) as u32
+}
+
+type TransportId = [u8; 16];
+
+static CONNECTION_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn new_transport_id() -> TransportId {
+    let time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let counter = CONNECTION_COUNTER.fetch_add(1, Ordering::Relaxed);
+    md5::compute(format!("{}_{}_{}", std::process::id(), time, counter)).into()
+}
+
+fn frame(frame_kind: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_LENGTH_SIZE + 1 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32 + 1).to_le_bytes());
+    frame.push(frame_kind);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn read_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut length = [0; FRAME_LENGTH_SIZE];
+    stream.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length) as usize;
+    if length == 0 || length > MAX_FRAME_SIZE {
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    let mut frame = vec![0; length];
+    stream.read_exact(&mut frame)?;
+    let payload = frame.split_off(1);
+    Ok((frame[0], payload))
+}
+
+/// Writes the frame failing if it is not completely written within the write timeout.
+/// The timeout of the socket alone only limits the time each write waits for progress.
+fn write_frame(stream: &mut TcpStream, frame: &[u8]) -> std::io::Result<()> {
+    let deadline = Instant::now() + WRITE_TIMEOUT;
+    let mut written_length = 0;
+    while written_length < frame.len() {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        if timeout.is_zero() {
+            return Err(std::io::ErrorKind::TimedOut.into());
+        }
+        stream.set_write_timeout(Some(timeout))?;
+        match stream.write(&frame[written_length..]) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(length) => written_length += length,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+struct Connection {
+    stream: TcpStream,
+    frame_sender: SyncSender<Arc<[u8]>>,
+    transport_id: TransportId,
+    domain_id: i32,
+}
+
+/// Running state of a participant which its threads can wait on
+#[derive(Default)]
+struct RunningState {
+    is_stopped: Mutex<bool>,
+    stopped: Condvar,
+}
+
+impl RunningState {
+    fn is_running(&self) -> bool {
+        !*self.is_stopped.lock().expect("Running state lock poisoned")
+    }
+
+    fn stop(&self) {
+        *self.is_stopped.lock().expect("Running state lock poisoned") = true;
+        self.stopped.notify_all();
+    }
+
+    /// Waits for the timeout or until stopped and returns whether it is still running
+    fn wait_timeout(&self, timeout: Duration) -> bool {
+        let is_stopped = self.is_stopped.lock().expect("Running state lock poisoned");
+        let (is_stopped, _) = self
+            .stopped
+            .wait_timeout_while(is_stopped, timeout, |is_stopped| !*is_stopped)
+            .expect("Running state lock poisoned");
+        !*is_stopped
+    }
+}
+
+/// Connections of a participant indexed by a number unique to each connection
+type ConnectionList = Arc<Mutex<HashMap<u64, Connection>>>;
+
+#[derive(Clone)]
+struct ConnectionContext {
+    transport_id: TransportId,
+    domain_id: i32,
+    connection_list: ConnectionList,
+    running_state: Arc<RunningState>,
+    data_channel_sender: TransportDataReceiver,
+}
+
+impl ConnectionContext {
+    /// Exchanges the hello frames and adds the connection to the connection list
+    fn start_connection(&self, mut stream: TcpStream) -> std::io::Result<(u64, TcpStream)> {
+        let mut hello = self.transport_id.to_vec();
+        hello.extend_from_slice(&self.domain_id.to_le_bytes());
+        stream.set_nodelay(true)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        stream.write_all(&frame(FRAME_KIND_HELLO, &hello))?;
+        let (frame_kind, payload) = read_frame(&mut stream)?;
+        if frame_kind != FRAME_KIND_HELLO || payload.len() != hello.len() {
+            return Err(std::io::ErrorKind::InvalidData.into());
+        }
+        stream.set_read_timeout(None)?;
+
+        let mut transport_id = [0; 16];
+        transport_id.copy_from_slice(&payload[..16]);
+        let domain_id = i32::from_le_bytes([payload[16], payload[17], payload[18], payload[19]]);
+        let connection_id = CONNECTION_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let (frame_sender, frame_receiver) = sync_channel(WRITE_QUEUE_CAPACITY);
+        let writer_stream = stream.try_clone()?;
+        let context = self.clone();
+        std::thread::Builder::new()
+            .name("TcpConnectionWriter".to_string())
+            .spawn(move || context.write_frames(connection_id, writer_stream, frame_receiver))?;
+        self.connection_list
+            .lock()
+            .expect("Connection list lock poisoned")
+            .insert(
+                connection_id,
+                Connection {
+                    stream: stream.try_clone()?,
+                    frame_sender,
+                    transport_id,
+                    domain_id,
+                },
+            );
+        Ok((connection_id, stream))
+    }
+
+    /// Writes the queued frames until the connection is removed or a write fails
+    fn write_frames(
+        &self,
+        connection_id: u64,
+        mut stream: TcpStream,
+        frame_receiver: Receiver<Arc<[u8]>>,
+    ) {
+        while let Ok(frame) = frame_receiver.recv() {
+            if let Err(e) = write_frame(&mut stream, &frame) {
+                info!("Failed to send TCP frame with error {}", e);
+                self.remove_connection(connection_id);
+                break;
+            }
+        }
+        stream.shutdown(Shutdown::Both).ok();
+    }
+
+    fn remove_connection(&self, connection_id: u64) {
+        let connection = self
+            .connection_list
+            .lock()
+            .expect("Connection list lock poisoned")
+            .remove(&connection_id);
+        if let Some(connection) = connection {
+            connection.stream.shutdown(Shutdown::Both).ok();
+        }
+    }
+
+    fn connect(&self, address: SocketAddr) -> Option<(u64, TcpStream)> {
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+            .and_then(|stream| self.start_connection(stream))
+            .inspect_err(|e| info!("Failed to connect to {} with error {}", address, e))
+            .ok()
+    }
+
+    /// Receives the RTPS messages until the connection is closed
+    fn receive_messages(&self, connection_id: u64, mut stream: TcpStream) {
+        while self.running_state.is_running() {
+            match read_frame(&mut stream) {
+                Ok((FRAME_KIND_RTPS_MESSAGE, payload)) => {
+                    std_runtime::executor::block_on(
+                        self.data_channel_sender.receive_message(payload),
+                    );
+                }
+                Ok(_) => info!("Ignoring unexpected TCP frame"),
+                Err(_) => break,
+            }
+        }
+
+        self.remove_connection(connection_id);
+        stream.shutdown(Shutdown::Both).ok();
+    }
+}
+
+fn spawn_listen_thread(listener: TcpListener, context: ConnectionContext) {
+    std::thread::Builder::new()
+        .name("TcpListener".to_string())
+        .spawn(move || {
+            while context.running_state.is_running() {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let context = context.clone();
+                        std::thread::Builder::new()
+                            .name("TcpAcceptedConnection".to_string())
+                            .spawn(move || {
+                                let connection = stream
+                                    .set_nonblocking(false)
+                                    .and_then(|_| context.start_connection(stream));
+                                match connection {
+                                    Ok((connection_id, stream)) => {
+                                        context.receive_messages(connection_id, stream)
+                                    }
+                                    Err(e) => {
+                                        info!("Failed to accept connection with error {}", e)
+                                    }
+                                }
+                            })
+                            .expect("failed to spawn thread");
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(ACCEPT_POLL_PERIOD)
+                    }
+                    Err(e) => info!("Failed to accept TCP connection with error {}", e),
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+fn spawn_connect_thread(
+    address: SocketAddr,
+    reconnect_period: Duration,
+    context: ConnectionContext,
+) {
+    // The connection is established in the background so that creating the participant
+    // never waits for the server. The participants find each other with the periodic
+    // announcements once connected.
+    std::thread::Builder::new()
+        .name("TcpConnection".to_string())
+        .spawn(move || {
+            let max_backoff = reconnect_period * MAX_RECONNECT_BACKOFF_FACTOR;
+            let mut backoff = reconnect_period;
+            while context.running_state.is_running() {
+                let wait_time = if let Some((connection_id, stream)) = context.connect(address) {
+                    context.receive_messages(connection_id, stream);
+                    backoff = reconnect_period;
+                    reconnect_period
+                } else {
+                    let wait_time = backoff;
+                    backoff = (backoff * 2).min(max_backoff);
+                    wait_time
+                };
+                if !context.running_state.wait_timeout(wait_time) {
+                    break;
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
+/// Transport sending the RTPS messages over TCP connections.
+///
+/// A participant can act as a server accepting connections on the listen address, as a
+/// client connecting to the connect addresses or as both. The participants discover and
+/// communicate with the participants at the other end of their connections. The clients
+/// connect in the background and open lost connections again, waiting twice as long after
+/// each failed attempt starting from the reconnect period.
+pub struct RtpsTcpTransportParticipantFactory {
+    listen_address: Option<SocketAddr>,
+    connect_address_list: Vec<SocketAddr>,
+    reconnect_period: Duration,
+    fragment_size: usize,
+}
+
+impl RtpsTcpTransportParticipantFactory {
+    /// Set the address on which connections are accepted or None for not acting as a server.
+    /// Only one participant at a time can listen on a given address.
+    pub fn set_listen_address(&mut self, listen_address: Option<SocketAddr>) -> &mut Self {
+        self.listen_address = listen_address;
+        self
+    }
+
+    /// Set the addresses of the servers to connect to. The connections are established in
+    /// the background after the participant is created.
+    pub fn set_connect_address_list(&mut self, connect_address_list: Vec<SocketAddr>) -> &mut Self {
+        self.connect_address_list = connect_address_list;
+        self
+    }
+
+    /// Set the time waited before connecting again to a server after a connection
+    /// is lost. It doubles after each failed attempt up to 32 times this period.
+    pub fn set_reconnect_period(&mut self, reconnect_period: Duration) -> &mut Self {
+        self.reconnect_period = reconnect_period;
+        self
+    }
+
+    /// Set the fragment size in a range between 8 to 1048576. This value is the maximum size of
+    /// the payload transmitted in a single RTPS data submessage. Sizes larger than this value
+    /// will be transmitted in separate message using RTPS data fragments
+    pub fn set_fragment_size(&mut self, fragment_size: usize) -> DdsResult<&mut Self> {
+        if !(8..=1024 * 1024).contains(&fragment_size) {
+            return Err(DdsError::BadParameter);
+        }
+        self.fragment_size = fragment_size;
+        Ok(self)
+    }
+
+    /// Get the address on which connections are accepted
+    pub fn listen_address(&self) -> Option<SocketAddr> {
+        self.listen_address
+    }
+
+    /// Get the addresses of the servers to connect to
+    pub fn connect_address_list(&self) -> &[SocketAddr] {
+        &self.connect_address_list
+    }
+
+    /// Get the time waited before connecting again to a server
+    pub fn reconnect_period(&self) -> Duration {
+        self.reconnect_period
+    }
+
+    /// Get the value of the currently configured fragment size
+    pub fn fragment_size(&self) -> usize {
+        self.fragment_size
+    }
+}
+
+impl Default for RtpsTcpTransportParticipantFactory {
+    fn default() -> Self {
+        Self {
+            listen_address: None,
+            connect_address_list: Vec::new(),
+            reconnect_period: Duration::from_secs(1),
+            fragment_size: 64000,
+        }
+    }
+}
+
+impl TransportParticipantFactory for RtpsTcpTransportParticipantFactory {
+    fn create_participant(
+        &self,
+        domain_id: i32,
+        data_channel_sender: TransportDataReceiver,
+    ) -> RtpsTransportParticipant {
+        let context = ConnectionContext {
+            transport_id: new_transport_id(),
+            domain_id,
+            connection_list: Arc::new(Mutex::new(HashMap::new())),
+            running_state: Arc::new(RunningState::default()),
+            data_channel_sender,
+        };
+
+        if let Some(listen_address) = self.listen_address {
+            match TcpListener::bind(listen_address).and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                Ok(listener)
+            }) {
+                Ok(listener) => spawn_listen_thread(listener, context.clone()),
+                Err(e) => info!("Failed to listen on {} with error {}", listen_address, e),
+            }
+        }
+        for &address in &self.connect_address_list {
+            spawn_connect_thread(address, self.reconnect_period, context.clone());
+        }
+
+        let unicast_locator = Locator::new(LOCATOR_KIND_TCP, 0, context.transport_id);
+        let multicast_locator = Locator::new(
+            LOCATOR_KIND_TCP,
+            port_builtin_multicast(domain_id),
+            MULTICAST_TRANSPORT_ID,
+        );
+
+        RtpsTransportParticipant {
+            message_writer: Box::new(MessageWriter {
+                domain_id,
+                connection_list: context.connection_list,
+                running_state: context.running_state,
+            }),
+            default_unicast_locator_list: vec![unicast_locator],
+            metatraffic_unicast_locator_list: vec![unicast_locator],
+            metatraffic_multicast_locator_list: vec![multicast_locator],
+            default_multicast_locator_list: Vec::new(),
+            initial_peer_locator_list: Vec::new(),
+            fragment_size: self.fragment_size,
+        }
+    }
+}
+
+struct MessageWriter {
+    domain_id: i32,
+    connection_list: ConnectionList,
+    running_state: Arc<RunningState>,
+}
+
+impl WriteMessage for MessageWriter {
+    fn write_message(&self, datagram: &[u8], locator_list: &[Locator]) {
+        let mut destination_list: Vec<(u64, SyncSender<Arc<[u8]>>)> = Vec::new();
+        {
+            let connection_list = self
+                .connection_list
+                .lock()
+                .expect("Connection list lock poisoned");
+            for locator in locator_list.iter().filter(|l| l.kind() == LOCATOR_KIND_TCP) {
+                let transport_id = locator.address();
+                let destinations = connection_list.iter().filter(|(_, c)| {
+                    if transport_id == MULTICAST_TRANSPORT_ID {
+                        c.domain_id == self.domain_id
+                    } else {
+                        c.transport_id == transport_id
+                    }
+                });
+                for (&connection_id, connection) in destinations {
+                    if !destination_list.iter().any(|(id, _)| *id == connection_id) {
+                        destination_list.push((connection_id, connection.frame_sender.clone()));
+                    }
+                }
+            }
+        }
+
+        if destination_list.is_empty() {
+            return;
+        }
+        // The frames are queued to the writer thread of each connection so that a slow or
+        // unreachable peer never blocks the sending of the others
+        let frame: Arc<[u8]> = frame(FRAME_KIND_RTPS_MESSAGE, datagram).into();
+        for (_, frame_sender) in destination_list {
+            match frame_sender.try_send(frame.clone()) {
+                Ok(()) | Err(TrySendError::Disconnected(_)) => (),
+                Err(TrySendError::Full(_)) => info!("Dropping TCP frame of a full connection"),
+            }
+        }
+    }
+}
+
+impl Drop for MessageWriter {
+    fn drop(&mut self) {
+        self.running_state.stop();
+        if let Ok(mut connection_list) = self.connection_list.lock() {
+            for (_, connection) in connection_list.drain() {
+                connection.stream.shutdown(Shutdown::Both).ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dds_async::domain_participant_factory::DcpsChannel,
+        infrastructure::instance::InstanceHandle,
+    };
+
+    fn data_receiver(channel: &'static DcpsChannel) -> TransportDataReceiver {
+        TransportDataReceiver::new(InstanceHandle::new([1; 16]), channel.sender())
+    }
+
+    fn accept_client(listener: &TcpListener, domain_id: i32) -> TcpStream {
+        let (mut stream, _) = listener.accept().unwrap();
+        let (frame_kind, _) = read_frame(&mut stream).unwrap();
+        assert_eq!(frame_kind, FRAME_KIND_HELLO);
+        let mut hello = [2; 16].to_vec();
+        hello.extend_from_slice(&domain_id.to_le_bytes());
+        stream.write_all(&frame(FRAME_KIND_HELLO, &hello)).unwrap();
+        stream
+    }
+
+    #[test]
+    fn participant_is_created_without_waiting_for_the_server() {
+        static CHANNEL: DcpsChannel = DcpsChannel::new();
+        // The server never accepts the connection so the handshake can not complete
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut factory = RtpsTcpTransportParticipantFactory::default();
+        factory.set_connect_address_list(vec![listener.local_addr().unwrap()]);
+
+        let start = Instant::now();
+        let _participant = factory.create_participant(0, data_receiver(&CHANNEL));
+
+        assert!(start.elapsed() < HANDSHAKE_TIMEOUT);
+    }
+
+    #[test]
+    fn writing_does_not_block_on_a_server_which_does_not_read() {
+        static CHANNEL: DcpsChannel = DcpsChannel::new();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut factory = RtpsTcpTransportParticipantFactory::default();
+        factory.set_connect_address_list(vec![listener.local_addr().unwrap()]);
+        let participant = factory.create_participant(0, data_receiver(&CHANNEL));
+        let mut server_stream = accept_client(&listener, 0);
+        let locator_list = [Locator::new(LOCATOR_KIND_TCP, 0, [2; 16])];
+        // Wait for the client side of the handshake to complete
+        server_stream
+            .set_read_timeout(Some(Duration::from_millis(10)))
+            .unwrap();
+        let start = Instant::now();
+        while server_stream.read(&mut [0; 1]).is_err() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            participant.message_writer.write_message(&[], &locator_list);
+        }
+
+        // The writes go on while the socket buffers and the queue of the connection fill up
+        let datagram = vec![0; 64000];
+        for _ in 0..10 {
+            let start = Instant::now();
+            for _ in 0..WRITE_QUEUE_CAPACITY {
+                participant
+                    .message_writer
+                    .write_message(&datagram, &locator_list);
+            }
+            assert!(start.elapsed() < WRITE_TIMEOUT);
+            std::thread::sleep(Duration::from_millis(100));
+        }
+
+        // The connection is closed once the blocked write times out
+        std::thread::sleep(2 * WRITE_TIMEOUT);
+        server_stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let mut buffer = vec![0; 1024 * 1024];
+        while server_stream.read(&mut buffer).unwrap() > 0 {}
+    }
+}
@@ -9,14 +9,14 @@ use crate::{
         types::LOCATOR_KIND_UDP_V6,
     },
 };
-use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
+use core::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    ops::RangeInclusive,
+};
 use dust_dds::transport::types::{LOCATOR_KIND_UDP_V4, Locator};
 use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
 use socket2::Socket;
-use std::{
-    net::{ToSocketAddrs, UdpSocket},
-    sync::Arc,
-};
+use std::net::{ToSocketAddrs, UdpSocket};
 use tracing::info;
 
 const MAX_DATAGRAM_SIZE: usize = 65507;
@@ -25,24 +25,111 @@ type LocatorAddress = [u8; 16];
 // As of 9.6.1.4.1  Default multicast address
 const DEFAULT_MULTICAST_LOCATOR_ADDRESS: LocatorAddress =
     [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 239, 255, 0, 1];
+// Link-local scope IPv6 multicast address embedding the IPv4 default multicast address
+const DEFAULT_IPV6_MULTICAST_LOCATOR_ADDRESS: LocatorAddress = [
+    0xff, 0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 239, 255, 0, 1,
+];
 
 const PB: i32 = 7400;
 const DG: i32 = 250;
+const PG: i32 = 2;
 #[allow(non_upper_case_globals)]
 const d0: i32 = 0;
+#[allow(non_upper_case_globals)]
+const d1: i32 = 10;
+// Largest participant id whose ports do not overlap with the ones of the next domain
+const MAX_PARTICIPANT_ID: u32 = ((DG - d1) / PG - 1) as u32;
 fn port_builtin_multicast(domain_id: i32) -> u16 {
     (PB + DG * domain_id + d0) as u16
 }
+fn port_builtin_unicast(domain_id: i32, participant_id: u32) -> u16 {
+    (PB + DG * domain_id + d1 + PG * participant_id as i32) as u16
+}
 
-fn get_multicast_socket(
-    multicast_address: LocatorAddress,
+/// IP versions used by the [`RtpsUdpTransportParticipantFactory`] to communicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpMode {
+    /// Communicate only through IPv4.
+    Ipv4,
+    /// Communicate only through IPv6.
+    Ipv6,
+    /// Communicate through both IPv4 and IPv6.
+    DualStack,
+}
+
+impl IpMode {
+    fn is_ipv4_enabled(&self) -> bool {
+        matches!(self, IpMode::Ipv4 | IpMode::DualStack)
+    }
+
+    fn is_ipv6_enabled(&self) -> bool {
+        matches!(self, IpMode::Ipv6 | IpMode::DualStack)
+    }
+}
+
+/// Host to which the participants are announced when they are created, for discovering
+/// participants on networks where multicast is not available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitialPeer {
+    /// Address of the host.
+    pub address: IpAddr,
+    /// Participant ids which are announced to. The participants listen for the announcements
+    /// on the well-known metatraffic unicast port of the first participant id available on
+    /// their host, starting from 0.
+    pub participant_id_range: RangeInclusive<u32>,
+}
+
+/// Address of an interface together with the index of the interface it belongs to
+#[derive(Clone, Copy)]
+struct InterfaceAddress {
+    index: u32,
+    addr: Addr,
+}
+
+fn get_unicast_socket(
+    unspecified_address: IpAddr,
     port: u16,
-    interface_address_list: impl IntoIterator<Item = Addr>,
+    udp_receive_buffer_size: Option<usize>,
+) -> std::io::Result<UdpSocket> {
+    let socket_addr = SocketAddr::new(unspecified_address, port);
+    let socket = Socket::new(
+        socket2::Domain::for_address(socket_addr),
+        socket2::Type::DGRAM,
+        None,
+    )?;
+    if socket_addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    socket.bind(&socket_addr.into())?;
+    socket.set_nonblocking(false)?;
+    if let Some(buffer_size) = udp_receive_buffer_size {
+        socket.set_recv_buffer_size(buffer_size)?;
+    }
+    Ok(socket.into())
+}
+
+fn get_multicast_socket(
+    multicast_locator: Locator,
+    interface_address_list: impl IntoIterator<Item = InterfaceAddress>,
 ) -> std::io::Result<std::net::UdpSocket> {
-    let socket_addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
+    let locator_address = multicast_locator.address();
+    let port = multicast_locator.port() as u16;
+    let multicast_address = match multicast_locator.kind() {
+        LOCATOR_KIND_UDP_V6 => IpAddr::V6(Ipv6Addr::from(locator_address)),
+        _ => IpAddr::V4(Ipv4Addr::new(
+            locator_address[12],
+            locator_address[13],
+            locator_address[14],
+            locator_address[15],
+        )),
+    };
+    let socket_addr = match multicast_address {
+        IpAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)),
+        IpAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+    };
 
     let socket = Socket::new(
-        socket2::Domain::IPV4,
+        socket2::Domain::for_address(socket_addr),
         socket2::Type::DGRAM,
         Some(socket2::Protocol::UDP),
     )?;
@@ -51,17 +138,15 @@ fn get_multicast_socket(
     #[cfg(target_family = "unix")]
     socket.set_reuse_port(true)?;
     socket.set_nonblocking(false)?;
+    if socket_addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
 
     socket.bind(&socket_addr.into())?;
-    let addr = Ipv4Addr::new(
-        multicast_address[12],
-        multicast_address[13],
-        multicast_address[14],
-        multicast_address[15],
-    );
-    for interface_addr in interface_address_list {
-        match interface_addr {
-            Addr::V4(a) => {
+    let mut joined_interface_index_list = Vec::new();
+    for interface_address in interface_address_list {
+        match (multicast_address, interface_address.addr) {
+            (IpAddr::V4(addr), Addr::V4(a)) => {
                 let r = socket.join_multicast_v4(&addr, &a.ip);
                 if let Err(e) = r {
                     info!(
@@ -70,19 +155,56 @@ fn get_multicast_socket(
                     )
                 }
             }
-            Addr::V6(_) => (),
+            // IPv6 multicast groups are joined once per interface
+            (IpAddr::V6(addr), Addr::V6(a))
+                if !joined_interface_index_list.contains(&interface_address.index) =>
+            {
+                joined_interface_index_list.push(interface_address.index);
+                let r = socket.join_multicast_v6(&addr, interface_address.index);
+                if let Err(e) = r {
+                    info!(
+                        "Failed to join multicast group on address {} with error {}",
+                        a.ip, e
+                    )
+                }
+            }
+            _ => (),
         }
     }
 
-    socket.set_multicast_loop_v4(true)?;
+    match multicast_address {
+        IpAddr::V4(_) => socket.set_multicast_loop_v4(true)?,
+        IpAddr::V6(_) => socket.set_multicast_loop_v6(true)?,
+    }
 
     Ok(socket.into())
 }
 
+fn spawn_receive_thread(name: &str, socket: UdpSocket, data_channel_sender: TransportDataReceiver) {
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let mut buf = [0; MAX_DATAGRAM_SIZE];
+            loop {
+                if let Ok(size) = socket.recv(&mut buf) {
+                    if size > 0 {
+                        std_runtime::executor::block_on(
+                            data_channel_sender.receive_message(buf[..size].to_vec()),
+                        );
+                    }
+                }
+            }
+        })
+        .expect("failed to spawn thread");
+}
+
 pub struct RtpsUdpTransportParticipantFactory {
     interface_name: Option<String>,
     fragment_size: usize,
     udp_receive_buffer_size: Option<usize>,
+    ip_mode: IpMode,
+    multicast_enabled: bool,
+    initial_peer_list: Vec<InitialPeer>,
 }
 
 impl RtpsUdpTransportParticipantFactory {
@@ -93,6 +215,36 @@ impl RtpsUdpTransportParticipantFactory {
         self
     }
 
+    /// Set the IP versions used to communicate. By default only IPv4 is used.
+    pub fn set_ip_mode(&mut self, ip_mode: IpMode) -> &mut Self {
+        self.ip_mode = ip_mode;
+        self
+    }
+
+    /// Set whether multicast is used. Without multicast the participants are only discovered
+    /// through the initial peers. By default multicast is enabled.
+    pub fn set_multicast_enabled(&mut self, multicast_enabled: bool) -> &mut Self {
+        self.multicast_enabled = multicast_enabled;
+        self
+    }
+
+    /// Set the peers to which the participants are announced by unicast in addition to the
+    /// multicast announcements. Peers of an IP version which is not used are ignored and
+    /// participant ids larger than 119 are rejected with [`DdsError::BadParameter`].
+    pub fn set_initial_peer_list(
+        &mut self,
+        initial_peer_list: Vec<InitialPeer>,
+    ) -> DdsResult<&mut Self> {
+        if initial_peer_list
+            .iter()
+            .any(|p| *p.participant_id_range.end() > MAX_PARTICIPANT_ID)
+        {
+            return Err(DdsError::BadParameter);
+        }
+        self.initial_peer_list = initial_peer_list;
+        Ok(self)
+    }
+
     /// Set the value of the SO_RCVBUF option on the UDP socket. [`None`] corresponds to the OS default
     pub fn set_udp_receive_buffer_size(
         &mut self,
@@ -128,6 +280,21 @@ impl RtpsUdpTransportParticipantFactory {
     pub fn udp_receive_buffer_size(&self) -> Option<usize> {
         self.udp_receive_buffer_size
     }
+
+    /// Get the currently configured IP versions
+    pub fn ip_mode(&self) -> IpMode {
+        self.ip_mode
+    }
+
+    /// Get whether multicast is used
+    pub fn multicast_enabled(&self) -> bool {
+        self.multicast_enabled
+    }
+
+    /// Get the currently configured initial peers
+    pub fn initial_peer_list(&self) -> &[InitialPeer] {
+        &self.initial_peer_list
+    }
 }
 
 impl Default for RtpsUdpTransportParticipantFactory {
@@ -136,6 +303,9 @@ impl Default for RtpsUdpTransportParticipantFactory {
             interface_name: None,
             fragment_size: 1344,
             udp_receive_buffer_size: None,
+            ip_mode: IpMode::Ipv4,
+            multicast_enabled: true,
+            initial_peer_list: Vec::new(),
         }
     }
 }
@@ -146,7 +316,7 @@ impl TransportParticipantFactory for RtpsUdpTransportParticipantFactory {
         domain_id: i32,
         data_channel_sender: TransportDataReceiver,
     ) -> RtpsTransportParticipant {
-        let interface_address_list = NetworkInterface::show()
+        let interface_address_list: Vec<InterfaceAddress> = NetworkInterface::show()
             .expect("Could not scan interfaces")
             .into_iter()
             .filter(|interface| {
@@ -158,130 +328,163 @@ impl TransportParticipantFactory for RtpsUdpTransportParticipantFactory {
                 interface
                     .addr
                     .into_iter()
-                    .filter(|a| matches!(a, Addr::V4(_)))
-            });
-
-        let default_unicast_socket =
-            socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None).unwrap();
-        default_unicast_socket
-            .bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)).into())
-            .unwrap();
-        default_unicast_socket.set_nonblocking(false).unwrap();
-        if let Some(buffer_size) = self.udp_receive_buffer_size {
-            default_unicast_socket
-                .set_recv_buffer_size(buffer_size)
-                .unwrap();
-        }
-
-        let default_unicast_socket = std::net::UdpSocket::from(default_unicast_socket);
-        let user_defined_unicast_port = default_unicast_socket.local_addr().unwrap().port().into();
-        let default_unicast_locator_list: Vec<_> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, user_defined_unicast_port))
-            .collect();
-        // Open socket for unicast metatraffic data
-        let metatraffic_unicast_socket = Arc::new(
-            std::net::UdpSocket::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))).unwrap(),
-        );
-
-        metatraffic_unicast_socket.set_nonblocking(false).unwrap();
-        let metattrafic_unicast_locator_port = metatraffic_unicast_socket
-            .local_addr()
-            .unwrap()
-            .port()
-            .into();
-        let metatraffic_unicast_locator_list: Vec<Locator> = interface_address_list
-            .clone()
-            .map(|a| Locator::from_ip_and_port(&a, metattrafic_unicast_locator_port))
+                    .map(move |addr| InterfaceAddress {
+                        index: interface.index,
+                        addr,
+                    })
+            })
+            .filter(|a| match a.addr {
+                Addr::V4(_) => self.ip_mode.is_ipv4_enabled(),
+                Addr::V6(_) => self.ip_mode.is_ipv6_enabled(),
+            })
             .collect();
+        // Link-local IPv6 addresses can not be reached without knowing the interface
+        // so they are not announced as locators
+        let locator_address_list = interface_address_list
+            .iter()
+            .filter(|a| !matches!(a.addr, Addr::V6(v6) if v6.ip.is_unicast_link_local()));
+
+        let mut default_unicast_locator_list = Vec::new();
+        let mut metatraffic_unicast_locator_list = Vec::new();
+        let mut metatraffic_multicast_locator_list = Vec::new();
+        let mut initial_peer_locator_list = Vec::new();
+        let mut ipv4_socket = None;
+        let mut ipv6_socket = None;
+
+        let ip_versions = [
+            (
+                self.ip_mode.is_ipv4_enabled(),
+                IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                LOCATOR_KIND_UDP_V4,
+                DEFAULT_MULTICAST_LOCATOR_ADDRESS,
+            ),
+            (
+                self.ip_mode.is_ipv6_enabled(),
+                IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                LOCATOR_KIND_UDP_V6,
+                DEFAULT_IPV6_MULTICAST_LOCATOR_ADDRESS,
+            ),
+        ];
+        for (is_enabled, unspecified_address, locator_kind, multicast_locator_address) in
+            ip_versions
+        {
+            if !is_enabled {
+                continue;
+            }
+            let is_ip_version =
+                |a: &&InterfaceAddress| a.addr.ip().is_ipv4() == unspecified_address.is_ipv4();
+
+            let default_unicast_socket =
+                get_unicast_socket(unspecified_address, 0, self.udp_receive_buffer_size).unwrap();
+            let user_defined_unicast_port =
+                default_unicast_socket.local_addr().unwrap().port().into();
+            default_unicast_locator_list.extend(
+                locator_address_list
+                    .clone()
+                    .filter(is_ip_version)
+                    .map(|a| Locator::from_ip_and_port(&a.addr, user_defined_unicast_port)),
+            );
+
+            // Open socket for unicast metatraffic data on the well-known port of the first
+            // available participant id so that it can be reached by the initial peers
+            let metatraffic_unicast_socket = (0..=MAX_PARTICIPANT_ID)
+                .find_map(|participant_id| {
+                    get_unicast_socket(
+                        unspecified_address,
+                        port_builtin_unicast(domain_id, participant_id),
+                        None,
+                    )
+                    .ok()
+                })
+                .unwrap_or_else(|| get_unicast_socket(unspecified_address, 0, None).unwrap());
+            let metattrafic_unicast_locator_port = metatraffic_unicast_socket
+                .local_addr()
+                .unwrap()
+                .port()
+                .into();
+            metatraffic_unicast_locator_list.extend(
+                locator_address_list
+                    .clone()
+                    .filter(is_ip_version)
+                    .map(|a| Locator::from_ip_and_port(&a.addr, metattrafic_unicast_locator_port)),
+            );
+
+            initial_peer_locator_list.extend(
+                self.initial_peer_list
+                    .iter()
+                    .filter(|p| p.address.is_ipv4() == unspecified_address.is_ipv4())
+                    .flat_map(|p| {
+                        p.participant_id_range.clone().map(|participant_id| {
+                            Locator::from_ip_address_and_port(
+                                p.address,
+                                port_builtin_unicast(domain_id, participant_id).into(),
+                            )
+                        })
+                    }),
+            );
+
+            // Open socket for multicast metatraffic data
+            if self.multicast_enabled {
+                let metatraffic_multicast_locator = Locator::new(
+                    locator_kind,
+                    port_builtin_multicast(domain_id) as u32,
+                    multicast_locator_address,
+                );
+                metatraffic_multicast_locator_list.push(metatraffic_multicast_locator);
+                let metatraffic_multicast_socket = get_multicast_socket(
+                    metatraffic_multicast_locator,
+                    interface_address_list.iter().filter(is_ip_version).copied(),
+                )
+                .unwrap();
+                spawn_receive_thread(
+                    "SomethingOnMetatrafficMulticastSocket",
+                    metatraffic_multicast_socket,
+                    data_channel_sender.clone(),
+                );
+            }
 
-        // Open socket for multicast metatraffic data
-        let metatraffic_multicast_locator_list = vec![Locator::new(
-            LOCATOR_KIND_UDP_V4,
-            port_builtin_multicast(domain_id) as u32,
-            DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-        )];
+            match unspecified_address {
+                IpAddr::V4(_) => {
+                    ipv4_socket = Some(default_unicast_socket.try_clone().expect("Socket cloning"))
+                }
+                IpAddr::V6(_) => {
+                    ipv6_socket = Some(default_unicast_socket.try_clone().expect("Socket cloning"))
+                }
+            }
 
-        let metatraffic_multicast_socket = Arc::new(
-            get_multicast_socket(
-                DEFAULT_MULTICAST_LOCATOR_ADDRESS,
-                port_builtin_multicast(domain_id),
-                interface_address_list,
-            )
-            .unwrap(),
-        );
+            spawn_receive_thread(
+                "SomethingOnMetatrafficUnicastSocket",
+                metatraffic_unicast_socket,
+                data_channel_sender.clone(),
+            );
+            spawn_receive_thread(
+                "SomethingOnDefaultUnicastSocket",
+                default_unicast_socket,
+                data_channel_sender.clone(),
+            );
+        }
 
-        let message_writer =
-            MessageWriter::new(default_unicast_socket.try_clone().expect("Socket cloning"));
+        let message_writer = MessageWriter::new(ipv4_socket, ipv6_socket, self.multicast_enabled);
 
-        let global_participant = RtpsTransportParticipant {
-            message_writer: Box::new(message_writer.clone()),
+        RtpsTransportParticipant {
+            message_writer: Box::new(message_writer),
             default_unicast_locator_list,
             metatraffic_unicast_locator_list,
             metatraffic_multicast_locator_list,
             default_multicast_locator_list: Vec::new(),
+            initial_peer_locator_list,
             fragment_size: self.fragment_size,
-        };
-
-        let data_channel_sender_clone = data_channel_sender.clone();
-
-        std::thread::Builder::new()
-            .name("SomethingOnMetatrafficMulticastSocket".to_string())
-            .spawn(move || {
-                let mut buf = [0; MAX_DATAGRAM_SIZE];
-                loop {
-                    if let Ok(size) = metatraffic_multicast_socket.recv(&mut buf) {
-                        if size > 0 {
-                            std_runtime::executor::block_on(
-                                data_channel_sender_clone.receive_message(buf[..size].to_vec()),
-                            );
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        let data_channel_sender_clone = data_channel_sender.clone();
-        std::thread::Builder::new()
-            .name("SomethingOnMetatrafficUnicastSocket".to_string())
-            .spawn(move || {
-                let mut buf = [0; MAX_DATAGRAM_SIZE];
-                loop {
-                    if let Ok(size) = metatraffic_unicast_socket.recv(&mut buf) {
-                        if size > 0 {
-                            std_runtime::executor::block_on(
-                                data_channel_sender_clone.receive_message(buf[..size].to_vec()),
-                            )
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        let data_channel_sender_clone = data_channel_sender.clone();
-        std::thread::Builder::new()
-            .name("SomethingOnDefaultUnicastSocket".to_string())
-            .spawn(move || {
-                let mut buf = [0; MAX_DATAGRAM_SIZE];
-                loop {
-                    if let Ok(size) = default_unicast_socket.recv(&mut buf) {
-                        if size > 0 {
-                            std_runtime::executor::block_on(
-                                data_channel_sender_clone.receive_message(buf[..size].to_vec()),
-                            )
-                        }
-                    }
-                }
-            })
-            .expect("failed to spawn thread");
-
-        global_participant
+        }
     }
 }
 
 impl Locator {
     pub fn from_ip_and_port(ip_addr: &Addr, port: u32) -> Self {
-        match ip_addr.ip() {
+        Self::from_ip_address_and_port(ip_addr.ip(), port)
+    }
+
+    fn from_ip_address_and_port(ip_addr: IpAddr, port: u32) -> Self {
+        match ip_addr {
             IpAddr::V4(a) => Locator::new(
                 LOCATOR_KIND_UDP_V4,
                 port,
@@ -329,7 +532,11 @@ impl ToSocketAddrs for UdpLocator {
                 );
                 Ok(Some(SocketAddr::V4(address)).into_iter())
             }
-            LOCATOR_KIND_UDP_V6 => todo!(),
+            LOCATOR_KIND_UDP_V6 => {
+                let address =
+                    SocketAddrV6::new(Ipv6Addr::from(locator_address), self.0.port() as u16, 0, 0);
+                Ok(Some(SocketAddr::V6(address)).into_iter())
+            }
             _ => Err(std::io::ErrorKind::InvalidInput.into()),
         }
     }
@@ -353,47 +560,74 @@ impl UdpLocator {
 }
 
 struct MessageWriter {
-    socket: UdpSocket,
+    ipv4_socket: Option<UdpSocket>,
+    ipv6_socket: Option<UdpSocket>,
+    multicast_enabled: bool,
 }
 
-impl Clone for MessageWriter {
-    fn clone(&self) -> Self {
+impl MessageWriter {
+    fn new(
+        ipv4_socket: Option<UdpSocket>,
+        ipv6_socket: Option<UdpSocket>,
+        multicast_enabled: bool,
+    ) -> Self {
         Self {
-            socket: self.socket.try_clone().expect("Socket cloning"),
+            ipv4_socket,
+            ipv6_socket,
+            multicast_enabled,
         }
     }
 }
 
-impl MessageWriter {
-    fn new(socket: UdpSocket) -> Self {
-        Self { socket }
-    }
-}
 impl WriteMessage for MessageWriter {
     fn write_message(&self, datagram: &[u8], locator_list: &[Locator]) {
         for &destination_locator in locator_list {
+            let socket = match destination_locator.kind() {
+                LOCATOR_KIND_UDP_V4 => &self.ipv4_socket,
+                LOCATOR_KIND_UDP_V6 => &self.ipv6_socket,
+                _ => &None,
+            };
+            let Some(socket) = socket else {
+                continue;
+            };
             if UdpLocator(destination_locator).is_multicast() {
-                let socket2: socket2::Socket = self.socket.try_clone().unwrap().into();
-                let interface_addresses = NetworkInterface::show();
-                let interface_addresses: Vec<_> = interface_addresses
-                    .expect("Could not scan interfaces")
-                    .into_iter()
-                    .flat_map(|i| {
-                        i.addr.into_iter().filter_map(|a| match a {
-                            Addr::V4(v4) => Some(v4.ip),
-                            _ => None,
-                        })
-                    })
-                    .collect();
-                for address in interface_addresses {
-                    if socket2.set_multicast_if_v4(&address).is_ok() {
-                        self.socket
-                            .send_to(datagram, UdpLocator(destination_locator))
-                            .ok();
+                if !self.multicast_enabled {
+                    continue;
+                }
+                let socket2: socket2::Socket = socket.try_clone().unwrap().into();
+                let interface_list = NetworkInterface::show().expect("Could not scan interfaces");
+                match destination_locator.kind() {
+                    LOCATOR_KIND_UDP_V4 => {
+                        let interface_addresses = interface_list.into_iter().flat_map(|i| {
+                            i.addr.into_iter().filter_map(|a| match a {
+                                Addr::V4(v4) => Some(v4.ip),
+                                _ => None,
+                            })
+                        });
+                        for address in interface_addresses {
+                            if socket2.set_multicast_if_v4(&address).is_ok() {
+                                socket
+                                    .send_to(datagram, UdpLocator(destination_locator))
+                                    .ok();
+                            }
+                        }
+                    }
+                    _ => {
+                        let interface_indexes = interface_list
+                            .into_iter()
+                            .filter(|i| i.addr.iter().any(|a| matches!(a, Addr::V6(_))))
+                            .map(|i| i.index);
+                        for index in interface_indexes {
+                            if socket2.set_multicast_if_v6(index).is_ok() {
+                                socket
+                                    .send_to(datagram, UdpLocator(destination_locator))
+                                    .ok();
+                            }
+                        }
                     }
                 }
             } else {
-                self.socket
+                socket
                     .send_to(datagram, UdpLocator(destination_locator))
                     .ok();
             }
@@ -52,6 +52,12 @@ pub trait DdsRuntime: Send + 'static {
     fn clock(&self) -> Self::ClockHandle;
     /// Returns a spawner handle for this runtime
     fn spawner(&self) -> Self::SpawnerHandle;
+    /// Returns a spawner handle for the tasks writing the messages of the asynchronous
+    /// data writers. These tasks block while the transport writes so runtimes should run
+    /// them apart from the other tasks. By default they run on the runtime spawner.
+    fn sender_spawner(&self) -> Self::SpawnerHandle {
+        self.spawner()
+    }
 }
 
 /// Generic either enumeration type
@@ -178,9 +178,14 @@ impl Default for Executor {
 
 impl Executor {
     pub fn new() -> Self {
+        Self::with_thread_name("Dust DDS Executor")
+    }
+
+    /// Creates an executor running its tasks on a thread with the given name
+    pub fn with_thread_name(thread_name: &str) -> Self {
         let (task_sender, task_receiver) = channel::<Arc<Task>>();
         let executor_thread_handle = std::thread::Builder::new()
-            .name("Dust DDS Executor".to_string())
+            .name(thread_name.to_string())
             .spawn(move || {
                 loop {
                     match task_receiver.try_recv() {
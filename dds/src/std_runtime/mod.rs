@@ -24,10 +24,18 @@ impl Clock for StdClock {
     }
 }
 
-#[derive(Default)]
 pub struct StdRuntime {
     timer_driver: TimerDriver,
     executor: Executor,
+    // The messages of the asynchronous writers are written on their own thread so that
+    // a blocking transport does not hold the other tasks
+    sender_executor: Executor,
+}
+
+impl Default for StdRuntime {
+    fn default() -> Self {
+        Self::new(Executor::new(), TimerDriver::default())
+    }
 }
 
 impl StdRuntime {
@@ -35,6 +43,7 @@ impl StdRuntime {
         Self {
             executor,
             timer_driver,
+            sender_executor: Executor::with_thread_name("Dust DDS Sender"),
         }
     }
 }
@@ -55,4 +64,8 @@ impl DdsRuntime for StdRuntime {
     fn spawner(&self) -> Self::SpawnerHandle {
         self.executor.handle()
     }
+
+    fn sender_spawner(&self) -> Self::SpawnerHandle {
+        self.sender_executor.handle()
+    }
 }
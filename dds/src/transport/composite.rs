@@ -0,0 +1,250 @@
+use super::{
+    interface::{
+        RtpsTransportParticipant, TransportDataReceiver, TransportParticipantFactory, WriteMessage,
+    },
+    types::{Locator, Long},
+};
+use crate::infrastructure::error::{DdsError, DdsResult};
+use alloc::{boxed::Box, vec::Vec};
+
+/// Transport communicating through several transports at the same time, for example
+/// UDP together with a custom transport.
+///
+/// The participants get the locators of all the transports and every message is sent by
+/// the transport handling the kind of each of its destination locators. The factory is
+/// created with a [`CompositeTransportParticipantFactoryBuilder`].
+pub struct CompositeTransportParticipantFactory {
+    transport_list: Vec<(Vec<Long>, Box<dyn TransportParticipantFactory>)>,
+}
+
+/// Builder for the [`CompositeTransportParticipantFactory`]
+#[derive(Default)]
+pub struct CompositeTransportParticipantFactoryBuilder {
+    transport_list: Vec<(Vec<Long>, Box<dyn TransportParticipantFactory>)>,
+}
+
+impl CompositeTransportParticipantFactoryBuilder {
+    /// Construct a builder without any transport.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a transport handling the locators of the given kinds. The locators of other kinds
+    /// created by the transport are not used. Returns [`DdsError::BadParameter`] if no kind is
+    /// given or if a kind is already handled by another transport.
+    pub fn add_transport(
+        mut self,
+        locator_kind_list: &[Long],
+        transport: impl TransportParticipantFactory,
+    ) -> DdsResult<Self> {
+        let is_kind_handled = |kind: &Long| {
+            self.transport_list
+                .iter()
+                .any(|(kind_list, _)| kind_list.contains(kind))
+        };
+        if locator_kind_list.is_empty() || locator_kind_list.iter().any(is_kind_handled) {
+            return Err(DdsError::BadParameter);
+        }
+        let mut locator_kind_list = locator_kind_list.to_vec();
+        locator_kind_list.sort_unstable();
+        locator_kind_list.dedup();
+        self.transport_list
+            .push((locator_kind_list, Box::new(transport)));
+        Ok(self)
+    }
+
+    /// Build the composite transport. Returns [`DdsError::BadParameter`] if no transport was added.
+    pub fn build(self) -> DdsResult<CompositeTransportParticipantFactory> {
+        if self.transport_list.is_empty() {
+            return Err(DdsError::BadParameter);
+        }
+        Ok(CompositeTransportParticipantFactory {
+            transport_list: self.transport_list,
+        })
+    }
+}
+
+impl TransportParticipantFactory for CompositeTransportParticipantFactory {
+    fn create_participant(
+        &self,
+        domain_id: i32,
+        data_receiver: TransportDataReceiver,
+    ) -> RtpsTransportParticipant {
+        let mut default_unicast_locator_list = Vec::new();
+        let mut metatraffic_unicast_locator_list = Vec::new();
+        let mut metatraffic_multicast_locator_list = Vec::new();
+        let mut default_multicast_locator_list = Vec::new();
+        let mut initial_peer_locator_list = Vec::new();
+        let mut fragment_size_list = Vec::new();
+        let mut message_writer_list = Vec::new();
+        for (locator_kind_list, transport) in &self.transport_list {
+            let participant = transport.create_participant(domain_id, data_receiver.clone());
+            let is_handled = |l: &Locator| locator_kind_list.contains(&l.kind());
+            default_unicast_locator_list.extend(
+                participant
+                    .default_unicast_locator_list
+                    .into_iter()
+                    .filter(is_handled),
+            );
+            metatraffic_unicast_locator_list.extend(
+                participant
+                    .metatraffic_unicast_locator_list
+                    .into_iter()
+                    .filter(is_handled),
+            );
+            metatraffic_multicast_locator_list.extend(
+                participant
+                    .metatraffic_multicast_locator_list
+                    .into_iter()
+                    .filter(is_handled),
+            );
+            default_multicast_locator_list.extend(
+                participant
+                    .default_multicast_locator_list
+                    .into_iter()
+                    .filter(is_handled),
+            );
+            initial_peer_locator_list.extend(
+                participant
+                    .initial_peer_locator_list
+                    .into_iter()
+                    .filter(is_handled),
+            );
+            fragment_size_list.push(participant.fragment_size);
+            message_writer_list.push((locator_kind_list.clone(), participant.message_writer));
+        }
+
+        RtpsTransportParticipant {
+            message_writer: Box::new(CompositeMessageWriter {
+                message_writer_list,
+            }),
+            default_unicast_locator_list,
+            metatraffic_unicast_locator_list,
+            metatraffic_multicast_locator_list,
+            default_multicast_locator_list,
+            initial_peer_locator_list,
+            fragment_size: fragment_size_list
+                .into_iter()
+                .min()
+                .expect("Composite transport has at least one transport"),
+        }
+    }
+}
+
+/// Message writers of the transports together with the locator kinds they handle
+struct CompositeMessageWriter {
+    message_writer_list: Vec<(Vec<Long>, Box<dyn WriteMessage + Send + Sync>)>,
+}
+
+impl WriteMessage for CompositeMessageWriter {
+    fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+        for (locator_kind_list, message_writer) in &self.message_writer_list {
+            let transport_locators: Vec<Locator> = locators
+                .iter()
+                .filter(|l| locator_kind_list.contains(&l.kind()))
+                .copied()
+                .collect();
+            if !transport_locators.is_empty() {
+                message_writer.write_message(buf, &transport_locators);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::transport::types::{LOCATOR_KIND_TCP, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6};
+
+    struct MockWriter {
+        locators: Arc<Mutex<Vec<Vec<Locator>>>>,
+    }
+
+    impl WriteMessage for MockWriter {
+        fn write_message(&self, _buf: &[u8], locators: &[Locator]) {
+            self.locators.lock().unwrap().push(locators.to_vec());
+        }
+    }
+
+    struct MockTransport;
+
+    impl TransportParticipantFactory for MockTransport {
+        fn create_participant(
+            &self,
+            _domain_id: i32,
+            _data_receiver: TransportDataReceiver,
+        ) -> RtpsTransportParticipant {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn composite_transport_requires_transports_with_distinct_locator_kinds() {
+        assert!(
+            CompositeTransportParticipantFactoryBuilder::new()
+                .build()
+                .is_err()
+        );
+        assert!(
+            CompositeTransportParticipantFactoryBuilder::new()
+                .add_transport(&[], MockTransport)
+                .is_err()
+        );
+        assert!(
+            CompositeTransportParticipantFactoryBuilder::new()
+                .add_transport(&[LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6], MockTransport)
+                .unwrap()
+                .add_transport(&[LOCATOR_KIND_TCP, LOCATOR_KIND_UDP_V6], MockTransport)
+                .is_err()
+        );
+        assert!(
+            CompositeTransportParticipantFactoryBuilder::new()
+                .add_transport(&[LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6], MockTransport)
+                .unwrap()
+                .add_transport(&[LOCATOR_KIND_TCP], MockTransport)
+                .unwrap()
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn messages_are_sent_by_the_transports_of_the_locator_kinds() {
+        let udp_locators = Arc::new(Mutex::new(Vec::new()));
+        let tcp_locators = Arc::new(Mutex::new(Vec::new()));
+        let message_writer = CompositeMessageWriter {
+            message_writer_list: vec![
+                (
+                    vec![LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6],
+                    Box::new(MockWriter {
+                        locators: udp_locators.clone(),
+                    }),
+                ),
+                (
+                    vec![LOCATOR_KIND_TCP],
+                    Box::new(MockWriter {
+                        locators: tcp_locators.clone(),
+                    }),
+                ),
+            ],
+        };
+        let udp_v4_locator = Locator::new(LOCATOR_KIND_UDP_V4, 7400, [1; 16]);
+        let udp_v6_locator = Locator::new(LOCATOR_KIND_UDP_V6, 7400, [2; 16]);
+        let tcp_locator = Locator::new(LOCATOR_KIND_TCP, 0, [3; 16]);
+        let unknown_locator = Locator::new(100, 0, [4; 16]);
+
+        message_writer.write_message(
+            &[0],
+            &[udp_v4_locator, tcp_locator, unknown_locator, udp_v6_locator],
+        );
+        message_writer.write_message(&[0], &[udp_v4_locator]);
+
+        assert_eq!(
+            *udp_locators.lock().unwrap(),
+            vec![vec![udp_v4_locator, udp_v6_locator], vec![udp_v4_locator]]
+        );
+        assert_eq!(*tcp_locators.lock().unwrap(), vec![vec![tcp_locator]]);
+    }
+}
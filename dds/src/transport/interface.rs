@@ -4,12 +4,18 @@ use crate::{
     infrastructure::instance::InstanceHandle,
     transport::types::Locator,
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 
 pub trait WriteMessage {
     fn write_message(&self, buf: &[u8], locators: &[Locator]);
 }
 
+impl<T: WriteMessage + ?Sized> WriteMessage for Arc<T> {
+    fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+        self.as_ref().write_message(buf, locators)
+    }
+}
+
 #[derive(Clone)]
 pub struct TransportDataReceiver {
     participant_handle: InstanceHandle,
@@ -23,6 +29,10 @@ impl TransportDataReceiver {
         }
     }
 
+    pub fn participant_handle(&self) -> InstanceHandle {
+        self.participant_handle
+    }
+
     pub async fn receive_message(&self, data_message: Vec<u8>) {
         self.dcps_sender
             .send(DcpsMail::Message(MessageServiceMail::HandleData {
@@ -39,6 +49,9 @@ pub struct RtpsTransportParticipant {
     pub metatraffic_unicast_locator_list: Vec<Locator>,
     pub metatraffic_multicast_locator_list: Vec<Locator>,
     pub default_multicast_locator_list: Vec<Locator>,
+    /// Locators to which the participant is announced in addition to the metatraffic
+    /// multicast locators. They are not part of the announced participant data.
+    pub initial_peer_locator_list: Vec<Locator>,
     pub fragment_size: usize,
 }
 pub trait TransportParticipantFactory: Send + 'static {
@@ -1,2 +1,3 @@
+pub mod composite;
 pub mod interface;
 pub mod types;
@@ -234,6 +234,10 @@ pub const LOCATOR_KIND_INVALID: Long = -1;
 pub const LOCATOR_KIND_RESERVED: Long = 0;
 pub const LOCATOR_KIND_UDP_V4: Long = 1;
 pub const LOCATOR_KIND_UDP_V6: Long = 2;
+// Dust DDS specific kinds. The kinds defined by the standard do not set the most significant bit
+pub const LOCATOR_KIND_SHM: Long = i32::MIN | 1;
+pub const LOCATOR_KIND_TCP: Long = i32::MIN | 2;
+pub const LOCATOR_KIND_LOOPBACK: Long = i32::MIN | 3;
 pub const LOCATOR_PORT_INVALID: UnsignedLong = 0;
 pub const LOCATOR_ADDRESS_INVALID: [Octet; 16] = [0; 16];
 
@@ -337,7 +341,26 @@ pub struct WriterProxy {
     pub multicast_locator_list: Vec<Locator>,
 }
 
+/// Coherent set a change belongs to, as announced by the PID_COHERENT_SET,
+/// PID_GROUP_COHERENT_SET and PID_WRITER_GROUP_INFO inline QoS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoherentSet {
+    /// Sequence number of the first change of the set sent by the writer
+    pub sequence_number: SequenceNumber,
+    /// Group sequence number of the first change of the set sent by the
+    /// publisher. Only present for sets with GROUP access scope.
+    pub group_sequence_number: Option<SequenceNumber>,
+    /// Digest of the writers taking part in a group coherent set. Only
+    /// present on the change marking the end of the set.
+    pub writer_set: Option<[u8; 4]>,
+    /// The change carries no data and only marks the end of the set
+    pub end_of_set: bool,
+}
+
+/// Change exchanged between the DCPS entities and the transport. New fields may be added
+/// in minor versions so transports outside this crate must create it with [`CacheChange::new`].
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct CacheChange {
     pub kind: ChangeKind,
     pub writer_guid: Guid,
@@ -345,6 +368,33 @@ pub struct CacheChange {
     pub source_timestamp: Option<Time>,
     pub instance_handle: Option<[u8; 16]>,
     pub data_value: Arc<[u8]>,
+    pub filter_signatures: Vec<[u8; 16]>,
+    pub coherent_set: Option<CoherentSet>,
+    pub group_sequence_number: Option<SequenceNumber>,
+}
+
+impl CacheChange {
+    /// Creates a change which passed no content filter and is not part of a coherent set.
+    pub fn new(
+        kind: ChangeKind,
+        writer_guid: Guid,
+        sequence_number: SequenceNumber,
+        source_timestamp: Option<Time>,
+        instance_handle: Option<[u8; 16]>,
+        data_value: Arc<[u8]>,
+    ) -> Self {
+        Self {
+            kind,
+            writer_guid,
+            sequence_number,
+            source_timestamp,
+            instance_handle,
+            data_value,
+            filter_signatures: Vec::new(),
+            coherent_set: None,
+            group_sequence_number: None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
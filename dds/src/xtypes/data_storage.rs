@@ -3,7 +3,7 @@ use crate::xtypes::{
     error::{XTypesError, XTypesResult},
     type_support::TypeSupport,
 };
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 /// Representation of data storage for dynamic types.
 #[derive(Debug, Clone, PartialEq)]
@@ -32,9 +32,12 @@ pub enum DataStorage {
     Float128(i128),
     /// 8-bit character.
     Char8(char),
+    /// 16-bit wide character. It is a single UTF-16 code unit so it must be in the basic
+    /// multilingual plane.
+    Char16(char),
     /// Boolean value.
     Boolean(bool),
-    /// String value.
+    /// String value. Wide strings are also stored as UTF-8 and encoded as UTF-16 when serialized.
     String(String),
     /// Complex data value represented by a [`DynamicData`].
     ComplexValue(DynamicData<'static>),
@@ -62,12 +65,18 @@ pub enum DataStorage {
     SequenceFloat128(Vec<i128>),
     /// Sequence of 8-bit characters.
     SequenceChar8(Vec<char>),
+    /// Sequence of 16-bit wide characters.
+    SequenceChar16(Vec<char>),
     /// Sequence of boolean values.
     SequenceBoolean(Vec<bool>),
     /// Sequence of string values.
     SequenceString(Vec<String>),
     /// Sequence of complex data values.
     SequenceComplexValue(Vec<DynamicData<'static>>),
+    /// Sequence of collections (sequences, arrays or maps).
+    SequenceCollection(Vec<DataStorage>),
+    /// Map of key and value pairs.
+    Map(Vec<(DataStorage, DataStorage)>),
 }
 
 /// Trait used to map Rust types to and from their corresponding [`DataStorage`] variants.
@@ -662,6 +671,212 @@ impl<T: TypeSupport, const N: usize> DataStorageMapping for [T; N] {
     }
 }
 
+impl<T> DataStorageMapping for Vec<Vec<T>>
+where
+    Vec<T>: DataStorageMapping,
+{
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceCollection(
+            self.into_iter()
+                .map(DataStorageMapping::into_storage)
+                .collect(),
+        )
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::SequenceCollection(x) => {
+                x.into_iter().map(Vec::<T>::try_from_storage).collect()
+            }
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl<T, const N: usize> DataStorageMapping for Vec<[T; N]>
+where
+    [T; N]: DataStorageMapping,
+{
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceCollection(
+            self.into_iter()
+                .map(DataStorageMapping::into_storage)
+                .collect(),
+        )
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::SequenceCollection(x) => {
+                x.into_iter().map(<[T; N]>::try_from_storage).collect()
+            }
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl<T, const N: usize> DataStorageMapping for [Vec<T>; N]
+where
+    Vec<T>: DataStorageMapping,
+{
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceCollection(
+            self.into_iter()
+                .map(DataStorageMapping::into_storage)
+                .collect(),
+        )
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::SequenceCollection(x) => {
+                let vec = x
+                    .into_iter()
+                    .map(Vec::<T>::try_from_storage)
+                    .collect::<XTypesResult<Vec<_>>>()?;
+                Self::try_from(vec).map_err(|_| XTypesError::InvalidType)
+            }
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+// Multi-dimensional arrays are stored as a single sequence with the elements of all the
+// dimensions, the same way as the arrays with several bounds of the dynamic types.
+impl<T, const M: usize, const N: usize> DataStorageMapping for [[T; M]; N]
+where
+    [T; M]: DataStorageMapping,
+{
+    fn into_storage(self) -> DataStorage {
+        concat_sequences(self.into_iter().map(DataStorageMapping::into_storage))
+            .expect("Arrays of the same type have the same storage")
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        let vec = split_sequence(data_storage, N, M)?
+            .into_iter()
+            .map(<[T; M]>::try_from_storage)
+            .collect::<XTypesResult<Vec<_>>>()?;
+        Self::try_from(vec).map_err(|_| XTypesError::InvalidType)
+    }
+}
+
+macro_rules! sequence_storage_variants {
+    ($macro:ident!($($arg:expr),*)) => {
+        $macro!(
+            $($arg),*;
+            SequenceUInt8,
+            SequenceInt8,
+            SequenceUInt16,
+            SequenceInt16,
+            SequenceInt32,
+            SequenceUInt32,
+            SequenceInt64,
+            SequenceUInt64,
+            SequenceFloat32,
+            SequenceFloat64,
+            SequenceFloat128,
+            SequenceChar8,
+            SequenceChar16,
+            SequenceBoolean,
+            SequenceString,
+            SequenceComplexValue,
+            SequenceCollection
+        )
+    };
+}
+
+/// Concatenates the elements of sequences stored in the same variant.
+fn concat_sequences(sequences: impl Iterator<Item = DataStorage>) -> XTypesResult<DataStorage> {
+    macro_rules! concat {
+        ($sequences:expr; $($variant:ident),*) => {{
+            let mut sequences = $sequences;
+            match sequences.next() {
+                $(Some(DataStorage::$variant(mut elements)) => {
+                    for sequence in sequences {
+                        match sequence {
+                            DataStorage::$variant(x) => elements.extend(x),
+                            _ => return Err(XTypesError::InvalidType),
+                        }
+                    }
+                    Ok(DataStorage::$variant(elements))
+                })*
+                Some(_) => Err(XTypesError::InvalidType),
+                None => Ok(DataStorage::SequenceCollection(Vec::new())),
+            }
+        }};
+    }
+    sequence_storage_variants!(concat!(sequences))
+}
+
+/// Splits a sequence into `count` sequences of `length` elements stored in the same variant.
+fn split_sequence(
+    sequence: DataStorage,
+    count: usize,
+    length: usize,
+) -> XTypesResult<Vec<DataStorage>> {
+    macro_rules! split {
+        ($sequence:expr, $count:expr, $length:expr; $($variant:ident),*) => {
+            match $sequence {
+                $(DataStorage::$variant(x) if x.len() == $count * $length => {
+                    let mut elements = x.into_iter();
+                    Ok((0..$count)
+                        .map(|_| DataStorage::$variant(elements.by_ref().take($length).collect()))
+                        .collect())
+                })*
+                _ => Err(XTypesError::InvalidType),
+            }
+        };
+    }
+    sequence_storage_variants!(split!(sequence, count, length))
+}
+
+impl<K: DataStorageMapping + Ord, V: DataStorageMapping> DataStorageMapping for BTreeMap<K, V> {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.into_storage(), v.into_storage()))
+                .collect(),
+        )
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::Map(x) => x
+                .into_iter()
+                .map(|(k, v)| Ok((K::try_from_storage(k)?, V::try_from_storage(v)?)))
+                .collect(),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DataStorageMapping for std::collections::HashMap<K, V, S>
+where
+    K: DataStorageMapping + Eq + core::hash::Hash,
+    V: DataStorageMapping,
+    S: core::hash::BuildHasher + Default,
+{
+    fn into_storage(self) -> DataStorage {
+        DataStorage::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.into_storage(), v.into_storage()))
+                .collect(),
+        )
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::Map(x) => x
+                .into_iter()
+                .map(|(k, v)| Ok((K::try_from_storage(k)?, V::try_from_storage(v)?)))
+                .collect(),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
 impl<T: DataStorageMapping> DataStorageMapping for Option<T> {
     fn into_storage(self) -> DataStorage {
         T::into_storage(self.expect("Only options with value are converted. This usually indicats a member annotation #[dust_dds(optional)] is missing."))
@@ -1,4 +1,5 @@
 use crate::xtypes::{
+    data_storage::DataStorage,
     dynamic_type::{
         DynamicData, DynamicDataFactory, DynamicType, DynamicTypeMember, ExtensibilityKind,
         TryConstructKind, TypeKind,
@@ -7,8 +8,9 @@ use crate::xtypes::{
         XTypesError::{self, PidNotFound},
         XTypesResult,
     },
+    serializer::bitset_bit_bound,
     type_object::TypeIdentifier,
-    type_support::TypeSupport,
+    type_support::{BitfieldHolder, TypeSupport},
 };
 use alloc::{string::String, vec::Vec};
 use tracing::debug;
@@ -121,23 +123,20 @@ trait EncodingVersion: Sized {
     /// Serialization Rule (9) & (10)
     fn deserialize_array_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()>;
+        array_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage>;
 
     /// Serialization Rule (12)
     fn deserialize_sequence_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()>;
+        sequence_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage>;
 
     /// Serialization Rule (15) & (16)
-    fn _deserialize_map_type<'a, E: EndiannessRead>(
-        _deserializer: &mut XTypesDeserializer<'a, E, Self>,
-    ) -> XTypesResult<()> {
-        todo!()
-    }
+    fn deserialize_map_type<'a, E: EndiannessRead>(
+        deserializer: &mut XTypesDeserializer<'a, E, Self>,
+        map_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage>;
 
     /// Serialization Rule (19) & (20)
     fn deserialize_opt_fmember<'a, E: EndiannessRead>(
@@ -223,17 +222,10 @@ impl EncodingVersion for EncodingVersion1 {
     ///                    << { O[i] : O.element_type }*
     fn deserialize_array_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
-        let bound = *member
-            .descriptor
-            .r#type
-            .descriptor
-            .bound
-            .first()
-            .ok_or(XTypesError::InvalidType)?;
-        deserializer.deserialize_sequence_elements(member, dynamic_data, bound as usize)
+        array_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
+        let length = array_length(array_type)?;
+        deserializer.deserialize_sequence_elements(array_type, length)
     }
 
     /// Sequences (any extensibility) using version 1 encoding
@@ -244,11 +236,25 @@ impl EncodingVersion for EncodingVersion1 {
     ///                     << { O[i] : O.element_type }*
     fn deserialize_sequence_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
+        sequence_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
         let length = deserializer.deserialize_primitive_type::<u32>()?;
-        deserializer.deserialize_sequence_elements(member, dynamic_data, length as usize)
+        deserializer.deserialize_sequence_elements(sequence_type, length as usize)
+    }
+
+    /// Maps (any extensibility) using version 1 encoding
+    ///
+    /// (16) XCDR[1] << {O : MAP_TYPE} =
+    ///                   XCDR
+    ///                     << { O.length : UInt32 }
+    ///                     << { O[i].key : O.key_element_type,
+    ///                          O[i].value : O.element_type }*
+    fn deserialize_map_type<'a, E: EndiannessRead>(
+        deserializer: &mut XTypesDeserializer<'a, E, Self>,
+        map_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
+        let length = deserializer.deserialize_primitive_type::<u32>()?;
+        deserializer.deserialize_map_entries(map_type, length as usize)
     }
 
     /// Optional member of final Aggregated type (structure, union), version 1
@@ -419,18 +425,11 @@ impl EncodingVersion for EncodingVersion2 {
     ///                   << { O[i] : O.element_type }*
     fn deserialize_array_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
+        array_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
         let _dheader = deserializer.deserialize_primitive_type::<u32>()?;
-        let bound = *member
-            .descriptor
-            .r#type
-            .descriptor
-            .bound
-            .first()
-            .ok_or(XTypesError::InvalidType)?;
-        deserializer.deserialize_sequence_elements(member, dynamic_data, bound as usize)
+        let length = array_length(array_type)?;
+        deserializer.deserialize_sequence_elements(array_type, length)
     }
 
     /// Sequences (any extensibility) using version 2 encoding
@@ -442,12 +441,28 @@ impl EncodingVersion for EncodingVersion2 {
     ///                    << { O[i] : O.element_type }*
     fn deserialize_sequence_type<'a, E: EndiannessRead>(
         deserializer: &mut XTypesDeserializer<'a, E, Self>,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
+        sequence_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
+        let _dheader = deserializer.deserialize_primitive_type::<u32>()?;
+        let length = deserializer.deserialize_primitive_type::<u32>()?;
+        deserializer.deserialize_sequence_elements(sequence_type, length as usize)
+    }
+
+    /// Maps (any extensibility) using version 2 encoding
+    ///
+    /// (15) XCDR[2] << {O : MAP_TYPE} =
+    ///                  XCDR
+    ///                    << { DHEADER(O) : UINT32 }
+    ///                    << { O.length : UINT32 }
+    ///                    << { O[i].key : O.key_element_type,
+    ///                         O[i].value : O.element_type }*
+    fn deserialize_map_type<'a, E: EndiannessRead>(
+        deserializer: &mut XTypesDeserializer<'a, E, Self>,
+        map_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
         let _dheader = deserializer.deserialize_primitive_type::<u32>()?;
         let length = deserializer.deserialize_primitive_type::<u32>()?;
-        deserializer.deserialize_sequence_elements(member, dynamic_data, length as usize)
+        deserializer.deserialize_map_entries(map_type, length as usize)
     }
 
     /// Optional member of final aggregated type (structure, union), version 2
@@ -621,15 +636,42 @@ struct XTypesDeserializer<'a, E, V> {
     _encoding_version: V,
 }
 
-fn is_element_type_kind_primitive(member: &DynamicTypeMember) -> XTypesResult<bool> {
-    Ok(matches!(
-        member
-            .descriptor
-            .r#type
+fn is_element_type_kind_primitive(collection_type: DynamicType) -> XTypesResult<bool> {
+    Ok(is_type_kind_primitive(
+        collection_type
             .descriptor
             .element_type
             .ok_or(XTypesError::InvalidType)?
             .get_kind(),
+    ))
+}
+
+fn is_map_type_primitive(map_type: DynamicType) -> XTypesResult<bool> {
+    Ok(is_type_kind_primitive(
+        map_type
+            .descriptor
+            .key_element_type
+            .ok_or(XTypesError::InvalidType)?
+            .get_kind(),
+    ) && is_element_type_kind_primitive(map_type)?)
+}
+
+/// Number of elements of an array, which is the product of all its dimensions.
+fn array_length(array_type: DynamicType) -> XTypesResult<usize> {
+    if array_type.descriptor.bound.is_empty() {
+        return Err(XTypesError::InvalidType);
+    }
+    Ok(array_type
+        .descriptor
+        .bound
+        .iter()
+        .map(|&dimension| dimension as usize)
+        .product())
+}
+
+fn is_type_kind_primitive(kind: TypeKind) -> bool {
+    matches!(
+        kind,
         TypeKind::BOOLEAN
             | TypeKind::BYTE
             | TypeKind::INT16
@@ -645,7 +687,7 @@ fn is_element_type_kind_primitive(member: &DynamicTypeMember) -> XTypesResult<bo
             | TypeKind::UINT8
             | TypeKind::CHAR8
             | TypeKind::CHAR16
-    ))
+    )
 }
 
 impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
@@ -670,10 +712,9 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
     /// Serialization rule: { O[i] : O.element_type }*
     fn deserialize_sequence_elements(
         &mut self,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
+        collection_type: DynamicType<'static>,
         length: usize,
-    ) -> XTypesResult<()> {
+    ) -> XTypesResult<DataStorage> {
         fn deserialize_primitive_sequence_elements<
             'a,
             O: AsBytes + Align,
@@ -690,120 +731,129 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
             Ok(sequence)
         }
 
-        let element_type = member
-            .descriptor
-            .r#type
+        let element_type = collection_type
             .descriptor
             .element_type
             .ok_or(XTypesError::InvalidType)?;
-        match element_type.get_kind() {
+        Ok(match element_type.get_kind() {
             TypeKind::NONE => todo!(),
-            TypeKind::BOOLEAN => dynamic_data.set_boolean_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::BYTE => {
-                let bytes = self.reader.read_bytes(length)?;
-                dynamic_data.set_byte_values(member.get_id(), bytes.to_vec())
+            TypeKind::BOOLEAN => {
+                DataStorage::SequenceBoolean(deserialize_primitive_sequence_elements(self, length)?)
             }
-            TypeKind::INT16 => dynamic_data.set_int16_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::INT32 => dynamic_data.set_int32_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::INT64 => dynamic_data.set_int64_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::UINT16 => dynamic_data.set_uint16_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::UINT32 => dynamic_data.set_uint32_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::UINT64 => dynamic_data.set_uint64_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::FLOAT32 => dynamic_data.set_float32_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::FLOAT64 => dynamic_data.set_float64_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::FLOAT128 => dynamic_data.set_float128_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::INT8 => dynamic_data.set_int8_values(
-                member.get_id(),
-                deserialize_primitive_sequence_elements(self, length)?,
-            ),
-            TypeKind::UINT8 => {
-                let bytes = self.reader.read_bytes(length)?;
-                dynamic_data.set_uint8_values(member.get_id(), bytes.to_vec())
+            TypeKind::BYTE | TypeKind::UINT8 => {
+                DataStorage::SequenceUInt8(self.reader.read_bytes(length)?.to_vec())
+            }
+            TypeKind::INT16 => {
+                DataStorage::SequenceInt16(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::INT32 => {
+                DataStorage::SequenceInt32(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::INT64 => {
+                DataStorage::SequenceInt64(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::UINT16 => {
+                DataStorage::SequenceUInt16(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::UINT32 => {
+                DataStorage::SequenceUInt32(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::UINT64 => {
+                DataStorage::SequenceUInt64(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::FLOAT32 => {
+                DataStorage::SequenceFloat32(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::FLOAT64 => {
+                DataStorage::SequenceFloat64(deserialize_primitive_sequence_elements(self, length)?)
             }
-            TypeKind::CHAR8 => dynamic_data.set_char8_values(
-                member.get_id(),
+            TypeKind::FLOAT128 => DataStorage::SequenceFloat128(
                 deserialize_primitive_sequence_elements(self, length)?,
             ),
-            TypeKind::CHAR16 => todo!(),
+            TypeKind::INT8 => {
+                DataStorage::SequenceInt8(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::CHAR8 => {
+                DataStorage::SequenceChar8(deserialize_primitive_sequence_elements(self, length)?)
+            }
+            TypeKind::CHAR16 => {
+                let mut values = Vec::with_capacity(length);
+                for _ in 0..length {
+                    values.push(self.deserialize_char16_type()?);
+                }
+                DataStorage::SequenceChar16(values)
+            }
             TypeKind::STRING8 => {
                 let mut values = Vec::with_capacity(length);
                 for _ in 0..length {
                     values.push(self.deserialize_string_type()?);
                 }
-                dynamic_data.set_string_values(member.get_id(), values)
+                DataStorage::SequenceString(values)
             }
             TypeKind::STRING16 => {
                 let mut values = Vec::with_capacity(length);
                 for _ in 0..length {
                     values.push(self.deserialize_wstring_type()?);
                 }
-                dynamic_data.set_string_values(member.get_id(), values)
+                DataStorage::SequenceString(values)
             }
             TypeKind::ALIAS => todo!(),
             TypeKind::BITMASK => {
                 let bound = element_type.descriptor.bound.first().copied().unwrap_or(32);
                 match bound {
-                    1..=8 => dynamic_data.set_uint8_values(
-                        member.get_id(),
-                        deserialize_primitive_sequence_elements(self, length)?,
-                    ),
-                    9..=16 => dynamic_data.set_uint16_values(
-                        member.get_id(),
-                        deserialize_primitive_sequence_elements(self, length)?,
-                    ),
-                    17..=32 => dynamic_data.set_uint32_values(
-                        member.get_id(),
-                        deserialize_primitive_sequence_elements(self, length)?,
-                    ),
-                    _ => dynamic_data.set_uint64_values(
-                        member.get_id(),
+                    1..=8 => DataStorage::SequenceUInt8(deserialize_primitive_sequence_elements(
+                        self, length,
+                    )?),
+                    9..=16 => DataStorage::SequenceUInt16(deserialize_primitive_sequence_elements(
+                        self, length,
+                    )?),
+                    17..=32 => DataStorage::SequenceUInt32(
                         deserialize_primitive_sequence_elements(self, length)?,
                     ),
+                    _ => DataStorage::SequenceUInt64(deserialize_primitive_sequence_elements(
+                        self, length,
+                    )?),
                 }
             }
             TypeKind::ANNOTATION => todo!(),
-            TypeKind::ENUM | TypeKind::STRUCTURE | TypeKind::UNION => {
+            TypeKind::ENUM | TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::BITSET => {
                 let mut values = Vec::with_capacity(length);
                 for _ in 0..length {
                     values.push(self.deserialize_as_nested(element_type)?);
                 }
-                dynamic_data.set_complex_values(member.get_id(), values)
+                DataStorage::SequenceComplexValue(values)
+            }
+            TypeKind::SEQUENCE | TypeKind::ARRAY | TypeKind::MAP => {
+                let mut values = Vec::with_capacity(length);
+                for _ in 0..length {
+                    values.push(self.deserialize_data(element_type)?);
+                }
+                DataStorage::SequenceCollection(values)
             }
-            TypeKind::BITSET => todo!(),
-            TypeKind::SEQUENCE => todo!(),
-            TypeKind::ARRAY => todo!(),
-            TypeKind::MAP => todo!(),
+        })
+    }
+
+    /// Serialization rule: { O[i].key : O.key_element_type, O[i].value : O.element_type }*
+    fn deserialize_map_entries(
+        &mut self,
+        map_type: DynamicType<'static>,
+        length: usize,
+    ) -> XTypesResult<DataStorage> {
+        let key_type = map_type
+            .descriptor
+            .key_element_type
+            .ok_or(XTypesError::InvalidType)?;
+        let element_type = map_type
+            .descriptor
+            .element_type
+            .ok_or(XTypesError::InvalidType)?;
+        let mut entries = Vec::new();
+        for _ in 0..length {
+            let key = self.deserialize_data(key_type)?;
+            let value = self.deserialize_data(element_type)?;
+            entries.push((key, value));
         }
+        Ok(DataStorage::Map(entries))
     }
 
     /// Serialization rule: { O : AsNested(O.type) }
@@ -831,8 +881,9 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
                 }
                 ExtensibilityKind::Mutable => V::deserialize_munion_type(self, &mut dynamic_data)?,
             },
+            TypeKind::BITSET => self.deserialize_bitset_type(&mut dynamic_data)?,
             kind => {
-                debug!("Expected structure, enum or union. Got kind {kind:?} ");
+                debug!("Expected structure, enum, union or bitset. Got kind {kind:?} ");
                 Err(XTypesError::InvalidType)?
             }
         }
@@ -852,83 +903,70 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
         } else {
             member_descriptor.r#type
         };
-        match member_type.get_kind() {
-            TypeKind::NONE => Ok(()),
-            TypeKind::BOOLEAN => dynamic_data
-                .set_boolean_value(member.get_id(), self.deserialize_primitive_type::<bool>()?),
-            TypeKind::BYTE => dynamic_data
-                .set_byte_value(member.get_id(), self.deserialize_primitive_type::<u8>()?),
-            TypeKind::INT16 => dynamic_data
-                .set_int16_value(member.get_id(), self.deserialize_primitive_type::<i16>()?),
-            TypeKind::INT32 => dynamic_data
-                .set_int32_value(member.get_id(), self.deserialize_primitive_type::<i32>()?),
-            TypeKind::INT64 => dynamic_data
-                .set_int64_value(member.get_id(), self.deserialize_primitive_type::<i64>()?),
-            TypeKind::UINT16 => dynamic_data
-                .set_uint16_value(member.get_id(), self.deserialize_primitive_type::<u16>()?),
-            TypeKind::UINT32 => dynamic_data
-                .set_uint32_value(member.get_id(), self.deserialize_primitive_type::<u32>()?),
-            TypeKind::UINT64 => dynamic_data
-                .set_uint64_value(member.get_id(), self.deserialize_primitive_type::<u64>()?),
-            TypeKind::FLOAT32 => dynamic_data
-                .set_float32_value(member.get_id(), self.deserialize_primitive_type::<f32>()?),
-            TypeKind::FLOAT64 => dynamic_data
-                .set_float64_value(member.get_id(), self.deserialize_primitive_type::<f64>()?),
-            TypeKind::FLOAT128 => dynamic_data
-                .set_float128_value(member.get_id(), self.deserialize_primitive_type::<i128>()?),
-            TypeKind::INT8 => dynamic_data
-                .set_int8_value(member.get_id(), self.deserialize_primitive_type::<i8>()?),
-            TypeKind::UINT8 => dynamic_data
-                .set_uint8_value(member.get_id(), self.deserialize_primitive_type::<u8>()?),
-            TypeKind::CHAR8 => dynamic_data
-                .set_char8_value(member.get_id(), self.deserialize_primitive_type::<char>()?),
-            TypeKind::CHAR16 => todo!(),
-            TypeKind::STRING8 => {
-                dynamic_data.set_string_value(member.get_id(), self.deserialize_string_type()?)
-            }
-            TypeKind::STRING16 => {
-                dynamic_data.set_string_value(member.get_id(), self.deserialize_wstring_type()?)
+        if member_type.get_kind() != TypeKind::NONE {
+            dynamic_data.set_value(member.get_id(), self.deserialize_data(member_type)?);
+        }
+        Ok(())
+    }
+
+    /// Serialization rule: { O : O.type }
+    fn deserialize_data(&mut self, data_type: DynamicType<'static>) -> XTypesResult<DataStorage> {
+        Ok(match data_type.get_kind() {
+            TypeKind::NONE => todo!(),
+            TypeKind::BOOLEAN => DataStorage::Boolean(self.deserialize_primitive_type()?),
+            TypeKind::BYTE | TypeKind::UINT8 => {
+                DataStorage::UInt8(self.deserialize_primitive_type()?)
             }
+            TypeKind::INT16 => DataStorage::Int16(self.deserialize_primitive_type()?),
+            TypeKind::INT32 => DataStorage::Int32(self.deserialize_primitive_type()?),
+            TypeKind::INT64 => DataStorage::Int64(self.deserialize_primitive_type()?),
+            TypeKind::UINT16 => DataStorage::UInt16(self.deserialize_primitive_type()?),
+            TypeKind::UINT32 => DataStorage::UInt32(self.deserialize_primitive_type()?),
+            TypeKind::UINT64 => DataStorage::UInt64(self.deserialize_primitive_type()?),
+            TypeKind::FLOAT32 => DataStorage::Float32(self.deserialize_primitive_type()?),
+            TypeKind::FLOAT64 => DataStorage::Float64(self.deserialize_primitive_type()?),
+            TypeKind::FLOAT128 => DataStorage::Float128(self.deserialize_primitive_type()?),
+            TypeKind::INT8 => DataStorage::Int8(self.deserialize_primitive_type()?),
+            TypeKind::CHAR8 => DataStorage::Char8(self.deserialize_primitive_type()?),
+            TypeKind::CHAR16 => DataStorage::Char16(self.deserialize_char16_type()?),
+            TypeKind::STRING8 => DataStorage::String(self.deserialize_string_type()?),
+            TypeKind::STRING16 => DataStorage::String(self.deserialize_wstring_type()?),
             TypeKind::ALIAS => todo!(),
-            TypeKind::ENUM | TypeKind::STRUCTURE | TypeKind::UNION => dynamic_data
-                .set_complex_value(member.get_id(), self.deserialize_as_nested(member_type)?),
+            TypeKind::ENUM | TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::BITSET => {
+                DataStorage::ComplexValue(self.deserialize_as_nested(data_type)?)
+            }
             TypeKind::BITMASK => {
-                let bound = member_type.descriptor.bound.first().copied().unwrap_or(32);
+                let bound = data_type.descriptor.bound.first().copied().unwrap_or(32);
                 match bound {
-                    1..=8 => dynamic_data
-                        .set_uint8_value(member.get_id(), self.deserialize_primitive_type::<u8>()?),
-                    9..=16 => dynamic_data.set_uint16_value(
-                        member.get_id(),
-                        self.deserialize_primitive_type::<u16>()?,
-                    ),
-                    17..=32 => dynamic_data.set_uint32_value(
-                        member.get_id(),
-                        self.deserialize_primitive_type::<u32>()?,
-                    ),
-                    _ => dynamic_data.set_uint64_value(
-                        member.get_id(),
-                        self.deserialize_primitive_type::<u64>()?,
-                    ),
+                    1..=8 => DataStorage::UInt8(self.deserialize_primitive_type()?),
+                    9..=16 => DataStorage::UInt16(self.deserialize_primitive_type()?),
+                    17..=32 => DataStorage::UInt32(self.deserialize_primitive_type()?),
+                    _ => DataStorage::UInt64(self.deserialize_primitive_type()?),
                 }
             }
             TypeKind::ANNOTATION => todo!(),
-            TypeKind::BITSET => todo!(),
             TypeKind::SEQUENCE => {
-                if is_element_type_kind_primitive(member)? {
-                    self.deserialize_psequence_type(member, dynamic_data)
+                if is_element_type_kind_primitive(data_type)? {
+                    self.deserialize_psequence_type(data_type)?
                 } else {
-                    V::deserialize_sequence_type(self, member, dynamic_data)
+                    V::deserialize_sequence_type(self, data_type)?
                 }
             }
             TypeKind::ARRAY => {
-                if is_element_type_kind_primitive(member)? {
-                    self.deserialize_parray_type(member, dynamic_data)
+                if is_element_type_kind_primitive(data_type)? {
+                    self.deserialize_parray_type(data_type)?
                 } else {
-                    V::deserialize_array_type(self, member, dynamic_data)
+                    V::deserialize_array_type(self, data_type)?
                 }
             }
-            TypeKind::MAP => todo!(),
-        }
+            TypeKind::MAP => {
+                if is_map_type_primitive(data_type)? {
+                    self.deserialize_pmap_type(data_type)?
+                } else {
+                    V::deserialize_map_type(self, data_type)?
+                }
+            }
+        })
     }
 
     /// Serialization rule: { O.member : FMEMBER }
@@ -966,24 +1004,62 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
         String::from_utf8(values).map_err(|_| XTypesError::InvalidData)
     }
 
+    /// (4) XCDR << {O : WSTRING_TYPE} =
+    ///               XCDR
+    ///                << { O.ssize : UInt32 } // number of bytes, no NUL
+    ///                << { O[i] : Char16 }* // no NUL
     fn deserialize_wstring_type(&mut self) -> XTypesResult<String> {
-        let length = self.deserialize_primitive_type::<u32>()?;
-        if length == 0 {
-            return Ok(String::new());
-        }
-        let num_units = length.saturating_sub(1) as usize;
-        let mut units = Vec::with_capacity(num_units);
-        for _ in 0..num_units {
-            let unit = self.deserialize_primitive_type::<u16>()?;
-            units.push(unit);
-        }
-        let nul_unit = self.deserialize_primitive_type::<u16>()?;
-        if nul_unit != 0 {
+        let length = self.deserialize_primitive_type::<u32>()? as usize;
+        if !length.is_multiple_of(2) {
             return Err(XTypesError::InvalidData);
         }
+        let mut units = Vec::new();
+        for _ in 0..length / 2 {
+            units.push(self.deserialize_primitive_type::<u16>()?);
+        }
         String::from_utf16(&units).map_err(|_| XTypesError::InvalidData)
     }
 
+    /// Wide characters are a single UTF-16 code unit deserialized as a primitive type
+    fn deserialize_char16_type(&mut self) -> XTypesResult<char> {
+        let unit = self.deserialize_primitive_type::<u16>()?;
+        char::from_u32(unit as u32).ok_or(XTypesError::InvalidData)
+    }
+
+    /// Bitsets are serialized as the smallest unsigned integer holding all their bitfields.
+    fn deserialize_bitset_type(&mut self, dynamic_data: &mut DynamicData) -> XTypesResult<()> {
+        let bitset_type = dynamic_data.r#type();
+        let bits = match bitset_bit_bound(bitset_type)? {
+            0..=8 => self.deserialize_primitive_type::<u8>()? as u64,
+            9..=16 => self.deserialize_primitive_type::<u16>()? as u64,
+            17..=32 => self.deserialize_primitive_type::<u32>()? as u64,
+            _ => self.deserialize_primitive_type::<u64>()?,
+        };
+        for (member, &bitcount) in bitset_type
+            .member_list
+            .iter()
+            .zip(bitset_type.descriptor.bound)
+        {
+            let field = bits.checked_shr(member.get_id()).unwrap_or(0);
+            let value = match member.descriptor.r#type.get_kind() {
+                TypeKind::BOOLEAN => DataStorage::Boolean(bool::from_bits(field, bitcount)),
+                TypeKind::BYTE | TypeKind::UINT8 => {
+                    DataStorage::UInt8(u8::from_bits(field, bitcount))
+                }
+                TypeKind::INT8 => DataStorage::Int8(i8::from_bits(field, bitcount)),
+                TypeKind::UINT16 => DataStorage::UInt16(u16::from_bits(field, bitcount)),
+                TypeKind::INT16 => DataStorage::Int16(i16::from_bits(field, bitcount)),
+                TypeKind::UINT32 => DataStorage::UInt32(u32::from_bits(field, bitcount)),
+                TypeKind::INT32 => DataStorage::Int32(i32::from_bits(field, bitcount)),
+                TypeKind::UINT64 => DataStorage::UInt64(u64::from_bits(field, bitcount)),
+                TypeKind::INT64 => DataStorage::Int64(i64::from_bits(field, bitcount)),
+                _ => return Err(XTypesError::InvalidType),
+            };
+            dynamic_data.set_value(member.get_id(), value);
+        }
+        Ok(())
+    }
+
     /// (5) XCDR << {O : ENUM_TYPE} =
     ///              XCDR
     ///                << { O.value : O.holder_type }
@@ -1053,17 +1129,10 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
     ///                << { O[i] : O.element_type }*
     fn deserialize_parray_type(
         &mut self,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
-        let bound = *member
-            .descriptor
-            .r#type
-            .descriptor
-            .bound
-            .first()
-            .ok_or(XTypesError::InvalidType)?;
-        self.deserialize_sequence_elements(member, dynamic_data, bound as usize)
+        array_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
+        let length = array_length(array_type)?;
+        self.deserialize_sequence_elements(array_type, length)
     }
 
     /// Sequences of primitive element type (version 1 and 2 encoding)
@@ -1074,16 +1143,25 @@ impl<'a, E: EndiannessRead, V: EncodingVersion> XTypesDeserializer<'a, E, V> {
     ///                 << { O[i] : O.element_type }*
     fn deserialize_psequence_type(
         &mut self,
-        member: &DynamicTypeMember,
-        dynamic_data: &mut DynamicData,
-    ) -> XTypesResult<()> {
+        sequence_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
         let length = self.deserialize_primitive_type::<u32>()?;
-        self.deserialize_sequence_elements(member, dynamic_data, length as usize)
+        self.deserialize_sequence_elements(sequence_type, length as usize)
     }
 
-    /// Serialization Rule (14)
-    fn _deserialize_pmap_type(&mut self) -> XTypesResult<()> {
-        todo!()
+    /// Maps of primitive key and element types (version 1 and 2 encoding)
+    ///
+    /// (14) XCDR << { O : PMAP_TYPE } =
+    ///               XCDR
+    ///                 << { O.length : UInt32 }
+    ///                 << { O[i].key : O.key_element_type,
+    ///                      O[i].value : O.element_type }*
+    fn deserialize_pmap_type(
+        &mut self,
+        map_type: DynamicType<'static>,
+    ) -> XTypesResult<DataStorage> {
+        let length = self.deserialize_primitive_type::<u32>()?;
+        self.deserialize_map_entries(map_type, length as usize)
     }
 
     /// Structures with extensibility FINAL (version 1 and 2 encoding)
@@ -1335,7 +1413,7 @@ mod tests {
                 TypeIdentifier, TypeIdentifierWithDependencies, TypeIdentifierWithSize,
                 TypeInformation,
             },
-            type_support::{Type, TypeSupport},
+            type_support::{Type, TypeSupport, WChar, WString},
         },
     };
 
@@ -1886,6 +1964,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_wide_characters() {
+        #[derive(Debug, PartialEq, TypeSupport)]
+        struct WideCharacters {
+            initial: WChar,
+            message: WString,
+        }
+
+        let expected = WideCharacters {
+            initial: WChar::try_from('Ω').unwrap(),
+            message: WString::from(String::from("añ")),
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            deserialize_top_level_type(
+                WideCharacters::TYPE,
+                &[
+                    0x00, 0x00, 0x00, 0x00, // CDR_BE
+                    0x03, 0xa9, 0, 0, // initial | padding (2 bytes)
+                    0, 0, 0, 4, // message: length in bytes
+                    0, b'a', 0, 0xf1, // message
+                ],
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            deserialize_top_level_type(
+                WideCharacters::TYPE,
+                &[
+                    0x00, 0x00, 0x00, 0x00, // CDR_BE
+                    0x03, 0xa9, 0, 0, // initial | padding (2 bytes)
+                    0, 0, 0, 3, // message: odd length in bytes
+                    0, b'a', 0, 0xf1, // message
+                ],
+            ),
+            Err(XTypesError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn deserialize_nested_collections() {
+        #[derive(Debug, PartialEq, TypeSupport)]
+        struct NestedCollections {
+            sequences: Vec<Vec<u16>>,
+            matrix: [[u8; 2]; 2],
+        }
+
+        let expected = NestedCollections {
+            sequences: vec![vec![1, 2], vec![3]],
+            matrix: [[1, 2], [3, 4]],
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            deserialize_top_level_type(
+                NestedCollections::TYPE,
+                &[
+                    0x00, 0x00, 0x00, 0x00, // CDR_BE
+                    0, 0, 0, 2, // sequences: length
+                    0, 0, 0, 2, // sequences[0]: length
+                    0, 1, 0, 2, // sequences[0]: elements
+                    0, 0, 0, 1, // sequences[1]: length
+                    0, 3, 1, 2, // sequences[1]: elements | matrix[0]
+                    3, 4, // matrix[1]
+                ],
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn deserialize_map() {
+        #[derive(Debug, PartialEq, TypeSupport)]
+        struct Parameters {
+            ids: alloc::collections::BTreeMap<u16, u16>,
+            names: alloc::collections::BTreeMap<String, u8>,
+        }
+
+        let expected = Parameters {
+            ids: [(1, 10), (2, 20)].into(),
+            names: [("a".into(), 1)].into(),
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            deserialize_top_level_type(
+                Parameters::TYPE,
+                &[
+                    0x00, 0x00, 0x00, 0x01, // CDR_BE
+                    0, 0, 0, 2, // ids length
+                    0, 1, 0, 10, // ids key | value
+                    0, 2, 0, 20, // ids key | value
+                    0, 0, 0, 1, // names length
+                    0, 0, 0, 2, // String length
+                    b'a', 0, 1, 0, // String | value | padding
+                ],
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            deserialize_top_level_type(
+                Parameters::TYPE,
+                &[
+                    0x00, 0x06, 0x00, 0x01, // CDR2_BE
+                    0, 0, 0, 2, // ids length
+                    0, 1, 0, 10, // ids key | value
+                    0, 2, 0, 20, // ids key | value
+                    0, 0, 0, 11, // names DHEADER
+                    0, 0, 0, 1, // names length
+                    0, 0, 0, 2, // String length
+                    b'a', 0, 1, 0, // String | value | padding
+                ],
+            )
+            .unwrap(),
+            expected
+        );
+    }
+
     #[test]
     fn deserialize_mutable_struct() {
         #[derive(Debug, PartialEq, TypeSupport)]
@@ -2078,6 +2275,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_bitset() {
+        #[derive(Debug, PartialEq, TypeSupport)]
+        #[dust_dds(bitset)]
+        struct Flags {
+            #[dust_dds(bits = 1)]
+            enabled: bool,
+            #[dust_dds(bits = 4, position = 4)]
+            level: i8,
+            #[dust_dds(bits = 3)]
+            mode: u16,
+        }
+        #[derive(Debug, PartialEq, TypeSupport)]
+        struct Status {
+            id: u8,
+            flags: Flags,
+        }
+        assert_eq!(
+            deserialize_top_level_type(
+                Status::TYPE,
+                &[
+                    0x00, 0x01, 0x00, 0x00, // CDR_LE
+                    3, 0, // id | padding
+                    0xe1, 0x05, // flags (u16)
+                ],
+            )
+            .unwrap(),
+            Status {
+                id: 3,
+                flags: Flags {
+                    enabled: true,
+                    level: -2,
+                    mode: 5,
+                },
+            }
+            .create_dynamic_sample()
+        );
+    }
+
     #[test]
     fn deserialize_appendable_shapes() {
         #[derive(Debug, PartialEq, TypeSupport)]
@@ -199,11 +199,24 @@ impl DynamicTypeBuilderFactory {
 
     /// Creates a [`DynamicTypeBuilder`] for a map type with the specified key, element type, and bound.
     pub fn create_map_type(
-        _key_element_type: DynamicType,
-        _element_type: DynamicType,
-        _bound: u32,
+        key_element_type: DynamicType<'static>,
+        element_type: DynamicType<'static>,
+        bound: u32,
     ) -> DynamicTypeBuilder {
-        todo!()
+        DynamicTypeBuilder {
+            descriptor: TypeDescriptor {
+                kind: TypeKind::MAP,
+                name: "",
+                base_type: None,
+                discriminator_type: None,
+                bound: vec![bound].leak(),
+                element_type: Some(element_type),
+                key_element_type: Some(key_element_type),
+                extensibility_kind: ExtensibilityKind::Final,
+                is_nested: false,
+            },
+            member_list: Vec::new(),
+        }
     }
 
     /// Creates a [`DynamicTypeBuilder`] for a bitmask type with the specified bound.
@@ -305,13 +318,11 @@ impl DynamicTypeBuilderFactory {
             }
         };
 
-        let resolve_member_type = |m_type: &str,
-                                   non_basic_type_name: Option<&str>,
-                                   string_max_length: Option<&str>,
-                                   array_dimensions: Option<&str>,
-                                   sequence_max_length: Option<&str>|
+        let resolve_base_type = |m_type: &str,
+                                 non_basic_type_name: Option<&str>,
+                                 string_max_length: Option<&str>|
          -> XTypesResult<DynamicType> {
-            let mut type_ptr: DynamicType = if m_type == "nonBasic" {
+            Ok(if m_type == "nonBasic" {
                 let non_basic_name = non_basic_type_name.ok_or(XTypesError::InvalidData)?;
                 let mut full_name = module_path.join("::");
                 if !full_name.is_empty() {
@@ -332,16 +343,36 @@ impl DynamicTypeBuilderFactory {
             } else {
                 let type_kind = parse_type_kind(m_type)?;
                 Self::get_primitive_type(type_kind)
-            };
+            })
+        };
+
+        let resolve_member_type = |node: &roxmltree::Node| -> XTypesResult<DynamicType> {
+            let mut type_ptr = resolve_base_type(
+                node.attribute("type").ok_or(XTypesError::InvalidData)?,
+                node.attribute("nonBasicTypeName"),
+                node.attribute("stringMaxLength"),
+            )?;
+
+            if let Some(map_len_str) = node.attribute("mapMaxLength") {
+                let bound: i32 = map_len_str.parse().map_err(|_| XTypesError::InvalidData)?;
+                let bound_u32 = if bound < 0 { 0 } else { bound as u32 };
+                let key_type = resolve_base_type(
+                    node.attribute("key_type").ok_or(XTypesError::InvalidData)?,
+                    node.attribute("key_nonBasicTypeName"),
+                    node.attribute("key_stringMaxLength"),
+                )?;
+                let builder = Self::create_map_type(key_type, type_ptr, bound_u32);
+                type_ptr = builder.build();
+            }
 
-            if let Some(seq_len_str) = sequence_max_length {
+            if let Some(seq_len_str) = node.attribute("sequenceMaxLength") {
                 let bound: i32 = seq_len_str.parse().map_err(|_| XTypesError::InvalidData)?;
                 let bound_u32 = if bound < 0 { 0 } else { bound as u32 };
                 let builder = Self::create_sequence_type(type_ptr, bound_u32);
                 type_ptr = builder.build();
             }
 
-            if let Some(dimensions) = array_dimensions {
+            if let Some(dimensions) = node.attribute("arrayDimensions") {
                 let dims: Vec<u32> = dimensions
                     .split(',')
                     .filter_map(|s| s.trim().parse().ok())
@@ -356,17 +387,7 @@ impl DynamicTypeBuilderFactory {
         if is_union {
             for child in target_node.children() {
                 if child.is_element() && child.tag_name().name() == "discriminator" {
-                    let d_type = child.attribute("type").ok_or(XTypesError::InvalidData)?;
-                    let non_basic_type_name = child.attribute("nonBasicTypeName");
-                    let string_max_length = child.attribute("stringMaxLength");
-                    let sequence_max_length = child.attribute("sequenceMaxLength");
-                    let type_ptr = resolve_member_type(
-                        d_type,
-                        non_basic_type_name,
-                        string_max_length,
-                        None,
-                        sequence_max_length,
-                    )?;
+                    let type_ptr = resolve_member_type(&child)?;
                     discriminator_type = Some(type_ptr);
                     break;
                 }
@@ -447,12 +468,7 @@ impl DynamicTypeBuilderFactory {
                     index += 1;
                 }
                 if child.is_element() && child.tag_name().name() == "case" {
-                    let mut m_name = None;
-                    let mut m_type = None;
-                    let mut non_basic_type_name = None;
-                    let mut string_max_length = None;
-                    let mut array_dimensions = None;
-                    let mut sequence_max_length = None;
+                    let mut member_node = None;
                     let mut label = Vec::new();
                     let mut is_default_label = false;
 
@@ -480,26 +496,17 @@ impl DynamicTypeBuilderFactory {
                         } else if case_child.is_element()
                             && case_child.tag_name().name() == "member"
                         {
-                            m_name = case_child.attribute("name");
-                            m_type = case_child.attribute("type");
-                            non_basic_type_name = case_child.attribute("nonBasicTypeName");
-                            string_max_length = case_child.attribute("stringMaxLength");
-                            array_dimensions = case_child.attribute("arrayDimensions");
-                            sequence_max_length = case_child.attribute("sequenceMaxLength");
+                            member_node = Some(case_child);
                             parse_try_construct_kind(&case_child, &mut try_construct_kind);
                         }
                     }
 
-                    let m_name = m_name.ok_or(XTypesError::InvalidData)?;
-                    let m_type = m_type.ok_or(XTypesError::InvalidData)?;
+                    let member_node = member_node.ok_or(XTypesError::InvalidData)?;
+                    let m_name = member_node
+                        .attribute("name")
+                        .ok_or(XTypesError::InvalidData)?;
 
-                    let type_ptr = resolve_member_type(
-                        m_type,
-                        non_basic_type_name,
-                        string_max_length,
-                        array_dimensions,
-                        sequence_max_length,
-                    )?;
+                    let type_ptr = resolve_member_type(&member_node)?;
                     let m_name_static = Box::leak(m_name.to_string().into_boxed_str());
                     let label = Vec::leak(label);
                     let member_desc = MemberDescriptor {
@@ -559,19 +566,7 @@ impl DynamicTypeBuilderFactory {
             for child in target_node.children() {
                 if child.is_element() && child.tag_name().name() == "member" {
                     let m_name = child.attribute("name").ok_or(XTypesError::InvalidData)?;
-                    let m_type = child.attribute("type").ok_or(XTypesError::InvalidData)?;
-                    let non_basic_type_name = child.attribute("nonBasicTypeName");
-                    let string_max_length = child.attribute("stringMaxLength");
-                    let array_dimensions = child.attribute("arrayDimensions");
-                    let sequence_max_length = child.attribute("sequenceMaxLength");
-
-                    let type_ptr = resolve_member_type(
-                        m_type,
-                        non_basic_type_name,
-                        string_max_length,
-                        array_dimensions,
-                        sequence_max_length,
-                    )?;
+                    let type_ptr = resolve_member_type(&child)?;
                     let m_name_static = Box::leak(m_name.to_string().into_boxed_str());
 
                     let m_id = if let Some(id_str) = child.attribute("id") {
@@ -804,6 +799,27 @@ impl DynamicTypeBuilder {
             return Err(XTypesError::IllegalOperation);
         }
 
+        // The bound of a bitset holds the number of bits of each of its bitfields and the id of
+        // each bitfield is the position of its least significant bit
+        if self.descriptor.kind == TypeKind::BITSET {
+            let bitcount = *self
+                .descriptor
+                .bound
+                .get(self.member_list.len())
+                .ok_or(XTypesError::IllegalOperation)?;
+            let holder_bits = match descriptor.r#type.get_kind() {
+                TypeKind::BOOLEAN => 1,
+                TypeKind::BYTE | TypeKind::INT8 | TypeKind::UINT8 => 8,
+                TypeKind::INT16 | TypeKind::UINT16 => 16,
+                TypeKind::INT32 | TypeKind::UINT32 => 32,
+                TypeKind::INT64 | TypeKind::UINT64 => 64,
+                _ => return Err(XTypesError::InvalidType),
+            };
+            if bitcount == 0 || bitcount > holder_bits || descriptor.id > 64 - bitcount {
+                return Err(XTypesError::InvalidData);
+            }
+        }
+
         self.member_list.push(DynamicTypeMember { descriptor });
 
         Ok(())
@@ -1214,6 +1230,29 @@ impl<'a> DynamicData<'a> {
         Ok(())
     }
 
+    /// Gets the `char` (representing `char16`) value for the specified member.
+    pub fn get_char16_value(&self, id: MemberId) -> XTypesResult<&char> {
+        if let DataStorage::Char16(d) = self
+            .abstract_data
+            .get(&id)
+            .ok_or(XTypesError::InvalidId(id))?
+        {
+            Ok(d)
+        } else {
+            Err(XTypesError::InvalidType)
+        }
+    }
+
+    /// Sets the `char` (representing `char16`) value for the specified member. The character
+    /// must be a single UTF-16 code unit.
+    pub fn set_char16_value(&mut self, id: MemberId, value: char) -> XTypesResult<()> {
+        if value.len_utf16() != 1 {
+            return Err(XTypesError::InvalidData);
+        }
+        self.abstract_data.insert(id, DataStorage::Char16(value));
+        Ok(())
+    }
+
     /// Gets the byte (8-bit unsigned integer) value for the specified member.
     pub fn get_byte_value(&self, id: MemberId) -> XTypesResult<&u8> {
         if let DataStorage::UInt8(d) = self
@@ -1271,6 +1310,16 @@ impl<'a> DynamicData<'a> {
         Ok(())
     }
 
+    /// Gets the `String` (representing `wstring`) value for the specified member.
+    pub fn get_wstring_value(&self, id: MemberId) -> XTypesResult<&String> {
+        self.get_string_value(id)
+    }
+
+    /// Sets the `String` (representing `wstring`) value for the specified member.
+    pub fn set_wstring_value(&mut self, id: MemberId, value: String) -> XTypesResult<()> {
+        self.set_string_value(id, value)
+    }
+
     /// Gets the complex (nested `DynamicData`) value for the specified member.
     pub fn get_complex_value(&self, id: MemberId) -> XTypesResult<&DynamicData<'static>> {
         if let DataStorage::ComplexValue(d) = self
@@ -1502,6 +1551,30 @@ impl<'a> DynamicData<'a> {
         Ok(())
     }
 
+    /// Gets a slice of `char` (representing `char16`) values for the specified sequence/array member.
+    pub fn get_char16_values(&self, id: MemberId) -> XTypesResult<&[char]> {
+        if let DataStorage::SequenceChar16(d) = self
+            .abstract_data
+            .get(&id)
+            .ok_or(XTypesError::InvalidId(id))?
+        {
+            Ok(d.as_slice())
+        } else {
+            Err(XTypesError::InvalidType)
+        }
+    }
+
+    /// Sets a sequence of `char` (representing `char16`) values for the specified member. The
+    /// characters must be single UTF-16 code units.
+    pub fn set_char16_values(&mut self, id: MemberId, value: Vec<char>) -> XTypesResult<()> {
+        if value.iter().any(|c| c.len_utf16() != 1) {
+            return Err(XTypesError::InvalidData);
+        }
+        self.abstract_data
+            .insert(id, DataStorage::SequenceChar16(value));
+        Ok(())
+    }
+
     /// Gets a slice of byte (8-bit unsigned integer) values for the specified sequence/array member.
     pub fn get_byte_values(&self, id: MemberId) -> XTypesResult<&[u8]> {
         if let DataStorage::SequenceUInt8(d) = self
@@ -1562,6 +1635,17 @@ impl<'a> DynamicData<'a> {
         Ok(())
     }
 
+    /// Gets a slice of `String` (representing `wstring`) values for the specified sequence/array
+    /// member.
+    pub fn get_wstring_values(&self, id: MemberId) -> XTypesResult<&[String]> {
+        self.get_string_values(id)
+    }
+
+    /// Sets a sequence of `String` (representing `wstring`) values for the specified member.
+    pub fn set_wstring_values(&mut self, id: MemberId, value: Vec<String>) -> XTypesResult<()> {
+        self.set_string_values(id, value)
+    }
+
     // Custom functions
     /// Gets a slice of `u8` values for the specified sequence/array member.
     pub fn get_uint8_values(&self, id: MemberId) -> XTypesResult<&[u8]> {
@@ -1687,9 +1771,12 @@ fn get_sequence_len(storage: &DataStorage) -> Option<usize> {
         DataStorage::SequenceFloat64(v) => Some(v.len()),
         DataStorage::SequenceFloat128(v) => Some(v.len()),
         DataStorage::SequenceChar8(v) => Some(v.len()),
+        DataStorage::SequenceChar16(v) => Some(v.len()),
         DataStorage::SequenceBoolean(v) => Some(v.len()),
         DataStorage::SequenceString(v) => Some(v.len()),
         DataStorage::SequenceComplexValue(v) => Some(v.len()),
+        DataStorage::SequenceCollection(v) => Some(v.len()),
+        DataStorage::Map(v) => Some(v.len()),
         _ => None,
     }
 }
@@ -1708,9 +1795,12 @@ fn reset_sequence_to_empty(storage: &mut DataStorage) {
         DataStorage::SequenceFloat64(v) => v.clear(),
         DataStorage::SequenceFloat128(v) => v.clear(),
         DataStorage::SequenceChar8(v) => v.clear(),
+        DataStorage::SequenceChar16(v) => v.clear(),
         DataStorage::SequenceBoolean(v) => v.clear(),
         DataStorage::SequenceString(v) => v.clear(),
         DataStorage::SequenceComplexValue(v) => v.clear(),
+        DataStorage::SequenceCollection(v) => v.clear(),
+        DataStorage::Map(v) => v.clear(),
         _ => {}
     }
 }
@@ -1729,9 +1819,12 @@ fn truncate_sequence(storage: &mut DataStorage, bound: usize) {
         DataStorage::SequenceFloat64(v) => v.truncate(bound),
         DataStorage::SequenceFloat128(v) => v.truncate(bound),
         DataStorage::SequenceChar8(v) => v.truncate(bound),
+        DataStorage::SequenceChar16(v) => v.truncate(bound),
         DataStorage::SequenceBoolean(v) => v.truncate(bound),
         DataStorage::SequenceString(v) => v.truncate(bound),
         DataStorage::SequenceComplexValue(v) => v.truncate(bound),
+        DataStorage::SequenceCollection(v) => v.truncate(bound),
+        DataStorage::Map(v) => v.truncate(bound),
         _ => {}
     }
 }
@@ -1752,12 +1845,12 @@ fn default_storage_for_type(t: DynamicType<'static>) -> DataStorage {
         TypeKind::FLOAT64 => DataStorage::Float64(0.0),
         TypeKind::FLOAT128 => DataStorage::Float128(0),
         TypeKind::CHAR8 => DataStorage::Char8('\0'),
-        TypeKind::CHAR16 => DataStorage::Char8('\0'),
+        TypeKind::CHAR16 => DataStorage::Char16('\0'),
         TypeKind::STRING8 => DataStorage::String(String::new()),
         TypeKind::STRING16 => DataStorage::String(String::new()),
         TypeKind::ENUM => DataStorage::Int32(0),
         TypeKind::BITMASK => DataStorage::UInt32(0),
-        TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::ANNOTATION => {
+        TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::ANNOTATION | TypeKind::BITSET => {
             DataStorage::ComplexValue(DynamicDataFactory::create_data(t))
         }
         TypeKind::SEQUENCE => {
@@ -1776,10 +1869,13 @@ fn default_storage_for_type(t: DynamicType<'static>) -> DataStorage {
                     TypeKind::FLOAT64 => DataStorage::SequenceFloat64(Vec::new()),
                     TypeKind::FLOAT128 => DataStorage::SequenceFloat128(Vec::new()),
                     TypeKind::CHAR8 => DataStorage::SequenceChar8(Vec::new()),
-                    TypeKind::CHAR16 => DataStorage::SequenceChar8(Vec::new()),
+                    TypeKind::CHAR16 => DataStorage::SequenceChar16(Vec::new()),
                     TypeKind::STRING8 | TypeKind::STRING16 => {
                         DataStorage::SequenceString(Vec::new())
                     }
+                    TypeKind::SEQUENCE | TypeKind::ARRAY | TypeKind::MAP => {
+                        DataStorage::SequenceCollection(Vec::new())
+                    }
                     _ => DataStorage::SequenceComplexValue(Vec::new()),
                 }
             } else {
@@ -1787,6 +1883,7 @@ fn default_storage_for_type(t: DynamicType<'static>) -> DataStorage {
             }
         }
         TypeKind::ARRAY => DataStorage::SequenceComplexValue(Vec::new()),
+        TypeKind::MAP => DataStorage::Map(Vec::new()),
         _ => DataStorage::Boolean(false),
     }
 }
@@ -1817,6 +1914,22 @@ fn get_selected_union_member<'a>(data: &DynamicData<'a>) -> Option<&'a DynamicTy
     default_member
 }
 
+fn validate_element(
+    element: &mut DynamicData<'static>,
+    element_type: DynamicType<'static>,
+    try_construct_kind: TryConstructKind,
+) -> bool {
+    if !element.validate_dynamic_data() {
+        match try_construct_kind {
+            TryConstructKind::Discard | TryConstructKind::Trim => return false,
+            TryConstructKind::UseDefault => {
+                *element = DynamicDataFactory::create_data(element_type);
+            }
+        }
+    }
+    true
+}
+
 fn validate_member_value(value: &mut DataStorage, member_descriptor: &MemberDescriptor) -> bool {
     match member_descriptor.r#type.get_kind() {
         TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::ANNOTATION => {
@@ -1826,26 +1939,31 @@ fn validate_member_value(value: &mut DataStorage, member_descriptor: &MemberDesc
                 false
             }
         }
-        TypeKind::SEQUENCE => {
-            if let DataStorage::SequenceComplexValue(vec) = value {
-                let element_type = member_descriptor
-                    .r#type
-                    .descriptor
-                    .element_type
-                    .expect("sequence must have element type");
-                for element in vec.iter_mut() {
-                    if !element.validate_dynamic_data() {
-                        match member_descriptor.try_construct_kind {
-                            TryConstructKind::Discard => return false,
-                            TryConstructKind::UseDefault => {
-                                *element = DynamicDataFactory::create_data(element_type);
-                            }
-                            TryConstructKind::Trim => {
+        TypeKind::SEQUENCE | TypeKind::MAP => {
+            let element_type = member_descriptor
+                .r#type
+                .descriptor
+                .element_type
+                .expect("sequence and map must have element type");
+            let try_construct_kind = member_descriptor.try_construct_kind;
+            match value {
+                DataStorage::SequenceComplexValue(vec) => {
+                    for element in vec.iter_mut() {
+                        if !validate_element(element, element_type, try_construct_kind) {
+                            return false;
+                        }
+                    }
+                }
+                DataStorage::Map(entries) => {
+                    for (_, element) in entries.iter_mut() {
+                        if let DataStorage::ComplexValue(element) = element {
+                            if !validate_element(element, element_type, try_construct_kind) {
                                 return false;
                             }
                         }
                     }
                 }
+                _ => (),
             }
 
             let bound = member_descriptor
@@ -2160,6 +2278,16 @@ impl<'a> DynamicData<'a> {
                     .unwrap_or_else(|| text.chars().next().unwrap_or('\0'));
                 Ok(DataStorage::Char8(val))
             }
+            TypeKind::CHAR16 => {
+                let val = parse_uint(text)
+                    .ok()
+                    .and_then(|v| char::from_u32(v as u32))
+                    .unwrap_or_else(|| text.chars().next().unwrap_or('\0'));
+                if val.len_utf16() != 1 {
+                    return Err(XTypesError::InvalidData);
+                }
+                Ok(DataStorage::Char16(val))
+            }
             TypeKind::STRING8 | TypeKind::STRING16 => {
                 let val = node.text().unwrap_or("");
                 Ok(DataStorage::String(String::from(val)))
@@ -2265,7 +2393,17 @@ impl<'a> DynamicData<'a> {
                         }
                         Ok(DataStorage::SequenceChar8(vec))
                     }
-                    TypeKind::STRING8 => {
+                    TypeKind::CHAR16 => {
+                        let mut vec = Vec::new();
+                        for item in node.children().filter(|c| c.is_element()) {
+                            match Self::parse_xml_node_to_data(item, element_type)? {
+                                DataStorage::Char16(val) => vec.push(val),
+                                _ => return Err(XTypesError::InvalidData),
+                            }
+                        }
+                        Ok(DataStorage::SequenceChar16(vec))
+                    }
+                    TypeKind::STRING8 | TypeKind::STRING16 => {
                         let mut vec = Vec::new();
                         for item in node.children().filter(|c| c.is_element()) {
                             let item_text = item.text().unwrap_or("");
@@ -2298,9 +2436,42 @@ impl<'a> DynamicData<'a> {
                         }
                         Ok(DataStorage::SequenceComplexValue(vec))
                     }
+                    TypeKind::SEQUENCE | TypeKind::ARRAY | TypeKind::MAP => {
+                        let mut vec = Vec::new();
+                        for item in node.children().filter(|c| c.is_element()) {
+                            vec.push(Self::parse_xml_node_to_data(item, element_type)?);
+                        }
+                        Ok(DataStorage::SequenceCollection(vec))
+                    }
                     _ => Err(XTypesError::InvalidData),
                 }
             }
+            TypeKind::MAP => {
+                let descriptor = r#type.get_descriptor();
+                let key_type = descriptor
+                    .key_element_type
+                    .ok_or(XTypesError::InvalidData)?;
+                let element_type = descriptor.element_type.ok_or(XTypesError::InvalidData)?;
+                let mut entries = Vec::new();
+                for item in node.children().filter(|c| c.is_element()) {
+                    let mut key = None;
+                    let mut value = None;
+                    for child in item.children().filter(|c| c.is_element()) {
+                        match child.tag_name().name() {
+                            "key" => key = Some(Self::parse_xml_node_to_data(child, key_type)?),
+                            "value" => {
+                                value = Some(Self::parse_xml_node_to_data(child, element_type)?)
+                            }
+                            _ => (),
+                        }
+                    }
+                    entries.push((
+                        key.ok_or(XTypesError::InvalidData)?,
+                        value.ok_or(XTypesError::InvalidData)?,
+                    ));
+                }
+                Ok(DataStorage::Map(entries))
+            }
             _ => Err(XTypesError::InvalidData),
         }
     }
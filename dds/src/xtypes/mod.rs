@@ -18,3 +18,6 @@ pub mod type_object;
 
 /// Traits related to the representation of XTypes required to be transmitted using DDS
 pub mod type_support;
+
+/// Serialization of types directly in the XCDR representation without using their dynamic representation
+pub mod xcdr;
@@ -1,10 +1,10 @@
 use super::dynamic_type::ExtensibilityKind;
 use crate::xtypes::{
     data_storage::DataStorage,
-    dynamic_type::{DynamicData, MemberDescriptor, TypeKind},
+    dynamic_type::{DynamicData, DynamicType, TypeKind},
     error::{XTypesError, XTypesResult},
     type_object::TypeIdentifier,
-    type_support::TypeSupport,
+    type_support::{BitfieldHolder, TypeSupport, bitfield_mask},
 };
 use alloc::{string::ToString, vec::Vec};
 
@@ -183,6 +183,7 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
                 ExtensibilityKind::Appendable => V::serialize_appendable_type(self, dynamic_data),
                 ExtensibilityKind::Mutable => V::serialize_munion_type(self, dynamic_data),
             }?,
+            TypeKind::BITSET => self.serialize_bitset_type(dynamic_data)?,
             kind => unimplemented!("Should not reach for {kind:?}"),
         }
         Ok(())
@@ -197,113 +198,174 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
             member_descriptor.r#type
         };
         match member_type.get_kind() {
-            TypeKind::NONE => (),
-            TypeKind::BOOLEAN => self.serialize_primitive_type(v.get_boolean_value(member_id)?),
-            TypeKind::BYTE => self.serialize_primitive_type(v.get_byte_value(member_id)?),
-            TypeKind::INT16 => self.serialize_primitive_type(v.get_int16_value(member_id)?),
-            TypeKind::INT32 => self.serialize_primitive_type(v.get_int32_value(member_id)?),
-            TypeKind::INT64 => self.serialize_primitive_type(v.get_int64_value(member_id)?),
-            TypeKind::UINT16 => self.serialize_primitive_type(v.get_uint16_value(member_id)?),
-            TypeKind::UINT32 => self.serialize_primitive_type(v.get_uint32_value(member_id)?),
-            TypeKind::UINT64 => self.serialize_primitive_type(v.get_uint64_value(member_id)?),
-            TypeKind::FLOAT32 => self.serialize_primitive_type(v.get_float32_value(member_id)?),
-            TypeKind::FLOAT64 => self.serialize_primitive_type(v.get_float64_value(member_id)?),
-            TypeKind::FLOAT128 => self.serialize_primitive_type(v.get_float128_value(member_id)?),
-            TypeKind::INT8 => self.serialize_primitive_type(v.get_int8_value(member_id)?),
-            TypeKind::UINT8 => self.serialize_primitive_type(v.get_uint8_value(member_id)?),
-            TypeKind::CHAR8 => self.serialize_primitive_type(v.get_char8_value(member_id)?),
-            TypeKind::CHAR16 => todo!(),
-            TypeKind::STRING8 => self.serialize_string_type(v.get_string_value(member_id)?),
-            TypeKind::STRING16 => self.serialize_wstring_type(v.get_string_value(member_id)?),
-            TypeKind::ALIAS => (),
-            TypeKind::ENUM => self.serialize_enum_type(v.get_complex_value(member_id)?)?,
-            TypeKind::BITMASK => todo!(),
-            TypeKind::ANNOTATION => todo!(),
-            TypeKind::STRUCTURE => self.serialize_t_as_nested(v.get_complex_value(member_id)?)?,
-            TypeKind::UNION => self.serialize_t_as_nested(v.get_complex_value(member_id)?)?,
-            TypeKind::BITSET => todo!(),
-            TypeKind::SEQUENCE => {
-                if is_element_type_kind_primitive(member_descriptor)? {
-                    self.serialize_p_sequence_type(v, member_id)?
+            TypeKind::NONE | TypeKind::ALIAS => Ok(()),
+            _ => self.serialize_data(v.get_value(member_id)?, member_type),
+        }
+    }
+
+    /// Serialization Rule: { O : O.type }
+    fn serialize_data(&mut self, data: &DataStorage, data_type: DynamicType) -> XTypesResult<()> {
+        match (data_type.get_kind(), data) {
+            (TypeKind::BOOLEAN, DataStorage::Boolean(x)) => self.serialize_primitive_type(x),
+            (TypeKind::BYTE | TypeKind::UINT8, DataStorage::UInt8(x)) => {
+                self.serialize_primitive_type(x)
+            }
+            (TypeKind::INT8, DataStorage::Int8(x)) => self.serialize_primitive_type(x),
+            (TypeKind::INT16, DataStorage::Int16(x)) => self.serialize_primitive_type(x),
+            (TypeKind::INT32, DataStorage::Int32(x)) => self.serialize_primitive_type(x),
+            (TypeKind::INT64, DataStorage::Int64(x)) => self.serialize_primitive_type(x),
+            (TypeKind::UINT16, DataStorage::UInt16(x)) => self.serialize_primitive_type(x),
+            (TypeKind::UINT32, DataStorage::UInt32(x)) => self.serialize_primitive_type(x),
+            (TypeKind::UINT64, DataStorage::UInt64(x)) => self.serialize_primitive_type(x),
+            (TypeKind::FLOAT32, DataStorage::Float32(x)) => self.serialize_primitive_type(x),
+            (TypeKind::FLOAT64, DataStorage::Float64(x)) => self.serialize_primitive_type(x),
+            (TypeKind::FLOAT128, DataStorage::Float128(x)) => self.serialize_primitive_type(x),
+            (TypeKind::CHAR8, DataStorage::Char8(x)) => self.serialize_primitive_type(x),
+            (TypeKind::CHAR16, DataStorage::Char16(x)) => self.serialize_char16_type(*x)?,
+            (TypeKind::STRING8, DataStorage::String(x)) => self.serialize_string_type(x),
+            (TypeKind::STRING16, DataStorage::String(x)) => self.serialize_wstring_type(x),
+            (TypeKind::ENUM, DataStorage::ComplexValue(x)) => self.serialize_enum_type(x)?,
+            (TypeKind::BITSET, DataStorage::ComplexValue(x)) => self.serialize_bitset_type(x)?,
+            (TypeKind::STRUCTURE | TypeKind::UNION, DataStorage::ComplexValue(x)) => {
+                self.serialize_t_as_nested(x)?
+            }
+            (TypeKind::SEQUENCE, _) => {
+                if is_element_type_kind_primitive(data_type)? {
+                    self.serialize_p_sequence_type(data, data_type)?
                 } else {
-                    V::serialize_sequence_type(self, v, member_id)?
+                    V::serialize_sequence_type(self, data, data_type)?
                 }
             }
-            TypeKind::ARRAY => {
-                if is_element_type_kind_primitive(member_descriptor)? {
-                    self.serialize_p_array_type(v, member_id)?
+            (TypeKind::ARRAY, _) => {
+                if is_element_type_kind_primitive(data_type)? {
+                    self.serialize_p_array_type(data, data_type)?
                 } else {
-                    V::serialize_array_type(self, v, member_id)?
+                    V::serialize_array_type(self, data, data_type)?
                 }
             }
-            TypeKind::MAP => todo!(),
+            (TypeKind::MAP, DataStorage::Map(entries)) => {
+                if is_map_type_primitive(data_type)? {
+                    self.serialize_p_map_type(entries, data_type)?
+                } else {
+                    V::serialize_map_type(self, entries, data_type)?
+                }
+            }
+            (TypeKind::BITMASK | TypeKind::ANNOTATION, _) => {
+                todo!()
+            }
+            _ => return Err(XTypesError::InvalidType),
         }
         Ok(())
     }
 
     /// Serialization Rule: { O[i] : O.element_type }*
-    fn serialize_elements(&mut self, v: &DynamicData, member_id: u32) -> Result<(), XTypesError> {
-        let member_descriptor = v.get_descriptor(member_id)?;
-        let element_type = member_descriptor
-            .r#type
+    fn serialize_elements(
+        &mut self,
+        data: &DataStorage,
+        collection_type: DynamicType,
+    ) -> Result<(), XTypesError> {
+        let element_type = collection_type
             .descriptor
             .element_type
-            .expect("array has element type");
-        match element_type.get_descriptor().kind {
-            TypeKind::NONE => todo!(),
-            TypeKind::BOOLEAN => {
-                serialize_primitive_slice(self, v.get_boolean_values(member_id)?);
+            .ok_or(XTypesError::InvalidType)?;
+        match (element_type.get_kind(), data) {
+            (TypeKind::BOOLEAN, DataStorage::SequenceBoolean(x)) => {
+                serialize_primitive_slice(self, x)
+            }
+            (TypeKind::BYTE | TypeKind::UINT8, DataStorage::SequenceUInt8(x)) => {
+                self.writer.write_slice(x)
+            }
+            (TypeKind::INT8, DataStorage::SequenceInt8(x)) => serialize_primitive_slice(self, x),
+            (TypeKind::INT16, DataStorage::SequenceInt16(x)) => serialize_primitive_slice(self, x),
+            (TypeKind::INT32, DataStorage::SequenceInt32(x)) => serialize_primitive_slice(self, x),
+            (TypeKind::INT64, DataStorage::SequenceInt64(x)) => serialize_primitive_slice(self, x),
+            (TypeKind::UINT16, DataStorage::SequenceUInt16(x)) => {
+                serialize_primitive_slice(self, x)
+            }
+            (TypeKind::UINT32, DataStorage::SequenceUInt32(x)) => {
+                serialize_primitive_slice(self, x)
+            }
+            (TypeKind::UINT64, DataStorage::SequenceUInt64(x)) => {
+                serialize_primitive_slice(self, x)
+            }
+            (TypeKind::FLOAT32, DataStorage::SequenceFloat32(x)) => {
+                serialize_primitive_slice(self, x)
+            }
+            (TypeKind::FLOAT64, DataStorage::SequenceFloat64(x)) => {
+                serialize_primitive_slice(self, x)
             }
-            TypeKind::BYTE => self.writer.write_slice(v.get_byte_values(member_id)?),
-            TypeKind::INT16 => serialize_primitive_slice(self, v.get_int16_values(member_id)?),
-            TypeKind::INT32 => serialize_primitive_slice(self, v.get_int32_values(member_id)?),
-            TypeKind::INT64 => serialize_primitive_slice(self, v.get_int64_values(member_id)?),
-            TypeKind::UINT16 => serialize_primitive_slice(self, v.get_uint16_values(member_id)?),
-            TypeKind::UINT32 => serialize_primitive_slice(self, v.get_uint32_values(member_id)?),
-            TypeKind::UINT64 => serialize_primitive_slice(self, v.get_uint64_values(member_id)?),
-            TypeKind::FLOAT32 => serialize_primitive_slice(self, v.get_float32_values(member_id)?),
-            TypeKind::FLOAT64 => serialize_primitive_slice(self, v.get_float64_values(member_id)?),
-            TypeKind::FLOAT128 => {
-                serialize_primitive_slice(self, v.get_float128_values(member_id)?)
+            (TypeKind::FLOAT128, DataStorage::SequenceFloat128(x)) => {
+                serialize_primitive_slice(self, x)
             }
-            TypeKind::INT8 => serialize_primitive_slice(self, v.get_int8_values(member_id)?),
-            TypeKind::UINT8 => self.writer.write_slice(v.get_uint8_values(member_id)?),
-            TypeKind::CHAR8 => serialize_primitive_slice(self, v.get_char8_values(member_id)?),
-            TypeKind::CHAR16 => todo!(),
-            TypeKind::STRING8 => {
-                for v in v.get_string_values(member_id)? {
+            (TypeKind::CHAR8, DataStorage::SequenceChar8(x)) => serialize_primitive_slice(self, x),
+            (TypeKind::CHAR16, DataStorage::SequenceChar16(x)) => {
+                for v in x {
+                    self.serialize_char16_type(*v)?;
+                }
+            }
+            (TypeKind::STRING8, DataStorage::SequenceString(x)) => {
+                for v in x {
                     self.serialize_string_type(v);
                 }
             }
-            TypeKind::STRING16 => {
-                for v in v.get_string_values(member_id)? {
+            (TypeKind::STRING16, DataStorage::SequenceString(x)) => {
+                for v in x {
                     self.serialize_wstring_type(v);
                 }
             }
-            TypeKind::ALIAS => todo!(),
-            TypeKind::BITMASK => todo!(),
-            TypeKind::ANNOTATION => todo!(),
-            TypeKind::ENUM | TypeKind::STRUCTURE => {
-                for v in v.get_complex_values(member_id)? {
+            (
+                TypeKind::ENUM | TypeKind::STRUCTURE | TypeKind::BITSET,
+                DataStorage::SequenceComplexValue(x),
+            ) => {
+                for v in x {
                     self.serialize_t_as_nested(v)?;
                 }
             }
-            TypeKind::UNION => {
-                for v in v.get_complex_values(member_id)? {
+            (TypeKind::UNION, DataStorage::SequenceComplexValue(x)) => {
+                for v in x {
                     self.serialize_funion_type(v)?;
                 }
             }
-            TypeKind::BITSET => todo!(),
-            TypeKind::SEQUENCE => todo!(),
-            TypeKind::ARRAY => todo!(),
-            TypeKind::MAP => todo!(),
+            (
+                TypeKind::SEQUENCE | TypeKind::ARRAY | TypeKind::MAP,
+                DataStorage::SequenceCollection(x),
+            ) => {
+                for v in x {
+                    self.serialize_data(v, element_type)?;
+                }
+            }
+            (TypeKind::NONE | TypeKind::ALIAS | TypeKind::BITMASK | TypeKind::ANNOTATION, _) => {
+                todo!()
+            }
+            _ => return Err(XTypesError::InvalidType),
         };
         Ok(())
     }
 
+    /// Serialization Rule: { O[i].key : O.key_element_type, O[i].value : O.element_type }*
+    fn serialize_map_entries(
+        &mut self,
+        entries: &[(DataStorage, DataStorage)],
+        map_type: DynamicType,
+    ) -> XTypesResult<()> {
+        let key_type = map_type
+            .descriptor
+            .key_element_type
+            .ok_or(XTypesError::InvalidType)?;
+        let element_type = map_type
+            .descriptor
+            .element_type
+            .ok_or(XTypesError::InvalidType)?;
+        for (key, value) in entries {
+            self.serialize_data(key, key_type)?;
+            self.serialize_data(value, element_type)?;
+        }
+        Ok(())
+    }
+
     /// Serialization Rule: { O.length : UInt32 }
-    fn serialize_length(&mut self, v: &DynamicData, member_id: u32) -> Result<(), XTypesError> {
-        let length = match v.get_value(member_id)? {
+    fn serialize_length(&mut self, data: &DataStorage) -> Result<(), XTypesError> {
+        let length = match data {
             DataStorage::UInt8(_)
             | DataStorage::Int8(_)
             | DataStorage::UInt16(_)
@@ -316,6 +378,7 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
             | DataStorage::Float64(_)
             | DataStorage::Float128(_)
             | DataStorage::Char8(_)
+            | DataStorage::Char16(_)
             | DataStorage::Boolean(_)
             | DataStorage::String(_)
             | DataStorage::ComplexValue(_) => 1,
@@ -331,9 +394,12 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
             DataStorage::SequenceFloat64(items) => items.len(),
             DataStorage::SequenceFloat128(items) => items.len(),
             DataStorage::SequenceChar8(items) => items.len(),
+            DataStorage::SequenceChar16(items) => items.len(),
             DataStorage::SequenceBoolean(items) => items.len(),
             DataStorage::SequenceString(items) => items.len(),
             DataStorage::SequenceComplexValue(items) => items.len(),
+            DataStorage::SequenceCollection(items) => items.len(),
+            DataStorage::Map(entries) => entries.len(),
         };
 
         self.serialize_primitive_type(&(length as u32));
@@ -403,13 +469,28 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
         self.writer.write_byte(0);
     }
 
+    /// Serialization Rule (4)
+    ///
+    /// XCDR << {O : WSTRING_TYPE} =
+    ///            XCDR
+    ///              << { O.ssize : UInt32 } // number of bytes, no NUL
+    ///              << { O[i] : Char16 }* // no NUL
     fn serialize_wstring_type(&mut self, v: &str) {
-        let utf16_units: Vec<u16> = v.encode_utf16().collect();
-        self.serialize_primitive_type(&(utf16_units.len() as u32 + 1));
-        for unit in utf16_units {
+        self.serialize_primitive_type(&(2 * v.encode_utf16().count() as u32));
+        for unit in v.encode_utf16() {
             self.serialize_primitive_type(&unit);
         }
-        self.serialize_primitive_type(&0u16);
+    }
+
+    /// Wide characters are a single UTF-16 code unit serialized as a primitive type
+    fn serialize_char16_type(&mut self, v: char) -> XTypesResult<()> {
+        let mut unit = [0; 1];
+        if v.len_utf16() != 1 {
+            return Err(XTypesError::InvalidData);
+        }
+        v.encode_utf16(&mut unit);
+        self.serialize_primitive_type(&unit[0]);
+        Ok(())
     }
 
     /// Serialization Rule (5)
@@ -440,6 +521,42 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
         }
     }
 
+    /// Bitsets are serialized as the smallest unsigned integer holding all their bitfields.
+    /// Bitfields which are not set are serialized as zero.
+    fn serialize_bitset_type(&mut self, v: &DynamicData) -> XTypesResult<()> {
+        let bitset_type = v.r#type();
+        let mut bits = 0;
+        for (member, &bitcount) in bitset_type
+            .member_list
+            .iter()
+            .zip(bitset_type.descriptor.bound)
+        {
+            let value = match v.get_value(member.get_id()) {
+                Ok(DataStorage::Boolean(x)) => x.to_bits(),
+                Ok(DataStorage::UInt8(x)) => x.to_bits(),
+                Ok(DataStorage::Int8(x)) => x.to_bits(),
+                Ok(DataStorage::UInt16(x)) => x.to_bits(),
+                Ok(DataStorage::Int16(x)) => x.to_bits(),
+                Ok(DataStorage::UInt32(x)) => x.to_bits(),
+                Ok(DataStorage::Int32(x)) => x.to_bits(),
+                Ok(DataStorage::UInt64(x)) => x.to_bits(),
+                Ok(DataStorage::Int64(x)) => x.to_bits(),
+                Ok(_) => return Err(XTypesError::InvalidType),
+                Err(_) => 0,
+            };
+            bits |= (value & bitfield_mask(bitcount))
+                .checked_shl(member.get_id())
+                .unwrap_or(0);
+        }
+        match bitset_bit_bound(bitset_type)? {
+            0..=8 => self.serialize_primitive_type(&(bits as u8)),
+            9..=16 => self.serialize_primitive_type(&(bits as u16)),
+            17..=32 => self.serialize_primitive_type(&(bits as u32)),
+            _ => self.serialize_primitive_type(&bits),
+        }
+        Ok(())
+    }
+
     /// Arrays of primitive element type (version 1 and 2 encoding)
     ///
     /// Serialization Rule (8)
@@ -447,8 +564,12 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
     /// XCDR << {O : PARRAY_TYPE} =
     ///           XCDR
     ///             << { O[i] : O.element_type }*
-    fn serialize_p_array_type(&mut self, v: &DynamicData, member_id: u32) -> XTypesResult<()> {
-        self.serialize_elements(v, member_id)
+    fn serialize_p_array_type(
+        &mut self,
+        data: &DataStorage,
+        array_type: DynamicType,
+    ) -> XTypesResult<()> {
+        self.serialize_elements(data, array_type)
     }
 
     /// Sequences of primitive element type (version 1 and 2 encoding)
@@ -459,9 +580,31 @@ impl<'a, E: EndiannessWrite, V: EncodingVersion> XTypesSerializer<'a, E, V> {
     ///            XCDR
     ///              << { O.length : UInt32 }
     ///              << { O[i] : O.element_type }*
-    fn serialize_p_sequence_type(&mut self, v: &DynamicData, member_id: u32) -> XTypesResult<()> {
-        self.serialize_length(v, member_id)?;
-        self.serialize_elements(v, member_id)
+    fn serialize_p_sequence_type(
+        &mut self,
+        data: &DataStorage,
+        sequence_type: DynamicType,
+    ) -> XTypesResult<()> {
+        self.serialize_length(data)?;
+        self.serialize_elements(data, sequence_type)
+    }
+
+    /// Maps of primitive key and element types (version 1 and 2 encoding)
+    ///
+    /// Serialization Rule (14)
+    ///
+    /// XCDR << { O : PMAP_TYPE } =
+    ///            XCDR
+    ///              << { O.length : UInt32 }
+    ///              << { O[i].key : O.key_element_type,
+    ///                   O[i].value : O.element_type }*
+    fn serialize_p_map_type(
+        &mut self,
+        entries: &[(DataStorage, DataStorage)],
+        map_type: DynamicType,
+    ) -> XTypesResult<()> {
+        self.serialize_primitive_type(&(entries.len() as u32));
+        self.serialize_map_entries(entries, map_type)
     }
 
     /// Structures with extensibility FINAL (version 1 and 2 encoding)
@@ -594,7 +737,9 @@ impl<'a, 'b, E: EndiannessWrite, V: EncodingVersion> EMheader1<'a, 'b, E, V> {
             member_descriptor.r#type.descriptor.extensibility_kind,
             ExtensibilityKind::Appendable | ExtensibilityKind::Mutable
         )) || member_descriptor.r#type.get_kind()
-            == TypeKind::SEQUENCE;
+            == TypeKind::SEQUENCE
+            || (member_descriptor.r#type.get_kind() == TypeKind::MAP
+                && !is_map_type_primitive(member_descriptor.r#type)?);
         let lc = if is_next_member_having_dheader {
             5
         } else {
@@ -625,14 +770,44 @@ impl<'a, 'b, E: EndiannessWrite, V: EncodingVersion> EMheader1<'a, 'b, E, V> {
     }
 }
 
-fn is_element_type_kind_primitive(member_descriptor: &MemberDescriptor) -> XTypesResult<bool> {
-    Ok(matches!(
-        member_descriptor
-            .r#type
+pub(crate) fn is_element_type_kind_primitive(collection_type: DynamicType) -> XTypesResult<bool> {
+    Ok(is_type_kind_primitive(
+        collection_type
             .descriptor
             .element_type
             .ok_or(XTypesError::InvalidType)?
             .get_kind(),
+    ))
+}
+
+/// Returns the number of bits of a bitset, which ends with the last bit of its bitfields.
+pub(crate) fn bitset_bit_bound(bitset_type: DynamicType) -> XTypesResult<u32> {
+    let bit_bound = bitset_type
+        .member_list
+        .iter()
+        .zip(bitset_type.descriptor.bound)
+        .map(|(member, &bitcount)| member.get_id().saturating_add(bitcount))
+        .max()
+        .unwrap_or(0);
+    if bit_bound > u64::BITS {
+        return Err(XTypesError::InvalidType);
+    }
+    Ok(bit_bound)
+}
+
+pub(crate) fn is_map_type_primitive(map_type: DynamicType) -> XTypesResult<bool> {
+    Ok(is_type_kind_primitive(
+        map_type
+            .descriptor
+            .key_element_type
+            .ok_or(XTypesError::InvalidType)?
+            .get_kind(),
+    ) && is_element_type_kind_primitive(map_type)?)
+}
+
+fn is_type_kind_primitive(kind: TypeKind) -> bool {
+    matches!(
+        kind,
         TypeKind::BOOLEAN
             | TypeKind::BYTE
             | TypeKind::INT16
@@ -648,7 +823,7 @@ fn is_element_type_kind_primitive(member_descriptor: &MemberDescriptor) -> XType
             | TypeKind::UINT8
             | TypeKind::CHAR8
             | TypeKind::CHAR16
-    ))
+    )
 }
 
 trait EncodingVersion: Sized {
@@ -664,15 +839,22 @@ trait EncodingVersion: Sized {
     /// Serialization Rule (9) & (10)
     fn serialize_array_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        array_type: DynamicType,
     ) -> Result<(), XTypesError>;
 
     /// Serialization Rule (12) & (13)
     fn serialize_sequence_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        sequence_type: DynamicType,
+    ) -> Result<(), XTypesError>;
+
+    /// Serialization Rule (15) & (16)
+    fn serialize_map_type<'a, E: EndiannessWrite>(
+        serializer: &mut XTypesSerializer<'a, E, Self>,
+        entries: &[(DataStorage, DataStorage)],
+        map_type: DynamicType,
     ) -> Result<(), XTypesError>;
 
     /// Serialization Rule (19) & (20)
@@ -745,10 +927,10 @@ impl EncodingVersion for EncodingVersion1 {
     ///                  << { O[i] : O.element_type }*
     fn serialize_array_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        array_type: DynamicType,
     ) -> Result<(), XTypesError> {
-        serializer.serialize_elements(v, member_id)
+        serializer.serialize_elements(data, array_type)
     }
 
     /// Sequences (any extensibility) using version 1 encoding
@@ -761,11 +943,29 @@ impl EncodingVersion for EncodingVersion1 {
     ///                << { O[i] : O.element_type }*
     fn serialize_sequence_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        sequence_type: DynamicType,
     ) -> Result<(), XTypesError> {
-        serializer.serialize_length(v, member_id)?;
-        serializer.serialize_elements(v, member_id)
+        serializer.serialize_length(data)?;
+        serializer.serialize_elements(data, sequence_type)
+    }
+
+    /// Maps (any extensibility) using version 1 encoding
+    ///
+    /// Serialization Rule (16)
+    ///
+    /// XCDR[1] << {O : MAP_TYPE} =
+    ///              XCDR
+    ///                << { O.length : UInt32 }
+    ///                << { O[i].key : O.key_element_type,
+    ///                     O[i].value : O.element_type }*
+    fn serialize_map_type<'a, E: EndiannessWrite>(
+        serializer: &mut XTypesSerializer<'a, E, Self>,
+        entries: &[(DataStorage, DataStorage)],
+        map_type: DynamicType,
+    ) -> Result<(), XTypesError> {
+        serializer.serialize_primitive_type(&(entries.len() as u32));
+        serializer.serialize_map_entries(entries, map_type)
     }
 
     /// Optional member of final Aggregated type (structure, union), version 1
@@ -921,11 +1121,11 @@ impl EncodingVersion for EncodingVersion2 {
     ///                << { O[i] : O.element_type }*
     fn serialize_array_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        array_type: DynamicType,
     ) -> Result<(), XTypesError> {
         let dheader = Dheader::new(serializer);
-        dheader.serializer.serialize_elements(v, member_id)?;
+        dheader.serializer.serialize_elements(data, array_type)?;
         dheader.write_header();
 
         Ok(())
@@ -942,12 +1142,38 @@ impl EncodingVersion for EncodingVersion2 {
     ///              << { O[i] : O.element_type }*
     fn serialize_sequence_type<'a, E: EndiannessWrite>(
         serializer: &mut XTypesSerializer<'a, E, Self>,
-        v: &DynamicData,
-        member_id: u32,
+        data: &DataStorage,
+        sequence_type: DynamicType,
+    ) -> Result<(), XTypesError> {
+        let dheader = Dheader::new(serializer);
+        dheader.serializer.serialize_length(data)?;
+        dheader.serializer.serialize_elements(data, sequence_type)?;
+        dheader.write_header();
+        Ok(())
+    }
+
+    /// Maps (any extensibility) using version 2 encoding
+    ///
+    /// Serialization Rule (15)
+    ///
+    /// XCDR[2] << {O : MAP_TYPE} =
+    ///             XCDR
+    ///              << { DHEADER(O) : UINT32 }
+    ///              << { O.length : UInt32 }
+    ///              << { O[i].key : O.key_element_type,
+    ///                   O[i].value : O.element_type }*
+    fn serialize_map_type<'a, E: EndiannessWrite>(
+        serializer: &mut XTypesSerializer<'a, E, Self>,
+        entries: &[(DataStorage, DataStorage)],
+        map_type: DynamicType,
     ) -> Result<(), XTypesError> {
         let dheader = Dheader::new(serializer);
-        dheader.serializer.serialize_length(v, member_id)?;
-        dheader.serializer.serialize_elements(v, member_id)?;
+        dheader
+            .serializer
+            .serialize_primitive_type(&(entries.len() as u32));
+        dheader
+            .serializer
+            .serialize_map_entries(entries, map_type)?;
         dheader.write_header();
         Ok(())
     }
@@ -1292,7 +1518,7 @@ mod tests {
                 TypeIdentifier, TypeIdentifierWithDependencies, TypeIdentifierWithSize,
                 TypeInformation,
             },
-            type_support::{Type, TypeSupport},
+            type_support::{Type, TypeSupport, WChar, WString},
         },
     };
     extern crate std;
@@ -1422,6 +1648,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_nested_collections() {
+        #[derive(TypeSupport)]
+        struct NestedCollections {
+            sequences: Vec<Vec<u16>>,
+            matrix: [[u8; 2]; 2],
+            names: [[String; 1]; 2],
+        }
+
+        let v = NestedCollections {
+            sequences: vec![vec![1, 2], vec![3]],
+            matrix: [[1, 2], [3, 4]],
+            names: [[String::from("x")], [String::from("y")]],
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            serialize_cdr2_le(&v).unwrap(),
+            vec![
+                0x00, 0x07, 0x00, 0x02, // CDR Header
+                18, 0, 0, 0, // sequences: DHEADER
+                2, 0, 0, 0, // sequences: length
+                2, 0, 0, 0, // sequences[0]: length
+                1, 0, 2, 0, // sequences[0]: elements
+                1, 0, 0, 0, // sequences[1]: length
+                3, 0, 1, 2, // sequences[1]: elements | matrix[0]
+                3, 4, 0, 0, // matrix[1] | padding (2 bytes)
+                14, 0, 0, 0, // names: DHEADER
+                2, 0, 0, 0, // names[0][0]: length
+                b'x', 0, 0, 0, // names[0][0] | padding (2 bytes)
+                2, 0, 0, 0, // names[1][0]: length
+                b'y', 0, 0, 0, // names[1][0] | padding (2 bytes)
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_wide_characters() {
+        #[derive(TypeSupport)]
+        struct WideCharacters {
+            initial: WChar,
+            message: WString,
+        }
+
+        let v = WideCharacters {
+            initial: WChar::try_from('Ω').unwrap(),
+            message: WString::from(String::from("añ")),
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            serialize_cdr2_le(&v).unwrap(),
+            vec![
+                0x00, 0x07, 0x00, 0x00, // CDR Header
+                0xa9, 0x03, 0, 0, // initial | padding (2 bytes)
+                4, 0, 0, 0, // message: length in bytes
+                b'a', 0, 0xf1, 0, // message
+            ]
+        );
+    }
+
     #[test]
     fn serialize_array_with_lc4() {
         #[derive(TypeSupport)]
@@ -1577,6 +1862,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_map() {
+        #[derive(TypeSupport)]
+        struct Parameters {
+            ids: alloc::collections::BTreeMap<u16, u16>,
+            names: alloc::collections::BTreeMap<String, u8>,
+        }
+
+        let v = Parameters {
+            ids: [(1, 10), (2, 20)].into(),
+            names: [("a".to_string(), 1)].into(),
+        }
+        .create_dynamic_sample();
+        assert_eq!(
+            serialize_cdr1_be(&v).unwrap(),
+            vec![
+                0x00, 0x00, 0x00, 0x01, // CDR Header
+                0, 0, 0, 2, // ids length
+                0, 1, 0, 10, // ids key | value
+                0, 2, 0, 20, // ids key | value
+                0, 0, 0, 1, // names length
+                0, 0, 0, 2, // String length
+                b'a', 0, 1, 0, // String | value | padding
+            ]
+        );
+        assert_eq!(
+            serialize_cdr2_be(&v).unwrap(),
+            vec![
+                0x00, 0x06, 0x00, 0x01, // CDR2 Header
+                0, 0, 0, 2, // ids length
+                0, 1, 0, 10, // ids key | value
+                0, 2, 0, 20, // ids key | value
+                0, 0, 0, 11, // names DHEADER
+                0, 0, 0, 1, // names length
+                0, 0, 0, 2, // String length
+                b'a', 0, 1, 0, // String | value | padding
+            ]
+        );
+    }
+
     #[derive(TypeSupport, Clone)]
     struct FinalType {
         field_u16: u16,
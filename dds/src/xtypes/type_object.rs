@@ -5,10 +5,10 @@ use crate::{
         dynamic_type::{DynamicType, DynamicTypeMember},
         serializer::serialize_without_header_cdr2_le,
         type_object::TypeIdentifier::EkMinimal,
-        type_support::TypeSupport,
+        type_support::{TypeSupport, WChar, WString},
     },
 };
-use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 use dust_dds_derive::DdsType;
 
 /* Manually created from dds-xtypes_typeobject.idl */
@@ -710,8 +710,12 @@ pub enum AnnotationParameterValue {
         /// The 8-bit character value.
         char_value: char,
     },
-    // TypeKind::CHAR16 {
-    // wchar_value: char16},
+    /// 16-bit wide character value.
+    #[dust_dds(case=TK_CHAR16)]
+    TkChar16 {
+        /// The wide character value.
+        wchar_value: WChar,
+    },
     /// Enumerated value represented as an integer.
     #[dust_dds(case=TK_ENUM)]
     TkEnum {
@@ -724,8 +728,12 @@ pub enum AnnotationParameterValue {
         /// The string value.
         string8_value: String, /*string<ANNOTATION_STR_VALUE_MAX_LEN>  */
     },
-    // TypeKind::STRING16:
-    // wstring<ANNOTATION_STR_VALUE_MAX_LEN> string16_value;
+    /// 16-bit wide string value.
+    #[dust_dds(case=TK_STRING16)]
+    TkString16 {
+        /// The wide string value.
+        string16_value: WString, /*wstring<ANNOTATION_STR_VALUE_MAX_LEN>  */
+    },
     /// Default case for future extensibility.
     #[dust_dds(default)]
     Default {
@@ -2000,6 +2008,27 @@ impl<'a> From<DynamicType<'a>> for MinimalTypeObject {
 
                 MinimalTypeObject::TkUnion { union_type }
             }
+            TypeKind::BITSET => {
+                let field_seq = value
+                    .member_list
+                    .iter()
+                    .zip(value.descriptor.bound)
+                    .map(|(member, &bitcount)| {
+                        let name_hash =
+                            <[u8; 16]>::from(md5::compute(member.get_name().as_bytes()));
+                        MinimalBitfield {
+                            common: common_bitfield(member, bitcount),
+                            name_hash: [name_hash[0], name_hash[1], name_hash[2], name_hash[3]],
+                        }
+                    })
+                    .collect();
+                let bitset_type = MinimalBitsetType {
+                    bitset_flags: TypeFlag(0),
+                    header: MinimalBitsetHeader {},
+                    field_seq,
+                };
+                MinimalTypeObject::TkBitset { bitset_type }
+            }
             t => todo!("Not yet implemeneted for {t:?}"),
         }
     }
@@ -2136,6 +2165,34 @@ impl<'a> From<DynamicType<'a>> for CompleteTypeObject {
 
                 CompleteTypeObject::TkUnion { union_type }
             }
+            TypeKind::BITSET => {
+                let header = CompleteBitsetHeader {
+                    detail: CompleteTypeDetail {
+                        ann_builtin: None,
+                        ann_custom: None,
+                        type_name: String::from(value.descriptor.name),
+                    },
+                };
+                let field_seq = value
+                    .member_list
+                    .iter()
+                    .zip(value.descriptor.bound)
+                    .map(|(member, &bitcount)| CompleteBitfield {
+                        common: common_bitfield(member, bitcount),
+                        detail: CompleteMemberDetail {
+                            name: String::from(member.get_name()),
+                            ann_builtin: None,
+                            ann_custom: None,
+                        },
+                    })
+                    .collect();
+                let bitset_type = CompleteBitsetType {
+                    bitset_flags: TypeFlag(0),
+                    header,
+                    field_seq,
+                };
+                CompleteTypeObject::TkBitset { bitset_type }
+            }
             t => todo!("Not yet implemeneted for {t:?}"),
         }
     }
@@ -2196,7 +2253,11 @@ impl<'a> From<&DynamicType<'a>> for TypeIdentifier {
             }
             TypeKind::ALIAS => todo!(),
             TypeKind::ANNOTATION => todo!(),
-            TypeKind::STRUCTURE | TypeKind::UNION | TypeKind::ENUM | TypeKind::BITMASK => {
+            TypeKind::STRUCTURE
+            | TypeKind::UNION
+            | TypeKind::ENUM
+            | TypeKind::BITMASK
+            | TypeKind::BITSET => {
                 let complete_type_object = TypeObject::EkComplete {
                     complete: CompleteTypeObject::from(*value),
                 };
@@ -2224,7 +2285,6 @@ impl<'a> From<&DynamicType<'a>> for TypeIdentifier {
                     ],
                 }
             }
-            TypeKind::BITSET => todo!(),
             TypeKind::SEQUENCE => {
                 let bound = *value.descriptor.bound.first().unwrap_or(&u32::MAX);
                 let element_identifier = Box::new(
@@ -2258,7 +2318,7 @@ impl<'a> From<&DynamicType<'a>> for TypeIdentifier {
                 }
             }
             TypeKind::ARRAY => {
-                let bound = *value.descriptor.bound.first().unwrap_or(&u32::MAX);
+                let bound = value.descriptor.bound;
                 let element_identifier = Box::new(
                     value
                         .descriptor
@@ -2271,11 +2331,11 @@ impl<'a> From<&DynamicType<'a>> for TypeIdentifier {
                     equiv_kind: EK_MINIMAL,
                     element_flags: MEMBER_FLAG_MINIMAL_MASK,
                 };
-                if bound <= u8::MAX as u32 {
+                if bound.iter().all(|&b| b <= u8::MAX as u32) {
                     TypeIdentifier::TiPlainArraySmall {
                         array_sdefn: PlainArraySElemDefn {
                             header,
-                            array_bound_seq: vec![bound as u8],
+                            array_bound_seq: bound.iter().map(|&b| b as u8).collect(),
                             element_identifier,
                         },
                     }
@@ -2283,17 +2343,71 @@ impl<'a> From<&DynamicType<'a>> for TypeIdentifier {
                     TypeIdentifier::TiPlainArrayLarge {
                         array_ldefn: PlainArrayLElemDefn {
                             header,
-                            array_bound_seq: vec![bound],
+                            array_bound_seq: bound.to_vec(),
+                            element_identifier,
+                        },
+                    }
+                }
+            }
+            TypeKind::MAP => {
+                let bound = *value.descriptor.bound.first().unwrap_or(&u32::MAX);
+                let element_identifier = Box::new(
+                    value
+                        .descriptor
+                        .element_type
+                        .as_ref()
+                        .map(From::from)
+                        .unwrap_or(TypeIdentifier::TkNone),
+                );
+                let key_identifier = Box::new(
+                    value
+                        .descriptor
+                        .key_element_type
+                        .as_ref()
+                        .map(From::from)
+                        .unwrap_or(TypeIdentifier::TkNone),
+                );
+                let header = PlainCollectionHeader {
+                    equiv_kind: EK_MINIMAL,
+                    element_flags: MEMBER_FLAG_MINIMAL_MASK,
+                };
+                if bound <= u8::MAX as u32 {
+                    TypeIdentifier::TiPlainMapSmall {
+                        map_sdefn: PlainMapSTypeDefn {
+                            header,
+                            bound: bound as u8,
+                            element_identifier,
+                            key_flags: MEMBER_FLAG_MINIMAL_MASK,
+                            key_identifier,
+                        },
+                    }
+                } else {
+                    TypeIdentifier::TiPlainMapLarge {
+                        map_ldefn: PlainMapLTypeDefn {
+                            header,
+                            bound,
                             element_identifier,
+                            key_flags: MEMBER_FLAG_MINIMAL_MASK,
+                            key_identifier,
                         },
                     }
                 }
             }
-            TypeKind::MAP => todo!(),
         }
     }
 }
 
+/// The bitfields of a bitset are its members, with the position as id and the number of bits
+/// given by the bound of the bitset.
+fn common_bitfield(member: &DynamicTypeMember, bitcount: u32) -> CommonBitfield {
+    CommonBitfield {
+        position: member.get_id() as u16,
+        flags: MemberFlag::default(),
+        bitcount: bitcount as u8,
+        holder_type: member.descriptor.r#type.get_kind() as u8,
+    }
+}
+
 impl From<&DynamicTypeMember> for CommonStructMember {
     fn from(value: &DynamicTypeMember) -> Self {
         let mut member_flags = MemberFlag::from(value.descriptor.try_construct_kind);
@@ -2617,8 +2731,19 @@ impl TypeIdentifier {
                 }
                 _ => false,
             },
-            TypeIdentifier::TiPlainMapSmall { map_sdefn: _ } => todo!(),
-            TypeIdentifier::TiPlainMapLarge { map_ldefn: _ } => todo!(),
+            TypeIdentifier::TiPlainMapSmall { .. } | TypeIdentifier::TiPlainMapLarge { .. } => {
+                match (self.plain_map_definition(), other.plain_map_definition()) {
+                    (Some((b1, k1, e1)), Some((b2, k2, e2))) => {
+                        let bounds_ok = type_consistency.ignore_sequence_bounds
+                            || b1 == 0
+                            || (b2 != 0 && b1 >= b2);
+                        bounds_ok
+                            && k1.is_assignable_from_w_type_consistency(k2, type_consistency)
+                            && e1.is_assignable_from_w_type_consistency(e2, type_consistency)
+                    }
+                    _ => false,
+                }
+            }
             TypeIdentifier::TiStronglyConnectedComponent { sc_component_id: _ } => todo!(),
             TypeIdentifier::EkComplete { .. } => matches!(
                 other,
@@ -2649,6 +2774,23 @@ impl TypeIdentifier {
             TypeIdentifier::Default { extended_type: _ } => todo!(),
         }
     }
+
+    /// The bound, key identifier and element identifier of a plain map
+    fn plain_map_definition(&self) -> Option<(u32, &TypeIdentifier, &TypeIdentifier)> {
+        match self {
+            TypeIdentifier::TiPlainMapSmall { map_sdefn } => Some((
+                map_sdefn.bound as u32,
+                &map_sdefn.key_identifier,
+                &map_sdefn.element_identifier,
+            )),
+            TypeIdentifier::TiPlainMapLarge { map_ldefn } => Some((
+                map_ldefn.bound,
+                &map_ldefn.key_identifier,
+                &map_ldefn.element_identifier,
+            )),
+            _ => None,
+        }
+    }
 }
 
 impl CompleteTypeObject {
@@ -2924,9 +3066,13 @@ impl CompleteTypeObject {
 #[cfg(test)]
 mod tests {
     use crate::xtypes::{
-        deserializer::deserialize_top_level_type, serializer::serialize_without_header_cdr2_le,
-        type_support::BoundedString,
+        deserializer::deserialize_top_level_type,
+        dynamic_type::{DynamicTypeBuilderFactory, MemberDescriptor, TypeDescriptor},
+        error::XTypesError,
+        serializer::serialize_without_header_cdr2_le,
+        type_support::{BoundedString, Type},
     };
+    use alloc::collections::BTreeMap;
 
     use super::*;
     #[test]
@@ -2960,6 +3106,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn map_type_identifier() {
+        let parameters_id = TypeIdentifier::from(&BTreeMap::<String, f64>::TYPE);
+        assert_eq!(
+            parameters_id,
+            TypeIdentifier::TiPlainMapLarge {
+                map_ldefn: PlainMapLTypeDefn {
+                    header: PlainCollectionHeader {
+                        equiv_kind: EK_MINIMAL,
+                        element_flags: MEMBER_FLAG_MINIMAL_MASK,
+                    },
+                    bound: u32::MAX,
+                    element_identifier: Box::new(TypeIdentifier::TkFloat64Type),
+                    key_flags: MEMBER_FLAG_MINIMAL_MASK,
+                    key_identifier: Box::new(TypeIdentifier::TiString8Large {
+                        string_ldefn: StringLTypeDefn { bound: u32::MAX }
+                    }),
+                },
+            }
+        );
+        assert!(parameters_id.is_assignable_from(&parameters_id));
+        assert!(
+            !parameters_id
+                .is_assignable_from(&TypeIdentifier::from(&BTreeMap::<String, f32>::TYPE))
+        );
+    }
+
+    #[test]
+    fn nested_collection_type_identifier() {
+        let header = PlainCollectionHeader {
+            equiv_kind: EK_MINIMAL,
+            element_flags: MEMBER_FLAG_MINIMAL_MASK,
+        };
+        assert_eq!(
+            TypeIdentifier::from(&<[[f64; 3]; 2]>::TYPE),
+            TypeIdentifier::TiPlainArraySmall {
+                array_sdefn: PlainArraySElemDefn {
+                    header: header.clone(),
+                    array_bound_seq: vec![2, 3],
+                    element_identifier: Box::new(TypeIdentifier::TkFloat64Type),
+                },
+            }
+        );
+        assert_eq!(
+            TypeIdentifier::from(&Vec::<Vec<f32>>::TYPE),
+            TypeIdentifier::TiPlainSequenceLarge {
+                seq_ldefn: PlainSequenceLElemDefn {
+                    header: header.clone(),
+                    bound: u32::MAX,
+                    element_identifier: Box::new(TypeIdentifier::TiPlainSequenceLarge {
+                        seq_ldefn: PlainSequenceLElemDefn {
+                            header,
+                            bound: u32::MAX,
+                            element_identifier: Box::new(TypeIdentifier::TkFloat32Type),
+                        },
+                    }),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn wide_character_type_identifier() {
+        assert_eq!(
+            TypeIdentifier::from(&WChar::TYPE),
+            TypeIdentifier::TkChar16Type
+        );
+        assert_eq!(
+            TypeIdentifier::from(&WString::TYPE),
+            TypeIdentifier::TiString16Large {
+                string_ldefn: StringLTypeDefn { bound: u32::MAX }
+            }
+        );
+        assert_eq!(
+            TypeIdentifier::from(&DynamicTypeBuilderFactory::create_wstring_type(20).build()),
+            TypeIdentifier::TiString16Small {
+                string_sdefn: StringSTypeDefn { bound: 20 }
+            }
+        );
+    }
+
+    #[test]
+    fn bitset_type_object() {
+        let bitfield = |name, id, kind| MemberDescriptor {
+            name,
+            id,
+            r#type: DynamicTypeBuilderFactory::get_primitive_type(kind),
+            default_value: None,
+            index: 0,
+            try_construct_kind: TryConstructKind::Discard,
+            label: &[],
+            is_key: false,
+            is_optional: false,
+            is_must_understand: false,
+            is_shared: false,
+            is_default_label: false,
+            is_external: false,
+        };
+        let mut builder = DynamicTypeBuilderFactory::create_type(TypeDescriptor {
+            kind: TypeKind::BITSET,
+            name: "Status",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[1, 12],
+            element_type: None,
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        });
+        assert_eq!(
+            builder.add_member(bitfield("ready", 0, TypeKind::FLOAT32)),
+            Err(XTypesError::InvalidType)
+        );
+        assert_eq!(
+            builder.add_member(bitfield("ready", 64, TypeKind::BOOLEAN)),
+            Err(XTypesError::InvalidData)
+        );
+        builder
+            .add_member(bitfield("ready", 0, TypeKind::BOOLEAN))
+            .unwrap();
+        assert_eq!(
+            builder.add_member(bitfield("level", 4, TypeKind::UINT8)),
+            Err(XTypesError::InvalidData)
+        );
+        builder
+            .add_member(bitfield("level", 4, TypeKind::INT16))
+            .unwrap();
+        assert_eq!(
+            builder.add_member(bitfield("extra", 16, TypeKind::UINT8)),
+            Err(XTypesError::IllegalOperation)
+        );
+        let status_type = builder.build();
+
+        let CompleteTypeObject::TkBitset { bitset_type } = CompleteTypeObject::from(status_type)
+        else {
+            panic!("Expected complete bitset type object")
+        };
+        assert_eq!(bitset_type.header.detail.type_name, "Status");
+        assert_eq!(
+            bitset_type
+                .field_seq
+                .iter()
+                .map(|f| (
+                    f.detail.name.as_str(),
+                    f.common.position,
+                    f.common.bitcount,
+                    f.common.holder_type
+                ))
+                .collect::<Vec<_>>(),
+            vec![("ready", 0, 1, TK_BOOLEAN), ("level", 4, 12, TK_INT16)]
+        );
+        let MinimalTypeObject::TkBitset { bitset_type } = MinimalTypeObject::from(status_type)
+        else {
+            panic!("Expected minimal bitset type object")
+        };
+        assert_eq!(bitset_type.field_seq[1].common.position, 4);
+        assert!(matches!(
+            TypeIdentifier::from(&status_type),
+            TypeIdentifier::EkComplete { .. }
+        ));
+    }
+
     #[test]
     fn serialize_plain_array_s_elem_defn() {
         let dynamic_data = PlainArraySElemDefn {
@@ -2,8 +2,9 @@ use crate::xtypes::{
     data_storage::DataStorageMapping,
     dynamic_type::{DynamicData, DynamicType, ExtensibilityKind, TypeDescriptor, TypeKind},
     error::XTypesError,
+    xcdr::{XcdrDeserialize, XcdrDeserializer, XcdrSerialize, XcdrSerializer},
 };
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
 pub use dust_dds_derive::TypeSupport;
 
 use super::{data_storage::DataStorage, error::XTypesResult};
@@ -28,6 +29,26 @@ pub trait TypeSupport: Type {
 
     /// Create a 'DynamicData' object with the contents of an input sample of the TypeSupport’s data type.
     fn create_dynamic_sample(self) -> DynamicData<'static>;
+
+    /// Serialize the sample directly in the XCDR representation. Types not providing it are
+    /// serialized through their 'DynamicData' representation.
+    fn serialize_nested(&self, _serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        Err(XTypesError::IllegalOperation)
+    }
+
+    /// Serialize the key members of the sample directly in the XCDR representation.
+    fn serialize_key_members(&self, _serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        Err(XTypesError::IllegalOperation)
+    }
+
+    /// Deserialize a sample directly from the XCDR representation. Types not providing it are
+    /// deserialized through their 'DynamicData' representation.
+    fn deserialize_nested(_deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self>
+    where
+        Self: Sized,
+    {
+        Err(XTypesError::IllegalOperation)
+    }
 }
 
 /// Preregistered String type as per Annex E: Built-in Types
@@ -263,6 +284,18 @@ impl<T: TypeSupport> TypeSupport for Box<T> {
     fn create_dynamic_sample(self) -> DynamicData<'static> {
         T::create_dynamic_sample(*self)
     }
+
+    fn serialize_nested(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.as_ref().serialize_nested(serializer)
+    }
+
+    fn serialize_key_members(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.as_ref().serialize_key_members(serializer)
+    }
+
+    fn deserialize_nested(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        T::deserialize_nested(deserializer).map(Box::new)
+    }
 }
 
 impl<T: Type> Type for Option<T> {
@@ -286,6 +319,8 @@ impl Type for String {
     };
 }
 
+// Arrays of arrays are represented as a single multi-dimensional array, with the outermost
+// dimension first, as done by the IDL to type mapping.
 impl<T: Type, const N: usize> Type for [T; N] {
     const TYPE: DynamicType<'static> = crate::xtypes::dynamic_type::DynamicType {
         descriptor: &TypeDescriptor {
@@ -293,8 +328,17 @@ impl<T: Type, const N: usize> Type for [T; N] {
             name: "",
             base_type: None,
             discriminator_type: None,
-            bound: &[N as u32],
-            element_type: Some(T::TYPE),
+            bound: match (T::TYPE.descriptor.kind, T::TYPE.descriptor.bound) {
+                (TypeKind::ARRAY, [a]) => &[N as u32, *a],
+                (TypeKind::ARRAY, [a, b]) => &[N as u32, *a, *b],
+                (TypeKind::ARRAY, [a, b, c]) => &[N as u32, *a, *b, *c],
+                (TypeKind::ARRAY, _) => panic!("Arrays with more than 4 dimensions not supported"),
+                _ => &[N as u32],
+            },
+            element_type: match T::TYPE.descriptor.kind {
+                TypeKind::ARRAY => T::TYPE.descriptor.element_type,
+                _ => Some(T::TYPE),
+            },
             key_element_type: None,
             extensibility_kind: ExtensibilityKind::Final,
             is_nested: false,
@@ -354,6 +398,46 @@ impl<T: TypeSupport> Type for Vec<T> {
     };
 }
 
+impl<T> Type for Vec<Vec<T>>
+where
+    Vec<T>: Type,
+{
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::SEQUENCE,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: Some(<Vec<T>>::TYPE),
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+impl<T, const N: usize> Type for Vec<[T; N]>
+where
+    [T; N]: Type,
+{
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::SEQUENCE,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: Some(<[T; N]>::TYPE),
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
 impl Type for Vec<bool> {
     const TYPE: DynamicType<'static> = DynamicType {
         descriptor: &TypeDescriptor {
@@ -575,6 +659,28 @@ impl Type for Vec<String> {
     };
 }
 
+impl<K: Type, V: Type> Type for BTreeMap<K, V> {
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::MAP,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: Some(V::TYPE),
+            key_element_type: Some(K::TYPE),
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+#[cfg(feature = "std")]
+impl<K: Type, V: Type, S> Type for std::collections::HashMap<K, V, S> {
+    const TYPE: DynamicType<'static> = BTreeMap::<K, V>::TYPE;
+}
+
 /// Type used to represent bounded strings on DDS Types
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoundedString<const N: u32>(String);
@@ -627,6 +733,320 @@ impl<const N: u32> DataStorageMapping for BoundedString<N> {
     }
 }
 
+impl<const N: u32> XcdrSerialize for BoundedString<N> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<const N: u32> XcdrDeserialize for BoundedString<N> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        // Strings exceeding the bound are rejected. Members whose try construct kind is not
+        // DISCARD are not deserialized directly but through the dynamic representation
+        let value = String::deserialize(deserializer)?;
+        if N > 0 && N != u32::MAX && value.chars().count() > N as usize {
+            return Err(XTypesError::InvalidData);
+        }
+        Ok(Self(value))
+    }
+}
+
+/// Type used to represent wide characters (`wchar`) on DDS Types. The character is serialized
+/// as a single UTF-16 code unit so it must be in the basic multilingual plane.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct WChar(char);
+
+impl TryFrom<char> for WChar {
+    type Error = XTypesError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        if value.len_utf16() == 1 {
+            Ok(Self(value))
+        } else {
+            Err(XTypesError::InvalidData)
+        }
+    }
+}
+
+impl From<WChar> for char {
+    fn from(value: WChar) -> Self {
+        value.0
+    }
+}
+
+impl Type for WChar {
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::CHAR16,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[],
+            element_type: None,
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+impl Type for Vec<WChar> {
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::SEQUENCE,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: Some(WChar::TYPE),
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+impl DataStorageMapping for WChar {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::Char16(self.0)
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::Char16(x) => Self::try_from(x),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl DataStorageMapping for Vec<WChar> {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceChar16(self.into_iter().map(char::from).collect())
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::SequenceChar16(x) => x.into_iter().map(WChar::try_from).collect(),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl<const N: usize> DataStorageMapping for [WChar; N] {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceChar16(self.into_iter().map(char::from).collect())
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        Vec::<WChar>::try_from_storage(data_storage)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidType)
+    }
+}
+
+impl XcdrSerialize for WChar {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        let mut unit = [0; 1];
+        self.0.encode_utf16(&mut unit);
+        unit[0].serialize(serializer)
+    }
+}
+
+impl XcdrDeserialize for WChar {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let unit = u16::deserialize(deserializer)?;
+        char::from_u32(unit as u32)
+            .map(Self)
+            .ok_or(XTypesError::InvalidData)
+    }
+}
+
+/// Type used to represent wide strings (`wstring`) on DDS Types. The string is serialized as
+/// UTF-16 code units.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct WString(String);
+
+impl From<String> for WString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<WString> for String {
+    fn from(value: WString) -> Self {
+        value.0
+    }
+}
+
+impl Type for WString {
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::STRING16,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: None,
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+impl Type for Vec<WString> {
+    const TYPE: DynamicType<'static> = DynamicType {
+        descriptor: &TypeDescriptor {
+            kind: TypeKind::SEQUENCE,
+            name: "",
+            base_type: None,
+            discriminator_type: None,
+            bound: &[u32::MAX],
+            element_type: Some(WString::TYPE),
+            key_element_type: None,
+            extensibility_kind: ExtensibilityKind::Final,
+            is_nested: false,
+        },
+        member_list: &[],
+    };
+}
+
+impl DataStorageMapping for WString {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::String(self.0)
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::String(x) => Ok(Self(x)),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl DataStorageMapping for Vec<WString> {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceString(self.into_iter().map(String::from).collect())
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        match data_storage {
+            DataStorage::SequenceString(x) => Ok(x.into_iter().map(WString).collect()),
+            _ => Err(XTypesError::InvalidType),
+        }
+    }
+}
+
+impl<const N: usize> DataStorageMapping for [WString; N] {
+    fn into_storage(self) -> DataStorage {
+        DataStorage::SequenceString(self.into_iter().map(String::from).collect())
+    }
+
+    fn try_from_storage(data_storage: DataStorage) -> XTypesResult<Self> {
+        Vec::<WString>::try_from_storage(data_storage)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidType)
+    }
+}
+
+impl XcdrSerialize for WString {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        // The length is the number of bytes and there is no NUL termination
+        (2 * self.0.encode_utf16().count() as u32).serialize(serializer)?;
+        for unit in self.0.encode_utf16() {
+            unit.serialize(serializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl XcdrDeserialize for WString {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let length = u32::deserialize(deserializer)? as usize;
+        if !length.is_multiple_of(2) {
+            return Err(XTypesError::InvalidData);
+        }
+        let mut units = Vec::new();
+        for _ in 0..length / 2 {
+            units.push(u16::deserialize(deserializer)?);
+        }
+        String::from_utf16(&units)
+            .map(Self)
+            .map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+/// Trait implemented by the primitive types which can hold the value of a bitfield of a bitset.
+pub trait BitfieldHolder: Sized {
+    /// Maximum number of bits of a bitfield held by this type.
+    const BITS: u32;
+
+    /// Returns the value as bits. Signed values are sign extended.
+    fn to_bits(&self) -> u64;
+
+    /// Creates the value from the `bitcount` least significant bits. Signed values are sign
+    /// extended from the most significant of those bits.
+    fn from_bits(bits: u64, bitcount: u32) -> Self;
+}
+
+/// Returns the mask selecting the `bitcount` least significant bits.
+pub const fn bitfield_mask(bitcount: u32) -> u64 {
+    if bitcount >= u64::BITS {
+        u64::MAX
+    } else {
+        (1 << bitcount) - 1
+    }
+}
+
+impl BitfieldHolder for bool {
+    const BITS: u32 = 1;
+
+    fn to_bits(&self) -> u64 {
+        *self as u64
+    }
+
+    fn from_bits(bits: u64, bitcount: u32) -> Self {
+        bits & bitfield_mask(bitcount) != 0
+    }
+}
+
+macro_rules! impl_bitfield_holder {
+    ($($unsigned:ty),*; $($signed:ty),*) => {
+        $(
+            impl BitfieldHolder for $unsigned {
+                const BITS: u32 = <$unsigned>::BITS;
+
+                fn to_bits(&self) -> u64 {
+                    *self as u64
+                }
+
+                fn from_bits(bits: u64, bitcount: u32) -> Self {
+                    (bits & bitfield_mask(bitcount)) as $unsigned
+                }
+            }
+        )*
+        $(
+            impl BitfieldHolder for $signed {
+                const BITS: u32 = <$signed>::BITS;
+
+                fn to_bits(&self) -> u64 {
+                    *self as u64
+                }
+
+                fn from_bits(bits: u64, bitcount: u32) -> Self {
+                    let shift = u64::BITS - bitcount.clamp(1, u64::BITS);
+                    (((bits << shift) as i64) >> shift) as $signed
+                }
+            }
+        )*
+    };
+}
+
+impl_bitfield_holder!(u8, u16, u32, u64; i8, i16, i32, i64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
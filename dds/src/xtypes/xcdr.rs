@@ -0,0 +1,1768 @@
+use crate::xtypes::{
+    deserializer::deserialize_top_level_type,
+    dynamic_type::{DynamicData, DynamicType, ExtensibilityKind, TypeKind},
+    error::{XTypesError, XTypesResult},
+    serializer::{
+        is_element_type_kind_primitive, is_map_type_primitive, serialize_cdr1_be,
+        serialize_cdr1_le, serialize_cdr2_be, serialize_cdr2_le,
+    },
+    type_support::{Type, TypeSupport, WChar, WString},
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+type RepresentationIdentifier = [u8; 2];
+const CDR_BE: RepresentationIdentifier = [0x00, 0x00];
+const CDR_LE: RepresentationIdentifier = [0x00, 0x01];
+const PL_CDR_BE: RepresentationIdentifier = [0x00, 0x02];
+const PL_CDR_LE: RepresentationIdentifier = [0x00, 0x03];
+const CDR2_BE: RepresentationIdentifier = [0x00, 0x06];
+const CDR2_LE: RepresentationIdentifier = [0x00, 0x07];
+const D_CDR2_BE: RepresentationIdentifier = [0x00, 0x08];
+const D_CDR2_LE: RepresentationIdentifier = [0x00, 0x09];
+const PL_CDR2_BE: RepresentationIdentifier = [0x00, 0x0a];
+const PL_CDR2_LE: RepresentationIdentifier = [0x00, 0x0b];
+const REPRESENTATION_OPTIONS: [u8; 2] = [0x00, 0x00];
+const PID_SENTINEL: u16 = 1;
+
+/// Version of the extended CDR encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XcdrVersion {
+    /// Extended CDR encoding version 1
+    Xcdr1,
+    /// Extended CDR encoding version 2
+    Xcdr2,
+}
+
+/// Byte order of the serialized data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    /// Most significant byte first
+    BigEndian,
+    /// Least significant byte first
+    LittleEndian,
+}
+
+impl Endianness {
+    /// Byte order of the target platform
+    pub const NATIVE: Self = if cfg!(target_endian = "big") {
+        Self::BigEndian
+    } else {
+        Self::LittleEndian
+    };
+}
+
+/// Types which can be serialized directly in the XCDR representation.
+pub trait XcdrSerialize {
+    /// Serialize the value as a member of an aggregated type or as an element of a collection.
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()>;
+
+    /// Serialize the keys contained in the value when it is a non-key member of a structure.
+    /// Only structures contain keys so nothing is serialized by default.
+    fn serialize_key(&self, _serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        Ok(())
+    }
+
+    /// Serialize the elements of an array without the DHEADER of the version 2 encoding. Arrays
+    /// of arrays are encoded as a single multi-dimensional array so their inner arrays only
+    /// write their elements. Other values are serialized as usual.
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.serialize(serializer)
+    }
+}
+
+/// Types which can be deserialized directly from the XCDR representation.
+pub trait XcdrDeserialize: Sized {
+    /// Deserialize the value of a member of an aggregated type or of an element of a collection.
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self>;
+
+    /// Deserialize the elements of an array without the DHEADER of the version 2 encoding.
+    /// Counterpart of [`XcdrSerialize::serialize_array_elements`].
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        Self::deserialize(deserializer)
+    }
+}
+
+/// Aggregated type (structure or union) being serialized or deserialized. It is returned when
+/// the aggregated type is started and must be given back when it is ended.
+pub struct XcdrAggregatedType {
+    extensibility: ExtensibilityKind,
+    start: usize,
+    end: Option<usize>,
+    member_end: usize,
+    is_member_list_finished: bool,
+}
+
+/// Serializer writing the XCDR representation of the values of the types deriving
+/// [`TypeSupport`] without creating their [`DynamicData`](crate::xtypes::dynamic_type::DynamicData).
+/// The output is identical to the one of the serialization of their dynamic representation.
+pub struct XcdrSerializer {
+    buffer: Vec<u8>,
+    position: usize,
+    version: XcdrVersion,
+    endianness: Endianness,
+}
+
+impl XcdrSerializer {
+    /// Create a serializer for a value without encapsulation header.
+    pub fn new(version: XcdrVersion, endianness: Endianness) -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+            version,
+            endianness,
+        }
+    }
+
+    /// Encoding version used by the serializer.
+    pub fn version(&self) -> XcdrVersion {
+        self.version
+    }
+
+    /// Return the serialized bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Start the serialization of an aggregated type with the given extensibility.
+    pub fn begin_aggregated_type(
+        &mut self,
+        extensibility: ExtensibilityKind,
+    ) -> XcdrAggregatedType {
+        let start = match (self.version, extensibility) {
+            (XcdrVersion::Xcdr2, ExtensibilityKind::Appendable | ExtensibilityKind::Mutable) => {
+                self.begin_dheader()
+            }
+            _ => self.buffer.len(),
+        };
+        XcdrAggregatedType {
+            extensibility,
+            start,
+            end: None,
+            member_end: start,
+            is_member_list_finished: false,
+        }
+    }
+
+    /// End the serialization of an aggregated type.
+    pub fn end_aggregated_type(&mut self, aggregated_type: XcdrAggregatedType) {
+        match (self.version, aggregated_type.extensibility) {
+            (XcdrVersion::Xcdr1, ExtensibilityKind::Mutable) => {
+                self.align(4);
+                self.write_u16(PID_SENTINEL);
+                self.write_u16(0);
+            }
+            (XcdrVersion::Xcdr2, ExtensibilityKind::Appendable | ExtensibilityKind::Mutable) => {
+                self.end_dheader(aggregated_type.start)
+            }
+            _ => (),
+        }
+    }
+
+    /// Serialize an optional member of a final or appendable aggregated type. A [`None`] value
+    /// is serialized as an absent member.
+    pub fn serialize_optional_member<T: XcdrSerialize + ?Sized>(
+        &mut self,
+        member_id: u32,
+        is_must_understand: bool,
+        value: Option<&T>,
+    ) -> XTypesResult<()> {
+        match self.version {
+            XcdrVersion::Xcdr1 => self.serialize_parameter(member_id, is_must_understand, value),
+            XcdrVersion::Xcdr2 => {
+                value.is_some().serialize(self)?;
+                if let Some(value) = value {
+                    value.serialize(self)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Serialize a present member of a mutable aggregated type.
+    pub fn serialize_mutable_member<T: XcdrSerialize + Type + ?Sized>(
+        &mut self,
+        member_id: u32,
+        is_must_understand: bool,
+        value: &T,
+    ) -> XTypesResult<()> {
+        match self.version {
+            XcdrVersion::Xcdr1 => {
+                self.serialize_parameter(member_id, is_must_understand, Some(value))
+            }
+            XcdrVersion::Xcdr2 => {
+                self.align(4);
+                self.write_slice(&[0; 4]);
+                let initial_pos = self.buffer.len();
+                value.serialize(self)?;
+                let ssize = (self.buffer.len() - initial_pos) as u32;
+                let is_next_member_having_dheader =
+                    (matches!(T::TYPE.get_kind(), TypeKind::STRUCTURE | TypeKind::UNION)
+                        && matches!(
+                            T::TYPE.descriptor.extensibility_kind,
+                            ExtensibilityKind::Appendable | ExtensibilityKind::Mutable
+                        ))
+                        || T::TYPE.get_kind() == TypeKind::SEQUENCE
+                        || (T::TYPE.get_kind() == TypeKind::MAP
+                            && !is_map_type_primitive(T::TYPE)?);
+                let lc = if is_next_member_having_dheader {
+                    5
+                } else {
+                    match ssize {
+                        1 => 0,
+                        2 => 1,
+                        4 => 2,
+                        8 => 3,
+                        _ => 4,
+                    }
+                };
+                let emheader =
+                    ((is_must_understand as u32) << 31) + (lc << 28) + (member_id & 0x0fffffff);
+                let emheader_bytes = self.u32_bytes(emheader);
+                self.buffer[initial_pos - 4..initial_pos].copy_from_slice(&emheader_bytes);
+                if lc == 4 {
+                    let ssize_bytes = self.u32_bytes(ssize);
+                    self.buffer.splice(initial_pos..initial_pos, ssize_bytes);
+                    self.position += 4;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Member of mutable aggregated type (or optional member of final aggregated type) in the
+    /// version 1 encoding using the short parameter list encoding
+    fn serialize_parameter<T: XcdrSerialize + ?Sized>(
+        &mut self,
+        member_id: u32,
+        is_must_understand: bool,
+        value: Option<&T>,
+    ) -> XTypesResult<()> {
+        self.align(4);
+        let pid = (member_id as u16).wrapping_add((is_must_understand as u16) << 14);
+        self.write_u16(pid);
+        self.write_slice(&[0; 2]);
+        let initial_pos = self.buffer.len();
+        self.position = 0;
+        if let Some(value) = value {
+            value.serialize(self)?;
+        }
+        let ssize = (self.buffer.len() - initial_pos) as u16;
+        let ssize_bytes = self.u16_bytes(ssize);
+        self.buffer[initial_pos - 2..initial_pos].copy_from_slice(&ssize_bytes);
+        Ok(())
+    }
+
+    fn begin_dheader(&mut self) -> usize {
+        self.align(4);
+        self.write_slice(&[0; 4]);
+        self.buffer.len()
+    }
+
+    fn end_dheader(&mut self, initial_pos: usize) {
+        let dheader = self.u32_bytes((self.buffer.len() - initial_pos) as u32);
+        self.buffer[initial_pos - 4..initial_pos].copy_from_slice(&dheader);
+    }
+
+    fn serialize_sequence_length(&mut self, length: usize) {
+        self.align(4);
+        let length = self.u32_bytes(length as u32);
+        self.write_slice(&length);
+    }
+
+    /// Sequence of non-primitive elements which in the version 2 encoding is preceded by a DHEADER
+    fn serialize_non_primitive_elements<T: XcdrSerialize + Type>(
+        &mut self,
+        elements: &[T],
+        is_sequence: bool,
+    ) -> XTypesResult<()> {
+        let dheader = match self.version {
+            XcdrVersion::Xcdr1 => None,
+            XcdrVersion::Xcdr2 => Some(self.begin_dheader()),
+        };
+        if is_sequence {
+            self.serialize_sequence_length(elements.len());
+        }
+        self.serialize_non_primitive_array_elements(elements)?;
+        if let Some(initial_pos) = dheader {
+            self.end_dheader(initial_pos);
+        }
+        Ok(())
+    }
+
+    /// Non-primitive elements of a collection without the DHEADER preceding them
+    fn serialize_non_primitive_array_elements<T: XcdrSerialize + Type>(
+        &mut self,
+        elements: &[T],
+    ) -> XTypesResult<()> {
+        // Unions in collections are serialized as final unions
+        if T::TYPE.get_kind() == TypeKind::UNION
+            && T::TYPE.descriptor.extensibility_kind != ExtensibilityKind::Final
+        {
+            return Err(XTypesError::IllegalOperation);
+        }
+        for element in elements {
+            element.serialize(self)?;
+        }
+        Ok(())
+    }
+
+    /// Map entries which in the version 2 encoding are preceded by a DHEADER unless both the key
+    /// and the element types are primitive
+    fn serialize_map_entries<'b, K, V>(
+        &mut self,
+        entries: impl ExactSizeIterator<Item = (&'b K, &'b V)>,
+    ) -> XTypesResult<()>
+    where
+        K: XcdrSerialize + Type + 'b,
+        V: XcdrSerialize + Type + 'b,
+    {
+        let dheader = match self.version {
+            XcdrVersion::Xcdr2 if !is_map_type_primitive(BTreeMap::<K, V>::TYPE)? => {
+                Some(self.begin_dheader())
+            }
+            _ => None,
+        };
+        self.serialize_sequence_length(entries.len());
+        for (key, value) in entries {
+            key.serialize(self)?;
+            value.serialize(self)?;
+        }
+        if let Some(initial_pos) = dheader {
+            self.end_dheader(initial_pos);
+        }
+        Ok(())
+    }
+
+    fn align(&mut self, size: usize) {
+        const ZEROS: [u8; 8] = [0; 8];
+        let alignment = match self.version {
+            XcdrVersion::Xcdr1 => core::cmp::min(size, 8),
+            XcdrVersion::Xcdr2 => core::cmp::min(size, 4),
+        };
+        let padding = self.position.div_ceil(alignment) * alignment - self.position;
+        self.write_slice(&ZEROS[..padding]);
+    }
+
+    fn write_slice(&mut self, value: &[u8]) {
+        self.buffer.extend_from_slice(value);
+        self.position += value.len();
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.align(2);
+        let bytes = self.u16_bytes(value);
+        self.write_slice(&bytes);
+    }
+
+    fn u16_bytes(&self, value: u16) -> [u8; 2] {
+        match self.endianness {
+            Endianness::BigEndian => value.to_be_bytes(),
+            Endianness::LittleEndian => value.to_le_bytes(),
+        }
+    }
+
+    fn u32_bytes(&self, value: u32) -> [u8; 4] {
+        match self.endianness {
+            Endianness::BigEndian => value.to_be_bytes(),
+            Endianness::LittleEndian => value.to_le_bytes(),
+        }
+    }
+}
+
+/// Deserializer reading the values of the types deriving [`TypeSupport`] directly from their
+/// XCDR representation.
+pub struct XcdrDeserializer<'a> {
+    buffer: &'a [u8],
+    position: usize,
+    origin: usize,
+    version: XcdrVersion,
+    endianness: Endianness,
+}
+
+impl<'a> XcdrDeserializer<'a> {
+    /// Create a deserializer for a value without encapsulation header.
+    pub fn new(buffer: &'a [u8], version: XcdrVersion, endianness: Endianness) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            origin: 0,
+            version,
+            endianness,
+        }
+    }
+
+    /// Encoding version used by the deserializer.
+    pub fn version(&self) -> XcdrVersion {
+        self.version
+    }
+
+    /// Start the deserialization of an aggregated type with the given extensibility.
+    pub fn begin_aggregated_type(
+        &mut self,
+        extensibility: ExtensibilityKind,
+    ) -> XTypesResult<XcdrAggregatedType> {
+        let end = match (self.version, extensibility) {
+            (XcdrVersion::Xcdr2, ExtensibilityKind::Appendable | ExtensibilityKind::Mutable) => {
+                Some(self.read_dheader()?)
+            }
+            _ => None,
+        };
+        Ok(XcdrAggregatedType {
+            extensibility,
+            start: self.position,
+            end,
+            member_end: self.position,
+            is_member_list_finished: false,
+        })
+    }
+
+    /// End the deserialization of an aggregated type, skipping the members which were not read.
+    pub fn end_aggregated_type(
+        &mut self,
+        mut aggregated_type: XcdrAggregatedType,
+    ) -> XTypesResult<()> {
+        if aggregated_type.extensibility == ExtensibilityKind::Mutable {
+            while self.next_mutable_member(&mut aggregated_type)?.is_some() {
+                self.skip_mutable_member(&aggregated_type)?;
+            }
+        }
+        if let Some(end) = aggregated_type.end {
+            if self.position > end {
+                return Err(XTypesError::InvalidData);
+            }
+            self.position = end;
+        }
+        Ok(())
+    }
+
+    /// Deserialize an optional member of a final or appendable aggregated type. An absent member
+    /// is returned as [`None`].
+    pub fn deserialize_optional_member<T: XcdrDeserialize>(
+        &mut self,
+        member_id: u32,
+    ) -> XTypesResult<Option<T>> {
+        match self.version {
+            XcdrVersion::Xcdr1 => {
+                self.align(4)?;
+                let pid = self.read_u16()?;
+                let length = self.read_u16()? as usize;
+                if pid & 0x3fff != member_id as u16 {
+                    return Err(XTypesError::PidNotFound(member_id as u16));
+                }
+                self.origin = self.position;
+                let end = self.position + length;
+                let value = if length > 0 {
+                    Some(T::deserialize(self)?)
+                } else {
+                    None
+                };
+                if self.position > end {
+                    return Err(XTypesError::InvalidData);
+                }
+                self.position = end;
+                Ok(value)
+            }
+            XcdrVersion::Xcdr2 => {
+                if bool::deserialize(self)? {
+                    Ok(Some(T::deserialize(self)?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    /// Read the header of the next present member of a mutable aggregated type and return its
+    /// id, or [`None`] at the end of the member list. The member value must then be either
+    /// deserialized or skipped.
+    pub fn next_mutable_member(
+        &mut self,
+        aggregated_type: &mut XcdrAggregatedType,
+    ) -> XTypesResult<Option<u32>> {
+        while !aggregated_type.is_member_list_finished {
+            match self.version {
+                XcdrVersion::Xcdr1 => {
+                    self.align(4)?;
+                    let pid = self.read_u16()? & 0x3fff;
+                    let length = self.read_u16()? as usize;
+                    if pid == PID_SENTINEL && length == 0 {
+                        aggregated_type.is_member_list_finished = true;
+                    } else {
+                        self.origin = self.position;
+                        aggregated_type.member_end = self.position + length;
+                        // Absent members are serialized with an empty value
+                        if length > 0 {
+                            return Ok(Some(pid as u32));
+                        }
+                    }
+                }
+                XcdrVersion::Xcdr2 => {
+                    let end = aggregated_type.end.ok_or(XTypesError::InvalidData)?;
+                    if self.position < end {
+                        self.align(4)?;
+                    }
+                    if self.position >= end {
+                        aggregated_type.is_member_list_finished = true;
+                    } else {
+                        let emheader = self.read_u32()?;
+                        let lc = (emheader >> 28) & 0b111;
+                        let length = match lc {
+                            0 => 1,
+                            1 => 2,
+                            2 => 4,
+                            3 => 8,
+                            4 | 5 => self.read_u32()? as usize,
+                            6 => 4 * self.read_u32()? as usize,
+                            _ => 8 * self.read_u32()? as usize,
+                        };
+                        aggregated_type.member_end = self.position + length;
+                        // The NEXTINT is also the first integer of the member value
+                        if lc >= 5 {
+                            self.position -= 4;
+                        }
+                        return Ok(Some(emheader & 0x0fffffff));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Skip the value of the member of a mutable aggregated type whose header was just read.
+    pub fn skip_mutable_member(
+        &mut self,
+        aggregated_type: &XcdrAggregatedType,
+    ) -> XTypesResult<()> {
+        if aggregated_type.member_end > self.buffer.len() {
+            return Err(XTypesError::NotEnoughData);
+        }
+        self.position = aggregated_type.member_end;
+        Ok(())
+    }
+
+    /// Deserialize the next member of a mutable aggregated type which must have the given id.
+    pub fn deserialize_next_mutable_member<T: XcdrDeserialize>(
+        &mut self,
+        aggregated_type: &mut XcdrAggregatedType,
+        member_id: u32,
+    ) -> XTypesResult<T> {
+        if self.next_mutable_member(aggregated_type)? == Some(member_id) {
+            T::deserialize(self)
+        } else {
+            Err(XTypesError::InvalidData)
+        }
+    }
+
+    fn read_dheader(&mut self) -> XTypesResult<usize> {
+        let length = self.read_u32()? as usize;
+        let end = self.position + length;
+        if end > self.buffer.len() {
+            return Err(XTypesError::NotEnoughData);
+        }
+        Ok(end)
+    }
+
+    fn deserialize_sequence_length(&mut self) -> XTypesResult<usize> {
+        Ok(self.read_u32()? as usize)
+    }
+
+    fn deserialize_non_primitive_elements<T: XcdrDeserialize + Type>(
+        &mut self,
+        length: Option<usize>,
+    ) -> XTypesResult<Vec<T>> {
+        if self.version == XcdrVersion::Xcdr2 {
+            self.read_dheader()?;
+        }
+        let length = match length {
+            Some(length) => length,
+            None => self.deserialize_sequence_length()?,
+        };
+        self.deserialize_non_primitive_array_elements(length)
+    }
+
+    fn deserialize_non_primitive_array_elements<T: XcdrDeserialize + Type>(
+        &mut self,
+        length: usize,
+    ) -> XTypesResult<Vec<T>> {
+        if T::TYPE.get_kind() == TypeKind::UNION
+            && T::TYPE.descriptor.extensibility_kind != ExtensibilityKind::Final
+        {
+            return Err(XTypesError::IllegalOperation);
+        }
+        let mut elements = Vec::with_capacity(core::cmp::min(length, self.remaining()));
+        for _ in 0..length {
+            elements.push(T::deserialize(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn deserialize_primitive_elements<T: XcdrDeserialize>(
+        &mut self,
+        length: usize,
+    ) -> XTypesResult<Vec<T>> {
+        let mut elements = Vec::with_capacity(core::cmp::min(length, self.remaining()));
+        for _ in 0..length {
+            elements.push(T::deserialize(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn deserialize_map_entries<K, V>(&mut self) -> XTypesResult<Vec<(K, V)>>
+    where
+        K: XcdrDeserialize + Type,
+        V: XcdrDeserialize + Type,
+    {
+        if self.version == XcdrVersion::Xcdr2 && !is_map_type_primitive(BTreeMap::<K, V>::TYPE)? {
+            self.read_dheader()?;
+        }
+        let length = self.deserialize_sequence_length()?;
+        let mut entries = Vec::with_capacity(core::cmp::min(length, self.remaining()));
+        for _ in 0..length {
+            let key = K::deserialize(self)?;
+            let value = V::deserialize(self)?;
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
+
+    fn remaining(&self) -> usize {
+        self.buffer.len().saturating_sub(self.position)
+    }
+
+    fn align(&mut self, size: usize) -> XTypesResult<()> {
+        let alignment = match self.version {
+            XcdrVersion::Xcdr1 => core::cmp::min(size, 8),
+            XcdrVersion::Xcdr2 => core::cmp::min(size, 4),
+        };
+        let offset = self.position - self.origin;
+        self.seek(offset.div_ceil(alignment) * alignment - offset)
+    }
+
+    fn seek(&mut self, length: usize) -> XTypesResult<()> {
+        if self.position + length > self.buffer.len() {
+            return Err(XTypesError::NotEnoughData);
+        }
+        self.position += length;
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, length: usize) -> XTypesResult<&'a [u8]> {
+        if self.position + length > self.buffer.len() {
+            return Err(XTypesError::NotEnoughData);
+        }
+        let bytes = &self.buffer[self.position..self.position + length];
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> XTypesResult<[u8; N]> {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(self.read_bytes(N)?);
+        Ok(bytes)
+    }
+
+    fn read_u16(&mut self) -> XTypesResult<u16> {
+        u16::deserialize(self)
+    }
+
+    fn read_u32(&mut self) -> XTypesResult<u32> {
+        u32::deserialize(self)
+    }
+}
+
+/// Serialize a sample in the XCDR representation including its encapsulation header.
+pub fn serialize_top_level<T: XcdrSerialize + Type + ?Sized>(
+    value: &T,
+    version: XcdrVersion,
+    endianness: Endianness,
+) -> XTypesResult<Vec<u8>> {
+    let extensibility = T::TYPE.descriptor.extensibility_kind;
+    let representation_identifier = match (version, extensibility, endianness) {
+        (
+            XcdrVersion::Xcdr1,
+            ExtensibilityKind::Final | ExtensibilityKind::Appendable,
+            Endianness::BigEndian,
+        ) => CDR_BE,
+        (
+            XcdrVersion::Xcdr1,
+            ExtensibilityKind::Final | ExtensibilityKind::Appendable,
+            Endianness::LittleEndian,
+        ) => CDR_LE,
+        (XcdrVersion::Xcdr1, ExtensibilityKind::Mutable, Endianness::BigEndian) => PL_CDR_BE,
+        (XcdrVersion::Xcdr1, ExtensibilityKind::Mutable, Endianness::LittleEndian) => PL_CDR_LE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Final, Endianness::BigEndian) => CDR2_BE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Final, Endianness::LittleEndian) => CDR2_LE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Appendable, Endianness::BigEndian) => D_CDR2_BE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Appendable, Endianness::LittleEndian) => D_CDR2_LE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Mutable, Endianness::BigEndian) => PL_CDR2_BE,
+        (XcdrVersion::Xcdr2, ExtensibilityKind::Mutable, Endianness::LittleEndian) => PL_CDR2_LE,
+    };
+    let mut serializer = XcdrSerializer::new(version, endianness);
+    serializer.write_slice(&representation_identifier);
+    serializer.write_slice(&REPRESENTATION_OPTIONS);
+    serializer.position = 0;
+    value.serialize(&mut serializer)?;
+
+    let mut buffer = serializer.into_bytes();
+    let padding = match buffer.len() % 4 {
+        1 => &[0, 0, 0][..],
+        2 => &[0, 0][..],
+        3 => &[0][..],
+        _ => &[][..],
+    };
+    buffer.extend_from_slice(padding);
+    buffer[3] = padding.len() as u8;
+    Ok(buffer)
+}
+
+/// Deserialize a sample from its XCDR representation including its encapsulation header.
+pub fn deserialize_top_level<T: XcdrDeserialize>(buffer: &[u8]) -> XTypesResult<T> {
+    if buffer.len() < 4 {
+        return Err(XTypesError::NotEnoughData);
+    }
+    let (version, endianness) = match [buffer[0], buffer[1]] {
+        CDR_BE | PL_CDR_BE => (XcdrVersion::Xcdr1, Endianness::BigEndian),
+        CDR_LE | PL_CDR_LE => (XcdrVersion::Xcdr1, Endianness::LittleEndian),
+        CDR2_BE | D_CDR2_BE | PL_CDR2_BE => (XcdrVersion::Xcdr2, Endianness::BigEndian),
+        CDR2_LE | D_CDR2_LE | PL_CDR2_LE => (XcdrVersion::Xcdr2, Endianness::LittleEndian),
+        representation_identifier => {
+            return Err(XTypesError::NotSupported(representation_identifier));
+        }
+    };
+    T::deserialize(&mut XcdrDeserializer::new(
+        &buffer[4..],
+        version,
+        endianness,
+    ))
+}
+
+/// Serialize the key of a sample as used for computing its instance handle, that is the key
+/// members of the sample and of its nested structures in the big-endian XCDR1 representation.
+pub fn serialize_key<T: TypeSupport + ?Sized>(value: &T) -> XTypesResult<Vec<u8>> {
+    let mut serializer = XcdrSerializer::new(XcdrVersion::Xcdr1, Endianness::BigEndian);
+    value.serialize_key_members(&mut serializer)?;
+    Ok(serializer.into_bytes())
+}
+
+/// Serialize a sample of a dynamic topic from its dynamic representation in the XCDR
+/// representation including its encapsulation header.
+pub fn serialize_dynamic_data(
+    dynamic_data: &DynamicData,
+    version: XcdrVersion,
+    endianness: Endianness,
+) -> XTypesResult<Vec<u8>> {
+    match (version, endianness) {
+        (XcdrVersion::Xcdr1, Endianness::BigEndian) => serialize_cdr1_be(dynamic_data),
+        (XcdrVersion::Xcdr1, Endianness::LittleEndian) => serialize_cdr1_le(dynamic_data),
+        (XcdrVersion::Xcdr2, Endianness::BigEndian) => serialize_cdr2_be(dynamic_data),
+        (XcdrVersion::Xcdr2, Endianness::LittleEndian) => serialize_cdr2_le(dynamic_data),
+    }
+}
+
+/// Deserialize a sample of a dynamic topic into its dynamic representation from its XCDR
+/// representation including its encapsulation header.
+pub fn deserialize_dynamic_data<'a>(
+    dynamic_type: DynamicType<'a>,
+    buffer: &[u8],
+) -> XTypesResult<DynamicData<'a>> {
+    deserialize_top_level_type(dynamic_type, buffer)
+}
+
+macro_rules! impl_xcdr_number {
+    ($($t:ty),*) => {$(
+        impl XcdrSerialize for $t {
+            fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+                serializer.align(size_of::<$t>());
+                match serializer.endianness {
+                    Endianness::BigEndian => serializer.write_slice(&self.to_be_bytes()),
+                    Endianness::LittleEndian => serializer.write_slice(&self.to_le_bytes()),
+                }
+                Ok(())
+            }
+        }
+
+        impl XcdrDeserialize for $t {
+            fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+                deserializer.align(size_of::<$t>())?;
+                let bytes = deserializer.read_array()?;
+                Ok(match deserializer.endianness {
+                    Endianness::BigEndian => <$t>::from_be_bytes(bytes),
+                    Endianness::LittleEndian => <$t>::from_le_bytes(bytes),
+                })
+            }
+        }
+    )*};
+}
+
+impl_xcdr_number!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
+
+impl XcdrSerialize for bool {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.write_slice(&[*self as u8]);
+        Ok(())
+    }
+}
+
+impl XcdrDeserialize for bool {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        match deserializer.read_array::<1>()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(XTypesError::InvalidData),
+        }
+    }
+}
+
+impl XcdrSerialize for char {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.write_slice(self.encode_utf8(&mut [0; 4]).as_bytes());
+        Ok(())
+    }
+}
+
+impl XcdrDeserialize for char {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let [byte] = deserializer.read_array()?;
+        Ok(char::from(byte))
+    }
+}
+
+impl XcdrSerialize for str {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_sequence_length(self.len() + 1);
+        serializer.write_slice(self.as_bytes());
+        serializer.write_slice(&[0]);
+        Ok(())
+    }
+}
+
+impl XcdrSerialize for String {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+impl XcdrDeserialize for String {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let length = deserializer.deserialize_sequence_length()?;
+        let bytes = deserializer.read_bytes(length.saturating_sub(1))?;
+        deserializer.read_bytes(1)?; // 0-termination
+        String::from_utf8(bytes.to_vec()).map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+macro_rules! impl_xcdr_primitive_collections {
+    ($($t:ty),*) => {$(
+        impl XcdrSerialize for Vec<$t> {
+            fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+                self.as_slice().serialize(serializer)
+            }
+        }
+
+        impl XcdrSerialize for [$t] {
+            fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+                serializer.serialize_sequence_length(self.len());
+                for element in self {
+                    element.serialize(serializer)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl XcdrDeserialize for Vec<$t> {
+            fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+                let length = deserializer.deserialize_sequence_length()?;
+                deserializer.deserialize_primitive_elements(length)
+            }
+        }
+
+        impl<const N: usize> XcdrSerialize for [$t; N] {
+            fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+                for element in self {
+                    element.serialize(serializer)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<const N: usize> XcdrDeserialize for [$t; N] {
+            fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+                deserializer
+                    .deserialize_primitive_elements(N)?
+                    .try_into()
+                    .map_err(|_| XTypesError::InvalidData)
+            }
+        }
+    )*};
+}
+
+impl_xcdr_primitive_collections!(
+    i8, u16, i16, u32, i32, u64, i64, f32, f64, bool, char, WChar
+);
+
+// Sequences and arrays of bytes are copied at once
+impl XcdrSerialize for Vec<u8> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+impl XcdrSerialize for [u8] {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_sequence_length(self.len());
+        serializer.write_slice(self);
+        Ok(())
+    }
+}
+
+impl XcdrDeserialize for Vec<u8> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let length = deserializer.deserialize_sequence_length()?;
+        Ok(deserializer.read_bytes(length)?.to_vec())
+    }
+}
+
+impl<const N: usize> XcdrSerialize for [u8; N] {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.write_slice(self);
+        Ok(())
+    }
+}
+
+impl<const N: usize> XcdrDeserialize for [u8; N] {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.read_array()
+    }
+}
+
+impl XcdrSerialize for Vec<String> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, true)
+    }
+}
+
+impl XcdrDeserialize for Vec<String> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.deserialize_non_primitive_elements(None)
+    }
+}
+
+impl<const N: usize> XcdrSerialize for [String; N] {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, false)
+    }
+
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_array_elements(self)
+    }
+}
+
+impl<const N: usize> XcdrDeserialize for [String; N] {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_elements(Some(N))?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_array_elements(N)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+impl XcdrSerialize for Vec<WString> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, true)
+    }
+}
+
+impl XcdrDeserialize for Vec<WString> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.deserialize_non_primitive_elements(None)
+    }
+}
+
+impl<const N: usize> XcdrSerialize for [WString; N] {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, false)
+    }
+
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_array_elements(self)
+    }
+}
+
+impl<const N: usize> XcdrDeserialize for [WString; N] {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_elements(Some(N))?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_array_elements(N)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+impl<T: TypeSupport> XcdrSerialize for Vec<T> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, true)
+    }
+}
+
+impl<T: TypeSupport> XcdrDeserialize for Vec<T> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.deserialize_non_primitive_elements(None)
+    }
+}
+
+impl<T: TypeSupport, const N: usize> XcdrSerialize for [T; N] {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, false)
+    }
+
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_array_elements(self)
+    }
+}
+
+impl<T: TypeSupport, const N: usize> XcdrDeserialize for [T; N] {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_elements(Some(N))?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_array_elements(N)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+impl<T> XcdrSerialize for Vec<Vec<T>>
+where
+    Vec<T>: XcdrSerialize + Type,
+{
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, true)
+    }
+}
+
+impl<T> XcdrDeserialize for Vec<Vec<T>>
+where
+    Vec<T>: XcdrDeserialize + Type,
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.deserialize_non_primitive_elements(None)
+    }
+}
+
+impl<T, const N: usize> XcdrSerialize for Vec<[T; N]>
+where
+    [T; N]: XcdrSerialize + Type,
+{
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, true)
+    }
+}
+
+impl<T, const N: usize> XcdrDeserialize for Vec<[T; N]>
+where
+    [T; N]: XcdrDeserialize + Type,
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer.deserialize_non_primitive_elements(None)
+    }
+}
+
+impl<T, const N: usize> XcdrSerialize for [Vec<T>; N]
+where
+    Vec<T>: XcdrSerialize + Type,
+{
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_elements(self, false)
+    }
+
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_non_primitive_array_elements(self)
+    }
+}
+
+impl<T, const N: usize> XcdrDeserialize for [Vec<T>; N]
+where
+    Vec<T>: XcdrDeserialize + Type,
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_elements(Some(N))?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        deserializer
+            .deserialize_non_primitive_array_elements(N)?
+            .try_into()
+            .map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+// Arrays of arrays are a single multi-dimensional array whose elements are preceded by one
+// DHEADER in the version 2 encoding unless they are primitive
+impl<T, const M: usize, const N: usize> XcdrSerialize for [[T; M]; N]
+where
+    [T; M]: XcdrSerialize + Type,
+{
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        let dheader = match serializer.version {
+            XcdrVersion::Xcdr2 if !is_element_type_kind_primitive(Self::TYPE)? => {
+                Some(serializer.begin_dheader())
+            }
+            _ => None,
+        };
+        self.serialize_array_elements(serializer)?;
+        if let Some(initial_pos) = dheader {
+            serializer.end_dheader(initial_pos);
+        }
+        Ok(())
+    }
+
+    fn serialize_array_elements(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        for element in self {
+            element.serialize_array_elements(serializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const M: usize, const N: usize> XcdrDeserialize for [[T; M]; N]
+where
+    [T; M]: XcdrDeserialize + Type,
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        if deserializer.version == XcdrVersion::Xcdr2
+            && !is_element_type_kind_primitive(Self::TYPE)?
+        {
+            deserializer.read_dheader()?;
+        }
+        Self::deserialize_array_elements(deserializer)
+    }
+
+    fn deserialize_array_elements(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        let mut elements = Vec::with_capacity(N);
+        for _ in 0..N {
+            elements.push(<[T; M]>::deserialize_array_elements(deserializer)?);
+        }
+        elements.try_into().map_err(|_| XTypesError::InvalidData)
+    }
+}
+
+impl<K: XcdrSerialize + Type, V: XcdrSerialize + Type> XcdrSerialize for BTreeMap<K, V> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_map_entries(self.iter())
+    }
+}
+
+impl<K: XcdrDeserialize + Type + Ord, V: XcdrDeserialize + Type> XcdrDeserialize
+    for BTreeMap<K, V>
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        Ok(deserializer
+            .deserialize_map_entries()?
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: XcdrSerialize + Type, V: XcdrSerialize + Type, S> XcdrSerialize
+    for std::collections::HashMap<K, V, S>
+{
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        serializer.serialize_map_entries(self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> XcdrDeserialize for std::collections::HashMap<K, V, S>
+where
+    K: XcdrDeserialize + Type + Eq + core::hash::Hash,
+    V: XcdrDeserialize + Type,
+    S: core::hash::BuildHasher + Default,
+{
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        Ok(deserializer
+            .deserialize_map_entries()?
+            .into_iter()
+            .collect())
+    }
+}
+
+impl<T: XcdrSerialize + Type> XcdrSerialize for Option<T> {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        match self {
+            Some(value) => value.serialize(serializer),
+            None => Err(XTypesError::InvalidData),
+        }
+    }
+
+    fn serialize_key(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        match self {
+            Some(value) => value.serialize_key(serializer),
+            None if T::TYPE.get_kind() == TypeKind::STRUCTURE => Err(XTypesError::InvalidData),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: XcdrDeserialize> XcdrDeserialize for Option<T> {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        T::deserialize(deserializer).map(Some)
+    }
+}
+
+impl<T: TypeSupport> XcdrSerialize for T {
+    fn serialize(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.serialize_nested(serializer)
+    }
+
+    fn serialize_key(&self, serializer: &mut XcdrSerializer) -> XTypesResult<()> {
+        self.serialize_key_members(serializer)
+    }
+}
+
+impl<T: TypeSupport> XcdrDeserialize for T {
+    fn deserialize(deserializer: &mut XcdrDeserializer<'_>) -> XTypesResult<Self> {
+        T::deserialize_nested(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dcps::xtypes_glue::key_and_instance_handle::{
+            get_instance_handle_from_dynamic_data, get_instance_handle_from_serialized_key,
+        },
+        xtypes::{
+            serializer::{
+                serialize_cdr1_be, serialize_cdr1_le, serialize_cdr2_be, serialize_cdr2_le,
+            },
+            type_support::{BoundedString, WChar, WString},
+        },
+    };
+    use alloc::{boxed::Box, string::ToString, vec};
+    use core::fmt::Debug;
+
+    fn assert_same_as_dynamic<T: TypeSupport + Clone + PartialEq + Debug>(value: T) {
+        let dynamic_data = value.clone().create_dynamic_sample();
+        for (version, endianness, expected) in [
+            (
+                XcdrVersion::Xcdr1,
+                Endianness::BigEndian,
+                serialize_cdr1_be(&dynamic_data).unwrap(),
+            ),
+            (
+                XcdrVersion::Xcdr1,
+                Endianness::LittleEndian,
+                serialize_cdr1_le(&dynamic_data).unwrap(),
+            ),
+            (
+                XcdrVersion::Xcdr2,
+                Endianness::BigEndian,
+                serialize_cdr2_be(&dynamic_data).unwrap(),
+            ),
+            (
+                XcdrVersion::Xcdr2,
+                Endianness::LittleEndian,
+                serialize_cdr2_le(&dynamic_data).unwrap(),
+            ),
+        ] {
+            let serialized = serialize_top_level(&value, version, endianness).unwrap();
+            assert_eq!(serialized, expected, "{version:?} {endianness:?}");
+            assert_eq!(deserialize_top_level::<T>(&serialized).unwrap(), value);
+        }
+        assert_eq!(
+            get_instance_handle_from_serialized_key(&serialize_key(&value).unwrap()),
+            get_instance_handle_from_dynamic_data(&dynamic_data).unwrap()
+        );
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Primitives {
+        #[dust_dds(key)]
+        id: u8,
+        f1: bool,
+        f2: i16,
+        f3: f64,
+        f4: char,
+        f5: u64,
+        f6: i8,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    enum Color {
+        Red,
+        Green = 10,
+        Blue,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "appendable")]
+    struct Appendable {
+        name: String,
+        color: Color,
+        inner: Primitives,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "mutable")]
+    struct Mutable {
+        #[dust_dds(id = 20)]
+        a: u16,
+        #[dust_dds(id = 10, key)]
+        b: String,
+        #[dust_dds(id = 5)]
+        c: Vec<i64>,
+        #[dust_dds(id = 7, optional)]
+        d: Option<u32>,
+        #[dust_dds(id = 8)]
+        e: Appendable,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Optionals {
+        a: u8,
+        #[dust_dds(optional)]
+        b: Option<f64>,
+        #[dust_dds(optional)]
+        c: Option<String>,
+        d: u64,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(switch(u8))]
+    enum FinalUnion {
+        A(u32),
+        #[dust_dds(case = 5, case = 6)]
+        B {
+            b: String,
+        },
+        C,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(switch(i32), extensibility = "mutable")]
+    enum MutableUnion {
+        A(u64),
+        B(Vec<String>),
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Collections {
+        bytes: Vec<u8>,
+        byte_array: [u8; 3],
+        numbers: Vec<u16>,
+        number_array: [i32; 2],
+        strings: Vec<String>,
+        string_array: [String; 2],
+        structs: Vec<Primitives>,
+        struct_array: [Color; 2],
+        unions: Vec<FinalUnion>,
+        bounded: BoundedString<10>,
+        boxed: Box<Primitives>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Unbounded {
+        name: String,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Bounded {
+        name: BoundedString<4>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct TrimmedBounded {
+        #[dust_dds(try_construct = "TRIM")]
+        name: BoundedString<4>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Maps {
+        numbers: BTreeMap<u16, f64>,
+        parameters: BTreeMap<String, f64>,
+        structs: BTreeMap<i32, Primitives>,
+        lists: BTreeMap<String, Vec<i32>>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "mutable")]
+    struct MutableMaps {
+        numbers: BTreeMap<u8, u32>,
+        parameters: BTreeMap<String, f64>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct NestedCollections {
+        samples: Vec<Vec<f32>>,
+        matrix: [[f64; 3]; 3],
+        ids: Vec<[u8; 16]>,
+        lists: [Vec<i32>; 2],
+        names: [[String; 2]; 2],
+        grid: [[[Color; 2]; 2]; 2],
+        paths: Vec<Vec<Primitives>>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "mutable")]
+    struct MutableNestedCollections {
+        #[dust_dds(optional)]
+        samples: Option<Vec<Vec<u16>>>,
+        #[dust_dds(optional)]
+        matrix: Option<[[u8; 2]; 3]>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct WideCharacters {
+        initial: WChar,
+        message: WString,
+        flag: u8,
+        letters: Vec<WChar>,
+        code: [WChar; 3],
+        lines: Vec<WString>,
+        labels: [WString; 2],
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "mutable")]
+    struct MutableWideCharacters {
+        #[dust_dds(key)]
+        id: u8,
+        message: WString,
+        #[dust_dds(optional)]
+        initial: Option<WChar>,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(bitset)]
+    struct StatusWord {
+        #[dust_dds(bits = 1)]
+        ready: bool,
+        #[dust_dds(bits = 3)]
+        mode: u8,
+        #[dust_dds(bits = 5, position = 8)]
+        offset: i8,
+        #[dust_dds(bits = 20)]
+        counter: u32,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(bitset)]
+    struct WideStatus(#[dust_dds(bits = 40)] i64, #[dust_dds(bits = 2)] u16);
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct Bitsets {
+        flag: u8,
+        status: StatusWord,
+        history: Vec<StatusWord>,
+        wide: WideStatus,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    #[dust_dds(extensibility = "mutable")]
+    struct MutableBitsets {
+        #[dust_dds(id = 3)]
+        status: StatusWord,
+        #[dust_dds(id = 1, key)]
+        id: u8,
+    }
+
+    #[derive(TypeSupport, Clone, Debug, PartialEq)]
+    struct NestedKey {
+        inner: Appendable,
+        #[dust_dds(key)]
+        id: u32,
+        #[dust_dds(key)]
+        full: Primitives,
+    }
+
+    fn primitives() -> Primitives {
+        Primitives {
+            id: 1,
+            f1: true,
+            f2: -3,
+            f3: 1.5,
+            f4: 'a',
+            f5: 7,
+            f6: -1,
+        }
+    }
+
+    #[test]
+    fn final_struct() {
+        assert_same_as_dynamic(primitives());
+    }
+
+    #[test]
+    fn appendable_struct() {
+        assert_same_as_dynamic(Appendable {
+            name: "name".to_string(),
+            color: Color::Green,
+            inner: primitives(),
+        });
+    }
+
+    #[test]
+    fn mutable_struct() {
+        let value = Mutable {
+            a: 1,
+            b: "key".to_string(),
+            c: vec![1, 2],
+            d: Some(4),
+            e: Appendable {
+                name: "a".to_string(),
+                color: Color::Blue,
+                inner: primitives(),
+            },
+        };
+        assert_same_as_dynamic(value.clone());
+        assert_same_as_dynamic(Mutable { d: None, ..value });
+    }
+
+    #[test]
+    fn optional_members() {
+        assert_same_as_dynamic(Optionals {
+            a: 1,
+            b: Some(2.0),
+            c: Some("c".to_string()),
+            d: 4,
+        });
+        assert_same_as_dynamic(Optionals {
+            a: 1,
+            b: None,
+            c: None,
+            d: 4,
+        });
+    }
+
+    #[test]
+    fn unions() {
+        assert_same_as_dynamic(FinalUnion::A(3));
+        assert_same_as_dynamic(FinalUnion::B { b: "b".to_string() });
+        assert_same_as_dynamic(FinalUnion::C);
+        assert_same_as_dynamic(MutableUnion::A(8));
+        assert_same_as_dynamic(MutableUnion::B(vec!["a".to_string(), "bc".to_string()]));
+    }
+
+    #[test]
+    fn union_with_secondary_case_label() {
+        let mut buffer = serialize_top_level(
+            &FinalUnion::B { b: "b".to_string() },
+            XcdrVersion::Xcdr1,
+            Endianness::LittleEndian,
+        )
+        .unwrap();
+        buffer[4] = 6;
+        assert_eq!(
+            deserialize_top_level::<FinalUnion>(&buffer),
+            Ok(FinalUnion::B { b: "b".to_string() })
+        );
+    }
+
+    #[test]
+    fn enums() {
+        assert_same_as_dynamic(Color::Red);
+        assert_same_as_dynamic(Color::Blue);
+        assert_eq!(
+            deserialize_top_level::<Color>(&[0, 1, 0, 0, 3, 0, 0, 0]),
+            Err(XTypesError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn collections() {
+        assert_same_as_dynamic(Collections {
+            bytes: vec![1, 2, 3],
+            byte_array: [4, 5, 6],
+            numbers: vec![7, 8, 9],
+            number_array: [-1, -2],
+            strings: vec!["a".to_string(), "bc".to_string()],
+            string_array: ["d".to_string(), "".to_string()],
+            structs: vec![primitives(), primitives()],
+            struct_array: [Color::Red, Color::Green],
+            unions: vec![FinalUnion::A(1), FinalUnion::C],
+            bounded: BoundedString::try_from("bounded".to_string()).unwrap(),
+            boxed: Box::new(primitives()),
+        });
+    }
+
+    #[test]
+    fn bounded_strings() {
+        let serialized = serialize_top_level(
+            &Unbounded {
+                name: "bounded".to_string(),
+            },
+            XcdrVersion::Xcdr2,
+            Endianness::LittleEndian,
+        )
+        .unwrap();
+        assert_eq!(
+            deserialize_top_level::<Bounded>(&serialized),
+            Err(XTypesError::InvalidData)
+        );
+        assert_eq!(
+            deserialize_top_level::<TrimmedBounded>(&serialized),
+            Err(XTypesError::IllegalOperation)
+        );
+    }
+
+    #[test]
+    fn maps() {
+        assert_same_as_dynamic(Maps {
+            numbers: BTreeMap::from([(1, 0.5), (3, -2.0)]),
+            parameters: BTreeMap::from([("gain".to_string(), 1.5), ("offset".to_string(), 0.0)]),
+            structs: BTreeMap::from([(-1, primitives())]),
+            lists: BTreeMap::from([("a".to_string(), vec![1, 2]), ("b".to_string(), vec![])]),
+        });
+        assert_same_as_dynamic(Maps {
+            numbers: BTreeMap::new(),
+            parameters: BTreeMap::new(),
+            structs: BTreeMap::new(),
+            lists: BTreeMap::new(),
+        });
+        assert_same_as_dynamic(MutableMaps {
+            numbers: BTreeMap::from([(2, 20), (4, 40)]),
+            parameters: BTreeMap::from([("gain".to_string(), 1.5)]),
+        });
+    }
+
+    #[test]
+    fn nested_collections() {
+        assert_same_as_dynamic(NestedCollections {
+            samples: vec![vec![1.0, 2.5], vec![], vec![-3.0]],
+            matrix: [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]],
+            ids: vec![[1; 16], [2; 16]],
+            lists: [vec![1, 2, 3], vec![]],
+            names: [
+                ["a".to_string(), "bc".to_string()],
+                ["".to_string(), "def".to_string()],
+            ],
+            grid: [
+                [[Color::Red, Color::Green], [Color::Green, Color::Red]],
+                [[Color::Red, Color::Red], [Color::Green, Color::Green]],
+            ],
+            paths: vec![vec![primitives()], vec![primitives(), primitives()]],
+        });
+        assert_same_as_dynamic(MutableNestedCollections {
+            samples: Some(vec![vec![1, 2], vec![3]]),
+            matrix: Some([[1, 2], [3, 4], [5, 6]]),
+        });
+        assert_same_as_dynamic(MutableNestedCollections {
+            samples: None,
+            matrix: None,
+        });
+    }
+
+    #[test]
+    fn wide_characters() {
+        let wchar = |c| WChar::try_from(c).unwrap();
+        let wstring = |s: &str| WString::from(s.to_string());
+        assert_same_as_dynamic(WideCharacters {
+            initial: wchar('ä'),
+            message: wstring("Grüße 👋"),
+            flag: 1,
+            letters: vec![wchar('a'), wchar('Ω'), wchar('€')],
+            code: [wchar('x'), wchar('y'), wchar('z')],
+            lines: vec![wstring("first"), wstring(""), wstring("日本語")],
+            labels: [wstring("on"), wstring("off")],
+        });
+        assert_same_as_dynamic(MutableWideCharacters {
+            id: 2,
+            message: wstring("ok"),
+            initial: Some(wchar('ß')),
+        });
+        assert_same_as_dynamic(MutableWideCharacters {
+            id: 3,
+            message: wstring(""),
+            initial: None,
+        });
+        assert_eq!(WChar::try_from('👋'), Err(XTypesError::InvalidData));
+    }
+
+    #[test]
+    fn bitsets() {
+        let status = StatusWord {
+            ready: true,
+            mode: 5,
+            offset: -3,
+            counter: 0xABCDE,
+        };
+        assert_same_as_dynamic(Bitsets {
+            flag: 1,
+            status: status.clone(),
+            history: vec![
+                StatusWord {
+                    ready: false,
+                    mode: 0,
+                    offset: 15,
+                    counter: 1,
+                },
+                status.clone(),
+            ],
+            wide: WideStatus(-0x12_3456_789A, 3),
+        });
+        assert_same_as_dynamic(MutableBitsets { status, id: 7 });
+        assert_eq!(
+            serialize_top_level(
+                &StatusWord {
+                    ready: true,
+                    mode: 2,
+                    offset: -1,
+                    counter: 3,
+                },
+                XcdrVersion::Xcdr2,
+                Endianness::BigEndian
+            )
+            .unwrap(),
+            vec![
+                0x00, 0x06, 0x00, 0x00, // encapsulation
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7f, 0x05, // 33 bits held in a u64
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_keys() {
+        assert_same_as_dynamic(NestedKey {
+            inner: Appendable {
+                name: "name".to_string(),
+                color: Color::Red,
+                inner: primitives(),
+            },
+            id: 3,
+            full: primitives(),
+        });
+    }
+
+    #[test]
+    fn missing_data() {
+        let serialized =
+            serialize_top_level(&primitives(), XcdrVersion::Xcdr2, Endianness::BigEndian).unwrap();
+        assert_eq!(
+            deserialize_top_level::<Primitives>(&serialized[..10]),
+            Err(XTypesError::NotEnoughData)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    sync::{Arc, Condvar, Mutex, OnceLock},
+    time::Instant,
+};
+
+use dust_dds::{
+    dds_async::domain_participant_factory::DomainParticipantFactoryAsync,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        listener::NO_LISTENER,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, PublishModeQosPolicy, PublishModeQosPolicyKind,
+            ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        status::{NO_STATUS, StatusKind},
+        time::{Duration, DurationKind},
+        type_support::DdsType,
+    },
+    rtps_loopback_transport::loopback_transport::LoopbackTransportParticipantFactory,
+    transport::{
+        interface::{
+            RtpsTransportParticipant, TransportDataReceiver, TransportParticipantFactory,
+            WriteMessage,
+        },
+        types::Locator,
+    },
+    wait_set::{Condition, WaitSet},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+const DATA_SUBMESSAGE_ID: u8 = 0x15;
+const DATA_WRITER_ID_OFFSET: usize = 12;
+
+/// Blocks the writing of the data submessages sent by the blocked writer until it is unblocked
+#[derive(Clone, Default)]
+struct TransportBlocker {
+    blocked_writer_id: Arc<(Mutex<Option<[u8; 4]>>, Condvar)>,
+}
+
+impl TransportBlocker {
+    fn block(&self, writer_id: [u8; 4]) {
+        *self.blocked_writer_id.0.lock().unwrap() = Some(writer_id);
+    }
+
+    fn unblock(&self) {
+        *self.blocked_writer_id.0.lock().unwrap() = None;
+        self.blocked_writer_id.1.notify_all();
+    }
+
+    fn wait_while_blocked(&self, buf: &[u8]) {
+        let (blocked_writer_id, unblocked) = &*self.blocked_writer_id;
+        let _blocked_writer_id = unblocked
+            .wait_timeout_while(
+                blocked_writer_id.lock().unwrap(),
+                std::time::Duration::from_secs(10),
+                |blocked_writer_id| {
+                    blocked_writer_id.is_some_and(|writer_id| {
+                        buf.windows(DATA_WRITER_ID_OFFSET + 4).any(|submessage| {
+                            submessage[0] == DATA_SUBMESSAGE_ID
+                                && submessage[DATA_WRITER_ID_OFFSET..] == writer_id
+                        })
+                    })
+                },
+            )
+            .unwrap();
+    }
+}
+
+struct BlockingMessageWriter {
+    message_writer: Box<dyn WriteMessage + Send + Sync>,
+    transport_blocker: TransportBlocker,
+}
+
+impl WriteMessage for BlockingMessageWriter {
+    fn write_message(&self, buf: &[u8], locators: &[Locator]) {
+        self.transport_blocker.wait_while_blocked(buf);
+        self.message_writer.write_message(buf, locators);
+    }
+}
+
+struct BlockingTransport {
+    transport: LoopbackTransportParticipantFactory,
+    transport_blocker: TransportBlocker,
+}
+
+impl TransportParticipantFactory for BlockingTransport {
+    fn create_participant(
+        &self,
+        domain_id: i32,
+        data_channel_sender: TransportDataReceiver,
+    ) -> RtpsTransportParticipant {
+        let participant = self
+            .transport
+            .create_participant(domain_id, data_channel_sender);
+        RtpsTransportParticipant {
+            message_writer: Box::new(BlockingMessageWriter {
+                message_writer: participant.message_writer,
+                transport_blocker: self.transport_blocker.clone(),
+            }),
+            ..participant
+        }
+    }
+}
+
+#[test]
+fn asynchronous_write_returns_while_the_transport_is_blocked() {
+    static TRANSPORT_BLOCKER: OnceLock<TransportBlocker> = OnceLock::new();
+    static PARTICIPANT_FACTORY_ASYNC: OnceLock<DomainParticipantFactoryAsync<BlockingTransport>> =
+        OnceLock::new();
+    let transport_blocker = TRANSPORT_BLOCKER.get_or_init(TransportBlocker::default);
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory =
+        DomainParticipantFactory::new(PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
+            let executor = dust_dds::std_runtime::executor::Executor::new();
+            let timer_driver = dust_dds::std_runtime::timer::TimerDriver::new();
+            let runtime = dust_dds::std_runtime::StdRuntime::new(executor, timer_driver);
+            let app_id = [1, 2, 3, 4];
+            let host_id = [5, 6, 7, 8];
+            let transport = BlockingTransport {
+                transport: LoopbackTransportParticipantFactory::default(),
+                transport_blocker: transport_blocker.clone(),
+            };
+            let configuration = Default::default();
+
+            DomainParticipantFactoryAsync::new(runtime, app_id, host_id, transport, configuration)
+        }));
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reliability = ReliabilityQosPolicy {
+        kind: ReliabilityQosPolicyKind::Reliable,
+        max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+    };
+    let writer_qos = DataWriterQos {
+        reliability: reliability.clone(),
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        publish_mode: PublishModeQosPolicy {
+            kind: PublishModeQosPolicyKind::Asynchronous,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability,
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let writer_guid = <[u8; 16]>::from(writer.get_instance_handle());
+    transport_blocker.block(writer_guid[12..].try_into().unwrap());
+    let start = Instant::now();
+    for value in 1..=3 {
+        writer.write(KeyedData { id: 1, value }, None).unwrap();
+    }
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    transport_blocker.unblock();
+
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let values: Vec<u32> = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .iter()
+        .map(|s| s.data.as_ref().unwrap().value)
+        .collect();
+    assert_eq!(values, vec![1, 2, 3]);
+}
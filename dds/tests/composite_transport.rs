@@ -0,0 +1,224 @@
+use std::sync::OnceLock;
+
+use dust_dds::{
+    dds_async::domain_participant_factory::DomainParticipantFactoryAsync,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        listener::NO_LISTENER,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        status::{NO_STATUS, StatusKind},
+        time::{Duration, DurationKind},
+        type_support::DdsType,
+    },
+    rtps_loopback_transport::loopback_transport::{
+        LoopbackFaults, LoopbackTransportParticipantFactory,
+    },
+    rtps_shared_memory_transport::shared_memory_transport::RtpsSharedMemoryTransportParticipantFactory,
+    rtps_udp_transport::udp_transport::RtpsUdpTransportParticipantFactory,
+    transport::{
+        composite::{
+            CompositeTransportParticipantFactory, CompositeTransportParticipantFactoryBuilder,
+        },
+        types::{
+            LOCATOR_KIND_LOOPBACK, LOCATOR_KIND_SHM, LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6,
+        },
+    },
+    wait_set::{Condition, WaitSet},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+// All the tests share the same factory since the participant factories of the same
+// transport type share their communication channel
+fn composite_participant_factory() -> (
+    DomainParticipantFactory<CompositeTransportParticipantFactory>,
+    LoopbackTransportParticipantFactory,
+) {
+    static LOOPBACK_TRANSPORT: OnceLock<LoopbackTransportParticipantFactory> = OnceLock::new();
+    static PARTICIPANT_FACTORY_ASYNC: OnceLock<
+        DomainParticipantFactoryAsync<CompositeTransportParticipantFactory>,
+    > = OnceLock::new();
+    let loopback_transport =
+        LOOPBACK_TRANSPORT.get_or_init(LoopbackTransportParticipantFactory::default);
+    let domain_participant_factory =
+        DomainParticipantFactory::new(PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
+            let transport = CompositeTransportParticipantFactoryBuilder::new()
+                .add_transport(
+                    &[LOCATOR_KIND_UDP_V4, LOCATOR_KIND_UDP_V6],
+                    RtpsUdpTransportParticipantFactory::default(),
+                )
+                .unwrap()
+                .add_transport(
+                    &[LOCATOR_KIND_SHM],
+                    RtpsSharedMemoryTransportParticipantFactory::default(),
+                )
+                .unwrap()
+                .add_transport(&[LOCATOR_KIND_LOOPBACK], loopback_transport.clone())
+                .unwrap()
+                .build()
+                .unwrap();
+            let executor = dust_dds::std_runtime::executor::Executor::new();
+            let timer_driver = dust_dds::std_runtime::timer::TimerDriver::new();
+            let runtime = dust_dds::std_runtime::StdRuntime::new(executor, timer_driver);
+            let app_id = std::process::id().to_ne_bytes();
+            let host_id = [127, 0, 0, 1];
+
+            DomainParticipantFactoryAsync::new(
+                runtime,
+                app_id,
+                host_id,
+                transport,
+                Default::default(),
+            )
+        }));
+    (domain_participant_factory, loopback_transport.clone())
+}
+
+fn write_and_read_samples(
+    domain_participant_factory: &DomainParticipantFactory<CompositeTransportParticipantFactory>,
+    loopback_transport: &LoopbackTransportParticipantFactory,
+    loopback_faults: LoopbackFaults,
+) -> Vec<u32> {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    for participant_handle in [
+        participant1.get_instance_handle(),
+        participant2.get_instance_handle(),
+    ] {
+        loopback_transport.set_locator_faults(
+            LoopbackTransportParticipantFactory::default_unicast_locator(participant_handle),
+            loopback_faults,
+        );
+    }
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    for value in 1..=5 {
+        writer.write(KeyedData { id: 1, value }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .iter()
+        .map(|s| s.data.as_ref().unwrap().value)
+        .collect()
+}
+
+#[test]
+fn samples_are_received_through_a_composite_of_udp_shared_memory_and_loopback_transports() {
+    let (domain_participant_factory, loopback_transport) = composite_participant_factory();
+
+    let values = write_and_read_samples(
+        &domain_participant_factory,
+        &loopback_transport,
+        LoopbackFaults::default(),
+    );
+
+    assert_eq!(values, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn samples_are_received_through_the_other_transports_when_the_loopback_transport_drops_them() {
+    let (domain_participant_factory, loopback_transport) = composite_participant_factory();
+
+    let values = write_and_read_samples(
+        &domain_participant_factory,
+        &loopback_transport,
+        LoopbackFaults {
+            loss_probability: 1.0,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(values, vec![1, 2, 3, 4, 5]);
+}
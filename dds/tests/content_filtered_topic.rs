@@ -1,6 +1,7 @@
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        error::DdsError,
         listener::NO_LISTENER,
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
@@ -119,3 +120,428 @@ fn samples_should_be_content_filtered() {
     assert_eq!(samples.len(), 1);
     assert_eq!(samples[0].data.as_ref().unwrap(), &data2);
 }
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct SensorData {
+    #[dust_dds(key)]
+    id: u8,
+    pos: Position,
+    temperature: f64,
+    location: String,
+}
+
+#[test]
+fn samples_should_be_filtered_with_compound_expression() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<SensorData>(
+            "MyTopic",
+            "SensorData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let content_filtered_topic = participant
+        .create_contentfilteredtopic(
+            "MyTopicFiltered",
+            &topic,
+            String::from(
+                "(temperature BETWEEN %0 AND %1 OR location LIKE 'Lab%') AND NOT pos.x < 0",
+            ),
+            vec![String::from("20.0"), String::from("30.0")],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<SensorData>(
+            &content_filtered_topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data1 = SensorData {
+        id: 1,
+        pos: Position { x: 1, y: 1 },
+        temperature: 25.0,
+        location: String::from("Office"),
+    };
+    let data2 = SensorData {
+        id: 2,
+        pos: Position { x: 1, y: 1 },
+        temperature: 35.0,
+        location: String::from("Office"),
+    };
+    let data3 = SensorData {
+        id: 3,
+        pos: Position { x: 1, y: 1 },
+        temperature: 35.0,
+        location: String::from("Lab 3"),
+    };
+    let data4 = SensorData {
+        id: 4,
+        pos: Position { x: -1, y: 1 },
+        temperature: 25.0,
+        location: String::from("Lab 3"),
+    };
+
+    writer.write(data1.clone(), None).unwrap();
+    writer.write(data2.clone(), None).unwrap();
+    writer.write(data3.clone(), None).unwrap();
+    writer.write(data4.clone(), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(5, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data1);
+    assert_eq!(samples[1].data.as_ref().unwrap(), &data3);
+}
+
+#[test]
+fn invalid_filter_expression_should_be_rejected() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    assert_eq!(
+        participant
+            .create_contentfilteredtopic(
+                "UnknownMember",
+                &topic,
+                String::from("color = %0"),
+                vec![String::from("RED")],
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+    assert_eq!(
+        participant
+            .create_contentfilteredtopic(
+                "MissingParameter",
+                &topic,
+                String::from("value = %1"),
+                vec![String::from("RED")],
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+    assert_eq!(
+        participant
+            .create_contentfilteredtopic(
+                "SyntaxError",
+                &topic,
+                String::from("value = 'RED' AND (id > 1"),
+                vec![],
+            )
+            .err(),
+        Some(DdsError::BadParameter)
+    );
+}
+
+#[test]
+fn expression_parameters_can_be_changed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let content_filtered_topic = participant
+        .create_contentfilteredtopic(
+            "MyTopicFiltered",
+            &topic,
+            String::from("id > %0"),
+            vec![String::from("5")],
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &content_filtered_topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    assert_eq!(
+        content_filtered_topic.get_expression_parameters(),
+        Ok(vec![String::from("5")])
+    );
+    assert_eq!(
+        content_filtered_topic.set_expression_parameters(vec![String::from("not a number")]),
+        Err(DdsError::BadParameter)
+    );
+    content_filtered_topic
+        .set_expression_parameters(vec![String::from("1")])
+        .unwrap();
+    assert_eq!(
+        content_filtered_topic.get_expression_parameters(),
+        Ok(vec![String::from("1")])
+    );
+
+    let data1 = KeyedData {
+        id: 1,
+        value: String::from("BLUE"),
+    };
+    let data2 = KeyedData {
+        id: 2,
+        value: String::from("RED"),
+    };
+
+    writer.write(data1.clone(), None).unwrap();
+    writer.write(data2.clone(), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(5, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data2);
+}
+
+#[test]
+fn samples_filtered_by_remote_writer_are_not_delivered() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let publisher_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber_participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let publisher_topic = publisher_participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber_topic = subscriber_participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let content_filtered_topic = subscriber_participant
+        .create_contentfilteredtopic(
+            "MyTopicFiltered",
+            &subscriber_topic,
+            String::from("id > %0 AND value LIKE 'R%'"),
+            vec![String::from("2")],
+        )
+        .unwrap();
+
+    let publisher = publisher_participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &publisher_topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = subscriber_participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &content_filtered_topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = [
+        KeyedData {
+            id: 1,
+            value: String::from("RED"),
+        },
+        KeyedData {
+            id: 3,
+            value: String::from("BLUE"),
+        },
+        KeyedData {
+            id: 4,
+            value: String::from("RED"),
+        },
+        KeyedData {
+            id: 5,
+            value: String::from("GREEN"),
+        },
+        KeyedData {
+            id: 6,
+            value: String::from("ROSE"),
+        },
+    ];
+    for sample in &samples {
+        writer.write(sample.clone(), None).unwrap();
+    }
+    // The samples rejected by the filter must still be acknowledged by the reader
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let received: Vec<KeyedData> = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .into_iter()
+        .map(|s| s.data.unwrap())
+        .collect();
+    assert_eq!(received, vec![samples[2].clone(), samples[4].clone()]);
+}
@@ -0,0 +1,154 @@
+use std::{net::Ipv4Addr, sync::OnceLock};
+
+use dust_dds::{
+    dds_async::{
+        configuration::{DiscoveryMode, DustDdsConfigurationBuilder},
+        domain_participant_factory::DomainParticipantFactoryAsync,
+    },
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        listener::NO_LISTENER,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        status::{NO_STATUS, StatusKind},
+        time::{Duration, DurationKind},
+        type_support::DdsType,
+    },
+    rtps_udp_transport::udp_transport::{InitialPeer, RtpsUdpTransportParticipantFactory},
+    wait_set::{Condition, WaitSet},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct UserType(#[dust_dds(key)] i32);
+
+#[test]
+fn clients_should_communicate_through_the_remaining_discovery_server() {
+    static PARTICIPANT_FACTORY_ASYNC: OnceLock<
+        DomainParticipantFactoryAsync<RtpsUdpTransportParticipantFactory>,
+    > = OnceLock::new();
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory =
+        DomainParticipantFactory::new(PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
+            let executor = dust_dds::std_runtime::executor::Executor::new();
+            let timer_driver = dust_dds::std_runtime::timer::TimerDriver::new();
+            let runtime = dust_dds::std_runtime::StdRuntime::new(executor, timer_driver);
+            let app_id = [1, 2, 3, 4];
+            let host_id = [5, 6, 7, 8];
+            // The servers are the first participants of the host so they get the participant ids 0 and 1
+            let mut transport = RtpsUdpTransportParticipantFactory::default();
+            transport
+                .set_multicast_enabled(false)
+                .set_initial_peer_list(vec![InitialPeer {
+                    address: Ipv4Addr::LOCALHOST.into(),
+                    participant_id_range: 0..=1,
+                }])
+                .unwrap();
+            let configuration = Default::default();
+
+            DomainParticipantFactoryAsync::new(runtime, app_id, host_id, transport, configuration)
+        }));
+
+    *domain_participant_factory.get_mut_configuration() = DustDdsConfigurationBuilder::new()
+        .discovery_mode(DiscoveryMode::Server)
+        .build()
+        .unwrap();
+    let server1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let _server2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    *domain_participant_factory.get_mut_configuration() = DustDdsConfigurationBuilder::new()
+        .discovery_mode(DiscoveryMode::Client)
+        .build()
+        .unwrap();
+    let client1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let client2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    domain_participant_factory
+        .delete_participant(&server1)
+        .unwrap();
+
+    let topic1 = client1
+        .create_topic::<UserType>(
+            "topic_name",
+            "UserType",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = client1
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let data_writer = publisher
+        .create_datawriter::<UserType>(
+            &topic1,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let topic2 = client2
+        .create_topic::<UserType>(
+            "topic_name",
+            "UserType",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = client2
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let data_reader = subscriber
+        .create_datareader::<UserType>(
+            &topic2,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = data_writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    data_writer.write(UserType(8), None).unwrap();
+    data_writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = data_reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data.as_ref().unwrap(), &UserType(8));
+}
@@ -0,0 +1,138 @@
+use std::{sync::OnceLock, time::Instant};
+
+use dust_dds::{
+    dds_async::{
+        configuration::{
+            DustDdsConfigurationBuilder, FlowControllerConfiguration,
+            FlowControllerSchedulingPolicy, FlowControllerScope,
+        },
+        domain_participant_factory::DomainParticipantFactoryAsync,
+    },
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        listener::NO_LISTENER,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        status::{NO_STATUS, StatusKind},
+        time::{Duration, DurationKind},
+        type_support::DdsType,
+    },
+    rtps_udp_transport::udp_transport::RtpsUdpTransportParticipantFactory,
+    wait_set::{Condition, WaitSet},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct LargeData {
+    #[dust_dds(key)]
+    id: u8,
+    value: Vec<u8>,
+}
+
+#[test]
+fn flow_controlled_writer_should_pace_large_samples() {
+    static PARTICIPANT_FACTORY_ASYNC: OnceLock<
+        DomainParticipantFactoryAsync<RtpsUdpTransportParticipantFactory>,
+    > = OnceLock::new();
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let domain_participant_factory =
+        DomainParticipantFactory::new(PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
+            let executor = dust_dds::std_runtime::executor::Executor::new();
+            let timer_driver = dust_dds::std_runtime::timer::TimerDriver::new();
+            let runtime = dust_dds::std_runtime::StdRuntime::new(executor, timer_driver);
+            let app_id = [1, 2, 3, 4];
+            let host_id = [5, 6, 7, 8];
+            let transport = RtpsUdpTransportParticipantFactory::default();
+            let configuration = Default::default();
+
+            DomainParticipantFactoryAsync::new(runtime, app_id, host_id, transport, configuration)
+        }));
+    // Room for a single fragment in each period
+    *domain_participant_factory.get_mut_configuration() = DustDdsConfigurationBuilder::new()
+        .flow_controller(Some(FlowControllerConfiguration {
+            max_bytes_per_period: 2000,
+            period: core::time::Duration::from_millis(100),
+            scope: FlowControllerScope::Participant,
+            scheduling_policy: FlowControllerSchedulingPolicy::Fifo,
+        }))
+        .build()
+        .unwrap();
+
+    let participant = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<LargeData>(
+            "LargeDataTopic",
+            "LargeData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter::<LargeData>(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<LargeData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let data = LargeData {
+        id: 1,
+        value: vec![7; 10_000],
+    };
+    let start = Instant::now();
+    writer.write(data.clone(), None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    // The 8 fragments of the sample need at least 7 periods
+    assert!(start.elapsed() >= std::time::Duration::from_millis(500));
+    let samples = reader
+        .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples[0].data.as_ref().unwrap(), &data);
+}
@@ -0,0 +1,252 @@
+use std::sync::OnceLock;
+
+use dust_dds::{
+    dds_async::domain_participant_factory::DomainParticipantFactoryAsync,
+    domain::domain_participant_factory::DomainParticipantFactory,
+    infrastructure::{
+        listener::NO_LISTENER,
+        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos_policy::{
+            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+        },
+        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+        status::{NO_STATUS, StatusKind},
+        time::{Duration, DurationKind},
+        type_support::DdsType,
+    },
+    rtps_loopback_transport::loopback_transport::{
+        LoopbackFaults, LoopbackTransportParticipantFactory,
+    },
+    wait_set::{Condition, WaitSet},
+};
+
+mod utils;
+use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
+
+#[derive(Clone, Debug, PartialEq, DdsType)]
+struct KeyedData {
+    #[dust_dds(key)]
+    id: u8,
+    value: u32,
+}
+
+// All the tests share the same factory since the participant factories of the same
+// transport type share their communication channel
+fn loopback_participant_factory() -> (
+    DomainParticipantFactory<LoopbackTransportParticipantFactory>,
+    LoopbackTransportParticipantFactory,
+) {
+    static TRANSPORT: OnceLock<LoopbackTransportParticipantFactory> = OnceLock::new();
+    static PARTICIPANT_FACTORY_ASYNC: OnceLock<
+        DomainParticipantFactoryAsync<LoopbackTransportParticipantFactory>,
+    > = OnceLock::new();
+    let transport = TRANSPORT.get_or_init(|| {
+        let transport = LoopbackTransportParticipantFactory::default();
+        transport.set_seed(1234);
+        transport
+    });
+    let domain_participant_factory =
+        DomainParticipantFactory::new(PARTICIPANT_FACTORY_ASYNC.get_or_init(|| {
+            let executor = dust_dds::std_runtime::executor::Executor::new();
+            let timer_driver = dust_dds::std_runtime::timer::TimerDriver::new();
+            let runtime = dust_dds::std_runtime::StdRuntime::new(executor, timer_driver);
+            let app_id = [1, 2, 3, 4];
+            let host_id = [5, 6, 7, 8];
+            let configuration = Default::default();
+
+            DomainParticipantFactoryAsync::new(
+                runtime,
+                app_id,
+                host_id,
+                transport.clone(),
+                configuration,
+            )
+        }));
+    (domain_participant_factory, transport.clone())
+}
+
+#[test]
+fn reliable_samples_are_received_in_order_despite_faults() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let (domain_participant_factory, transport) = loopback_participant_factory();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let faults = LoopbackFaults {
+        loss_probability: 0.3,
+        duplication_probability: 0.3,
+        reordering_probability: 0.3,
+        ..Default::default()
+    };
+    for participant_handle in [
+        participant1.get_instance_handle(),
+        participant2.get_instance_handle(),
+    ] {
+        transport.set_locator_faults(
+            LoopbackTransportParticipantFactory::default_unicast_locator(participant_handle),
+            faults,
+        );
+    }
+
+    for value in 1..=10 {
+        writer.write(KeyedData { id: 1, value }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(30, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(20, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let values: Vec<_> = samples
+        .iter()
+        .map(|s| s.data.as_ref().unwrap().value)
+        .collect();
+    assert_eq!(values, (1..=10).collect::<Vec<_>>());
+}
+
+#[test]
+fn best_effort_samples_sent_to_a_lossy_locator_are_not_received() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let (domain_participant_factory, transport) = loopback_participant_factory();
+
+    let participant1 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant1
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant1
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(&topic1, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let participant2 = domain_participant_factory
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic2 = participant2
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant2
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(&topic2, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    transport.set_locator_faults(
+        LoopbackTransportParticipantFactory::default_unicast_locator(
+            participant2.get_instance_handle(),
+        ),
+        LoopbackFaults {
+            loss_probability: 1.0,
+            ..Default::default()
+        },
+    );
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    assert!(
+        reader
+            .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .is_err()
+    );
+}
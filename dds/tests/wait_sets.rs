@@ -2,9 +2,14 @@ mod utils;
 use dust_dds::{
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
+        error::DdsError,
         listener::NO_LISTENER,
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{DeadlineQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind},
+        sample_info::{
+            ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE, InstanceStateKind,
+            SampleStateKind,
+        },
         status::{NO_STATUS, StatusKind},
         time::{Duration, DurationKind},
         type_support::DdsType,
@@ -113,3 +118,256 @@ fn writer_offered_deadline_missed_waitset() {
     assert_eq!(status.total_count, 1);
     assert_eq!(status.total_count_change, 1);
 }
+
+#[test]
+fn query_condition_waitset() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<MyData>(
+            "QueryConditionTopic",
+            "MyData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let query_condition = reader
+        .create_querycondition(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            &[InstanceStateKind::Alive],
+            String::from("value > %0"),
+            &[String::from("3")],
+        )
+        .unwrap();
+    assert!(!query_condition.get_trigger_value().unwrap());
+
+    writer.write(MyData { id: 1, value: 1 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert!(!query_condition.get_trigger_value().unwrap());
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::QueryCondition(query_condition.clone()))
+        .unwrap();
+    writer.write(MyData { id: 2, value: 5 }, None).unwrap();
+    let triggered = wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(triggered.len(), 1);
+
+    let samples = reader.take_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data, Some(MyData { id: 2, value: 5 }));
+    assert!(!query_condition.get_trigger_value().unwrap());
+
+    query_condition
+        .set_query_parameters(&[String::from("0")])
+        .unwrap();
+    assert_eq!(
+        query_condition.get_query_parameters().unwrap(),
+        vec![String::from("0")]
+    );
+    assert!(query_condition.get_trigger_value().unwrap());
+
+    let read_condition = reader
+        .create_readcondition(
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .unwrap();
+    let samples = reader.read_w_condition(10, &read_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(samples[0].data, Some(MyData { id: 1, value: 1 }));
+    assert!(!read_condition.get_trigger_value().unwrap());
+
+    assert_eq!(
+        subscriber.delete_datareader(&reader),
+        Err(DdsError::PreconditionNotMet(String::from(
+            "Data reader still has read conditions"
+        )))
+    );
+    reader.delete_readcondition(&read_condition).unwrap();
+    reader.delete_readcondition(&query_condition).unwrap();
+    subscriber.delete_datareader(&reader).unwrap();
+}
+
+#[test]
+fn key_query_condition_on_disposed_instance() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<MyData>(
+            "KeyQueryConditionTopic",
+            "MyData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<MyData>(
+            &topic,
+            QosKind::Specific(reader_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let other_reader = subscriber
+        .create_datareader::<MyData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let query_condition = reader
+        .create_querycondition(
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            &[InstanceStateKind::NotAliveDisposed],
+            String::from("id = %0"),
+            &[String::from("2")],
+        )
+        .unwrap();
+    let other_read_condition = other_reader
+        .create_readcondition(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    // Conditions belong to the reader that created them
+    assert_eq!(
+        reader.delete_readcondition(&other_read_condition),
+        Err(DdsError::PreconditionNotMet(String::from(
+            "Condition not created by this data reader"
+        )))
+    );
+
+    writer.write(MyData { id: 2, value: 5 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(
+        reader
+            .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(!query_condition.get_trigger_value().unwrap());
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::QueryCondition(query_condition.clone()))
+        .unwrap();
+    writer.dispose(MyData { id: 2, value: 5 }, None).unwrap();
+    let triggered = wait_set.wait(Duration::new(10, 0)).unwrap();
+    assert_eq!(triggered.len(), 1);
+    let samples = reader.take_w_condition(10, &query_condition).unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].sample_info.instance_state,
+        InstanceStateKind::NotAliveDisposed
+    );
+
+    reader.delete_contained_entities().unwrap();
+    other_reader.delete_contained_entities().unwrap();
+    subscriber.delete_datareader(&reader).unwrap();
+    subscriber.delete_datareader(&other_reader).unwrap();
+}
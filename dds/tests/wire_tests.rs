@@ -42,6 +42,7 @@ fn detect_stale_participant() {
                 metatraffic_unicast_locator_list: Vec::new(),
                 metatraffic_multicast_locator_list: Vec::new(),
                 default_multicast_locator_list: Vec::new(),
+                initial_peer_locator_list: Vec::new(),
                 fragment_size: 1000,
             }
         }
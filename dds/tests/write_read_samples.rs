@@ -1,16 +1,19 @@
 use dust_dds::{
+    configuration::DustDdsConfigurationBuilder,
     domain::domain_participant_factory::DomainParticipantFactory,
     infrastructure::{
         error::DdsError,
         instance::InstanceHandle,
         listener::NO_LISTENER,
-        qos::{DataReaderQos, DataWriterQos, QosKind},
+        qos::{DataReaderQos, DataWriterQos, PublisherQos, QosKind, SubscriberQos},
         qos_policy::{
             DeadlineQosPolicy, DestinationOrderQosPolicy, DestinationOrderQosPolicyKind,
-            DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
-            Length, LifespanQosPolicy, OwnershipQosPolicy, OwnershipQosPolicyKind,
-            OwnershipStrengthQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
-            ResourceLimitsQosPolicy, TimeBasedFilterQosPolicy, WriterDataLifecycleQosPolicy,
+            DurabilityQosPolicy, DurabilityQosPolicyKind, DurabilityServiceQosPolicy,
+            HistoryQosPolicy, HistoryQosPolicyKind, Length, LifespanQosPolicy, OwnershipQosPolicy,
+            OwnershipQosPolicyKind, OwnershipStrengthQosPolicy, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, PublishModeQosPolicy, PublishModeQosPolicyKind,
+            ReliabilityQosPolicy, ReliabilityQosPolicyKind, ResourceLimitsQosPolicy,
+            TimeBasedFilterQosPolicy, WriterDataLifecycleQosPolicy,
         },
         sample_info::{
             ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE, InstanceStateKind,
@@ -20,9 +23,13 @@ use dust_dds::{
         time::{Duration, DurationKind, Time},
         type_support::DdsType,
     },
+    persistence::FilePersistenceService,
+    publication::data_writer::DataWriter,
     wait_set::{Condition, WaitSet},
 };
 
+use std::sync::Arc;
+
 mod utils;
 use crate::utils::domain_id_generator::TEST_DOMAIN_ID_GENERATOR;
 
@@ -344,6 +351,144 @@ fn writer_with_keep_last_1_should_send_only_last_sample_to_reader() {
     assert_eq!(samples[0].data.as_ref().unwrap(), &data5);
 }
 
+#[test]
+fn persistent_writer_should_send_samples_written_by_previous_writer_to_late_joining_reader() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let persistence_directory =
+        std::env::temp_dir().join(format!("dust_dds_persistence_test_{}", std::process::id()));
+    *DomainParticipantFactory::get_instance().get_mut_configuration() =
+        DustDdsConfigurationBuilder::new()
+            .persistence_service(Some(Arc::new(FilePersistenceService::new(
+                &persistence_directory,
+            ))))
+            .build()
+            .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::Persistent,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        durability_service: DurabilityServiceQosPolicy {
+            history_kind: HistoryQosPolicyKind::KeepLast(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "PersistentTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(KeyedData { id: 2, value: 3 }, None).unwrap();
+    participant.delete_contained_entities().unwrap();
+    DomainParticipantFactory::get_instance()
+        .delete_participant(&participant)
+        .unwrap();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "PersistentTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let _writer = publisher
+        .create_datawriter::<KeyedData>(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        durability: DurabilityQosPolicy {
+            kind: DurabilityQosPolicyKind::TransientLocal,
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::SubscriptionMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+    reader
+        .wait_for_historical_data(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    std::fs::remove_dir_all(persistence_directory).unwrap();
+
+    assert_eq!(samples.len(), 2);
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 2 }
+    );
+    assert_eq!(
+        samples[1].data.as_ref().unwrap(),
+        &KeyedData { id: 2, value: 3 }
+    );
+}
+
 #[test]
 fn writer_with_keep_last_3_should_send_last_3_samples_to_reader() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -2009,6 +2154,116 @@ fn reader_with_minimum_time_separation_qos() {
     assert_eq!(samples[3].data.as_ref().unwrap(), &data2_3);
 }
 
+#[test]
+fn reader_with_minimum_time_separation_receives_latest_sample_once_separation_elapses() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let reader_qos = DataReaderQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        time_based_filter: TimeBasedFilterQosPolicy {
+            minimum_separation: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    for value in 1..=5 {
+        writer.write(KeyedData { id: 1, value }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 1 }
+    );
+
+    let cond = reader.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::DataAvailable])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+    assert_eq!(
+        samples[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 5 }
+    );
+}
+
 #[test]
 fn transient_local_writer_reader_wait_for_historical_data() {
     let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
@@ -3903,3 +4158,1044 @@ fn shared_ownership_writer1_should_write_and_writer2_should_dispose_same_data()
         InstanceStateKind::NotAliveDisposed
     );
 }
+
+#[test]
+fn group_coherent_set_is_only_available_after_end_coherent_changes() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: true,
+        ordered_access: false,
+    };
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher = participant
+        .create_publisher(QosKind::Specific(publisher_qos), NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber_qos = SubscriberQos {
+        presentation,
+        ..Default::default()
+    };
+    let subscriber = participant
+        .create_subscriber(QosKind::Specific(subscriber_qos), NO_LISTENER, NO_STATUS)
+        .unwrap();
+
+    let writer_qos = DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    };
+    let writer1 = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(writer_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(
+            &topic2,
+            QosKind::Specific(writer_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let reader_qos = DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    };
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reader_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reader_qos),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    for writer in [&writer1, &writer2] {
+        let cond = writer.get_statuscondition();
+        cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+            .unwrap();
+        let mut wait_set = WaitSet::new();
+        wait_set
+            .attach_condition(Condition::StatusCondition(cond))
+            .unwrap();
+        wait_set.wait(Duration::new(10, 0)).unwrap();
+    }
+
+    publisher.begin_coherent_changes().unwrap();
+    writer1.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer1.write(KeyedData { id: 2, value: 2 }, None).unwrap();
+    writer2.write(KeyedData { id: 1, value: 3 }, None).unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(
+        reader1.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+    assert_eq!(
+        reader2.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    publisher.end_coherent_changes().unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples1 = reader1
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    let samples2 = reader2
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples1.len(), 2);
+    assert_eq!(samples2.len(), 1);
+    assert_eq!(
+        samples2[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 3 }
+    );
+}
+
+fn coherent_presentation(
+    access_scope: PresentationQosPolicyAccessScopeKind,
+) -> PresentationQosPolicy {
+    PresentationQosPolicy {
+        access_scope,
+        coherent_access: true,
+        ordered_access: false,
+    }
+}
+
+fn reliable_writer_qos() -> DataWriterQos {
+    DataWriterQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        ..Default::default()
+    }
+}
+
+fn reliable_keep_all_reader_qos() -> DataReaderQos {
+    DataReaderQos {
+        reliability: ReliabilityQosPolicy {
+            kind: ReliabilityQosPolicyKind::Reliable,
+            max_blocking_time: DurationKind::Finite(Duration::new(1, 0)),
+        },
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        ..Default::default()
+    }
+}
+
+fn wait_for_publication_matched(writer: &DataWriter<KeyedData>) {
+    let cond = writer.get_statuscondition();
+    cond.set_enabled_statuses(&[StatusKind::PublicationMatched])
+        .unwrap();
+    let mut wait_set = WaitSet::new();
+    wait_set
+        .attach_condition(Condition::StatusCondition(cond))
+        .unwrap();
+    wait_set.wait(Duration::new(10, 0)).unwrap();
+}
+
+#[test]
+fn topic_coherent_set_is_only_available_after_end_coherent_changes() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let presentation = coherent_presentation(PresentationQosPolicyAccessScopeKind::Topic);
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer);
+
+    publisher.begin_coherent_changes().unwrap();
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(KeyedData { id: 2, value: 2 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(
+        reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    publisher.end_coherent_changes().unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 2);
+}
+
+#[test]
+fn group_coherent_sets_of_publishers_in_the_same_participant_are_independent() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let presentation = coherent_presentation(PresentationQosPolicyAccessScopeKind::Group);
+    let publisher_qos = PublisherQos {
+        presentation: presentation.clone(),
+        ..Default::default()
+    };
+    let publisher1 = participant
+        .create_publisher(
+            QosKind::Specific(publisher_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher2 = participant
+        .create_publisher(QosKind::Specific(publisher_qos), NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let writer1 = publisher1
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher2
+        .create_datawriter(
+            &topic2,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer1);
+    wait_for_publication_matched(&writer2);
+
+    // Both publishers start their group sequence numbers at the same value
+    publisher1.begin_coherent_changes().unwrap();
+    publisher2.begin_coherent_changes().unwrap();
+    writer1.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer2.write(KeyedData { id: 1, value: 2 }, None).unwrap();
+    publisher1.end_coherent_changes().unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples1 = reader1
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples1.len(), 1);
+    assert_eq!(
+        reader2.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    publisher2.end_coherent_changes().unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let samples2 = reader2
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples2.len(), 1);
+    assert_eq!(
+        samples2[0].data.as_ref().unwrap(),
+        &KeyedData { id: 1, value: 2 }
+    );
+}
+
+#[test]
+fn coherent_sets_are_not_made_available_during_subscriber_access() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let presentation = coherent_presentation(PresentationQosPolicyAccessScopeKind::Group);
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer);
+
+    assert_eq!(
+        subscriber.end_access(),
+        Err(DdsError::PreconditionNotMet(String::from(
+            "No matching call to begin_access"
+        )))
+    );
+
+    subscriber.begin_access().unwrap();
+    subscriber.begin_access().unwrap();
+    publisher.begin_coherent_changes().unwrap();
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    publisher.end_coherent_changes().unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(
+        reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    subscriber.end_access().unwrap();
+    assert_eq!(
+        reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+    subscriber.end_access().unwrap();
+    let samples = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples.len(), 1);
+}
+
+#[test]
+fn group_coherent_set_with_changes_filtered_by_the_writer_is_complete() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let filtered_topic2 = participant
+        .create_contentfilteredtopic(
+            "MyTopic2Filtered",
+            &topic2,
+            String::from("value > %0"),
+            vec![String::from("10")],
+        )
+        .unwrap();
+
+    let presentation = coherent_presentation(PresentationQosPolicyAccessScopeKind::Group);
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer1 = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(
+            &topic2,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &filtered_topic2,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer1);
+    wait_for_publication_matched(&writer2);
+
+    publisher.begin_coherent_changes().unwrap();
+    writer1.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer2.write(KeyedData { id: 1, value: 2 }, None).unwrap();
+    publisher.end_coherent_changes().unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let samples1 = reader1
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(samples1.len(), 1);
+    assert_eq!(
+        reader2.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+}
+
+#[test]
+fn group_ordered_access_returns_readers_in_the_order_the_samples_were_written() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+
+    let presentation = PresentationQosPolicy {
+        access_scope: PresentationQosPolicyAccessScopeKind::Group,
+        coherent_access: false,
+        ordered_access: true,
+    };
+    let publisher = participant
+        .create_publisher(
+            QosKind::Specific(PublisherQos {
+                presentation: presentation.clone(),
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(
+            QosKind::Specific(SubscriberQos {
+                presentation,
+                ..Default::default()
+            }),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer1 = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(
+            &topic2,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer1);
+    wait_for_publication_matched(&writer2);
+
+    writer2.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer1.write(KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer1.write(KeyedData { id: 1, value: 3 }, None).unwrap();
+    writer2.write(KeyedData { id: 1, value: 4 }, None).unwrap();
+    writer1
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    writer2
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert!(matches!(
+        subscriber.get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    subscriber.begin_access().unwrap();
+    let reader1_handle = reader1.get_instance_handle();
+    let reader2_handle = reader2.get_instance_handle();
+    let readers = subscriber
+        .get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        readers,
+        vec![
+            reader2_handle,
+            reader1_handle,
+            reader1_handle,
+            reader2_handle
+        ]
+    );
+    let mut values = Vec::new();
+    for reader_handle in readers {
+        let reader = if reader_handle == reader1_handle {
+            &reader1
+        } else {
+            &reader2
+        };
+        let samples = reader
+            .take(1, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap();
+        values.push(samples[0].data.as_ref().unwrap().value);
+    }
+    subscriber.end_access().unwrap();
+
+    assert_eq!(values, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn get_datareaders_returns_readers_with_matching_samples() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let _reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer);
+
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(KeyedData { id: 2, value: 2 }, None).unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    assert_eq!(
+        subscriber
+            .get_datareaders(ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap(),
+        vec![reader1.get_instance_handle()]
+    );
+    reader1
+        .read(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap();
+    assert_eq!(
+        subscriber
+            .get_datareaders(
+                &[SampleStateKind::NotRead],
+                ANY_VIEW_STATE,
+                ANY_INSTANCE_STATE
+            )
+            .unwrap(),
+        Vec::<InstanceHandle>::new()
+    );
+}
+
+#[test]
+fn suspended_publications_are_sent_when_resumed() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer);
+
+    assert!(matches!(
+        publisher.resume_publications(),
+        Err(DdsError::PreconditionNotMet(_))
+    ));
+
+    publisher.suspend_publications().unwrap();
+    // Replacing a sample which was not sent does not wait for its acknowledgment
+    writer.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer.write(KeyedData { id: 1, value: 2 }, None).unwrap();
+    writer.write(KeyedData { id: 2, value: 3 }, None).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert_eq!(
+        reader.take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE),
+        Err(DdsError::NoData)
+    );
+
+    publisher.resume_publications().unwrap();
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    let values: Vec<u32> = reader
+        .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .into_iter()
+        .map(|s| s.data.unwrap().value)
+        .collect();
+    assert!(values.ends_with(&[2, 3]));
+}
+
+#[test]
+fn publisher_wait_for_acknowledgments_waits_for_all_writers() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic1 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic1",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let topic2 = participant
+        .create_topic::<KeyedData>(
+            "MyTopic2",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer1 = publisher
+        .create_datawriter(
+            &topic1,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let writer2 = publisher
+        .create_datawriter(
+            &topic2,
+            QosKind::Specific(reliable_writer_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader1 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic1,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader2 = subscriber
+        .create_datareader::<KeyedData>(
+            &topic2,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer1);
+    wait_for_publication_matched(&writer2);
+
+    writer1.write(KeyedData { id: 1, value: 1 }, None).unwrap();
+    writer2.write(KeyedData { id: 2, value: 2 }, None).unwrap();
+    publisher
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+    assert_eq!(
+        reader1
+            .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        reader2
+            .take(10, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // The samples held while the publications are suspended can't be acknowledged
+    publisher.suspend_publications().unwrap();
+    writer2.write(KeyedData { id: 2, value: 3 }, None).unwrap();
+    assert_eq!(
+        publisher.wait_for_acknowledgments(Duration::new(0, 200_000_000)),
+        Err(DdsError::Timeout)
+    );
+    publisher.resume_publications().unwrap();
+    publisher
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+}
+
+#[test]
+fn asynchronous_writer_sends_written_samples_in_order() {
+    let domain_id = TEST_DOMAIN_ID_GENERATOR.generate_unique_domain_id();
+    let participant = DomainParticipantFactory::get_instance()
+        .create_participant(domain_id, QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let topic = participant
+        .create_topic::<KeyedData>(
+            "MyTopic",
+            "KeyedData",
+            QosKind::Default,
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let publisher = participant
+        .create_publisher(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let subscriber = participant
+        .create_subscriber(QosKind::Default, NO_LISTENER, NO_STATUS)
+        .unwrap();
+    let writer_qos = DataWriterQos {
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepAll,
+        },
+        publish_mode: PublishModeQosPolicy {
+            kind: PublishModeQosPolicyKind::Asynchronous,
+        },
+        ..reliable_writer_qos()
+    };
+    let writer = publisher
+        .create_datawriter(
+            &topic,
+            QosKind::Specific(writer_qos.clone()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    let reader = subscriber
+        .create_datareader::<KeyedData>(
+            &topic,
+            QosKind::Specific(reliable_keep_all_reader_qos()),
+            NO_LISTENER,
+            NO_STATUS,
+        )
+        .unwrap();
+    wait_for_publication_matched(&writer);
+
+    for value in 1..=20 {
+        writer.write(KeyedData { id: 1, value }, None).unwrap();
+    }
+    writer
+        .wait_for_acknowledgments(Duration::new(10, 0))
+        .unwrap();
+
+    let values: Vec<u32> = reader
+        .take(100, ANY_SAMPLE_STATE, ANY_VIEW_STATE, ANY_INSTANCE_STATE)
+        .unwrap()
+        .into_iter()
+        .map(|s| s.data.unwrap().value)
+        .collect();
+    assert_eq!(values, (1..=20).collect::<Vec<u32>>());
+    assert_eq!(
+        writer.set_qos(QosKind::Specific(DataWriterQos {
+            publish_mode: PublishModeQosPolicy::default(),
+            ..writer_qos
+        })),
+        Err(DdsError::ImmutablePolicy)
+    );
+}
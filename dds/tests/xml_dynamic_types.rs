@@ -1416,3 +1416,183 @@ fn parse_bitmask_type_and_data_from_xml() {
     assert_eq!(*data.get_uint32_value(0).unwrap(), 2);
     assert_eq!(*data.get_int16_value(1).unwrap(), 128);
 }
+
+#[cfg(feature = "xtypes-xml")]
+#[test]
+fn parse_map_type_and_data_from_xml() {
+    use dust_dds::xtypes::data_storage::DataStorage;
+
+    let type_xml = r#"<dds>
+        <types>
+            <module name="Test">
+                <struct name="struct_map" extensibility="final">
+                    <member name="x1" type="float64" key_type="string" mapMaxLength="-1"/>
+                    <member name="x2" type="int32" key_type="uint16" mapMaxLength="5" sequenceMaxLength="-1"/>
+                </struct>
+            </module>
+        </types>
+    </dds>"#;
+
+    let builder =
+        DynamicTypeBuilderFactory::create_type_w_document(type_xml, "Test::struct_map", vec![])
+            .unwrap();
+    let dynamic_type = builder.build();
+
+    let x1_type = dynamic_type
+        .get_member_by_name("x1")
+        .unwrap()
+        .descriptor
+        .r#type;
+    assert_eq!(x1_type.get_kind(), TypeKind::MAP);
+    assert_eq!(
+        x1_type
+            .get_descriptor()
+            .key_element_type
+            .unwrap()
+            .get_kind(),
+        TypeKind::STRING8
+    );
+    assert_eq!(
+        x1_type.get_descriptor().element_type.unwrap().get_kind(),
+        TypeKind::FLOAT64
+    );
+    let x2_type = dynamic_type
+        .get_member_by_name("x2")
+        .unwrap()
+        .descriptor
+        .r#type;
+    assert_eq!(x2_type.get_kind(), TypeKind::SEQUENCE);
+    let x2_element_type = x2_type.get_descriptor().element_type.unwrap();
+    assert_eq!(x2_element_type.get_kind(), TypeKind::MAP);
+    assert_eq!(x2_element_type.get_descriptor().bound, &[5]);
+
+    let mut data = DynamicDataFactory::create_data(dynamic_type);
+    let data_xml = r#"<struct_map>
+        <x1>
+            <item><key>gain</key><value>1.5</value></item>
+            <item><key>offset</key><value>-2</value></item>
+        </x1>
+    </struct_map>"#;
+    data.from_xml(data_xml).unwrap();
+    assert_eq!(
+        data.get_value(0).unwrap(),
+        &DataStorage::Map(vec![
+            (
+                DataStorage::String("gain".to_string()),
+                DataStorage::Float64(1.5)
+            ),
+            (
+                DataStorage::String("offset".to_string()),
+                DataStorage::Float64(-2.0)
+            ),
+        ])
+    );
+}
+
+#[test]
+fn parse_nested_collection_type_and_data_from_xml() {
+    use dust_dds::xtypes::data_storage::DataStorage;
+
+    let type_xml = r#"<dds>
+        <types>
+            <module name="Test">
+                <struct name="struct_nested" extensibility="final">
+                    <member name="x1" type="float32" sequenceMaxLength="-1" arrayDimensions="2"/>
+                    <member name="x2" type="float64" arrayDimensions="2,3"/>
+                </struct>
+            </module>
+        </types>
+    </dds>"#;
+
+    let builder =
+        DynamicTypeBuilderFactory::create_type_w_document(type_xml, "Test::struct_nested", vec![])
+            .unwrap();
+    let dynamic_type = builder.build();
+
+    let x1_type = dynamic_type
+        .get_member_by_name("x1")
+        .unwrap()
+        .descriptor
+        .r#type;
+    assert_eq!(x1_type.get_kind(), TypeKind::ARRAY);
+    assert_eq!(
+        x1_type.get_descriptor().element_type.unwrap().get_kind(),
+        TypeKind::SEQUENCE
+    );
+    let x2_type = dynamic_type
+        .get_member_by_name("x2")
+        .unwrap()
+        .descriptor
+        .r#type;
+    assert_eq!(x2_type.get_kind(), TypeKind::ARRAY);
+    assert_eq!(x2_type.get_descriptor().bound, &[2, 3]);
+
+    let mut data = DynamicDataFactory::create_data(dynamic_type);
+    let data_xml = r#"<struct_nested>
+        <x1>
+            <item><item>1.5</item><item>2</item></item>
+            <item></item>
+        </x1>
+        <x2>
+            <item>1</item><item>2</item><item>3</item>
+            <item>4</item><item>5</item><item>6</item>
+        </x2>
+    </struct_nested>"#;
+    data.from_xml(data_xml).unwrap();
+    assert_eq!(
+        data.get_value(0).unwrap(),
+        &DataStorage::SequenceCollection(vec![
+            DataStorage::SequenceFloat32(vec![1.5, 2.0]),
+            DataStorage::SequenceFloat32(vec![]),
+        ])
+    );
+    assert_eq!(
+        data.get_value(1).unwrap(),
+        &DataStorage::SequenceFloat64(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+    );
+}
+
+#[test]
+fn parse_wide_character_type_and_data_from_xml() {
+    let type_xml = r#"<dds>
+        <types>
+            <module name="Test">
+                <struct name="struct_wide" extensibility="final">
+                    <member name="initial" type="char16"/>
+                    <member name="message" type="wstring" stringMaxLength="64"/>
+                    <member name="letters" type="char16" sequenceMaxLength="-1"/>
+                    <member name="lines" type="wstring" arrayDimensions="2"/>
+                </struct>
+            </module>
+        </types>
+    </dds>"#;
+
+    let builder =
+        DynamicTypeBuilderFactory::create_type_w_document(type_xml, "Test::struct_wide", vec![])
+            .unwrap();
+    let dynamic_type = builder.build();
+
+    let message_type = dynamic_type
+        .get_member_by_name("message")
+        .unwrap()
+        .descriptor
+        .r#type;
+    assert_eq!(message_type.get_kind(), TypeKind::STRING16);
+    assert_eq!(message_type.get_descriptor().bound, &[64]);
+
+    let mut data = DynamicDataFactory::create_data(dynamic_type);
+    let data_xml = r#"<struct_wide>
+        <initial>Ω</initial>
+        <message>Grüße</message>
+        <letters><item>ä</item><item>0x20AC</item></letters>
+        <lines><item>日本語</item><item></item></lines>
+    </struct_wide>"#;
+    data.from_xml(data_xml).unwrap();
+    assert_eq!(data.get_char16_value(0).unwrap(), &'Ω');
+    assert_eq!(data.get_wstring_value(1).unwrap(), "Grüße");
+    assert_eq!(data.get_char16_values(2).unwrap(), &['ä', '€']);
+    assert_eq!(
+        data.get_wstring_values(3).unwrap(),
+        &[String::from("日本語"), String::new()]
+    );
+}
@@ -25,6 +25,8 @@ pub struct StructureMemberAttributes {
     pub hashid: bool,
     pub default_value: Option<Expr>,
     pub try_construct: Option<TryConstructKind>,
+    pub bits: Option<u32>,
+    pub position: Option<u32>,
 }
 
 pub fn get_structure_member_attributes(field: &Field) -> Result<StructureMemberAttributes> {
@@ -36,6 +38,8 @@ pub fn get_structure_member_attributes(field: &Field) -> Result<StructureMemberA
     let mut external = false;
     let mut hashid = false;
     let mut try_construct = None;
+    let mut bits = None;
+    let mut position = None;
 
     if let Some(xtypes_attribute) = field
         .attrs
@@ -64,6 +68,12 @@ pub fn get_structure_member_attributes(field: &Field) -> Result<StructureMemberA
             } else if meta.path.is_ident("hashid") {
                 hashid = true;
                 Ok(())
+            } else if meta.path.is_ident("bits") {
+                bits = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                Ok(())
+            } else if meta.path.is_ident("position") {
+                position = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                Ok(())
             } else if meta.path.is_ident("try_construct") {
                let format_str: syn::LitStr = meta.value()?.parse()?;
                 match format_str.value().as_ref() {
@@ -96,6 +106,8 @@ pub fn get_structure_member_attributes(field: &Field) -> Result<StructureMemberA
         hashid,
         default_value,
         try_construct,
+        bits,
+        position,
     })
 }
 
@@ -111,6 +123,7 @@ pub struct StructAttributes {
     pub extensibility: Extensibility,
     pub is_nested: bool,
     pub base_type: Option<syn::Type>,
+    pub is_bitset: bool,
 }
 
 pub fn get_struct_attributes(input: &DeriveInput) -> Result<StructAttributes> {
@@ -118,6 +131,7 @@ pub fn get_struct_attributes(input: &DeriveInput) -> Result<StructAttributes> {
     let mut extensibility = Extensibility::Final;
     let mut is_nested = false;
     let mut base_type = None;
+    let mut is_bitset = false;
 
     if let Some(xtypes_attribute) = input
         .attrs
@@ -151,6 +165,9 @@ pub fn get_struct_attributes(input: &DeriveInput) -> Result<StructAttributes> {
             } else if meta.path.is_ident("nested") {
                 is_nested = true;
                 Ok(())
+            } else if meta.path.is_ident("bitset") {
+                is_bitset = true;
+                Ok(())
             } else {
                 Err(meta.error(UnknownAttributeError))
             }
@@ -162,6 +179,7 @@ pub fn get_struct_attributes(input: &DeriveInput) -> Result<StructAttributes> {
         extensibility,
         is_nested,
         base_type,
+        is_bitset,
     })
 }
 
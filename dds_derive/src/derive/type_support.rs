@@ -1,131 +1,152 @@
 use crate::derive::{
     attributes::{
-        BitBound, Extensibility, TryConstructKind, get_enumerated_type_attributes,
-        get_struct_attributes, get_structure_member_attributes, get_union_type_attributes,
-        get_union_variant_attributes,
+        BitBound, Extensibility, StructAttributes, TryConstructKind,
+        get_enumerated_type_attributes, get_struct_attributes, get_structure_member_attributes,
+        get_union_type_attributes, get_union_variant_attributes,
     },
     enum_support::read_enum_variant_discriminant_mapping,
 };
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{DataEnum, DeriveInput, Fields, Index, Result, parse_quote, spanned::Spanned};
 
 pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
-    let ident = &input.ident;
-    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
-    let (get_type_quote, create_dynamic_sample_quote, create_sample_quote) = match &input.data {
-        syn::Data::Struct(xtypes_struct) => {
-            // Get the type declaration attributes as defined in Table 21 – IDL Built-in Annotations Usage of the XTypes standard
-            let r#struct = get_struct_attributes(input)?;
-            let type_name = r#struct.name.as_str();
-            let extensibility_kind = match r#struct.extensibility {
-                Extensibility::Final => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final}
-                }
-                Extensibility::Appendable => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Appendable}
+    let (get_type_quote, create_dynamic_sample_quote, create_sample_quote, xcdr_quote) =
+        match &input.data {
+            syn::Data::Struct(xtypes_struct) => {
+                // Get the type declaration attributes as defined in Table 21 – IDL Built-in Annotations Usage of the XTypes standard
+                let r#struct = get_struct_attributes(input)?;
+                if r#struct.is_bitset {
+                    return expand_bitset_type_support(input, xtypes_struct, &r#struct);
                 }
-                Extensibility::Mutable => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Mutable}
-                }
-            };
-            let is_nested = r#struct.is_nested;
-            let base_type = match r#struct.base_type {
-                Some(t) => quote! {Some(<#t as dust_dds::xtypes::type_support::Type>::TYPE)},
-                None => quote! {None},
-            };
-
-            let struct_descriptor = quote! {
-                &dust_dds::xtypes::dynamic_type::TypeDescriptor {
-                    kind: dust_dds::xtypes::dynamic_type::TypeKind::STRUCTURE,
-                    name: #type_name,
-                    base_type: #base_type,
-                    discriminator_type: None,
-                    bound: &[],
-                    element_type: None,
-                    key_element_type: None,
-                    extensibility_kind: #extensibility_kind,
-                    is_nested: #is_nested,
-                }
-            };
+                let type_name = r#struct.name.as_str();
+                let extensibility_kind = match r#struct.extensibility {
+                    Extensibility::Final => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final}
+                    }
+                    Extensibility::Appendable => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Appendable}
+                    }
+                    Extensibility::Mutable => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Mutable}
+                    }
+                };
+                let is_nested = r#struct.is_nested;
+                let base_type = match &r#struct.base_type {
+                    Some(t) => quote! {Some(<#t as dust_dds::xtypes::type_support::Type>::TYPE)},
+                    None => quote! {None},
+                };
 
-            let mut member_list = Vec::new();
-            let mut member_sample_seq = Vec::new();
-            let mut member_dynamic_sample_seq = Vec::new();
-
-            let mut next_auto_id = 0;
-            for (member_index, member) in xtypes_struct.fields.iter().enumerate() {
-                let index = member_index as u32;
-                let struct_member_attributes = get_structure_member_attributes(member)?;
-
-                let member_name = member
-                    .ident
-                    .as_ref()
-                    .map(|i| i.to_string())
-                    .unwrap_or(member_index.to_string());
-
-                let member_id = if struct_member_attributes.hashid {
-                    let member_hash = <[u8; 16]>::from(md5::compute(member_name.as_bytes()));
-                    let member_hash_int = u32::from_le_bytes([
-                        member_hash[0],
-                        member_hash[1],
-                        member_hash[2],
-                        member_hash[3],
-                    ]);
-                    syn::parse_str(&member_hash_int.to_string())?
-                } else {
-                    match r#struct.extensibility {
-                        Extensibility::Final | Extensibility::Appendable => {
-                            syn::parse_str(&member_index.to_string())
-                        }
-                        Extensibility::Mutable => {
-                            if let Some(provided_id) = struct_member_attributes.id {
-                                Ok(provided_id)
-                            } else {
-                                syn::parse_str(&next_auto_id.to_string())
-                            }
-                        }
-                    }?
+                let struct_descriptor = quote! {
+                    &dust_dds::xtypes::dynamic_type::TypeDescriptor {
+                        kind: dust_dds::xtypes::dynamic_type::TypeKind::STRUCTURE,
+                        name: #type_name,
+                        base_type: #base_type,
+                        discriminator_type: None,
+                        bound: &[],
+                        element_type: None,
+                        key_element_type: None,
+                        extensibility_kind: #extensibility_kind,
+                        is_nested: #is_nested,
+                    }
                 };
 
-                if !struct_member_attributes.hashid {
-                    if let syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Int(lit_int),
-                        ..
-                    }) = &member_id
+                let mut member_list = Vec::new();
+                let mut member_sample_seq = Vec::new();
+                let mut member_dynamic_sample_seq = Vec::new();
+                // Direct XCDR serialization is only generated when all the members are supported
+                let mut is_xcdr_supported = r#struct.base_type.is_none();
+                let mut member_serialize_seq = Vec::new();
+                let mut member_deserialize_seq = Vec::new();
+                let mut member_key_seq = Vec::new();
+                let mut mutable_member_variable_seq = Vec::new();
+                let mut mutable_member_read_seq = Vec::new();
+
+                let mut next_auto_id = 0;
+                for (member_index, member) in xtypes_struct.fields.iter().enumerate() {
+                    let index = member_index as u32;
+                    let struct_member_attributes = get_structure_member_attributes(member)?;
+                    if struct_member_attributes.bits.is_some()
+                        || struct_member_attributes.position.is_some()
                     {
-                        next_auto_id = lit_int.base10_parse::<u32>()? + 1;
+                        return Err(syn::Error::new(
+                            member.span(),
+                            "#[dust_dds(bits)] and #[dust_dds(position)] are only supported in #[dust_dds(bitset)] structs",
+                        ));
                     }
-                }
 
-                let member_type = &member.ty;
-                let is_key = struct_member_attributes.key;
-                let is_optional = struct_member_attributes.optional;
-                let is_external = struct_member_attributes.external;
-                let default_value = struct_member_attributes.default_value.map(|x| quote! {#x});
-                let try_construct = match struct_member_attributes.try_construct {
-                    Some(TryConstructKind::Discard) | None => {
-                        quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::Discard}
-                    }
-                    Some(TryConstructKind::UseDefault) => {
-                        quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::UseDefault}
-                    }
-                    Some(TryConstructKind::Trim) => {
-                        quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::Trim}
+                    let member_name = member
+                        .ident
+                        .as_ref()
+                        .map(|i| i.to_string())
+                        .unwrap_or(member_index.to_string());
+
+                    let member_id = if struct_member_attributes.hashid {
+                        let member_hash = <[u8; 16]>::from(md5::compute(member_name.as_bytes()));
+                        let member_hash_int = u32::from_le_bytes([
+                            member_hash[0],
+                            member_hash[1],
+                            member_hash[2],
+                            member_hash[3],
+                        ]);
+                        syn::parse_str(&member_hash_int.to_string())?
+                    } else {
+                        match r#struct.extensibility {
+                            Extensibility::Final | Extensibility::Appendable => {
+                                syn::parse_str(&member_index.to_string())
+                            }
+                            Extensibility::Mutable => {
+                                if let Some(provided_id) = struct_member_attributes.id {
+                                    Ok(provided_id)
+                                } else {
+                                    syn::parse_str(&next_auto_id.to_string())
+                                }
+                            }
+                        }?
+                    };
+
+                    if !struct_member_attributes.hashid {
+                        if let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(lit_int),
+                            ..
+                        }) = &member_id
+                        {
+                            next_auto_id = lit_int.base10_parse::<u32>()? + 1;
+                        }
                     }
-                };
 
-                let member_dynamic_type = if is_external {
-                    let inner_type = if let syn::Type::Path(type_path) = member_type {
-                        if let Some(segment) = type_path.path.segments.last() {
-                            if segment.ident == "Box" {
-                                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments
-                                {
-                                    if args.args.len() == 1 {
-                                        if let syn::GenericArgument::Type(inner_type) =
-                                            &args.args[0]
-                                        {
-                                            Some(inner_type)
+                    let member_type = &member.ty;
+                    let is_key = struct_member_attributes.key;
+                    let is_optional = struct_member_attributes.optional;
+                    let is_external = struct_member_attributes.external;
+                    let default_value = struct_member_attributes.default_value.map(|x| quote! {#x});
+                    let try_construct = match struct_member_attributes.try_construct {
+                        Some(TryConstructKind::Discard) | None => {
+                            quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::Discard}
+                        }
+                        Some(TryConstructKind::UseDefault) => {
+                            quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::UseDefault}
+                        }
+                        Some(TryConstructKind::Trim) => {
+                            quote! { dust_dds::xtypes::dynamic_type::TryConstructKind::Trim}
+                        }
+                    };
+
+                    let member_dynamic_type = if is_external {
+                        let inner_type = if let syn::Type::Path(type_path) = member_type {
+                            if let Some(segment) = type_path.path.segments.last() {
+                                if segment.ident == "Box" {
+                                    if let syn::PathArguments::AngleBracketed(args) =
+                                        &segment.arguments
+                                    {
+                                        if args.args.len() == 1 {
+                                            if let syn::GenericArgument::Type(inner_type) =
+                                                &args.args[0]
+                                            {
+                                                Some(inner_type)
+                                            } else {
+                                                None
+                                            }
                                         } else {
                                             None
                                         }
@@ -140,95 +161,197 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                             }
                         } else {
                             None
-                        }
-                    } else {
-                        None
-                    };
+                        };
 
-                    let inner_type = inner_type.ok_or_else(|| {
-                        syn::Error::new(
-                            member_type.span(),
-                            "#[dust_dds(external)] is only supported on Box<T> types",
-                        )
-                    })?;
+                        let inner_type = inner_type.ok_or_else(|| {
+                            syn::Error::new(
+                                member_type.span(),
+                                "#[dust_dds(external)] is only supported on Box<T> types",
+                            )
+                        })?;
 
-                    let member_type_name = quote::quote!(#inner_type).to_string().replace(" ", "");
+                        let member_type_name =
+                            quote::quote!(#inner_type).to_string().replace(" ", "");
 
-                    quote! {
-                        dust_dds::xtypes::dynamic_type::DynamicType {
-                            descriptor: &dust_dds::xtypes::dynamic_type::TypeDescriptor {
-                                kind: dust_dds::xtypes::dynamic_type::TypeKind::NONE,
-                                name: #member_type_name,
-                                base_type: None,
-                                discriminator_type: None,
-                                bound: &[],
-                                element_type: None,
-                                key_element_type: None,
-                                extensibility_kind: dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final,
-                                is_nested: false,
-                            },
-                            member_list: &[]
+                        quote! {
+                            dust_dds::xtypes::dynamic_type::DynamicType {
+                                descriptor: &dust_dds::xtypes::dynamic_type::TypeDescriptor {
+                                    kind: dust_dds::xtypes::dynamic_type::TypeKind::NONE,
+                                    name: #member_type_name,
+                                    base_type: None,
+                                    discriminator_type: None,
+                                    bound: &[],
+                                    element_type: None,
+                                    key_element_type: None,
+                                    extensibility_kind: dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final,
+                                    is_nested: false,
+                                },
+                                member_list: &[]
+                            }
                         }
-                    }
-                } else {
-                    quote! { <#member_type as dust_dds::xtypes::type_support::Type>::TYPE}
-                };
+                    } else {
+                        quote! { <#member_type as dust_dds::xtypes::type_support::Type>::TYPE}
+                    };
 
-                member_list.push(quote! {
-                     dust_dds::xtypes::dynamic_type::DynamicTypeMember {
-                        descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
-                            name: #member_name,
-                            id: #member_id,
-                            r#type: #member_dynamic_type,
-                            default_value: None,
-                            index: #index as u32,
-                            try_construct_kind: #try_construct,
-                            label: &[],
-                            is_key: #is_key,
-                            is_optional: #is_optional,
-                            is_must_understand: #is_key,
-                            is_shared: false,
-                            is_default_label: false,
-                            is_external: #is_external,
+                    member_list.push(quote! {
+                         dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                            descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
+                                name: #member_name,
+                                id: #member_id,
+                                r#type: #member_dynamic_type,
+                                default_value: None,
+                                index: #index as u32,
+                                try_construct_kind: #try_construct,
+                                label: &[],
+                                is_key: #is_key,
+                                is_optional: #is_optional,
+                                is_must_understand: #is_key,
+                                is_shared: false,
+                                is_default_label: false,
+                                is_external: #is_external,
+                            }
                         }
-                    }
-                });
+                    });
+
+                    let member_type = &member.ty;
+                    let member_default_value = default_value
+                        .unwrap_or(quote! { <#member_type as ::core::default::Default>::default()});
 
-                let member_type = &member.ty;
-                let member_default_value = default_value
-                    .unwrap_or(quote! { <#member_type as ::core::default::Default>::default()});
-                if struct_member_attributes.non_serialized {
-                    match &member.ident {
+                    let member_id_value = match &member_id {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(lit_int),
+                            ..
+                        }) => Some(lit_int.base10_parse::<u32>()?),
+                        _ => None,
+                    };
+                    let is_mutable = r#struct.extensibility == Extensibility::Mutable;
+                    // Members which are not discarded when failing to construct are deserialized
+                    // through the dynamic representation which implements the try construct rules
+                    if is_external
+                        || struct_member_attributes.non_serialized
+                        || (is_mutable && member_id_value.is_none())
+                        || matches!(
+                            struct_member_attributes.try_construct,
+                            Some(TryConstructKind::UseDefault | TryConstructKind::Trim)
+                        )
+                    {
+                        is_xcdr_supported = false;
+                    }
+                    let (member_access, member_field) = match &member.ident {
                         Some(member_ident) => {
-                            member_sample_seq.push(quote! {
-                                #member_ident: #member_default_value,
-                            });
+                            (quote! {self.#member_ident}, quote! {#member_ident:})
                         }
                         None => {
-                            member_sample_seq.push(quote! {
-                                #member_default_value,
-                            });
+                            let index = Index::from(member_index);
+                            (quote! {self.#index}, quote! {})
                         }
+                    };
+                    // In Mutable structs every tuple member is optional even when not explicitly marked as such
+                    let is_omitted_when_default =
+                        is_optional || (is_mutable && member.ident.is_none());
+                    if is_mutable {
+                        let serialize_member = quote! {
+                            serializer.serialize_mutable_member(#member_id, #is_key, &#member_access)?;
+                        };
+                        member_serialize_seq.push((
+                            member_id_value.unwrap_or_default(),
+                            if is_omitted_when_default {
+                                quote! {
+                                    if #member_access != #member_default_value {
+                                        #serialize_member
+                                    }
+                                }
+                            } else {
+                                serialize_member
+                            },
+                        ));
+                        // Mutable members are read in the order in which they are found and assigned at the end
+                        let member_variable = format_ident!("member_{}", member_index);
+                        mutable_member_variable_seq.push(quote! {
+                            let mut #member_variable: ::core::option::Option<#member_type> = None;
+                        });
+                        mutable_member_read_seq.push(quote! {
+                        #member_id => #member_variable = Some(dust_dds::xtypes::xcdr::XcdrDeserialize::deserialize(deserializer)?),
+                    });
+                        member_deserialize_seq.push(
+                        if is_omitted_when_default {
+                            quote! {
+                                #member_field #member_variable.unwrap_or_else(|| #member_default_value),
+                            }
+                        } else {
+                            quote! {
+                                #member_field #member_variable.ok_or(dust_dds::xtypes::error::XTypesError::InvalidData)?,
+                            }
+                        },
+                    );
+                    } else if is_optional {
+                        member_serialize_seq.push((
+                        member_id_value.unwrap_or_default(),
+                        quote! {
+                            serializer.serialize_optional_member(#member_id, #is_key, (#member_access != #member_default_value).then_some(&#member_access))?;
+                        },
+                    ));
+                        member_deserialize_seq.push(quote! {
+                        #member_field deserializer.deserialize_optional_member(#member_id)?.unwrap_or_else(|| #member_default_value),
+                    });
+                    } else {
+                        member_serialize_seq.push((
+                        member_id_value.unwrap_or_default(),
+                        quote! {
+                            dust_dds::xtypes::xcdr::XcdrSerialize::serialize(&#member_access, serializer)?;
+                        },
+                    ));
+                        member_deserialize_seq.push(quote! {
+                        #member_field dust_dds::xtypes::xcdr::XcdrDeserialize::deserialize(deserializer)?,
+                    });
                     }
-                } else {
-                    match &member.ident {
-                        Some(member_ident) => {
-                            if is_optional {
+                    // The key members are serialized as the members of a final structure containing
+                    // the key members of the type and of its nested structures
+                    if is_key && is_optional {
+                        member_key_seq.push(quote! {
+                        serializer.serialize_optional_member(#member_id, true, (#member_access != #member_default_value).then_some(&#member_access))?;
+                    });
+                    } else if is_key {
+                        member_key_seq.push(quote! {
+                        dust_dds::xtypes::xcdr::XcdrSerialize::serialize(&#member_access, serializer)?;
+                    });
+                    } else if !is_optional {
+                        member_key_seq.push(quote! {
+                        dust_dds::xtypes::xcdr::XcdrSerialize::serialize_key(&#member_access, serializer)?;
+                    });
+                    }
+                    if struct_member_attributes.non_serialized {
+                        match &member.ident {
+                            Some(member_ident) => {
+                                member_sample_seq.push(quote! {
+                                    #member_ident: #member_default_value,
+                                });
+                            }
+                            None => {
                                 member_sample_seq.push(quote! {
+                                    #member_default_value,
+                                });
+                            }
+                        }
+                    } else {
+                        match &member.ident {
+                            Some(member_ident) => {
+                                if is_optional {
+                                    member_sample_seq.push(quote! {
                                     #member_ident: src.remove_value(#member_id).ok().map_or(
                                         Some(#member_default_value),
                                         |x| dust_dds::xtypes::data_storage::DataStorageMapping::try_from_storage(x).ok()
                                     )?,
                                 });
 
-                                member_dynamic_sample_seq
+                                    member_dynamic_sample_seq
                                     .push(quote! {
                                         if self.#member_ident != #member_default_value {
                                             data.set_value(#member_id, dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(self.#member_ident));
                                         }
                                     });
-                            } else {
-                                member_sample_seq.push(
+                                } else {
+                                    member_sample_seq.push(
                                     if struct_member_attributes.try_construct == Some(TryConstructKind::UseDefault) {
                                         quote! {
                                             #member_ident: src.remove_value(#member_id).ok().map_or(
@@ -243,27 +366,27 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                                     }
                                     );
 
-                                member_dynamic_sample_seq
+                                    member_dynamic_sample_seq
                                     .push(quote! {data.set_value(#member_id, dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(self.#member_ident));});
+                                }
                             }
-                        }
-                        None => {
-                            let index = Index::from(member_index);
-                            // In Mutable structs every member is optional even when not explicitly marked as such
-                            if r#struct.extensibility == Extensibility::Mutable || is_optional {
-                                member_sample_seq.push(quote! {
+                            None => {
+                                let index = Index::from(member_index);
+                                // In Mutable structs every member is optional even when not explicitly marked as such
+                                if r#struct.extensibility == Extensibility::Mutable || is_optional {
+                                    member_sample_seq.push(quote! {
                                     src.remove_value(#member_id).ok().map_or(
                                         Some(#member_default_value),
                                         |x| dust_dds::xtypes::data_storage::DataStorageMapping::try_from_storage(x).ok()
                                     )?,
                                 });
-                                member_dynamic_sample_seq.push(quote! {
+                                    member_dynamic_sample_seq.push(quote! {
                                     if self.#index != #member_default_value {
                                         data.set_value(#member_id, dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(self.#index));
                                     }
                                 })
-                            } else {
-                                member_sample_seq.push(if struct_member_attributes.try_construct == Some(TryConstructKind::UseDefault) {
+                                } else {
+                                    member_sample_seq.push(if struct_member_attributes.try_construct == Some(TryConstructKind::UseDefault) {
                                     quote! {
                                         src.remove_value(#member_id).ok().map_or(
                                             #member_default_value,
@@ -274,132 +397,247 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                                      quote! { dust_dds::xtypes::data_storage::DataStorageMapping::try_from_storage(src.remove_value(#member_id).ok()?).ok()?,}
                                 });
 
-                                member_dynamic_sample_seq.push(quote! {
+                                    member_dynamic_sample_seq.push(quote! {
                                     data.set_value(#member_id, dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(self.#index));
                                 })
+                                }
                             }
                         }
                     }
                 }
-            }
-            let is_tuple = match xtypes_struct.fields.iter().next() {
-                Some(s) => s.ident.is_none(),
-                None => false,
-            };
+                let is_tuple = match xtypes_struct.fields.iter().next() {
+                    Some(s) => s.ident.is_none(),
+                    None => false,
+                };
 
-            let get_type_quote = quote! {
-                const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
-                    dust_dds::xtypes::dynamic_type::DynamicType {
-                        descriptor: #struct_descriptor,
-                        member_list: &[#(#member_list,)*]
-                    };
-            };
+                let get_type_quote = quote! {
+                    const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
+                        dust_dds::xtypes::dynamic_type::DynamicType {
+                            descriptor: #struct_descriptor,
+                            member_list: &[#(#member_list,)*]
+                        };
+                };
 
-            let create_dynamic_sample_quote = quote! {
-                #(#member_dynamic_sample_seq)*
-            };
-            let create_sample_quote = if is_tuple {
-                quote! {Some(Self(#(#member_sample_seq)*))}
-            } else {
-                quote! {Some(Self{#(#member_sample_seq)*})}
-            };
-            Ok((
-                get_type_quote,
-                create_dynamic_sample_quote,
-                create_sample_quote,
-            ))
-        }
-        // Separate between Unions and Enumeration which are both
-        // mapped as Rust enum types
-        syn::Data::Enum(xtypes_union) if is_enum_xtypes_union(xtypes_union) => {
-            let union_attributes = get_union_type_attributes(input)?;
-            let discriminator_type = union_attributes.discriminator_type;
-            let type_name = union_attributes.name.as_str();
-            let extensibility_kind = match union_attributes.extensibility {
-                Extensibility::Final => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final}
-                }
-                Extensibility::Appendable => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Appendable}
-                }
-                Extensibility::Mutable => {
-                    quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Mutable}
-                }
-            };
-            let is_nested = union_attributes.is_nested;
-            if xtypes_union.variants.len() > (u32::MAX as usize + 1) {
-                return Err(syn::Error::new(
-                    input.span(),
-                    "Union can hold at most `u32::MAX + 1` variants",
-                ));
-            }
+                let create_dynamic_sample_quote = quote! {
+                    #(#member_dynamic_sample_seq)*
+                };
+                let create_sample_quote = if is_tuple {
+                    quote! {Some(Self(#(#member_sample_seq)*))}
+                } else {
+                    quote! {Some(Self{#(#member_sample_seq)*})}
+                };
 
-            let union_descriptor = quote! {
-                &dust_dds::xtypes::dynamic_type::TypeDescriptor {
-                    kind: dust_dds::xtypes::dynamic_type::TypeKind::UNION,
-                    name: #type_name,
-                    base_type: None,
-                    discriminator_type: ::core::option::Option::Some(<#discriminator_type as ::dust_dds::xtypes::type_support::Type>::TYPE),
-                    bound: &[],
-                    element_type: None,
-                    key_element_type: None,
-                    extensibility_kind: #extensibility_kind,
-                    is_nested: #is_nested,
+                // Members of mutable types are serialized in increasing id order as in their dynamic representation
+                if r#struct.extensibility == Extensibility::Mutable {
+                    member_serialize_seq.sort_by_key(|(member_id, _)| *member_id);
                 }
-            };
+                let member_serialize_seq = member_serialize_seq.into_iter().map(|(_, s)| s);
+                let deserialize_value = if is_tuple {
+                    quote! {Self(#(#member_deserialize_seq)*)}
+                } else {
+                    quote! {Self{#(#member_deserialize_seq)*}}
+                };
+                let deserialize_members_quote = if r#struct.extensibility == Extensibility::Mutable
+                {
+                    quote! {
+                        let mut aggregated_type = deserializer.begin_aggregated_type(#extensibility_kind)?;
+                        #(#mutable_member_variable_seq)*
+                        while let Some(member_id) = deserializer.next_mutable_member(&mut aggregated_type)? {
+                            match member_id {
+                                #(#mutable_member_read_seq)*
+                                _ => deserializer.skip_mutable_member(&aggregated_type)?,
+                            }
+                        }
+                        deserializer.end_aggregated_type(aggregated_type)?;
+                        Ok(#deserialize_value)
+                    }
+                } else {
+                    quote! {
+                        let aggregated_type = deserializer.begin_aggregated_type(#extensibility_kind)?;
+                        let value = #deserialize_value;
+                        deserializer.end_aggregated_type(aggregated_type)?;
+                        Ok(value)
+                    }
+                };
+                let xcdr_quote = is_xcdr_supported.then(|| {
+                quote! {
+                    fn serialize_nested(&self, serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        let aggregated_type = serializer.begin_aggregated_type(#extensibility_kind);
+                        #(#member_serialize_seq)*
+                        serializer.end_aggregated_type(aggregated_type);
+                        Ok(())
+                    }
 
-            let is_key = union_attributes.is_discriminator_key;
-            let mut variant_list: Vec<TokenStream> = vec![quote! {
-                 dust_dds::xtypes::dynamic_type::DynamicTypeMember {
-                    descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
-                        name: "discriminator",
-                        id: 0u32,
-                        r#type: <#discriminator_type as dust_dds::xtypes::type_support::Type>::TYPE,
-                        default_value: None,
-                        index: 0u32,
-                        try_construct_kind: dust_dds::xtypes::dynamic_type::TryConstructKind::Discard,
-                        label: &[],
-                        is_key: #is_key,
-                        is_optional: false,
-                        is_must_understand: true,
-                        is_shared: false,
-                        is_default_label: false,
-                        is_external: false,
+                    fn serialize_key_members(&self, serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        #(#member_key_seq)*
+                        Ok(())
+                    }
+
+                    fn deserialize_nested(deserializer: &mut dust_dds::xtypes::xcdr::XcdrDeserializer<'_>) -> dust_dds::xtypes::error::XTypesResult<Self> {
+                        #deserialize_members_quote
                     }
                 }
-            }];
-            let mut has_default = false;
-            let mut variant_sample_seq = Vec::new();
-            let mut variant_dynamic_sample_seq = Vec::new();
-
-            for (variant_index, variant) in xtypes_union.variants.iter().enumerate() {
-                let variant_attributes = get_union_variant_attributes(variant)?;
-                let variant_index_unsuffixed = syn::Index::from(variant_index + 1);
-                let is_default_label = variant_attributes.is_default;
-
-                if !has_default && variant_attributes.is_default {
-                    has_default = true;
+            });
+                Ok((
+                    get_type_quote,
+                    create_dynamic_sample_quote,
+                    create_sample_quote,
+                    xcdr_quote,
+                ))
+            }
+            // Separate between Unions and Enumeration which are both
+            // mapped as Rust enum types
+            syn::Data::Enum(xtypes_union) if is_enum_xtypes_union(xtypes_union) => {
+                let union_attributes = get_union_type_attributes(input)?;
+                let discriminator_type = union_attributes.discriminator_type;
+                let type_name = union_attributes.name.as_str();
+                let extensibility_kind = match union_attributes.extensibility {
+                    Extensibility::Final => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final}
+                    }
+                    Extensibility::Appendable => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Appendable}
+                    }
+                    Extensibility::Mutable => {
+                        quote! {dust_dds::xtypes::dynamic_type::ExtensibilityKind::Mutable}
+                    }
+                };
+                let is_nested = union_attributes.is_nested;
+                if xtypes_union.variants.len() > (u32::MAX as usize + 1) {
+                    return Err(syn::Error::new(
+                        input.span(),
+                        "Union can hold at most `u32::MAX + 1` variants",
+                    ));
                 }
-                let case_list = if variant_attributes.case.is_empty() {
-                    vec![parse_quote!(#variant_index_unsuffixed)]
+
+                let union_descriptor = quote! {
+                    &dust_dds::xtypes::dynamic_type::TypeDescriptor {
+                        kind: dust_dds::xtypes::dynamic_type::TypeKind::UNION,
+                        name: #type_name,
+                        base_type: None,
+                        discriminator_type: ::core::option::Option::Some(<#discriminator_type as ::dust_dds::xtypes::type_support::Type>::TYPE),
+                        bound: &[],
+                        element_type: None,
+                        key_element_type: None,
+                        extensibility_kind: #extensibility_kind,
+                        is_nested: #is_nested,
+                    }
+                };
+
+                let is_key = union_attributes.is_discriminator_key;
+                let mut variant_list: Vec<TokenStream> = vec![quote! {
+                     dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                        descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
+                            name: "discriminator",
+                            id: 0u32,
+                            r#type: <#discriminator_type as dust_dds::xtypes::type_support::Type>::TYPE,
+                            default_value: None,
+                            index: 0u32,
+                            try_construct_kind: dust_dds::xtypes::dynamic_type::TryConstructKind::Discard,
+                            label: &[],
+                            is_key: #is_key,
+                            is_optional: false,
+                            is_must_understand: true,
+                            is_shared: false,
+                            is_default_label: false,
+                            is_external: false,
+                        }
+                    }
+                }];
+                let mut has_default = false;
+                let mut variant_sample_seq = Vec::new();
+                let mut variant_dynamic_sample_seq = Vec::new();
+                let mut variant_serialize_seq = Vec::new();
+                let mut variant_deserialize_seq = Vec::new();
+                let mut default_variant_deserialize = quote! {
+                    _ => return Err(dust_dds::xtypes::error::XTypesError::InvalidData),
+                };
+                let is_mutable = union_attributes.extensibility == Extensibility::Mutable;
+                let serialize_discriminator = if is_mutable {
+                    quote! {serializer.serialize_mutable_member(0, true, &discriminator)?;}
+                } else {
+                    quote! {dust_dds::xtypes::xcdr::XcdrSerialize::serialize(&discriminator, serializer)?;}
+                };
+                let aggregated_type_binding = if is_mutable {
+                    quote! {mut aggregated_type}
+                } else {
+                    quote! {aggregated_type}
+                };
+                let deserialize_discriminator = if is_mutable {
+                    quote! {deserializer.deserialize_next_mutable_member(&mut aggregated_type, 0)?}
                 } else {
-                    variant_attributes.case
+                    quote! {dust_dds::xtypes::xcdr::XcdrDeserialize::deserialize(deserializer)?}
                 };
-                let first_discriminator = case_list[0].clone();
-                let variant_ident = &variant.ident;
-                let variant_name = variant_ident.to_string();
-
-                match &variant.fields {
-                    // If there is a single field we handle this as the single type wrapper which is the most common case
-                    Fields::Named(fields_named) if fields_named.named.len() == 1 => {
-                        let variant_field_name =
-                            fields_named.named[0].ident.as_ref().ok_or(syn::Error::new(
-                                fields_named.span(),
-                                "Field of named variant must have defined name",
-                            ))?;
-                        let variant_ty = &fields_named.named[0].ty;
-
-                        variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+
+                for (variant_index, variant) in xtypes_union.variants.iter().enumerate() {
+                    let variant_attributes = get_union_variant_attributes(variant)?;
+                    let variant_index_unsuffixed = syn::Index::from(variant_index + 1);
+                    let is_default_label = variant_attributes.is_default;
+
+                    if !has_default && variant_attributes.is_default {
+                        has_default = true;
+                    }
+                    let case_list = if variant_attributes.case.is_empty() {
+                        vec![parse_quote!(#variant_index_unsuffixed)]
+                    } else {
+                        variant_attributes.case
+                    };
+                    let first_discriminator = case_list[0].clone();
+                    let variant_ident = &variant.ident;
+                    let variant_name = variant_ident.to_string();
+                    let (serialize_variant_value, deserialize_variant_value) = if is_mutable {
+                        (
+                            quote! {serializer.serialize_mutable_member(#variant_index_unsuffixed, false, value)?;},
+                            quote! {deserializer.deserialize_next_mutable_member(&mut aggregated_type, #variant_index_unsuffixed)?},
+                        )
+                    } else {
+                        (
+                            quote! {dust_dds::xtypes::xcdr::XcdrSerialize::serialize(value, serializer)?;},
+                            quote! {dust_dds::xtypes::xcdr::XcdrDeserialize::deserialize(deserializer)?},
+                        )
+                    };
+                    let (variant_pattern, variant_value) = match &variant.fields {
+                        Fields::Named(fields_named) if fields_named.named.len() == 1 => {
+                            let variant_field_name = &fields_named.named[0].ident;
+                            (
+                                quote! {Self::#variant_ident {#variant_field_name: value}},
+                                quote! {Self::#variant_ident {#variant_field_name: #deserialize_variant_value}},
+                            )
+                        }
+                        Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => (
+                            quote! {Self::#variant_ident(value)},
+                            quote! {Self::#variant_ident(#deserialize_variant_value)},
+                        ),
+                        _ => (quote! {Self::#variant_ident}, quote! {Self::#variant_ident}),
+                    };
+                    let serialize_variant_value = match &variant.fields {
+                        Fields::Unit => quote! {},
+                        _ => serialize_variant_value,
+                    };
+                    variant_serialize_seq.push(quote! {
+                        #variant_pattern => {
+                            let discriminator: #discriminator_type = #first_discriminator;
+                            #serialize_discriminator
+                            #serialize_variant_value
+                        }
+                    });
+                    if variant_attributes.is_default {
+                        default_variant_deserialize = quote! {_ => #variant_value,};
+                    } else {
+                        variant_deserialize_seq.push(quote! {#(#case_list)|* => #variant_value,});
+                    }
+
+                    match &variant.fields {
+                        // If there is a single field we handle this as the single type wrapper which is the most common case
+                        Fields::Named(fields_named) if fields_named.named.len() == 1 => {
+                            let variant_field_name =
+                                fields_named.named[0].ident.as_ref().ok_or(syn::Error::new(
+                                    fields_named.span(),
+                                    "Field of named variant must have defined name",
+                                ))?;
+                            let variant_ty = &fields_named.named[0].ty;
+
+                            variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
                             descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
                                 name: #variant_name,
                                 id: #variant_index_unsuffixed as u32,
@@ -417,27 +655,27 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                             }
                         }
                         });
-                        let variant_sample = quote! {
-                            Self::#variant_ident {#variant_field_name: <#variant_ty as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
-                              src.remove_value(#variant_index_unsuffixed as u32).expect("Must exist"),
-                            ).expect("Must match")},
-                        };
+                            let variant_sample = quote! {
+                                Self::#variant_ident {#variant_field_name: <#variant_ty as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
+                                  src.remove_value(#variant_index_unsuffixed as u32).expect("Must exist"),
+                                ).expect("Must match")},
+                            };
 
-                        variant_sample_seq.push(if variant_attributes.is_default {
-                            quote! {_ => #variant_sample}
-                        } else {
-                            quote! {#first_discriminator => #variant_sample}
-                        });
-                        variant_dynamic_sample_seq
+                            variant_sample_seq.push(if variant_attributes.is_default {
+                                quote! {_ => #variant_sample}
+                            } else {
+                                quote! {#first_discriminator => #variant_sample}
+                            });
+                            variant_dynamic_sample_seq
                             .push(quote! {Self::#variant_ident {#variant_field_name} => {
                                 data.set_value(0, <#discriminator_type as ::dust_dds::xtypes::data_storage::DataStorageMapping>::into_storage(#first_discriminator));
                                 data.set_value(#variant_index_unsuffixed as u32, ::dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(#variant_field_name));
                             }});
-                    }
-                    Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
-                        let variant_ty = &fields_unnamed.unnamed[0].ty;
+                        }
+                        Fields::Unnamed(fields_unnamed) if fields_unnamed.unnamed.len() == 1 => {
+                            let variant_ty = &fields_unnamed.unnamed[0].ty;
 
-                        variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                            variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
                             descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
                                 name: #variant_name,
                                 id: #variant_index_unsuffixed as u32,
@@ -455,25 +693,25 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                             }
                         }
                         });
-                        let variant_sample = quote! {
-                            Self::#variant_ident(<#variant_ty as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
-                              src.remove_value(#variant_index_unsuffixed as u32).ok()?
-                            ).ok()?),
-                        };
+                            let variant_sample = quote! {
+                                Self::#variant_ident(<#variant_ty as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
+                                  src.remove_value(#variant_index_unsuffixed as u32).ok()?
+                                ).ok()?),
+                            };
 
-                        variant_sample_seq.push(if variant_attributes.is_default {
-                            quote! {_ => #variant_sample}
-                        } else {
-                            quote! {#first_discriminator => #variant_sample}
-                        });
-                        variant_dynamic_sample_seq
+                            variant_sample_seq.push(if variant_attributes.is_default {
+                                quote! {_ => #variant_sample}
+                            } else {
+                                quote! {#first_discriminator => #variant_sample}
+                            });
+                            variant_dynamic_sample_seq
                             .push(quote! {Self::#variant_ident (a) => {
                                 data.set_value(0, <#discriminator_type as ::dust_dds::xtypes::data_storage::DataStorageMapping>::into_storage(#first_discriminator));
                                 data.set_value(#variant_index_unsuffixed as u32, ::dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(a));
                             }});
-                    }
-                    Fields::Unit => {
-                        variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                        }
+                        Fields::Unit => {
+                            variant_list.push(quote!{ dust_dds::xtypes::dynamic_type::DynamicTypeMember {
                             descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
                                 name: #variant_name,
                                 id: #variant_index_unsuffixed as u32,
@@ -504,143 +742,251 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                             }
                         }
                         });
-                        let variant_sample = quote! {
-                            Self::#variant_ident,
-                        };
+                            let variant_sample = quote! {
+                                Self::#variant_ident,
+                            };
 
-                        variant_sample_seq.push(if variant_attributes.is_default {
-                            quote! {_ => #variant_sample}
-                        } else {
-                            quote! {#first_discriminator => #variant_sample}
-                        });
-                        variant_dynamic_sample_seq.push(quote! {Self::#variant_ident => {
+                            variant_sample_seq.push(if variant_attributes.is_default {
+                                quote! {_ => #variant_sample}
+                            } else {
+                                quote! {#first_discriminator => #variant_sample}
+                            });
+                            variant_dynamic_sample_seq.push(quote! {Self::#variant_ident => {
                             data.set_value(0, <#discriminator_type as ::dust_dds::xtypes::data_storage::DataStorageMapping>::into_storage(#first_discriminator));
                         },});
-                    }
-                    Fields::Named(_) | Fields::Unnamed(_) => {
-                        return Err(syn::Error::new(
-                            variant.span(),
-                            "Only variants with a single field are supported",
-                        ));
+                        }
+                        Fields::Named(_) | Fields::Unnamed(_) => {
+                            return Err(syn::Error::new(
+                                variant.span(),
+                                "Only variants with a single field are supported",
+                            ));
+                        }
                     }
                 }
-            }
 
-            if !has_default {
-                variant_sample_seq.push(quote! {_ => return None,});
+                if !has_default {
+                    variant_sample_seq.push(quote! {_ => return None,});
+                }
+
+                let get_type_quote = quote! {
+                    const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
+                        dust_dds::xtypes::dynamic_type::DynamicType {
+                            descriptor: #union_descriptor,
+                            member_list: &[#(#variant_list,)*]
+                        };
+                };
+
+                let create_dynamic_sample_quote = quote! {
+                    match self {
+                        #(#variant_dynamic_sample_seq)*
+                    }
+                };
+
+                let create_sample_quote = quote! {
+                    let disc =
+                        <#discriminator_type as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
+                            src.remove_value(0).ok()?,
+                        )
+                        .ok()?;
+                    Some(match disc {
+                        #(#variant_sample_seq)*
+                    })
+                };
+
+                let xcdr_quote = quote! {
+                    fn serialize_nested(&self, serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        let aggregated_type = serializer.begin_aggregated_type(#extensibility_kind);
+                        match self {
+                            #(#variant_serialize_seq)*
+                        }
+                        serializer.end_aggregated_type(aggregated_type);
+                        Ok(())
+                    }
+
+                    fn serialize_key_members(&self, _serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        Ok(())
+                    }
+
+                    fn deserialize_nested(deserializer: &mut dust_dds::xtypes::xcdr::XcdrDeserializer<'_>) -> dust_dds::xtypes::error::XTypesResult<Self> {
+                        let #aggregated_type_binding = deserializer.begin_aggregated_type(#extensibility_kind)?;
+                        let discriminator: #discriminator_type = #deserialize_discriminator;
+                        let value = match discriminator {
+                            #(#variant_deserialize_seq)*
+                            #default_variant_deserialize
+                        };
+                        deserializer.end_aggregated_type(aggregated_type)?;
+                        Ok(value)
+                    }
+                };
+                Ok((
+                    get_type_quote,
+                    create_dynamic_sample_quote,
+                    create_sample_quote,
+                    Some(xcdr_quote),
+                ))
             }
+            syn::Data::Enum(xtypes_enum) => {
+                let enum_type_attributes = get_enumerated_type_attributes(input)?;
+                let type_name = enum_type_attributes.name;
+                let is_nested = enum_type_attributes.is_nested;
+                // Note: Mapping has to be done with a match self strategy because the enum might not be copy so casting it using e.g. "self as i64" would
+                // be consuming it.
+                let discriminator_type = match enum_type_attributes.bit_bound {
+                    BitBound::I8 => {
+                        quote! {<i8 as dust_dds::xtypes::type_support::Type>::TYPE}
+                    }
+                    BitBound::I16 => {
+                        quote! {<i16 as dust_dds::xtypes::type_support::Type>::TYPE}
+                    }
+                    BitBound::I32 => {
+                        quote! {<i32 as dust_dds::xtypes::type_support::Type>::TYPE}
+                    }
+                };
 
-            let get_type_quote = quote! {
-                const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
-                    dust_dds::xtypes::dynamic_type::DynamicType {
-                        descriptor: #union_descriptor,
-                        member_list: &[#(#variant_list,)*]
-                    };
-            };
+                let discriminator_dynamic_value = match enum_type_attributes.bit_bound {
+                    BitBound::I8 => quote! {data.set_int8_value(0, self as i8).unwrap();},
+                    BitBound::I16 => quote! {data.set_int16_value(0, self as i16).unwrap();},
+                    BitBound::I32 => quote! {data.set_int32_value(0, self as i32).unwrap();},
+                };
 
-            let create_dynamic_sample_quote = quote! {
-                match self {
-                    #(#variant_dynamic_sample_seq)*
-                }
-            };
+                let discriminator_sample = match enum_type_attributes.bit_bound {
+                    BitBound::I8 => quote! {*src.get_int8_value(0).ok()?},
+                    BitBound::I16 => quote! {*src.get_int16_value(0).ok()?},
+                    BitBound::I32 => quote! {*src.get_int32_value(0).ok()?},
+                };
 
-            let create_sample_quote = quote! {
-                let disc =
-                    <#discriminator_type as ::dust_dds::xtypes::data_storage::DataStorageMapping>::try_from_storage(
-                        src.remove_value(0).ok()?,
-                    )
-                    .ok()?;
-                Some(match disc {
-                    #(#variant_sample_seq)*
-                })
-            };
-            Ok((
-                get_type_quote,
-                create_dynamic_sample_quote,
-                create_sample_quote,
-            ))
-        }
-        syn::Data::Enum(xtypes_enum) => {
-            let enum_type_attributes = get_enumerated_type_attributes(input)?;
-            let type_name = enum_type_attributes.name;
-            let is_nested = enum_type_attributes.is_nested;
-            // Note: Mapping has to be done with a match self strategy because the enum might not be copy so casting it using e.g. "self as i64" would
-            // be consuming it.
-            let discriminator_type = match enum_type_attributes.bit_bound {
-                BitBound::I8 => {
-                    quote! {<i8 as dust_dds::xtypes::type_support::Type>::TYPE}
-                }
-                BitBound::I16 => {
-                    quote! {<i16 as dust_dds::xtypes::type_support::Type>::TYPE}
+                let enum_descriptor = quote! {
+                    &dust_dds::xtypes::dynamic_type::TypeDescriptor {
+                        kind: dust_dds::xtypes::dynamic_type::TypeKind::ENUM,
+                        name: #type_name,
+                        base_type: None,
+                        discriminator_type: Some(#discriminator_type),
+                        bound: &[],
+                        element_type: None,
+                        key_element_type: None,
+                        extensibility_kind: dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final,
+                        is_nested: #is_nested,
+                    }
+                };
+                let enum_variant_mapping = read_enum_variant_discriminant_mapping(xtypes_enum);
+                let mut enumerator_list = Vec::new();
+                for (index, (variant_ident, variant_discriminant)) in
+                    enum_variant_mapping.iter().enumerate()
+                {
+                    let enumerator_name = variant_ident.to_string();
+                    let enumerator_value = Index::from(*variant_discriminant);
+                    enumerator_list.push(quote! {
+                     dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                        descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
+                            name: #enumerator_name,
+                            id: #index as u32,
+                            r#type: #discriminator_type,
+                            default_value: None,
+                            index: #index as u32,
+                            try_construct_kind: dust_dds::xtypes::dynamic_type::TryConstructKind::Discard,
+                            label: &[#enumerator_value as i32],
+                            is_key: false,
+                            is_optional: false,
+                            is_must_understand: false,
+                            is_shared: false,
+                            is_default_label: false,
+                            is_external: false,
+                        }
+                    }
+                });
                 }
-                BitBound::I32 => {
-                    quote! {<i32 as dust_dds::xtypes::type_support::Type>::TYPE}
+                let get_type_quote = quote! {
+                    const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
+                        dust_dds::xtypes::dynamic_type::DynamicType {
+                            descriptor: #enum_descriptor,
+                            member_list: &[#(#enumerator_list,)*]
+                        };
+                };
+
+                let create_dynamic_sample_quote = quote! {
+                    #discriminator_dynamic_value
+                };
+                let mut create_sample_quote_variants = Vec::new();
+                for (variant_ident, variant_discriminant) in enum_variant_mapping {
+                    let d = Index::from(variant_discriminant);
+                    create_sample_quote_variants.push(quote! {#d => Self::#variant_ident,});
                 }
-            };
+                let create_sample_quote = quote! {
+                        let discriminator = #discriminator_sample;
+                        Some(match discriminator {
+                            #(#create_sample_quote_variants)*
+                            _ => return None,
+                        })
+                };
 
-            let discriminator_dynamic_value = match enum_type_attributes.bit_bound {
-                BitBound::I8 => quote! {data.set_int8_value(0, self as i8).unwrap();},
-                BitBound::I16 => quote! {data.set_int16_value(0, self as i16).unwrap();},
-                BitBound::I32 => quote! {data.set_int32_value(0, self as i32).unwrap();},
-            };
+                let holder_type = match enum_type_attributes.bit_bound {
+                    BitBound::I8 => quote! {i8},
+                    BitBound::I16 => quote! {i16},
+                    BitBound::I32 => quote! {i32},
+                };
+                let mut serialize_quote_variants = Vec::new();
+                let mut deserialize_quote_variants = Vec::new();
+                for (variant_ident, variant_discriminant) in
+                    read_enum_variant_discriminant_mapping(xtypes_enum)
+                {
+                    let d = Index::from(variant_discriminant);
+                    serialize_quote_variants
+                        .push(quote! {Self::#variant_ident => #d as #holder_type,});
+                    deserialize_quote_variants.push(quote! {#d => Self::#variant_ident,});
+                }
+                let xcdr_quote = quote! {
+                    fn serialize_nested(&self, serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        let discriminator: #holder_type = match self {
+                            #(#serialize_quote_variants)*
+                        };
+                        dust_dds::xtypes::xcdr::XcdrSerialize::serialize(&discriminator, serializer)
+                    }
 
-            let discriminator_sample = match enum_type_attributes.bit_bound {
-                BitBound::I8 => quote! {*src.get_int8_value(0).ok()?},
-                BitBound::I16 => quote! {*src.get_int16_value(0).ok()?},
-                BitBound::I32 => quote! {*src.get_int32_value(0).ok()?},
-            };
+                    fn serialize_key_members(&self, _serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+                        Ok(())
+                    }
 
-            let enum_descriptor = quote! {
-                &dust_dds::xtypes::dynamic_type::TypeDescriptor {
-                    kind: dust_dds::xtypes::dynamic_type::TypeKind::ENUM,
-                    name: #type_name,
-                    base_type: None,
-                    discriminator_type: Some(#discriminator_type),
-                    bound: &[],
-                    element_type: None,
-                    key_element_type: None,
-                    extensibility_kind: dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final,
-                    is_nested: #is_nested,
-                }
-            };
-            let get_type_quote = quote! {
-                const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
-                    dust_dds::xtypes::dynamic_type::DynamicType {
-                        descriptor: #enum_descriptor,
-                        member_list: &[]
-                    };
-            };
+                    fn deserialize_nested(deserializer: &mut dust_dds::xtypes::xcdr::XcdrDeserializer<'_>) -> dust_dds::xtypes::error::XTypesResult<Self> {
+                        let discriminator: #holder_type = dust_dds::xtypes::xcdr::XcdrDeserialize::deserialize(deserializer)?;
+                        Ok(match discriminator {
+                            #(#deserialize_quote_variants)*
+                            _ => return Err(dust_dds::xtypes::error::XTypesError::InvalidData),
+                        })
+                    }
+                };
 
-            let create_dynamic_sample_quote = quote! {
-                #discriminator_dynamic_value
-            };
-            let enum_variant_mapping = read_enum_variant_discriminant_mapping(xtypes_enum);
-            let mut create_sample_quote_variants = Vec::new();
-            for (variant_ident, variant_discriminant) in enum_variant_mapping {
-                let d = Index::from(variant_discriminant);
-                create_sample_quote_variants.push(quote! {#d => Self::#variant_ident,});
+                Ok((
+                    get_type_quote,
+                    create_dynamic_sample_quote,
+                    create_sample_quote,
+                    Some(xcdr_quote),
+                ))
             }
-            let create_sample_quote = quote! {
-                    let discriminator = #discriminator_sample;
-                    Some(match discriminator {
-                        #(#create_sample_quote_variants)*
-                        _ => return None,
-                    })
-            };
+            syn::Data::Union(data_union) => Err(syn::Error::new(
+                data_union.union_token.span,
+                "Rust union not supported in Dust DDS. For IDL union mapping use enum with types in variants.",
+            )),
+        }?;
 
-            Ok((
-                get_type_quote,
-                create_dynamic_sample_quote,
-                create_sample_quote,
-            ))
-        }
-        syn::Data::Union(data_union) => Err(syn::Error::new(
-            data_union.union_token.span,
-            "Rust union not supported in Dust DDS. For IDL union mapping use enum with types in variants.",
-        )),
-    }?;
+    Ok(type_support_impl(
+        input,
+        get_type_quote,
+        create_dynamic_sample_quote,
+        create_sample_quote,
+        xcdr_quote,
+    ))
+}
 
-    Ok(quote! {
+fn type_support_impl(
+    input: &DeriveInput,
+    get_type_quote: TokenStream,
+    create_dynamic_sample_quote: TokenStream,
+    create_sample_quote: TokenStream,
+    xcdr_quote: Option<TokenStream>,
+) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
         #[automatically_derived]
         impl #impl_generics dust_dds::xtypes::type_support::TypeSupport for #ident #type_generics #where_clause {
             fn create_sample(src: &mut dust_dds::xtypes::dynamic_type::DynamicData) -> ::core::option::Option<Self> {
@@ -652,13 +998,191 @@ pub fn expand_type_support(input: &DeriveInput) -> Result<TokenStream> {
                 #create_dynamic_sample_quote
                 data
             }
+
+            #xcdr_quote
         }
 
         #[automatically_derived]
         impl #impl_generics dust_dds::xtypes::type_support::Type for #ident #type_generics #where_clause {
             #get_type_quote
         }
-    })
+    }
+}
+
+// The bitfields of a bitset are packed in a single integer, each of them starting at its position
+// which is also used as the member id of the bitfield.
+fn expand_bitset_type_support(
+    input: &DeriveInput,
+    xtypes_struct: &syn::DataStruct,
+    r#struct: &StructAttributes,
+) -> Result<TokenStream> {
+    if r#struct.base_type.is_some() {
+        return Err(syn::Error::new(
+            input.span(),
+            "Bitset inheritance is not supported",
+        ));
+    }
+    let type_name = r#struct.name.as_str();
+    let is_nested = r#struct.is_nested;
+    let mut member_list = Vec::new();
+    let mut bound_list = Vec::new();
+    let mut member_sample_seq = Vec::new();
+    let mut member_dynamic_sample_seq = Vec::new();
+    let mut member_serialize_seq = Vec::new();
+    let mut member_deserialize_seq = Vec::new();
+    let mut holder_check_seq = Vec::new();
+    let mut next_position = 0u32;
+    for (member_index, member) in xtypes_struct.fields.iter().enumerate() {
+        let member_attributes = get_structure_member_attributes(member)?;
+        if member_attributes.id.is_some()
+            || member_attributes.key
+            || member_attributes.optional
+            || member_attributes.non_serialized
+            || member_attributes.external
+            || member_attributes.hashid
+            || member_attributes.default_value.is_some()
+            || member_attributes.try_construct.is_some()
+        {
+            return Err(syn::Error::new(
+                member.span(),
+                "Bitfields only support the #[dust_dds(bits)] and #[dust_dds(position)] attributes",
+            ));
+        }
+        let bits = member_attributes.bits.ok_or_else(|| {
+            syn::Error::new(
+                member.span(),
+                "Bitfields require the number of bits with #[dust_dds(bits = N)]",
+            )
+        })?;
+        let position = member_attributes.position.unwrap_or(next_position);
+        if bits == 0 || position.saturating_add(bits) > 64 {
+            return Err(syn::Error::new(
+                member.span(),
+                "Bitfields must have at least 1 bit and fit in the 64 bits of a bitset",
+            ));
+        }
+        next_position = position + bits;
+
+        let member_name = member
+            .ident
+            .as_ref()
+            .map(|i| i.to_string())
+            .unwrap_or(member_index.to_string());
+        let member_type = &member.ty;
+        let index = member_index as u32;
+        member_list.push(quote! {
+             dust_dds::xtypes::dynamic_type::DynamicTypeMember {
+                descriptor: dust_dds::xtypes::dynamic_type::MemberDescriptor {
+                    name: #member_name,
+                    id: #position,
+                    r#type: <#member_type as dust_dds::xtypes::type_support::Type>::TYPE,
+                    default_value: None,
+                    index: #index,
+                    try_construct_kind: dust_dds::xtypes::dynamic_type::TryConstructKind::Discard,
+                    label: &[],
+                    is_key: false,
+                    is_optional: false,
+                    is_must_understand: false,
+                    is_shared: false,
+                    is_default_label: false,
+                    is_external: false,
+                }
+            }
+        });
+        bound_list.push(bits);
+
+        let (member_access, member_field) = match &member.ident {
+            Some(member_ident) => (quote! {self.#member_ident}, quote! {#member_ident:}),
+            None => {
+                let index = Index::from(member_index);
+                (quote! {self.#index}, quote! {})
+            }
+        };
+        member_sample_seq.push(quote! {
+            #member_field dust_dds::xtypes::data_storage::DataStorageMapping::try_from_storage(src.remove_value(#position).ok()?).ok()?,
+        });
+        member_dynamic_sample_seq.push(quote! {
+            data.set_value(#position, dust_dds::xtypes::data_storage::DataStorageMapping::into_storage(#member_access));
+        });
+        let mask = if bits == 64 {
+            u64::MAX
+        } else {
+            (1u64 << bits) - 1
+        };
+        member_serialize_seq.push(quote! {
+            bits |= (dust_dds::xtypes::type_support::BitfieldHolder::to_bits(&#member_access) & #mask) << #position;
+        });
+        member_deserialize_seq.push(quote! {
+            #member_field dust_dds::xtypes::type_support::BitfieldHolder::from_bits(bits >> #position, #bits),
+        });
+        let holder_check_message = format!("bitfield `{member_name}` has more bits than its type");
+        holder_check_seq.push(quote! {
+            const _: () = assert!(#bits <= <#member_type as dust_dds::xtypes::type_support::BitfieldHolder>::BITS, #holder_check_message);
+        });
+    }
+
+    let get_type_quote = quote! {
+        const TYPE: dust_dds::xtypes::dynamic_type::DynamicType<'static> =
+            dust_dds::xtypes::dynamic_type::DynamicType {
+                descriptor: &dust_dds::xtypes::dynamic_type::TypeDescriptor {
+                    kind: dust_dds::xtypes::dynamic_type::TypeKind::BITSET,
+                    name: #type_name,
+                    base_type: None,
+                    discriminator_type: None,
+                    bound: &[#(#bound_list),*],
+                    element_type: None,
+                    key_element_type: None,
+                    extensibility_kind: dust_dds::xtypes::dynamic_type::ExtensibilityKind::Final,
+                    is_nested: #is_nested,
+                },
+                member_list: &[#(#member_list,)*]
+            };
+    };
+    let is_tuple = matches!(xtypes_struct.fields, Fields::Unnamed(_));
+    let (create_sample_quote, deserialize_value) = if is_tuple {
+        (
+            quote! {Some(Self(#(#member_sample_seq)*))},
+            quote! {Self(#(#member_deserialize_seq)*)},
+        )
+    } else {
+        (
+            quote! {Some(Self{#(#member_sample_seq)*})},
+            quote! {Self{#(#member_deserialize_seq)*}},
+        )
+    };
+    // The bitset is transmitted as the smallest unsigned integer holding all its bits
+    let holder_type = match next_position {
+        0..=8 => quote! {u8},
+        9..=16 => quote! {u16},
+        17..=32 => quote! {u32},
+        _ => quote! {u64},
+    };
+    let xcdr_quote = quote! {
+        fn serialize_nested(&self, serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+            let mut bits = 0u64;
+            #(#member_serialize_seq)*
+            dust_dds::xtypes::xcdr::XcdrSerialize::serialize(&(bits as #holder_type), serializer)
+        }
+
+        fn serialize_key_members(&self, _serializer: &mut dust_dds::xtypes::xcdr::XcdrSerializer) -> dust_dds::xtypes::error::XTypesResult<()> {
+            Ok(())
+        }
+
+        fn deserialize_nested(deserializer: &mut dust_dds::xtypes::xcdr::XcdrDeserializer<'_>) -> dust_dds::xtypes::error::XTypesResult<Self> {
+            let bits = <#holder_type as dust_dds::xtypes::xcdr::XcdrDeserialize>::deserialize(deserializer)? as u64;
+            Ok(#deserialize_value)
+        }
+    };
+
+    let mut output = type_support_impl(
+        input,
+        get_type_quote,
+        quote! {#(#member_dynamic_sample_seq)*},
+        create_sample_quote,
+        Some(xcdr_quote),
+    );
+    output.extend(holder_check_seq);
+    Ok(output)
 }
 
 pub fn is_enum_xtypes_union(data_enum: &DataEnum) -> bool {
@@ -97,6 +97,7 @@ impl<'a> CGenerator<'a> {
             Rule::boolean_type => self.boolean(pair),
             Rule::octet_type => self.octet_type(pair),
             Rule::template_type_spec => self.template_type_spec(pair),
+            Rule::map_type => self.map_type(pair),
             Rule::sequence_type => todo!(),
             Rule::string_type => self.string_type(pair),
             Rule::wide_string_type => self.wide_string_type(pair),
@@ -228,11 +229,10 @@ impl<'a> CGenerator<'a> {
             Rule::actual_parameter => todo!(),
             Rule::template_module_ref => todo!(),
             Rule::formal_parameter_names => todo!(),
-            Rule::map_type => todo!(),
-            Rule::bitset_dcl => todo!(),
-            Rule::bitfield => todo!(),
-            Rule::bitfield_spec => todo!(),
-            Rule::destination_type => todo!(),
+            Rule::bitset_dcl => self.bitset_dcl(pair),
+            Rule::bitfield => (),         // Handled inside bitset_dcl
+            Rule::bitfield_spec => (),    // Handled inside bitset_dcl
+            Rule::destination_type => (), // Handled inside bitset_dcl
             Rule::bitmask_dcl => todo!(),
             Rule::bit_value => todo!(),
             Rule::annotation_dcl => todo!(),
@@ -428,8 +428,10 @@ impl<'a> CGenerator<'a> {
         // Emit add_member calls using DDS_MemberDescriptor
         for (member_id, type_spec, field_name, is_key, is_optional) in &members {
             let type_expr = self.get_dynamic_type_expr(type_spec.clone());
-            let needs_type_var =
-                type_expr.contains("create_string_type") || type_expr.contains("_get_type");
+            let needs_type_var = type_expr.contains("create_string_type")
+                || type_expr.contains("create_wstring_type")
+                || type_expr.contains("create_map_type")
+                || type_expr.contains("_get_type");
 
             self.writer.push_str("            {\n");
             let type_val = if needs_type_var {
@@ -511,11 +513,11 @@ impl<'a> CGenerator<'a> {
                 }
                 Rule::wide_char_type => {
                     create_sample_code.push_str(&format!(
-                        "        {{\n            char temp;\n            DDS_DynamicData_get_char8_value(src, {}, &temp);\n            sample.{} = (wchar_t)temp;\n        }}\n",
+                        "        {{\n            uint16_t temp;\n            DDS_DynamicData_get_char16_value(src, {}, &temp);\n            sample.{} = (wchar_t)temp;\n        }}\n",
                         member_id, field_name
                     ));
                     create_dynamic_sample_code.push_str(&format!(
-                        "            DDS_DynamicData_set_char8_value(sample, {}, (char)src->{});\n",
+                        "            DDS_DynamicData_set_char16_value(sample, {}, (uint16_t)src->{});\n",
                         member_id, field_name
                     ));
                 }
@@ -647,6 +649,48 @@ impl<'a> CGenerator<'a> {
                     ));
                     free_sample_code.push_str(&format!("        free(sample->{});\n", field_name));
                 }
+                Rule::map_type => {
+                    let map_type = type_spec
+                        .clone()
+                        .into_inner()
+                        .flatten()
+                        .find(|p| p.as_rule() == Rule::map_type)
+                        .expect("Map type must exist");
+                    let mut type_specs = map_type
+                        .into_inner()
+                        .filter(|p| p.as_rule() == Rule::type_spec);
+                    let key_type_spec = self.resolve_type_spec(
+                        type_specs
+                            .next()
+                            .expect("Must have a key type_spec according to the grammar"),
+                    );
+                    let value_type_spec = self.resolve_type_spec(
+                        type_specs
+                            .next()
+                            .expect("Must have a value type_spec according to the grammar"),
+                    );
+                    let (get_key, set_key, free_key) =
+                        self.map_entry_code(key_type_spec, 0, &format!("{}.keys[i]", field_name));
+                    let (get_value, set_value, free_value) = self.map_entry_code(
+                        value_type_spec,
+                        1,
+                        &format!("{}.values[i]", field_name),
+                    );
+                    create_sample_code.push_str(&format!(
+                        "        {{\n            uint32_t length = 0;\n            DDS_DynamicData_get_map_length(src, {member_id}, &length);\n            sample.{field_name}.length = length;\n            sample.{field_name}.keys = calloc(length, sizeof(*sample.{field_name}.keys));\n            sample.{field_name}.values = calloc(length, sizeof(*sample.{field_name}.values));\n            for (uint32_t i = 0; i < length; i++) {{\n                DDS_DynamicData* entry = NULL;\n                DDS_DynamicData_get_map_entry(src, {member_id}, i, &entry);\n                if (entry != NULL) {{\n{get_key}{get_value}                    DDS_DynamicData_free(entry);\n                }}\n            }}\n        }}\n",
+                    ));
+                    create_dynamic_sample_code.push_str(&format!(
+                        "            DDS_DynamicData_clear_map(sample, {member_id});\n            for (uint32_t i = 0; i < src->{field_name}.length; i++) {{\n                DDS_DynamicData* entry = DDS_DynamicData_create_map_entry(sample, {member_id});\n{set_key}{set_value}                DDS_DynamicData_add_map_entry(sample, {member_id}, entry);\n                DDS_DynamicData_free(entry);\n            }}\n",
+                    ));
+                    if !free_key.is_empty() || !free_value.is_empty() {
+                        free_sample_code.push_str(&format!(
+                            "        for (uint32_t i = 0; i < sample->{field_name}.length; i++) {{\n{free_key}{free_value}        }}\n",
+                        ));
+                    }
+                    free_sample_code.push_str(&format!(
+                        "        free(sample->{field_name}.keys);\n        free(sample->{field_name}.values);\n",
+                    ));
+                }
                 _ => {
                     // Custom identifier / nested struct
                     let resolved_leaf = self.resolve_type(&leaf_str);
@@ -787,6 +831,59 @@ impl<'a> CGenerator<'a> {
         ));
     }
 
+    /// Returns the code getting, setting and freeing the key (`entry_id` 0) or the
+    /// value (`entry_id` 1) of a map entry stored in `target`.
+    fn map_entry_code(
+        &self,
+        type_spec: IdlPair<'a>,
+        entry_id: u32,
+        target: &str,
+    ) -> (String, String, String) {
+        let (leaf_rule, leaf_str) = self.get_type_leaf(type_spec);
+        let value_kind = match leaf_rule {
+            Rule::boolean_type => "boolean",
+            Rule::char_type => "char8",
+            Rule::octet_type | Rule::unsigned_tiny_int => "uint8",
+            Rule::signed_tiny_int => "int8",
+            Rule::signed_short_int => "int16",
+            Rule::unsigned_short_int => "uint16",
+            Rule::signed_long_int => "int32",
+            Rule::unsigned_long_int => "uint32",
+            Rule::signed_longlong_int => "int64",
+            Rule::unsigned_longlong_int => "uint64",
+            Rule::floating_pt_type if leaf_str == "float" => "float32",
+            Rule::floating_pt_type if leaf_str == "double" => "float64",
+            Rule::string_type => "string",
+            Rule::scoped_name => {
+                let resolved_leaf = self.resolve_type(&leaf_str);
+                return (
+                    format!(
+                        "                    {{\n                        DDS_DynamicData* member_data = NULL;\n                        DDS_DynamicData_get_complex_value(entry, {entry_id}, &member_data);\n                        if (member_data != NULL) {{\n                            sample.{target} = {resolved_leaf}_create_sample(member_data);\n                            DDS_DynamicData_free(member_data);\n                        }}\n                    }}\n",
+                    ),
+                    format!(
+                        "                {{\n                    DDS_DynamicData* member_data = {resolved_leaf}_create_dynamic_sample(&src->{target});\n                    DDS_DynamicData_set_complex_value(entry, {entry_id}, member_data);\n                    DDS_DynamicData_free(member_data);\n                }}\n",
+                    ),
+                    format!("            {resolved_leaf}_free_sample(&sample->{target});\n"),
+                );
+            }
+            _ => todo!("Map entry type not supported in the C mapping"),
+        };
+        let free_code = if value_kind == "string" {
+            format!("            DDS_String_free(sample->{target});\n")
+        } else {
+            String::new()
+        };
+        (
+            format!(
+                "                    DDS_DynamicData_get_{value_kind}_value(entry, {entry_id}, &sample.{target});\n"
+            ),
+            format!(
+                "                DDS_DynamicData_set_{value_kind}_value(entry, {entry_id}, src->{target});\n"
+            ),
+            free_code,
+        )
+    }
+
     fn get_type_leaf(&self, type_spec: IdlPair<'a>) -> (Rule, String) {
         let mut current = type_spec;
         loop {
@@ -831,7 +928,7 @@ impl<'a> CGenerator<'a> {
                     return "DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_CHAR8)".to_string();
                 }
                 Rule::wide_char_type => {
-                    return "DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_CHAR8)".to_string();
+                    return "DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_CHAR16)".to_string();
                 }
                 Rule::octet_type => {
                     return "DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_UINT8)".to_string();
@@ -875,13 +972,41 @@ impl<'a> CGenerator<'a> {
                     }
                     _ => panic!("Invalid floating point type"),
                 },
+                Rule::map_type => {
+                    let inner_pairs = current.into_inner();
+                    let mut type_specs = inner_pairs
+                        .clone()
+                        .filter(|p| p.as_rule() == Rule::type_spec);
+                    let key_type_spec = type_specs
+                        .next()
+                        .expect("Must have a key type_spec according to the grammar");
+                    let value_type_spec = type_specs
+                        .next()
+                        .expect("Must have a value type_spec according to the grammar");
+                    let bound = inner_pairs
+                        .clone()
+                        .find(|p| p.as_rule() == Rule::positive_int_const)
+                        .map(|p| p.as_str().to_string())
+                        .unwrap_or_else(|| "4294967295".to_string());
+                    return format!(
+                        "DDS_DynamicType_create_map_type({}, {}, {})",
+                        self.get_dynamic_type_expr(self.resolve_type_spec(key_type_spec)),
+                        self.get_dynamic_type_expr(self.resolve_type_spec(value_type_spec)),
+                        bound
+                    );
+                }
                 Rule::string_type | Rule::wide_string_type => {
+                    let create_fn = if current.as_rule() == Rule::string_type {
+                        "DDS_DynamicType_create_string_type"
+                    } else {
+                        "DDS_DynamicType_create_wstring_type"
+                    };
                     let bound = current
                         .into_inner()
                         .next()
                         .map(|p| p.as_str().to_string())
                         .unwrap_or_else(|| "4294967295".to_string());
-                    return format!("DDS_DynamicType_create_string_type({})", bound);
+                    return format!("{}({})", create_fn, bound);
                 }
                 _ => {
                     let resolved = self.resolve_type(current.as_str());
@@ -1052,6 +1177,22 @@ impl<'a> CGenerator<'a> {
         )
     }
 
+    fn map_type(&mut self, pair: IdlPair<'a>) {
+        let mut type_specs = pair.into_inner().filter(|p| p.as_rule() == Rule::type_spec);
+        let key_type_spec = type_specs
+            .next()
+            .expect("Must have a key type_spec according to the grammar");
+        let value_type_spec = type_specs
+            .next()
+            .expect("Must have a value type_spec according to the grammar");
+
+        self.writer.push_str("struct { uint32_t length; ");
+        self.generate(key_type_spec);
+        self.writer.push_str("* keys; ");
+        self.generate(value_type_spec);
+        self.writer.push_str("* values; }");
+    }
+
     #[inline]
     fn string_type(&mut self, _pair: IdlPair<'a>) {
         self.writer.push_str("char*")
@@ -1159,6 +1300,208 @@ impl<'a> CGenerator<'a> {
         self.writer.push_str("    };\n");
     }
 
+    fn bitset_dcl(&mut self, pair: IdlPair<'a>) {
+        let inner_pairs = pair.into_inner();
+        let identifier = inner_pairs
+            .clone()
+            .find(|p| p.as_rule() == Rule::identifier)
+            .expect("Must have an identifier according to the grammar");
+        if inner_pairs
+            .clone()
+            .any(|p| p.as_rule() == Rule::scoped_name)
+        {
+            unimplemented!("Bitset inheritance not supported in C mapping")
+        }
+
+        let bitset_name = identifier.as_str();
+        let prefixed_bitset_name = self.current_qualified_name(bitset_name).replace("::", "_");
+        let dds_bitset_name = self.current_qualified_name(bitset_name);
+
+        // Bitsets are handled as structures when used as members
+        self.defined_types
+            .push((dds_bitset_name.clone(), TypeDef::Struct));
+
+        // Each bitfield is (position, bitcount, name, C type, dynamic data value kind, type kind)
+        let mut bitfields = Vec::new();
+        let mut position = 0u32;
+        for bitfield in inner_pairs.filter(|p| p.as_rule() == Rule::bitfield) {
+            let bitfield_inner = bitfield.into_inner();
+            let bitfield_spec = bitfield_inner
+                .clone()
+                .find(|p| p.as_rule() == Rule::bitfield_spec)
+                .expect("Must have a bitfield spec according to the grammar")
+                .into_inner();
+            let bits: u32 = bitfield_spec
+                .clone()
+                .find(|p| p.as_rule() == Rule::positive_int_const)
+                .expect("Must have a positive int const according to the grammar")
+                .as_str()
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| {
+                    unimplemented!("Only literal bitfield widths supported in C mapping")
+                });
+            let holder_rule = match bitfield_spec
+                .clone()
+                .find(|p| p.as_rule() == Rule::destination_type)
+            {
+                Some(destination_type) => {
+                    self.get_type_leaf(
+                        destination_type
+                            .into_inner()
+                            .next()
+                            .expect("Must have an element according to the grammar"),
+                    )
+                    .0
+                }
+                None => match bits {
+                    1 => Rule::boolean_type,
+                    2..=8 => Rule::unsigned_tiny_int,
+                    9..=16 => Rule::unsigned_short_int,
+                    17..=32 => Rule::unsigned_long_int,
+                    _ => Rule::unsigned_longlong_int,
+                },
+            };
+            let (c_type, value_kind, type_kind) = match holder_rule {
+                Rule::boolean_type => ("bool", "boolean", "BOOLEAN"),
+                Rule::octet_type | Rule::unsigned_tiny_int => ("uint8_t", "uint8", "UINT8"),
+                Rule::signed_tiny_int => ("int8_t", "int8", "INT8"),
+                Rule::signed_short_int => ("int16_t", "int16", "INT16"),
+                Rule::unsigned_short_int => ("uint16_t", "uint16", "UINT16"),
+                Rule::signed_long_int => ("int32_t", "int32", "INT32"),
+                Rule::unsigned_long_int => ("uint32_t", "uint32", "UINT32"),
+                Rule::signed_longlong_int => ("int64_t", "int64", "INT64"),
+                Rule::unsigned_longlong_int => ("uint64_t", "uint64", "UINT64"),
+                _ => panic!("Invalid bitfield destination type"),
+            };
+            // Bitfields without a name only reserve their bits
+            let mut is_anonymous = true;
+            for identifier in bitfield_inner.filter(|p| p.as_rule() == Rule::identifier) {
+                is_anonymous = false;
+                bitfields.push((
+                    position,
+                    bits,
+                    identifier.as_str(),
+                    c_type,
+                    value_kind,
+                    type_kind,
+                ));
+                position += bits;
+            }
+            if is_anonymous {
+                position += bits;
+            }
+        }
+
+        self.writer.push_str("    struct ");
+        self.writer.push_str(&prefixed_bitset_name);
+        self.writer.push_str(" {\n");
+        for (_, _, field_name, c_type, _, _) in &bitfields {
+            self.writer
+                .push_str(&format!("        {} {};\n", c_type, field_name));
+        }
+        self.writer.push_str("    };\n");
+
+        // --- get_type() ---
+        let bound = bitfields
+            .iter()
+            .map(|(_, bits, ..)| bits.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.writer.push_str(&format!(
+            "\n    static inline const DDS_DynamicType* {}_get_type(void) {{\n",
+            prefixed_bitset_name
+        ));
+        self.writer
+            .push_str("        static const DDS_DynamicType* type = NULL;\n");
+        self.writer.push_str("        if (type == NULL) {\n");
+        self.writer.push_str(&format!(
+            "            static const uint32_t bound[] = {{{}}};\n",
+            bound
+        ));
+        self.writer
+            .push_str("            DDS_TypeDescriptor descriptor = {\n");
+        self.writer
+            .push_str("                .kind = DDS_TYPE_KIND_BITSET,\n");
+        self.writer.push_str(&format!(
+            "                .name = \"{}\",\n",
+            dds_bitset_name
+        ));
+        self.writer.push_str("                .base_type = NULL,\n");
+        self.writer
+            .push_str("                .discriminator_type = NULL,\n");
+        self.writer.push_str("                .bound = bound,\n");
+        self.writer.push_str(&format!(
+            "                .bound_length = {},\n",
+            bitfields.len()
+        ));
+        self.writer
+            .push_str("                .element_type = NULL,\n");
+        self.writer
+            .push_str("                .key_element_type = NULL,\n");
+        self.writer
+            .push_str("                .extensibility_kind = DDS_EXTENSIBILITY_KIND_FINAL,\n");
+        self.writer.push_str("                .is_nested = false\n");
+        self.writer.push_str("            };\n");
+        self.writer.push_str("            DDS_DynamicTypeBuilder* builder = DDS_DynamicTypeBuilderFactory_create_type(&descriptor);\n");
+        for (position, _, field_name, _, _, type_kind) in &bitfields {
+            self.writer.push_str("            {\n");
+            self.writer
+                .push_str("                DDS_MemberDescriptor member = {\n");
+            self.writer.push_str(&format!(
+                "                    .name = \"{}\",\n",
+                field_name
+            ));
+            self.writer
+                .push_str(&format!("                    .id = {},\n", position));
+            self.writer.push_str(&format!(
+                "                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_{}),\n",
+                type_kind
+            ));
+            self.writer
+                .push_str("                    .is_key = false,\n");
+            self.writer
+                .push_str("                    .is_optional = false,\n");
+            self.writer
+                .push_str("                    .is_must_understand = false\n");
+            self.writer.push_str("                };\n");
+            self.writer
+                .push_str("                DDS_DynamicTypeBuilder_add_member(builder, &member);\n");
+            self.writer.push_str("            }\n");
+        }
+        self.writer
+            .push_str("            type = DDS_DynamicTypeBuilder_build(builder);\n");
+        self.writer.push_str("        }\n");
+        self.writer.push_str("        return type;\n");
+        self.writer.push_str("    }\n");
+
+        // --- create_sample / create_dynamic_sample / free_sample ---
+        let mut create_sample_code = String::new();
+        let mut create_dynamic_sample_code = String::new();
+        for (position, _, field_name, _, value_kind, _) in &bitfields {
+            create_sample_code.push_str(&format!(
+                "        DDS_DynamicData_get_{}_value(src, {}, &sample.{});\n",
+                value_kind, position, field_name
+            ));
+            create_dynamic_sample_code.push_str(&format!(
+                "            DDS_DynamicData_set_{}_value(sample, {}, src->{});\n",
+                value_kind, position, field_name
+            ));
+        }
+        self.writer.push_str(&format!(
+            "\n    static inline struct {} {}_create_sample(DDS_DynamicData* src) {{\n        struct {} sample;\n        memset(&sample, 0, sizeof(sample));\n{}        return sample;\n    }}\n",
+            prefixed_bitset_name, prefixed_bitset_name, prefixed_bitset_name, create_sample_code
+        ));
+        self.writer.push_str(&format!(
+            "\n    static inline DDS_DynamicData* {}_create_dynamic_sample(const struct {}* src) {{\n        DDS_DynamicData* sample = DDS_DynamicData_create({}_get_type());\n        if (sample != NULL) {{\n{}        }}\n        return sample;\n    }}\n",
+            prefixed_bitset_name, prefixed_bitset_name, prefixed_bitset_name, create_dynamic_sample_code
+        ));
+        self.writer.push_str(&format!(
+            "\n    static inline void {}_free_sample(struct {}* sample) {{\n        if (sample != NULL) {{\n        }}\n    }}\n",
+            prefixed_bitset_name, prefixed_bitset_name
+        ));
+    }
+
     #[inline]
     fn typedef_dcl(&mut self, pair: IdlPair<'a>) {
         self.generate(
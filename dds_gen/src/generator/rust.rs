@@ -89,6 +89,7 @@ impl<'a> RustGenerator<'a> {
             Rule::octet_type => self.octet_type(pair),
             Rule::template_type_spec => self.template_type_spec(pair),
             Rule::sequence_type => self.sequence_type(pair),
+            Rule::map_type => self.map_type(pair),
             Rule::string_type => self.string_type(pair),
             Rule::wide_string_type => self.wide_string_type(pair),
             Rule::fixed_pt_type => unimplemented!("Fixed point not supported in Rust mapping"),
@@ -221,11 +222,10 @@ impl<'a> RustGenerator<'a> {
             Rule::actual_parameter => todo!(),
             Rule::template_module_ref => todo!(),
             Rule::formal_parameter_names => todo!(),
-            Rule::map_type => todo!(),
-            Rule::bitset_dcl => todo!(),
-            Rule::bitfield => todo!(),
-            Rule::bitfield_spec => todo!(),
-            Rule::destination_type => todo!(),
+            Rule::bitset_dcl => self.bitset_dcl(pair),
+            Rule::bitfield => (),      // Handled inside bitset_dcl
+            Rule::bitfield_spec => (), // Handled inside bitset_dcl
+            Rule::destination_type => self.destination_type(pair),
             Rule::bitmask_dcl => todo!(),
             Rule::bit_value => todo!(),
             Rule::annotation_dcl => todo!(),
@@ -452,6 +452,105 @@ impl<'a> RustGenerator<'a> {
         self.writer.push_str("}\n");
     }
 
+    fn bitset_dcl(&mut self, pair: IdlPair) {
+        let inner_pairs = pair.into_inner();
+        let identifier = inner_pairs
+            .clone()
+            .find(|p| p.as_rule() == Rule::identifier)
+            .expect("Must have an identifier according to the grammar");
+        if inner_pairs
+            .clone()
+            .any(|p| p.as_rule() == Rule::scoped_name)
+        {
+            unimplemented!("Bitset inheritance not supported in Rust mapping")
+        }
+
+        self.writer
+            .push_str("#[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]\n");
+        self.writer.push_str("#[dust_dds(bitset)]\n");
+        if !self.modules.is_empty() {
+            let name = format!(
+                "#[dust_dds(name = \"{}\")]\n",
+                self.hierarchical_type_name(identifier.as_str())
+            );
+            self.writer.push_str(&name);
+        }
+        self.writer.push_str("pub struct ");
+        self.generate(identifier);
+        self.writer.push_str(" {");
+
+        // Bitfields without a name only reserve their bits so the next field needs an explicit position
+        let mut position = 0;
+        let mut is_after_gap = false;
+        for bitfield in inner_pairs.filter(|p| p.as_rule() == Rule::bitfield) {
+            let bitfield_inner = bitfield.into_inner();
+            let bitfield_spec = bitfield_inner
+                .clone()
+                .find(|p| p.as_rule() == Rule::bitfield_spec)
+                .expect("Must have a bitfield spec according to the grammar")
+                .into_inner();
+            let bits: u32 = bitfield_spec
+                .clone()
+                .find(|p| p.as_rule() == Rule::positive_int_const)
+                .expect("Must have a positive int const according to the grammar")
+                .as_str()
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| {
+                    unimplemented!("Only literal bitfield widths supported in Rust mapping")
+                });
+            let destination_type = bitfield_spec
+                .clone()
+                .find(|p| p.as_rule() == Rule::destination_type);
+
+            let identifiers: Vec<_> = bitfield_inner
+                .filter(|p| p.as_rule() == Rule::identifier)
+                .collect();
+            if identifiers.is_empty() {
+                is_after_gap = true;
+            }
+            for identifier in identifiers {
+                if is_after_gap {
+                    self.writer.push_str(&format!(
+                        "#[dust_dds(bits = {bits}, position = {position})]"
+                    ));
+                    is_after_gap = false;
+                } else {
+                    self.writer.push_str(&format!("#[dust_dds(bits = {bits})]"));
+                }
+                self.writer.push_str("pub ");
+                self.generate(identifier);
+                self.writer.push(':');
+                match &destination_type {
+                    Some(destination_type) => self.generate(destination_type.clone()),
+                    None => self.writer.push_str(match bits {
+                        1 => "bool",
+                        2..=8 => "u8",
+                        9..=16 => "u16",
+                        17..=32 => "u32",
+                        _ => "u64",
+                    }),
+                }
+                self.writer.push(',');
+                position += bits;
+            }
+            if is_after_gap {
+                position += bits;
+            }
+        }
+
+        self.writer.push_str("}\n");
+    }
+
+    #[inline]
+    fn destination_type(&mut self, pair: IdlPair) {
+        self.generate(
+            pair.into_inner()
+                .next()
+                .expect("Must have an element according to the grammar"),
+        )
+    }
+
     fn union_def(&mut self, pair: IdlPair) {
         let identifier = pair
             .clone()
@@ -606,24 +705,15 @@ impl<'a> RustGenerator<'a> {
             .expect("Must have an element according to the grammar");
         match array_or_simple_declarator.as_rule() {
             Rule::array_declarator => {
-                let array_declarator = array_or_simple_declarator.into_inner();
-                let identifier = array_declarator
+                let identifier = array_or_simple_declarator
                     .clone()
+                    .into_inner()
                     .find(|p| p.as_rule() == Rule::identifier)
                     .expect("Identifier must exist according to grammar");
-                // TODO: Only single array supported
-                let fixed_array_size = array_declarator
-                    .clone()
-                    .find(|p| p.as_rule() == Rule::fixed_array_size)
-                    .expect("Identifier must exist according to grammar");
                 self.generate(identifier);
                 self.writer.push(':');
 
-                self.writer.push('[');
-                self.generate(type_spec.clone());
-                self.writer.push(';');
-                self.generate(fixed_array_size);
-                self.writer.push(']');
+                self.array_type(type_spec.clone(), array_or_simple_declarator);
             }
             Rule::simple_declarator => {
                 self.generate(array_or_simple_declarator);
@@ -695,26 +785,17 @@ impl<'a> RustGenerator<'a> {
             self.writer.push_str("pub ");
             match array_or_simple_declarator.as_rule() {
                 Rule::array_declarator => {
-                    let array_declarator = array_or_simple_declarator.into_inner();
-                    let identifier = array_declarator
+                    let identifier = array_or_simple_declarator
                         .clone()
+                        .into_inner()
                         .find(|p| p.as_rule() == Rule::identifier)
                         .expect("Identifier must exist according to grammar");
-                    // TODO: Only single array supported
-                    let fixed_array_size = array_declarator
-                        .clone()
-                        .find(|p| p.as_rule() == Rule::fixed_array_size)
-                        .expect("Identifier must exist according to grammar");
                     self.generate(identifier);
                     self.writer.push(':');
                     if is_optional {
                         self.writer.push_str(" Option<");
                     }
-                    self.writer.push('[');
-                    self.generate(type_spec.clone());
-                    self.writer.push(';');
-                    self.generate(fixed_array_size);
-                    self.writer.push(']');
+                    self.array_type(type_spec.clone(), array_or_simple_declarator);
                     if is_optional {
                         self.writer.push('>');
                     }
@@ -914,14 +995,49 @@ impl<'a> RustGenerator<'a> {
             .find(|p| p.as_rule() == Rule::any_declarators)
             .expect("Must have any_declarators according to grammar");
         for any_declarator in any_declarators.into_inner() {
+            let array_or_simple_declarator = any_declarator
+                .into_inner()
+                .next()
+                .expect("Must have an element according to the grammar");
             self.writer.push_str("pub type ");
-            self.generate(any_declarator);
-            self.writer.push('=');
-            self.generate(type_spec.clone());
+            match array_or_simple_declarator.as_rule() {
+                Rule::array_declarator => {
+                    let identifier = array_or_simple_declarator
+                        .clone()
+                        .into_inner()
+                        .find(|p| p.as_rule() == Rule::identifier)
+                        .expect("Identifier must exist according to grammar");
+                    self.generate(identifier);
+                    self.writer.push('=');
+                    self.array_type(type_spec.clone(), array_or_simple_declarator);
+                }
+                _ => {
+                    self.generate(array_or_simple_declarator);
+                    self.writer.push('=');
+                    self.generate(type_spec.clone());
+                }
+            }
             self.writer.push_str(";\n");
         }
     }
 
+    // Multi-dimensional arrays are nested arrays with the first size being the outermost one
+    fn array_type(&mut self, type_spec: IdlPair, array_declarator: IdlPair) {
+        let fixed_array_sizes: Vec<_> = array_declarator
+            .into_inner()
+            .filter(|p| p.as_rule() == Rule::fixed_array_size)
+            .collect();
+        for _ in &fixed_array_sizes {
+            self.writer.push('[');
+        }
+        self.generate(type_spec);
+        for fixed_array_size in fixed_array_sizes.into_iter().rev() {
+            self.writer.push(';');
+            self.generate(fixed_array_size);
+            self.writer.push(']');
+        }
+    }
+
     #[inline]
     fn any_declarator(&mut self, pair: IdlPair) {
         self.generate(
@@ -1066,6 +1182,23 @@ impl<'a> RustGenerator<'a> {
         self.writer.push('>');
     }
 
+    fn map_type(&mut self, pair: IdlPair) {
+        let mut type_specs = pair.into_inner().filter(|p| p.as_rule() == Rule::type_spec);
+
+        let key_type_spec = type_specs
+            .next()
+            .expect("Must have a key type_spec according to the grammar");
+        let value_type_spec = type_specs
+            .next()
+            .expect("Must have a value type_spec according to the grammar");
+
+        self.writer.push_str("std::collections::BTreeMap<");
+        self.generate(key_type_spec);
+        self.writer.push(',');
+        self.generate(value_type_spec);
+        self.writer.push('>');
+    }
+
     #[inline]
     fn string_type(&mut self, _pair: IdlPair) {
         self.writer.push_str("String")
@@ -1073,7 +1206,8 @@ impl<'a> RustGenerator<'a> {
 
     #[inline]
     fn wide_string_type(&mut self, _pair: IdlPair) {
-        self.writer.push_str("String")
+        self.writer
+            .push_str("dust_dds::xtypes::type_support::WString")
     }
 
     #[inline]
@@ -1159,7 +1293,8 @@ impl<'a> RustGenerator<'a> {
 
     #[inline]
     fn wide_char_type(&mut self, _pair: IdlPair) {
-        self.writer.push_str("char")
+        self.writer
+            .push_str("dust_dds::xtypes::type_support::WChar")
     }
 
     #[inline]
@@ -1245,6 +1380,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_bitset() {
+        let p = IdlParser::parse(
+            Rule::bitset_dcl,
+            "bitset MyBitset {
+                bitfield<1> ready;
+                bitfield<3, short> mode;
+                bitfield<4>;
+                bitfield<12> low;
+                bitfield<12> high;
+            };",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+        let mut writer = String::new();
+        let mut rust_generator = RustGenerator::new(&mut writer);
+        rust_generator.generate(p);
+
+        assert_eq!(
+            &writer,
+            "#[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]\n#[dust_dds(bitset)]\npub struct MyBitset {#[dust_dds(bits = 1)]pub ready:bool,#[dust_dds(bits = 3)]pub mode:i16,#[dust_dds(bits = 12, position = 8)]pub low:u16,#[dust_dds(bits = 12)]pub high:u16,}\n",
+        );
+    }
+
     #[test]
     fn parse_member_with_key() {
         let p = IdlParser::parse(Rule::member, "@key long a;")
@@ -1284,6 +1444,45 @@ mod tests {
         assert_eq!(&writer, "pub a:Vec<Vec<u8>>,");
     }
 
+    #[test]
+    fn parse_member_map_type() {
+        let p = IdlParser::parse(Rule::member, "map<string, double, 16> a;")
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut writer = String::new();
+        let mut rust_generator = RustGenerator::new(&mut writer);
+        rust_generator.generate(p);
+
+        assert_eq!(&writer, "pub a:std::collections::BTreeMap<String,f64>,");
+    }
+
+    #[test]
+    fn parse_member_multi_dimensional_array() {
+        let p = IdlParser::parse(Rule::member, "double m[3][4];")
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut writer = String::new();
+        let mut rust_generator = RustGenerator::new(&mut writer);
+        rust_generator.generate(p);
+
+        assert_eq!(&writer, "pub m:[[f64;4];3],");
+    }
+
+    #[test]
+    fn parse_typedef_array() {
+        let p = IdlParser::parse(Rule::typedef_dcl, r#"typedef float Matrix[2][2];"#)
+            .unwrap()
+            .next()
+            .unwrap();
+        let mut writer = String::new();
+        let mut rust_generator = RustGenerator::new(&mut writer);
+        rust_generator.generate(p);
+
+        assert_eq!(&writer, "pub type Matrix=[[f32;2];2];\n");
+    }
+
     #[test]
     fn parse_const_with_literals() {
         let p = IdlParser::parse(Rule::specification, r#"const string a = 'a';"#)
@@ -11,10 +11,10 @@ fn basic_types() {
             pub struct BasicTypes {
                 pub a: bool,
                 pub b: char,
-                pub c: char,
+                pub c: dust_dds::xtypes::type_support::WChar,
                 pub d: u8,
                 pub e: String,
-                pub f: String,
+                pub f: dust_dds::xtypes::type_support::WString,
                 pub g: i16,
                 pub h: u16,
                 pub i: i32,
@@ -69,7 +69,7 @@ fn basic_types() {
                 DDS_MemberDescriptor member = {
                     .name = "c",
                     .id = 2,
-                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_CHAR8),
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_CHAR16),
                     .is_key = false,
                     .is_optional = false,
                     .is_must_understand = true
@@ -101,7 +101,7 @@ fn basic_types() {
                 DDS_DynamicType_free(member_type);
             }
             {
-                DDS_DynamicType* member_type = DDS_DynamicType_create_string_type(4294967295);
+                DDS_DynamicType* member_type = DDS_DynamicType_create_wstring_type(4294967295);
                 DDS_MemberDescriptor member = {
                     .name = "f",
                     .id = 5,
@@ -212,8 +212,8 @@ fn basic_types() {
         DDS_DynamicData_get_boolean_value(src, 0, &sample.a);
         DDS_DynamicData_get_char8_value(src, 1, &sample.b);
         {
-            char temp;
-            DDS_DynamicData_get_char8_value(src, 2, &temp);
+            uint16_t temp;
+            DDS_DynamicData_get_char16_value(src, 2, &temp);
             sample.c = (wchar_t)temp;
         }
         DDS_DynamicData_get_uint8_value(src, 3, &sample.d);
@@ -246,7 +246,7 @@ fn basic_types() {
         if (sample != NULL) {
             DDS_DynamicData_set_boolean_value(sample, 0, src->a);
             DDS_DynamicData_set_char8_value(sample, 1, src->b);
-            DDS_DynamicData_set_char8_value(sample, 2, (char)src->c);
+            DDS_DynamicData_set_char16_value(sample, 2, (uint16_t)src->c);
             DDS_DynamicData_set_uint8_value(sample, 3, src->d);
             DDS_DynamicData_set_string_value(sample, 4, src->e);
             {
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use syn::File;
+
+#[test]
+fn bitset_types() {
+    let idl_file = Path::new("tests/bitset_types.idl");
+
+    let expected = syn::parse2::<File>(
+        r#"
+            #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
+            #[dust_dds(bitset)]
+            pub struct StatusWord {
+                #[dust_dds(bits = 1)]
+                pub ready: bool,
+                #[dust_dds(bits = 3)]
+                pub mode: u8,
+                #[dust_dds(bits = 12, position = 8)]
+                pub offset: i16,
+                #[dust_dds(bits = 20)]
+                pub counter: u32,
+            }
+
+            #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
+            pub struct Device {
+                pub id: i32,
+                pub status: StatusWord,
+            }
+    "#
+        .parse()
+        .unwrap(),
+    )
+    .unwrap();
+
+    let result = syn::parse2::<File>(
+        dust_dds_gen::compile_idl(idl_file)
+            .unwrap()
+            .parse()
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,521 @@
+use std::path::Path;
+
+#[test]
+fn bitset_types() {
+    let idl_file = Path::new("tests/bitset_types.idl");
+    let expected = r###"
+    #include <stdbool.h>
+    #include <stdint.h>
+    #include <stddef.h>
+    #include <stdlib.h>
+    #include <string.h>
+    #include "dust_dds.h"
+
+    struct StatusWord {
+        bool ready;
+        uint8_t mode;
+        int16_t offset;
+        uint32_t counter;
+    };
+
+    static inline const DDS_DynamicType* StatusWord_get_type(void) {
+        static const DDS_DynamicType* type = NULL;
+        if (type == NULL) {
+            static const uint32_t bound[] = {1, 3, 12, 20};
+            DDS_TypeDescriptor descriptor = {
+                .kind = DDS_TYPE_KIND_BITSET,
+                .name = "StatusWord",
+                .base_type = NULL,
+                .discriminator_type = NULL,
+                .bound = bound,
+                .bound_length = 4,
+                .element_type = NULL,
+                .key_element_type = NULL,
+                .extensibility_kind = DDS_EXTENSIBILITY_KIND_FINAL,
+                .is_nested = false
+            };
+            DDS_DynamicTypeBuilder* builder = DDS_DynamicTypeBuilderFactory_create_type(&descriptor);
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "ready",
+                    .id = 0,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_BOOLEAN),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = false
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "mode",
+                    .id = 1,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_UINT8),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = false
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "offset",
+                    .id = 8,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_INT16),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = false
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "counter",
+                    .id = 20,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_UINT32),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = false
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            type = DDS_DynamicTypeBuilder_build(builder);
+        }
+        return type;
+    }
+
+    static inline struct StatusWord StatusWord_create_sample(DDS_DynamicData* src) {
+        struct StatusWord sample;
+        memset(&sample, 0, sizeof(sample));
+        DDS_DynamicData_get_boolean_value(src, 0, &sample.ready);
+        DDS_DynamicData_get_uint8_value(src, 1, &sample.mode);
+        DDS_DynamicData_get_int16_value(src, 8, &sample.offset);
+        DDS_DynamicData_get_uint32_value(src, 20, &sample.counter);
+        return sample;
+    }
+
+    static inline DDS_DynamicData* StatusWord_create_dynamic_sample(const struct StatusWord* src) {
+        DDS_DynamicData* sample = DDS_DynamicData_create(StatusWord_get_type());
+        if (sample != NULL) {
+            DDS_DynamicData_set_boolean_value(sample, 0, src->ready);
+            DDS_DynamicData_set_uint8_value(sample, 1, src->mode);
+            DDS_DynamicData_set_int16_value(sample, 8, src->offset);
+            DDS_DynamicData_set_uint32_value(sample, 20, src->counter);
+        }
+        return sample;
+    }
+
+    static inline void StatusWord_free_sample(struct StatusWord* sample) {
+        if (sample != NULL) {
+        }
+    }
+    struct Device {
+        int32_t id;
+        StatusWord status;
+    };
+
+    static inline const DDS_DynamicType* Device_get_type(void) {
+        static const DDS_DynamicType* type = NULL;
+        if (type == NULL) {
+            DDS_TypeDescriptor descriptor = {
+                .kind = DDS_TYPE_KIND_STRUCTURE,
+                .name = "Device",
+                .base_type = NULL,
+                .discriminator_type = NULL,
+                .bound = NULL,
+                .element_type = NULL,
+                .key_element_type = NULL,
+                .extensibility_kind = DDS_EXTENSIBILITY_KIND_FINAL,
+                .is_nested = false
+            };
+            DDS_DynamicTypeBuilder* builder = DDS_DynamicTypeBuilderFactory_create_type(&descriptor);
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "id",
+                    .id = 0,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_INT32),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            {
+                DDS_DynamicType* member_type = (DDS_DynamicType*)StatusWord_get_type();
+                DDS_MemberDescriptor member = {
+                    .name = "status",
+                    .id = 1,
+                    .type = member_type,
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+                DDS_DynamicType_free(member_type);
+            }
+            type = DDS_DynamicTypeBuilder_build(builder);
+        }
+        return type;
+    }
+
+    static inline struct Device Device_create_sample(DDS_DynamicData* src) {
+        struct Device sample;
+        memset(&sample, 0, sizeof(sample));
+        DDS_DynamicData_get_int32_value(src, 0, &sample.id);
+        {
+            DDS_DynamicData* member_data = NULL;
+            DDS_DynamicData_get_complex_value(src, 1, &member_data);
+            if (member_data != NULL) {
+                sample.status = StatusWord_create_sample(member_data);
+                DDS_DynamicData_free(member_data);
+            }
+        }
+        return sample;
+    }
+
+    static inline DDS_DynamicData* Device_create_dynamic_sample(const struct Device* src) {
+        DDS_DynamicData* sample = DDS_DynamicData_create(Device_get_type());
+        if (sample != NULL) {
+            DDS_DynamicData_set_int32_value(sample, 0, src->id);
+            {
+                DDS_DynamicData* member_data = StatusWord_create_dynamic_sample(&src->status);
+                DDS_DynamicData_set_complex_value(sample, 1, member_data);
+                DDS_DynamicData_free(member_data);
+            }
+        }
+        return sample;
+    }
+
+    static inline void Device_free_sample(struct Device* sample) {
+        if (sample != NULL) {
+        StatusWord_free_sample(&sample->status);
+        }
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_write(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_write_w_timestamp(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_register_instance(DDS_DataWriter* writer, const struct Device* data, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_register_instance_w_timestamp(DDS_DataWriter* writer, const struct Device* data, struct DDS_Time_t source_timestamp, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance_w_timestamp(writer, sample, source_timestamp, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_unregister_instance(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_unregister_instance_w_timestamp(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_dispose(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_dispose_w_timestamp(DDS_DataWriter* writer, const struct Device* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_get_key_value(DDS_DataWriter* writer, struct Device* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_get_key_value(writer, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = Device_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataWriter_lookup_instance(DDS_DataWriter* writer, const struct Device* key_holder, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_lookup_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_read(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_take(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_read_next_sample(DDS_DataReader* reader, struct Device* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_read_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = Device_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_take_next_sample(DDS_DataReader* reader, struct Device* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_take_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = Device_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_read_instance(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_take_instance(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_read_next_instance(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_take_next_instance(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Device_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_return_loan(DDS_DataReader* reader, struct Device* data_values, struct DDS_SampleInfo* sample_infos) {
+        return DDS_DataReader_return_loan(reader, NULL, sample_infos);
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_get_key_value(DDS_DataReader* reader, struct Device* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_get_key_value(reader, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = Device_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode DeviceDataReader_lookup_instance(DDS_DataReader* reader, const struct Device* key_holder, DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Device_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_lookup_instance(reader, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+"###;
+
+    let result = dust_dds_gen::compile_idl_c(idl_file).unwrap();
+
+    assert_eq!(result, expected);
+}
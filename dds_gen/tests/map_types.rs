@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use syn::File;
+
+#[test]
+fn map_types() {
+    let idl_file = Path::new("tests/map_types.idl");
+
+    let expected = syn::parse2::<File>(
+        r#"
+            #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
+            pub struct Point {
+                pub x: i32,
+                pub y: i32,
+            }
+
+            #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
+            pub struct MapTypes {
+                pub parameters: std::collections::BTreeMap<String, f64>,
+                pub points: std::collections::BTreeMap<u16, Point>,
+            }
+    "#
+        .parse()
+        .unwrap(),
+    )
+    .unwrap();
+
+    let result = syn::parse2::<File>(
+        dust_dds_gen::compile_idl(idl_file)
+            .unwrap()
+            .parse()
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,868 @@
+use std::path::Path;
+
+#[test]
+fn map_types() {
+    let idl_file = Path::new("tests/map_types.idl");
+    let expected = r###"
+    #include <stdbool.h>
+    #include <stdint.h>
+    #include <stddef.h>
+    #include <stdlib.h>
+    #include <string.h>
+    #include "dust_dds.h"
+
+    struct Point {
+        int32_t x;
+        int32_t y;
+    };
+
+    static inline const DDS_DynamicType* Point_get_type(void) {
+        static const DDS_DynamicType* type = NULL;
+        if (type == NULL) {
+            DDS_TypeDescriptor descriptor = {
+                .kind = DDS_TYPE_KIND_STRUCTURE,
+                .name = "Point",
+                .base_type = NULL,
+                .discriminator_type = NULL,
+                .bound = NULL,
+                .element_type = NULL,
+                .key_element_type = NULL,
+                .extensibility_kind = DDS_EXTENSIBILITY_KIND_FINAL,
+                .is_nested = false
+            };
+            DDS_DynamicTypeBuilder* builder = DDS_DynamicTypeBuilderFactory_create_type(&descriptor);
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "x",
+                    .id = 0,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_INT32),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            {
+                DDS_MemberDescriptor member = {
+                    .name = "y",
+                    .id = 1,
+                    .type = DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_INT32),
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+            }
+            type = DDS_DynamicTypeBuilder_build(builder);
+        }
+        return type;
+    }
+
+    static inline struct Point Point_create_sample(DDS_DynamicData* src) {
+        struct Point sample;
+        memset(&sample, 0, sizeof(sample));
+        DDS_DynamicData_get_int32_value(src, 0, &sample.x);
+        DDS_DynamicData_get_int32_value(src, 1, &sample.y);
+        return sample;
+    }
+
+    static inline DDS_DynamicData* Point_create_dynamic_sample(const struct Point* src) {
+        DDS_DynamicData* sample = DDS_DynamicData_create(Point_get_type());
+        if (sample != NULL) {
+            DDS_DynamicData_set_int32_value(sample, 0, src->x);
+            DDS_DynamicData_set_int32_value(sample, 1, src->y);
+        }
+        return sample;
+    }
+
+    static inline void Point_free_sample(struct Point* sample) {
+        if (sample != NULL) {
+        }
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_write(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_write_w_timestamp(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_register_instance(DDS_DataWriter* writer, const struct Point* data, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_register_instance_w_timestamp(DDS_DataWriter* writer, const struct Point* data, struct DDS_Time_t source_timestamp, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance_w_timestamp(writer, sample, source_timestamp, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_unregister_instance(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_unregister_instance_w_timestamp(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_dispose(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_dispose_w_timestamp(DDS_DataWriter* writer, const struct Point* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_get_key_value(DDS_DataWriter* writer, struct Point* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_get_key_value(writer, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = Point_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataWriter_lookup_instance(DDS_DataWriter* writer, const struct Point* key_holder, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_lookup_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_read(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_take(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_read_next_sample(DDS_DataReader* reader, struct Point* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_read_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = Point_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_take_next_sample(DDS_DataReader* reader, struct Point* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_take_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = Point_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_read_instance(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_take_instance(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_read_next_instance(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_take_next_instance(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = Point_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_return_loan(DDS_DataReader* reader, struct Point* data_values, struct DDS_SampleInfo* sample_infos) {
+        return DDS_DataReader_return_loan(reader, NULL, sample_infos);
+    }
+
+    static inline DDS_ReturnCode PointDataReader_get_key_value(DDS_DataReader* reader, struct Point* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_get_key_value(reader, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = Point_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode PointDataReader_lookup_instance(DDS_DataReader* reader, const struct Point* key_holder, DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = Point_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_lookup_instance(reader, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+    struct MapTypes {
+        struct { uint32_t length; char** keys; double* values; } parameters;
+        struct { uint32_t length; uint16_t* keys; Point* values; } points;
+    };
+
+    static inline const DDS_DynamicType* MapTypes_get_type(void) {
+        static const DDS_DynamicType* type = NULL;
+        if (type == NULL) {
+            DDS_TypeDescriptor descriptor = {
+                .kind = DDS_TYPE_KIND_STRUCTURE,
+                .name = "MapTypes",
+                .base_type = NULL,
+                .discriminator_type = NULL,
+                .bound = NULL,
+                .element_type = NULL,
+                .key_element_type = NULL,
+                .extensibility_kind = DDS_EXTENSIBILITY_KIND_FINAL,
+                .is_nested = false
+            };
+            DDS_DynamicTypeBuilder* builder = DDS_DynamicTypeBuilderFactory_create_type(&descriptor);
+            {
+                DDS_DynamicType* member_type = DDS_DynamicType_create_map_type(DDS_DynamicType_create_string_type(4294967295), DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_FLOAT64), 4294967295);
+                DDS_MemberDescriptor member = {
+                    .name = "parameters",
+                    .id = 0,
+                    .type = member_type,
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+                DDS_DynamicType_free(member_type);
+            }
+            {
+                DDS_DynamicType* member_type = DDS_DynamicType_create_map_type(DDS_DynamicType_get_primitive_type(DDS_TYPE_KIND_UINT16), (DDS_DynamicType*)Point_get_type(), 16);
+                DDS_MemberDescriptor member = {
+                    .name = "points",
+                    .id = 1,
+                    .type = member_type,
+                    .is_key = false,
+                    .is_optional = false,
+                    .is_must_understand = true
+                };
+                DDS_DynamicTypeBuilder_add_member(builder, &member);
+                DDS_DynamicType_free(member_type);
+            }
+            type = DDS_DynamicTypeBuilder_build(builder);
+        }
+        return type;
+    }
+
+    static inline struct MapTypes MapTypes_create_sample(DDS_DynamicData* src) {
+        struct MapTypes sample;
+        memset(&sample, 0, sizeof(sample));
+        {
+            uint32_t length = 0;
+            DDS_DynamicData_get_map_length(src, 0, &length);
+            sample.parameters.length = length;
+            sample.parameters.keys = calloc(length, sizeof(*sample.parameters.keys));
+            sample.parameters.values = calloc(length, sizeof(*sample.parameters.values));
+            for (uint32_t i = 0; i < length; i++) {
+                DDS_DynamicData* entry = NULL;
+                DDS_DynamicData_get_map_entry(src, 0, i, &entry);
+                if (entry != NULL) {
+                    DDS_DynamicData_get_string_value(entry, 0, &sample.parameters.keys[i]);
+                    DDS_DynamicData_get_float64_value(entry, 1, &sample.parameters.values[i]);
+                    DDS_DynamicData_free(entry);
+                }
+            }
+        }
+        {
+            uint32_t length = 0;
+            DDS_DynamicData_get_map_length(src, 1, &length);
+            sample.points.length = length;
+            sample.points.keys = calloc(length, sizeof(*sample.points.keys));
+            sample.points.values = calloc(length, sizeof(*sample.points.values));
+            for (uint32_t i = 0; i < length; i++) {
+                DDS_DynamicData* entry = NULL;
+                DDS_DynamicData_get_map_entry(src, 1, i, &entry);
+                if (entry != NULL) {
+                    DDS_DynamicData_get_uint16_value(entry, 0, &sample.points.keys[i]);
+                    {
+                        DDS_DynamicData* member_data = NULL;
+                        DDS_DynamicData_get_complex_value(entry, 1, &member_data);
+                        if (member_data != NULL) {
+                            sample.points.values[i] = Point_create_sample(member_data);
+                            DDS_DynamicData_free(member_data);
+                        }
+                    }
+                    DDS_DynamicData_free(entry);
+                }
+            }
+        }
+        return sample;
+    }
+
+    static inline DDS_DynamicData* MapTypes_create_dynamic_sample(const struct MapTypes* src) {
+        DDS_DynamicData* sample = DDS_DynamicData_create(MapTypes_get_type());
+        if (sample != NULL) {
+            DDS_DynamicData_clear_map(sample, 0);
+            for (uint32_t i = 0; i < src->parameters.length; i++) {
+                DDS_DynamicData* entry = DDS_DynamicData_create_map_entry(sample, 0);
+                DDS_DynamicData_set_string_value(entry, 0, src->parameters.keys[i]);
+                DDS_DynamicData_set_float64_value(entry, 1, src->parameters.values[i]);
+                DDS_DynamicData_add_map_entry(sample, 0, entry);
+                DDS_DynamicData_free(entry);
+            }
+            DDS_DynamicData_clear_map(sample, 1);
+            for (uint32_t i = 0; i < src->points.length; i++) {
+                DDS_DynamicData* entry = DDS_DynamicData_create_map_entry(sample, 1);
+                DDS_DynamicData_set_uint16_value(entry, 0, src->points.keys[i]);
+                {
+                    DDS_DynamicData* member_data = Point_create_dynamic_sample(&src->points.values[i]);
+                    DDS_DynamicData_set_complex_value(entry, 1, member_data);
+                    DDS_DynamicData_free(member_data);
+                }
+                DDS_DynamicData_add_map_entry(sample, 1, entry);
+                DDS_DynamicData_free(entry);
+            }
+        }
+        return sample;
+    }
+
+    static inline void MapTypes_free_sample(struct MapTypes* sample) {
+        if (sample != NULL) {
+        for (uint32_t i = 0; i < sample->parameters.length; i++) {
+            DDS_String_free(sample->parameters.keys[i]);
+        }
+        free(sample->parameters.keys);
+        free(sample->parameters.values);
+        for (uint32_t i = 0; i < sample->points.length; i++) {
+            Point_free_sample(&sample->points.values[i]);
+        }
+        free(sample->points.keys);
+        free(sample->points.values);
+        }
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_write(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_write_w_timestamp(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_write_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_register_instance(DDS_DataWriter* writer, const struct MapTypes* data, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_register_instance_w_timestamp(DDS_DataWriter* writer, const struct MapTypes* data, struct DDS_Time_t source_timestamp, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_register_instance_w_timestamp(writer, sample, source_timestamp, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_unregister_instance(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_unregister_instance_w_timestamp(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_unregister_instance_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_dispose(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_dispose_w_timestamp(DDS_DataWriter* writer, const struct MapTypes* data, const DDS_InstanceHandle_t* handle, struct DDS_Time_t source_timestamp) {
+        if (writer == NULL || data == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(data);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_dispose_w_timestamp(writer, sample, handle, source_timestamp);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_get_key_value(DDS_DataWriter* writer, struct MapTypes* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_get_key_value(writer, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = MapTypes_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataWriter_lookup_instance(DDS_DataWriter* writer, const struct MapTypes* key_holder, DDS_InstanceHandle_t* handle) {
+        if (writer == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataWriter_lookup_instance(writer, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_read(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_take(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take(reader, samples, sample_infos, max_samples, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_read_next_sample(DDS_DataReader* reader, struct MapTypes* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_read_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = MapTypes_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_take_next_sample(DDS_DataReader* reader, struct MapTypes* data_value, struct DDS_SampleInfo* sample_info) {
+        if (reader == NULL || data_value == NULL || sample_info == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = NULL;
+        DDS_ReturnCode result = DDS_DataReader_take_next_sample(reader, &sample, sample_info);
+        if (result == DDS_RETCODE_OK) {
+            if (sample != NULL) {
+                *data_value = MapTypes_create_sample(sample);
+                DDS_DynamicData_free(sample);
+            }
+        }
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_read_instance(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_take_instance(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* a_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || a_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_instance(reader, samples, sample_infos, max_samples, a_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_read_next_instance(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_read_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_take_next_instance(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos, int32_t max_samples, const DDS_InstanceHandle_t* previous_handle, DDS_SampleStateMask sample_states, DDS_ViewStateMask view_states, DDS_InstanceStateMask instance_states, int32_t* received_samples) {
+        if (reader == NULL || data_values == NULL || previous_handle == NULL || received_samples == NULL || max_samples <= 0) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData** samples = (DDS_DynamicData**)calloc(max_samples, sizeof(DDS_DynamicData*));
+        if (samples == NULL) {
+            return DDS_RETCODE_OUT_OF_RESOURCES;
+        }
+        DDS_ReturnCode result = DDS_DataReader_take_next_instance(reader, samples, sample_infos, max_samples, previous_handle, sample_states, view_states, instance_states, received_samples);
+        if (result == DDS_RETCODE_OK) {
+            for (int32_t i = 0; i < *received_samples; i++) {
+                if (samples[i] != NULL) {
+                    data_values[i] = MapTypes_create_sample(samples[i]);
+                    DDS_DynamicData_free(samples[i]);
+                }
+            }
+        }
+        free(samples);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_return_loan(DDS_DataReader* reader, struct MapTypes* data_values, struct DDS_SampleInfo* sample_infos) {
+        return DDS_DataReader_return_loan(reader, NULL, sample_infos);
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_get_key_value(DDS_DataReader* reader, struct MapTypes* key_holder, const DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_get_key_value(reader, sample, handle);
+        if (result == DDS_RETCODE_OK) {
+            *key_holder = MapTypes_create_sample(sample);
+        }
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+
+    static inline DDS_ReturnCode MapTypesDataReader_lookup_instance(DDS_DataReader* reader, const struct MapTypes* key_holder, DDS_InstanceHandle_t* handle) {
+        if (reader == NULL || key_holder == NULL || handle == NULL) {
+            return DDS_RETCODE_BAD_PARAMETER;
+        }
+        DDS_DynamicData* sample = MapTypes_create_dynamic_sample(key_holder);
+        if (sample == NULL) {
+            return DDS_RETCODE_ERROR;
+        }
+        DDS_ReturnCode result = DDS_DataReader_lookup_instance(reader, sample, handle);
+        DDS_DynamicData_free(sample);
+        return result;
+    }
+"###;
+
+    let result = dust_dds_gen::compile_idl_c(idl_file).unwrap();
+
+    assert_eq!(result, expected);
+}
@@ -25,12 +25,12 @@ fn structs_generation() {
             }
             #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
             pub struct Sentence {
-                pub words: Vec<String>,
+                pub words: Vec<dust_dds::xtypes::type_support::WString>,
                 pub dependencies: Vec<Vec<u32>>,
             }
             #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
             pub struct User {
-                pub name: String,
+                pub name: dust_dds::xtypes::type_support::WString,
                 pub active: bool,
             }
             #[derive(Debug, Clone, dust_dds::infrastructure::type_support::DdsType)]
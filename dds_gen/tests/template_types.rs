@@ -13,7 +13,7 @@ fn template_types() {
                 pub a: Vec<Vec<u8>>,
                 pub b: String,
                 pub c: Vec<i16>,
-                pub d: String,
+                pub d: dust_dds::xtypes::type_support::WString,
             }
     "#
         .parse()